@@ -0,0 +1,168 @@
+//! The combat clock: how far real-time cooldowns, status effects and the
+//! monster turn delay advance each update
+//!
+//! With a fixed step the clock ticks in `FixedUpdate`, advancing exactly one
+//! step per tick, so the same inputs give the same fight on every machine,
+//! whatever its frame rate. Without one it ticks every frame by frame time,
+//! carrying the sub-millisecond remainder over to the next frame so no time is
+//! lost. The mode can be switched at runtime by replacing [`CombatClock`].
+
+use bevy::prelude::*;
+use bevy_wasm_fsharp_ref_logic as logic;
+use std::time::Duration;
+
+/// Step the clock advances by unless told otherwise
+pub const DEFAULT_STEP: logic::Millis = logic::Millis(20);
+
+/// Resource setting how the combat clock advances
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct CombatClock {
+    step: Option<logic::Millis>,
+    /// Frame time not yet counted because it is under a millisecond
+    carry: Duration,
+    /// How far the clock advanced on its latest tick
+    delta: logic::Millis,
+}
+
+impl CombatClock {
+    /// A clock advancing exactly `step` per `FixedUpdate` tick
+    pub fn fixed(step: logic::Millis) -> Self {
+        Self {
+            step: Some(step),
+            carry: Duration::ZERO,
+            delta: logic::Millis::ZERO,
+        }
+    }
+
+    /// A clock advancing by frame time every `Update`
+    pub fn frame_time() -> Self {
+        Self {
+            step: None,
+            carry: Duration::ZERO,
+            delta: logic::Millis::ZERO,
+        }
+    }
+
+    /// The fixed step, or `None` when advancing by frame time
+    pub fn step(&self) -> Option<logic::Millis> {
+        self.step
+    }
+
+    /// How far the clock advanced on its latest tick
+    pub fn delta(&self) -> logic::Millis {
+        self.delta
+    }
+
+    /// Advances the clock for an update that took `elapsed`
+    ///
+    /// A fixed clock ignores `elapsed` and advances one step. A frame-time
+    /// clock advances by the whole milliseconds in `elapsed` plus the carried
+    /// remainder, and carries what is left.
+    pub fn advance(&mut self, elapsed: Duration) -> logic::Millis {
+        self.delta = match self.step {
+            Some(step) => step,
+            None => {
+                let total = self.carry + elapsed;
+                let whole = logic::Millis::from(total);
+                self.carry = total - Duration::from(whole);
+                whole
+            }
+        };
+        self.delta
+    }
+}
+
+impl Default for CombatClock {
+    fn default() -> Self {
+        Self::fixed(DEFAULT_STEP)
+    }
+}
+
+/// Event sent for each status effect that ticks or expires in real time
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct RealTimeStatus(pub logic::StatusEvent);
+
+/// System set the clock ticks in, in `FixedUpdate` with a fixed step and in
+/// `Update` without one
+///
+/// Systems reading [`CombatClock::delta`] run after it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TickCombatClock;
+
+/// Plugin ticking the [`logic::Encounter`] resource's real-time cooldowns and
+/// status effects on the combat clock
+pub struct CombatClockPlugin;
+
+impl Plugin for CombatClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CombatClock>()
+            .add_event::<RealTimeStatus>()
+            .add_systems(
+                PreUpdate,
+                sync_fixed_timestep.run_if(resource_changed::<CombatClock>),
+            )
+            .add_systems(
+                FixedUpdate,
+                (advance_clock, tick_real_time)
+                    .chain()
+                    .in_set(TickCombatClock)
+                    .run_if(is_fixed),
+            )
+            .add_systems(
+                Update,
+                (advance_clock, tick_real_time)
+                    .chain()
+                    .in_set(TickCombatClock)
+                    .run_if(not(is_fixed)),
+            );
+    }
+}
+
+/// Run condition: is the combat clock on a fixed step
+pub fn is_fixed(clock: Res<CombatClock>) -> bool {
+    clock.step.is_some()
+}
+
+/// Runs `FixedUpdate` at the clock's step
+fn sync_fixed_timestep(clock: Res<CombatClock>, mut fixed: ResMut<Time<Fixed>>) {
+    if let Some(step) = clock.step {
+        fixed.set_timestep(step.into());
+    }
+}
+
+fn advance_clock(time: Res<Time>, mut clock: ResMut<CombatClock>) {
+    clock.advance(time.delta());
+}
+
+/// Counts down every living combatant's real-time cooldowns and status effects
+fn tick_real_time(
+    clock: Res<CombatClock>,
+    encounter: Option<ResMut<logic::Encounter>>,
+    mut statuses: EventWriter<RealTimeStatus>,
+) {
+    let Some(mut encounter) = encounter else {
+        return;
+    };
+    let delta = clock.delta;
+    if delta.is_zero() {
+        return;
+    }
+
+    // Only mark the encounter changed when a character was affected, so
+    // cooldowns ticking don't resync every sprite each tick
+    let mut events = Vec::new();
+    for combatant in &mut encounter.bypass_change_detection().combatants {
+        if !combatant.is_alive() {
+            continue;
+        }
+        combatant.abilities.tick_all(delta);
+        // Like turn-based ticks, heal up to the effective max HP
+        let mut character = combatant.effective_character();
+        events.extend(combatant.effects.tick(delta, &mut character));
+        combatant.character.hp = character.hp;
+    }
+    if !events.is_empty() {
+        encounter.set_changed();
+        statuses.send_batch(events.into_iter().map(RealTimeStatus));
+    }
+}
@@ -0,0 +1,338 @@
+//! Player-vs-player duel between two native instances of the app
+//!
+//! One instance hosts with `--host [addr]` and another joins with
+//! `--join [addr]`. Each controls one duelist. Only their actions cross the
+//! connection; both instances simulate every turn and compare state checksums
+//! through [`logic::Lockstep`], stopping the duel if they ever disagree.
+
+use bevy::prelude::*;
+use bevy_wasm_fsharp_ref_logic as logic;
+use std::net::TcpListener;
+
+use crate::{
+    animate_action, animate_attack_flash, animate_damage_numbers, animate_hit_shake,
+    column_position, log_outcomes, log_status_events, spawn_health_bar, sync_characters,
+    update_health_bars, CombatLog, GameOverMessage,
+};
+
+/// Address used when `--host` or `--join` is not followed by one
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// Seed both instances roll crits, evasion and damage variance with
+const DUEL_SEED: u64 = 1337;
+
+/// The duelists, host first: names and class names
+const DUELISTS: [(&str, &str); 2] = [("Host", "Warrior"), ("Guest", "Ranger")];
+
+/// Level both duelists fight at
+const DUEL_LEVEL: u32 = 3;
+
+/// Ability duelists use on Space
+const DUEL_ABILITY: &str = "Basic Attack";
+
+/// Which side of the duel this instance plays
+pub(crate) enum DuelRole {
+    Host(String),
+    Join(String),
+}
+
+impl DuelRole {
+    /// Reads `--host [addr]` or `--join [addr]` from the command line arguments
+    /// after the program name
+    pub(crate) fn from_args(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let flag = args.next()?;
+        let addr = args.next().unwrap_or_else(|| DEFAULT_ADDR.to_string());
+        match flag.as_str() {
+            "--host" => Some(DuelRole::Host(addr)),
+            "--join" => Some(DuelRole::Join(addr)),
+            _ => None,
+        }
+    }
+
+    /// The team this instance controls: the host's duelist is an ally
+    fn team(&self) -> logic::Team {
+        match self {
+            DuelRole::Host(_) => logic::Team::Allies,
+            DuelRole::Join(_) => logic::Team::Enemies,
+        }
+    }
+
+    /// Waits for the guest, or connects to the host
+    fn connect(&self) -> Result<logic::TcpTransport, logic::LockstepError> {
+        match self {
+            DuelRole::Host(addr) => {
+                let listener = TcpListener::bind(addr).map_err(|e| logic::LockstepError::Io {
+                    message: e.to_string(),
+                })?;
+                println!("Waiting for a challenger on {}...", addr);
+                logic::TcpTransport::accept(&listener)
+            }
+            DuelRole::Join(addr) => {
+                println!("Joining the duel at {}...", addr);
+                logic::TcpTransport::connect(addr.as_str())
+            }
+        }
+    }
+}
+
+/// Resource holding this instance's side of the duel
+///
+/// The TCP transport cannot be shared between threads, so this is a non-send
+/// resource.
+struct Duel {
+    session: logic::Lockstep<logic::TcpTransport>,
+    stopped: bool,
+}
+
+/// Connects to the other instance and runs the duel until the window closes
+pub(crate) fn run(role: DuelRole) {
+    let transport = match role.connect() {
+        Ok(transport) => transport,
+        Err(err) => {
+            eprintln!("Could not start the duel: {}", err);
+            return;
+        }
+    };
+
+    let name = match role {
+        DuelRole::Host(_) => DUELISTS[0].0,
+        DuelRole::Join(_) => DUELISTS[1].0,
+    };
+    let encounter = duel_encounter();
+    let session = logic::Lockstep::new(encounter.clone(), DUEL_SEED, role.team(), transport);
+
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!("Bevy WASM F# Reference - Duel ({})", name),
+                resolution: (800., 600.).into(),
+                ..default()
+            }),
+            ..default()
+        }))
+        .insert_resource(CombatLog::new(10))
+        .insert_resource(encounter)
+        .insert_non_send_resource(Duel {
+            session,
+            stopped: false,
+        })
+        .add_systems(Startup, setup_duel)
+        .add_systems(
+            Update,
+            (
+                handle_duel_input,
+                poll_duel,
+                sync_characters,
+                update_health_bars,
+                animate_damage_numbers,
+                animate_attack_flash,
+                animate_hit_shake,
+            )
+                .chain(),
+        )
+        .run();
+}
+
+/// The same starting encounter on both instances: the host's duelist against
+/// the guest's
+fn duel_encounter() -> logic::Encounter {
+    let catalog = logic::AbilityCatalog::builtin();
+    let classes = logic::ClassCatalog::builtin();
+    let teams = [logic::Team::Allies, logic::Team::Enemies];
+
+    let duelists = DUELISTS
+        .iter()
+        .zip(teams)
+        .map(|((name, class), team)| {
+            classes
+                .require(class)
+                .expect("duelist class is in the catalog")
+                .combatant(team, name, DUEL_LEVEL, &catalog, logic::CooldownMode::Turns)
+                .expect("class abilities are in the catalog")
+        })
+        .collect();
+    logic::Encounter::new(duelists)
+}
+
+fn setup_duel(
+    mut commands: Commands,
+    encounter: Res<logic::Encounter>,
+    duel: NonSend<Duel>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    commands.spawn(Camera2d);
+
+    // This instance's duelist is blue on the left, the opponent red on the right
+    let local = duel.session.local_team();
+    for combatant in &encounter.combatants {
+        let (x, color) = if combatant.team == local {
+            (-200.0, Color::srgb(0.2, 0.4, 0.8))
+        } else {
+            (200.0, Color::srgb(0.8, 0.2, 0.2))
+        };
+        let position = column_position(x, 0, 1);
+        let entity = commands
+            .spawn((
+                combatant.id,
+                combatant.effective_character(),
+                Sprite {
+                    color,
+                    custom_size: Some(Vec2::new(60.0, 60.0)),
+                    ..default()
+                },
+                Transform::from_translation(position),
+            ))
+            .id();
+        spawn_health_bar(&mut commands, entity, position + Vec3::new(0.0, 50.0, 1.0));
+    }
+
+    combat_log.add("=== Duel Start ===".to_string());
+    combat_log.add("Press SPACE to attack on your turn!".to_string());
+    announce_turn(&duel.session, &mut combat_log);
+}
+
+/// System that sends the local duelist's action on Space, or a pass while it
+/// is stunned
+fn handle_duel_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut duel: NonSendMut<Duel>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    if duel.stopped || !duel.session.awaiting_local_action() {
+        return;
+    }
+
+    let state = duel.session.state();
+    let Some(actor) = state.current() else {
+        return;
+    };
+    let action = if state.combatants[actor.0].effects.is_stunned() {
+        logic::Action::Pass
+    } else if keys.just_pressed(KeyCode::Space) {
+        let Some(&target) = state.targets(actor, logic::Targeting::Enemy).first() else {
+            return;
+        };
+        logic::Action::UseAbility {
+            ability: DUEL_ABILITY.to_string(),
+            target,
+        }
+    } else {
+        return;
+    };
+
+    if let Err(err) = duel.session.submit(action) {
+        combat_log.add(format!("Cannot act: {}", err));
+    }
+}
+
+/// System that exchanges inputs with the other instance and shows every turn
+/// both have played
+fn poll_duel(
+    mut commands: Commands,
+    mut duel: NonSendMut<Duel>,
+    mut encounter: ResMut<logic::Encounter>,
+    mut combat_log: ResMut<CombatLog>,
+    combatants: Query<(Entity, &logic::CombatantId, &Transform, &Sprite)>,
+) {
+    if duel.stopped {
+        return;
+    }
+
+    let events = match duel.session.poll() {
+        Ok(events) => events,
+        Err(err) => {
+            combat_log.add(format!("Duel stopped: {}", err));
+            duel.stopped = true;
+            return;
+        }
+    };
+    if events.is_empty() {
+        return;
+    }
+
+    let session = &duel.session;
+    let state = session.state();
+    let id = |name: &str| {
+        state
+            .combatants
+            .iter()
+            .find(|c| c.character.name == name)
+            .map(|c| (c.id, c.team))
+    };
+    for event in &events {
+        if event.ability_used == logic::PASS {
+            combat_log.add(format!(
+                "{} is stunned and cannot act!",
+                event.attacker_name
+            ));
+        } else {
+            log_outcomes(&mut combat_log, event);
+        }
+        log_status_events(&mut combat_log, &event.status_events);
+
+        if let (Some((actor, team)), Some((target, _))) =
+            (id(&event.attacker_name), id(&event.defender_name))
+        {
+            // Red numbers for our hits, orange for the opponent's
+            let color = if team == session.local_team() {
+                Color::srgb(1.0, 0.3, 0.3)
+            } else {
+                Color::srgb(1.0, 0.6, 0.0)
+            };
+            animate_action(&mut commands, &combatants, actor, target, event, color);
+        }
+    }
+    info!(
+        "Turn {} in step, checksum {:016x}",
+        session.turn(),
+        session.checksum()
+    );
+
+    *encounter = state.clone();
+    announce_turn(session, &mut combat_log);
+    if session.is_over() {
+        spawn_result(&mut commands, session);
+    }
+}
+
+/// Logs whose turn it is, or who won
+fn announce_turn(session: &logic::Lockstep<logic::TcpTransport>, combat_log: &mut CombatLog) {
+    let state = session.state();
+    if session.is_over() {
+        if let Some(winner) = state.combatants.iter().find(|c| c.is_alive()) {
+            combat_log.add(format!("=== {} wins the duel! ===", winner.character.name));
+        }
+        return;
+    }
+
+    let Some(next) = state.current().and_then(|id| state.combatant(id)) else {
+        return;
+    };
+    if next.team == session.local_team() {
+        combat_log.add(format!("--- Your turn, {} ---", next.character.name));
+    } else {
+        combat_log.add(format!("--- Waiting for {} ---", next.character.name));
+    }
+}
+
+/// Shows VICTORY or DEFEAT for this instance's duelist
+fn spawn_result(commands: &mut Commands, session: &logic::Lockstep<logic::TcpTransport>) {
+    let allies_won = session.state().outcome() == logic::EncounterOutcome::Victory;
+    let victory = allies_won == (session.local_team() == logic::Team::Allies);
+    let (text, color) = if victory {
+        ("VICTORY!", Color::srgb(0.2, 1.0, 0.2))
+    } else {
+        ("DEFEAT!", Color::srgb(1.0, 0.2, 0.2))
+    };
+
+    commands.spawn((
+        GameOverMessage,
+        Text2d::new(text),
+        TextFont {
+            font_size: 72.0,
+            ..default()
+        },
+        TextColor(color),
+        Transform::from_translation(Vec3::new(0.0, 100.0, 100.0)),
+    ));
+}
@@ -1,57 +1,136 @@
 use bevy::prelude::*;
 use bevy_wasm_fsharp_ref_logic as logic;
+use logic::{AiPolicy, CombatRng, DamageFormula};
 use std::collections::VecDeque;
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
-/// Component marking the player entity
+pub mod clock;
+#[cfg(feature = "duel")]
+mod duel;
+
+/// Component marking a party member controlled by the player
 #[derive(Component)]
 struct Player;
 
-/// Component marking the monster entity
+/// Component marking a monster entity
 #[derive(Component)]
 struct Monster;
 
-/// Resource tracking monster abilities with cooldowns
-#[derive(Resource)]
-struct MonsterAbilities {
-    abilities: Vec<logic::AbilityWithMeta>,
-}
+/// Component naming the loot table a monster rolls on when defeated
+#[derive(Component)]
+struct Drops(String);
 
-impl MonsterAbilities {
-    fn new() -> Self {
-        Self {
-            abilities: vec![
-                logic::AbilityWithMeta::basic_attack_meta(),
-                logic::AbilityWithMeta::powerful_attack_meta(),
-                logic::AbilityWithMeta::heal_meta(),
+/// Component marking a defeated monster whose loot has been rolled
+#[derive(Component)]
+struct Looted;
+
+/// Ability party members use on Space
+const PLAYER_ABILITY: &str = "Basic Attack";
+
+/// Damage formula for this encounter; try `PercentArmor` or `AttackRatio`
+const DAMAGE_MODEL: logic::DamageModel = logic::DamageModel::FlatDefense;
+
+/// Vertical distance between combatants in the same column
+const ROW_SPACING: f32 = 120.0;
+
+/// The player's party for this encounter: names and class names
+const PARTY: [(&str, &str); 2] = [("Hero", "Warrior"), ("Ranger", "Ranger")];
+
+/// Level the party starts at
+const PARTY_LEVEL: u32 = 1;
+
+/// Consumable party members drink on P
+const POTION: &str = "Potion";
+
+/// Items each party member starts with; weapons and armor start equipped
+fn starting_items() -> Vec<logic::Item> {
+    vec![
+        logic::Item::equipment(
+            "Iron Sword",
+            logic::EquipmentSlot::Weapon,
+            vec![logic::StatModifier::Flat {
+                stat: logic::StatKind::Attack,
+                amount: 3,
+            }],
+        ),
+        logic::Item::equipment(
+            "Leather Armor",
+            logic::EquipmentSlot::Armor,
+            vec![
+                logic::StatModifier::Flat {
+                    stat: logic::StatKind::Defense,
+                    amount: 1,
+                },
+                logic::StatModifier::Percent {
+                    stat: logic::StatKind::MaxHp,
+                    percent: 10,
+                },
             ],
+        ),
+        logic::Item::potion(),
+        logic::Item::potion(),
+    ]
+}
+
+/// Seed for loot rolls, so every run of the demo drops the same loot
+const LOOT_SEED: u64 = 2024;
+
+/// Where the fight's replay is saved when it ends
+#[cfg(not(target_arch = "wasm32"))]
+const REPLAY_PATH: &str = "combat_replay.json";
+
+/// Seed for picking this run's encounter from the bestiary
+///
+/// Read from `--seed <n>` on the command line, so a fight can be replayed, or
+/// taken from the clock so each run meets a different encounter.
+#[derive(Resource, Clone, Copy)]
+struct RunSeed(u64);
+
+impl RunSeed {
+    /// Reads `--seed <n>` from the command line arguments after the program
+    /// name, falling back to the clock
+    fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        while let Some(arg) = args.next() {
+            if arg == "--seed" {
+                match args.next().map(|seed| seed.parse()) {
+                    Some(Ok(seed)) => return RunSeed(seed),
+                    _ => eprintln!("--seed needs a number, seeding from the clock"),
+                }
+                break;
+            }
         }
+        RunSeed::from_clock()
     }
 
-    fn tick_cooldowns(&mut self) {
-        for ability in &mut self.abilities {
-            ability.tick_cooldown();
-        }
+    fn from_clock() -> Self {
+        let now = bevy::utils::SystemTime::now()
+            .duration_since(bevy::utils::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        RunSeed(now.as_nanos() as u64)
     }
+}
 
-    fn activate_ability(&mut self, ability_name: &str) {
-        if let Some(ability) = self
-            .abilities
-            .iter_mut()
-            .find(|a| a.ability.name == ability_name)
-        {
-            ability.activate();
-        }
+/// Picks this run's encounter from the bestiary, or `None` if it has none
+fn pick_encounter(bestiary: &logic::Bestiary, seed: RunSeed) -> Option<&logic::EncounterTemplate> {
+    let encounters = bestiary.encounters();
+    if encounters.is_empty() {
+        return None;
     }
+    let pick = logic::SeededRng::new(seed.0).roll_below(encounters.len() as u32);
+    encounters.get(pick as usize)
+}
+
+/// Screen position of the `index`-th of `count` combatants in a column
+fn column_position(x: f32, index: usize, count: usize) -> Vec3 {
+    let offset = (count as f32 - 1.0) / 2.0 - index as f32;
+    Vec3::new(x, offset * ROW_SPACING, 0.0)
 }
 
 /// Component for health bar UI elements
 #[derive(Component)]
-struct HealthBar {
-    owner: Entity,
-}
+struct HealthBar;
 
 /// Component for health bar background
 #[derive(Component)]
@@ -95,18 +174,19 @@ struct HitShake {
 #[derive(Component)]
 struct GameOverMessage;
 
+/// Component for the marker above the selected target
+#[derive(Component)]
+struct TargetMarker;
+
 /// Resource tracking the current state of combat
-#[derive(Resource, Debug, Clone, PartialEq)]
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
 enum CombatState {
+    #[default]
     PlayerTurn,
     MonsterTurn,
-    GameOver { winner: String },
-}
-
-impl Default for CombatState {
-    fn default() -> Self {
-        CombatState::PlayerTurn
-    }
+    GameOver {
+        outcome: logic::EncounterOutcome,
+    },
 }
 
 /// Resource for tracking combat events and messages
@@ -144,22 +224,41 @@ impl CombatLog {
     }
 }
 
-/// Timer for automatic monster turn execution
+/// How long a monster waits before acting
+const MONSTER_TURN_DELAY: logic::Millis = logic::Millis::SECOND;
+
+/// Countdown to the next automatic monster turn
 #[derive(Resource)]
 struct MonsterTurnTimer {
-    timer: Timer,
+    remaining: logic::Millis,
 }
 
 impl Default for MonsterTurnTimer {
     fn default() -> Self {
         Self {
-            timer: Timer::from_seconds(1.0, TimerMode::Once),
+            remaining: MONSTER_TURN_DELAY,
         }
     }
 }
 
+/// Resource holding the generator loot is rolled with
+#[derive(Resource)]
+struct LootRng(logic::SeededRng);
+
+/// Resource tracking the enemy the player will attack on Space
+#[derive(Resource, Default)]
+struct SelectedTarget(Option<logic::CombatantId>);
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(start))]
 pub fn main() {
+    #[cfg(feature = "duel")]
+    if let Some(role) = duel::DuelRole::from_args(std::env::args().skip(1)) {
+        duel::run(role);
+        return;
+    }
+
+    let seed = RunSeed::from_args(std::env::args().skip(1));
+
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -174,70 +273,211 @@ pub fn main() {
             }),
             ..default()
         }))
-        .init_resource::<CombatState>()
-        .insert_resource(CombatLog::new(10))
-        .insert_resource(MonsterAbilities::new())
-        .init_resource::<MonsterTurnTimer>()
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                handle_player_turn,
-                handle_monster_turn,
-                check_game_over,
-                display_turn_indicator,
-                display_combat_log,
-                update_health_bars,
-                animate_damage_numbers,
-                animate_attack_flash,
-                animate_hit_shake,
+        .add_plugins(CombatDemoPlugin { seed: seed.0 })
+        .run();
+}
+
+/// Plugin with the combat demo's resources and systems, on top of Bevy's own
+/// plugins
+///
+/// Picks the encounter from the built-in bestiary with `seed`. Replace the
+/// [`logic::Bestiary`] resource before startup to fight from another one.
+pub struct CombatDemoPlugin {
+    pub seed: u64,
+}
+
+impl Plugin for CombatDemoPlugin {
+    fn build(&self, app: &mut App) {
+        // Without an encounter to fight, only the log and animations run
+        let in_encounter = resource_exists::<logic::Encounter>;
+
+        app.init_resource::<CombatState>()
+            .insert_resource(CombatLog::new(10))
+            .insert_resource(logic::AbilityCatalog::builtin())
+            .insert_resource(logic::ClassCatalog::builtin())
+            .insert_resource(logic::Bestiary::builtin())
+            .insert_resource(RunSeed(self.seed))
+            .insert_resource(logic::LootCatalog::builtin())
+            .insert_resource(LootRng(logic::SeededRng::new(LOOT_SEED)))
+            .init_resource::<SelectedTarget>()
+            .init_resource::<MonsterTurnTimer>()
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    handle_player_turn.run_if(in_encounter),
+                    sync_characters.run_if(in_encounter),
+                    drop_loot.run_if(in_encounter),
+                    check_game_over.run_if(in_encounter),
+                    display_turn_indicator,
+                    display_combat_log,
+                    update_health_bars,
+                    update_target_marker,
+                    animate_damage_numbers,
+                    animate_attack_flash,
+                    animate_hit_shake,
+                )
+                    .chain(),
             )
-                .chain(),
+            .add_plugins((clock::CombatClockPlugin, schedule_monster_turns));
+    }
+}
+
+/// Runs monster turns on the combat clock, in `FixedUpdate` with a fixed step
+/// and every frame without one, and logs real-time status effects
+fn schedule_monster_turns(app: &mut App) {
+    app.configure_sets(
+        Update,
+        clock::TickCombatClock
+            .after(handle_player_turn)
+            .before(sync_characters),
+    )
+    .add_systems(
+        FixedUpdate,
+        handle_monster_turn
+            .after(clock::TickCombatClock)
+            .run_if(clock::is_fixed.and(resource_exists::<logic::Encounter>)),
+    )
+    .add_systems(
+        Update,
+        (
+            handle_monster_turn
+                .run_if(not(clock::is_fixed).and(resource_exists::<logic::Encounter>)),
+            log_real_time_statuses,
         )
-        .run();
+            .after(clock::TickCombatClock)
+            .before(sync_characters),
+    );
 }
 
-fn setup(mut commands: Commands, mut combat_log: ResMut<CombatLog>) {
+fn setup(
+    mut commands: Commands,
+    catalog: Res<logic::AbilityCatalog>,
+    classes: Res<logic::ClassCatalog>,
+    bestiary: Res<logic::Bestiary>,
+    seed: Res<RunSeed>,
+    mut combat_state: ResMut<CombatState>,
+    mut combat_log: ResMut<CombatLog>,
+) {
     // Spawn 2D camera
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 
-    // Player: Blue circle at (-100, 0)
-    let player_entity = commands
-        .spawn((
-            Player,
-            logic::Character::new_player("Hero"),
-            Sprite {
-                color: Color::srgb(0.2, 0.4, 0.8),        // Blue
-                custom_size: Some(Vec2::new(60.0, 60.0)), // 60x60 square to represent circle
-                ..default()
-            },
-            Transform::from_xyz(-100.0, 0.0, 0.0),
-        ))
-        .id();
+    let Some(encounter_template) = pick_encounter(&bestiary, *seed) else {
+        error!("The bestiary has no encounters to fight");
+        combat_log.add("No encounters in the bestiary, there is nothing to fight!".to_string());
+        return;
+    };
 
-    // Monster: Red rectangle at (100, 0)
-    let monster_entity = commands
-        .spawn((
+    let party: Vec<logic::Combatant> = PARTY
+        .iter()
+        .map(|(name, class)| {
+            let mut member = classes
+                .require(class)
+                .expect("party class is in the catalog")
+                .combatant(
+                    logic::Team::Allies,
+                    name,
+                    PARTY_LEVEL,
+                    &catalog,
+                    logic::CooldownMode::Turns,
+                )
+                .expect("class abilities are in the catalog")
+                .with_inventory(logic::Inventory::new(starting_items()));
+            for item in ["Iron Sword", "Leather Armor"] {
+                member.equip(item).expect("starting gear is equipment");
+            }
+            // Start at full HP including the armor's bonus
+            member.character.hp = member.effective_stats().hp;
+            member
+        })
+        .collect();
+    info!(
+        "Encounter {} picked with seed {}, pass --seed {} to fight it again",
+        encounter_template.id, seed.0, seed.0
+    );
+    let monsters = bestiary
+        .spawn_encounter(&encounter_template.id, &catalog, logic::CooldownMode::Turns)
+        .expect("bestiary abilities are in the catalog");
+
+    let mut combatants = Vec::new();
+
+    // Party: blue squares in a column on the left
+    let party_size = party.len();
+    for (index, member) in party.into_iter().enumerate() {
+        let position = column_position(-200.0, index, party_size);
+        let entity = commands
+            .spawn((
+                Player,
+                logic::CombatantId(combatants.len()),
+                member.effective_character(),
+                Sprite {
+                    color: Color::srgb(0.2, 0.4, 0.8),        // Blue
+                    custom_size: Some(Vec2::new(60.0, 60.0)), // 60x60 square to represent circle
+                    ..default()
+                },
+                Transform::from_translation(position),
+            ))
+            .id();
+        spawn_health_bar(&mut commands, entity, position + Vec3::new(0.0, 50.0, 1.0));
+
+        combatants.push(member);
+    }
+
+    // Monsters: red rectangles in a column on the right
+    let monster_count = monsters.len();
+    for (index, (monster, template)) in monsters.iter().enumerate() {
+        let position = column_position(200.0, index, monster_count);
+        let mut entity = commands.spawn((
             Monster,
-            logic::Character::new_monster("Slime"),
+            logic::CombatantId(combatants.len()),
+            monster.character.clone(),
+            template.personality,
             Sprite {
                 color: Color::srgb(0.8, 0.2, 0.2),        // Red
                 custom_size: Some(Vec2::new(50.0, 60.0)), // 50x60 rectangle
                 ..default()
             },
-            Transform::from_xyz(100.0, 0.0, 0.0),
-        ))
-        .id();
+            Transform::from_translation(position),
+        ));
+        if let Some(table) = &template.loot {
+            entity.insert(Drops(table.clone()));
+        }
+        let entity = entity.id();
+        spawn_health_bar(&mut commands, entity, position + Vec3::new(0.0, 50.0, 1.0));
 
-    // Create health bars for player
-    spawn_health_bar(&mut commands, player_entity, Vec3::new(-100.0, 50.0, 1.0));
+        combatants.push(monster.clone());
+    }
 
-    // Create health bars for monster
-    spawn_health_bar(&mut commands, monster_entity, Vec3::new(100.0, 50.0, 1.0));
+    // Marker shown above the selected target
+    commands.spawn((
+        TargetMarker,
+        Sprite {
+            color: Color::srgb(1.0, 0.9, 0.2), // Yellow
+            custom_size: Some(Vec2::new(16.0, 8.0)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 5.0),
+        Visibility::Hidden,
+    ));
 
     // Welcome message
     combat_log.add("=== Combat Start ===".to_string());
-    combat_log.add("Press SPACE to attack on your turn!".to_string());
+    combat_log.add(format!("Damage formula: {}", DAMAGE_MODEL.name()));
+    combat_log.add(format!("Encounter: {}", encounter_template.name));
+    for (monster, template) in &monsters {
+        combat_log.add(format!(
+            "{} ({}) appears!",
+            monster.character.name,
+            template.personality.name()
+        ));
+    }
+    combat_log
+        .add("Press TAB to choose a target, SPACE to attack and P to drink a potion!".to_string());
+
+    let encounter = logic::Encounter::new(combatants).with_formula(DAMAGE_MODEL);
+    advance_turn(&encounter, &mut combat_state, &mut combat_log);
+    commands.insert_resource(logic::Replay::new(encounter.clone()));
+    commands.insert_resource(encounter);
 }
 
 /// Helper function to spawn a health bar for a character entity
@@ -248,7 +488,7 @@ fn spawn_health_bar(commands: &mut Commands, owner: Entity, position: Vec3) {
     // Background (red)
     commands.spawn((
         HealthBarBackground,
-        HealthBar { owner },
+        HealthBar,
         Sprite {
             color: Color::srgb(0.6, 0.1, 0.1), // Dark red
             custom_size: Some(Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
@@ -282,237 +522,384 @@ fn spawn_health_bar(commands: &mut Commands, owner: Entity, position: Vec3) {
 }
 
 /// System that handles player input and actions during PlayerTurn
+#[allow(clippy::too_many_arguments)]
 fn handle_player_turn(
     keys: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
     mut combat_state: ResMut<CombatState>,
     mut combat_log: ResMut<CombatLog>,
-    mut players: Query<(Entity, &mut logic::Character, &Transform, &Sprite), With<Player>>,
-    mut monsters: Query<
-        (Entity, &mut logic::Character, &Transform, &mut Sprite),
-        (With<Monster>, Without<Player>),
-    >,
+    mut encounter: ResMut<logic::Encounter>,
+    mut replay: ResMut<logic::Replay>,
+    mut selected: ResMut<SelectedTarget>,
+    combatants: Query<(Entity, &logic::CombatantId, &Transform, &Sprite)>,
 ) {
     // Only process during player's turn
     if *combat_state != CombatState::PlayerTurn {
         return;
     }
 
-    // Wait for space key press
-    if !keys.just_pressed(KeyCode::Space) {
+    let Some(actor) = encounter.current() else {
+        return;
+    };
+
+    // A stunned party member loses its turn
+    if encounter.combatants[actor.0].effects.is_stunned() {
+        dispatch(
+            &mut encounter,
+            &mut replay,
+            &logic::Action::Pass,
+            &mut combat_state,
+            &mut combat_log,
+        );
         return;
     }
 
-    // Get player and monster
-    let (player_entity, player, _player_transform, player_sprite) =
-        match players.iter_mut().next() {
-            Some(p) => p,
-            None => return,
+    // Keep the selection on a living enemy, TAB cycles through them
+    let targets = encounter.targets(actor, logic::Targeting::Enemy);
+    let mut index = selected
+        .0
+        .and_then(|target| targets.iter().position(|&t| t == target))
+        .unwrap_or(0);
+    if keys.just_pressed(KeyCode::Tab) && !targets.is_empty() {
+        index = (index + 1) % targets.len();
+        let name = &encounter.combatants[targets[index].0].character.name;
+        combat_log.add(format!("Targeting {}", name));
+    }
+    selected.0 = targets.get(index).copied();
+
+    // P drinks a potion, Space attacks the selected target
+    let (action, target) = if keys.just_pressed(KeyCode::KeyP) {
+        if encounter.combatants[actor.0]
+            .inventory
+            .get(POTION)
+            .is_none()
+        {
+            combat_log.add("No potions left!".to_string());
+            return;
+        }
+        let action = logic::Action::UseItem {
+            item: POTION.to_string(),
+            target: actor,
         };
-
-    let (monster_entity, mut monster, monster_transform, _monster_sprite) =
-        match monsters.iter_mut().next() {
-            Some(m) => m,
-            None => return,
+        (action, actor)
+    } else if keys.just_pressed(KeyCode::Space) {
+        let Some(target) = selected.0 else {
+            return;
         };
-
-    // Execute attack
-    let ability = logic::Ability::basic_attack();
-    let event = logic::compute_attack(&player, &monster, &ability);
-
-    // Log the attack
-    combat_log.add(format!(
-        "{} attacks {} for {} damage!",
-        event.attacker_name, event.defender_name, event.damage
-    ));
-
-    // Apply damage
-    monster.hp = event.defender_hp_after;
-
-    // Spawn damage number at monster position
-    spawn_damage_number(
-        &mut commands,
-        event.damage,
-        monster_transform.translation + Vec3::new(0.0, 30.0, 10.0),
-        Color::srgb(1.0, 0.3, 0.3), // Red damage numbers for player attacking
-    );
-
-    // Add attack flash to player
-    commands.entity(player_entity).insert(AttackFlash {
-        timer: Timer::from_seconds(0.15, TimerMode::Once),
-        original_color: player_sprite.color,
-    });
-
-    // Add hit shake to monster
-    commands.entity(monster_entity).insert(HitShake {
-        timer: Timer::from_seconds(0.3, TimerMode::Once),
-        original_position: monster_transform.translation,
-        intensity: 5.0,
-    });
-
-    // Log HP status
-    combat_log.add(format!(
-        "{} HP: {} / {}",
-        monster.name, monster.hp, monster.stats.hp
-    ));
-
-    // Check if monster is defeated
-    if monster.hp <= 0 {
-        combat_log.add(format!("{} has been defeated!", monster.name));
-        *combat_state = CombatState::GameOver {
-            winner: player.name.clone(),
+        let action = logic::Action::UseAbility {
+            ability: PLAYER_ABILITY.to_string(),
+            target,
         };
+        (action, target)
     } else {
-        // Switch to monster's turn
-        *combat_state = CombatState::MonsterTurn;
-        combat_log.add("--- Monster's Turn ---".to_string());
+        return;
+    };
+
+    if let Some(event) = dispatch(
+        &mut encounter,
+        &mut replay,
+        &action,
+        &mut combat_state,
+        &mut combat_log,
+    ) {
+        // Red damage numbers for player attacking
+        animate_action(
+            &mut commands,
+            &combatants,
+            actor,
+            target,
+            &event,
+            Color::srgb(1.0, 0.3, 0.3),
+        );
     }
 }
 
 /// System that handles automatic monster actions during MonsterTurn
+#[allow(clippy::too_many_arguments)]
 fn handle_monster_turn(
-    time: Res<Time>,
+    clock: Res<clock::CombatClock>,
     mut commands: Commands,
     mut timer: ResMut<MonsterTurnTimer>,
     mut combat_state: ResMut<CombatState>,
     mut combat_log: ResMut<CombatLog>,
-    mut monster_abilities: ResMut<MonsterAbilities>,
-    mut players: Query<
-        (Entity, &mut logic::Character, &Transform, &mut Sprite),
-        With<Player>,
-    >,
-    mut monsters: Query<
-        (Entity, &mut logic::Character, &Transform, &Sprite),
-        (With<Monster>, Without<Player>),
-    >,
+    mut encounter: ResMut<logic::Encounter>,
+    mut replay: ResMut<logic::Replay>,
+    combatants: Query<(Entity, &logic::CombatantId, &Transform, &Sprite)>,
+    personalities: Query<(&logic::CombatantId, &logic::AiPersonality)>,
 ) {
     // Only process during monster's turn
     if *combat_state != CombatState::MonsterTurn {
         return;
     }
 
-    // Tick the timer
-    timer.timer.tick(time.delta());
+    timer.remaining = timer.remaining.saturating_sub(clock.delta());
 
-    // Wait for timer to finish
-    if !timer.timer.finished() {
+    // Wait for the countdown to finish
+    if !timer.remaining.is_zero() {
         return;
     }
 
-    // Reset timer for next turn
-    timer.timer.reset();
+    // Reset the countdown for next turn
+    timer.remaining = MONSTER_TURN_DELAY;
 
-    // Tick cooldowns at the start of turn
-    monster_abilities.tick_cooldowns();
+    let Some(actor) = encounter.current() else {
+        return;
+    };
 
-    // Get player and monster
-    let (monster_entity, mut monster, _monster_transform, monster_sprite) =
-        match monsters.iter_mut().next() {
-            Some(m) => m,
-            None => return,
-        };
+    // The monster's personality picks an ability and a target: itself for heals,
+    // the weakest party member for attacks. Stunned monsters pass.
+    let personality = personalities
+        .iter()
+        .find(|(id, _)| **id == actor)
+        .map(|(_, personality)| *personality)
+        .unwrap_or_default();
+    let Some(action) = logic::choose_ai_action(&encounter, &personality) else {
+        return;
+    };
 
-    let (player_entity, mut player, player_transform, _player_sprite) =
-        match players.iter_mut().next() {
-            Some(p) => p,
-            None => return,
-        };
+    let event = dispatch(
+        &mut encounter,
+        &mut replay,
+        &action,
+        &mut combat_state,
+        &mut combat_log,
+    );
+    if let (Some(event), logic::Action::UseAbility { target, .. }) = (event, &action) {
+        // Orange damage numbers for monster attacking
+        animate_action(
+            &mut commands,
+            &combatants,
+            actor,
+            *target,
+            &event,
+            Color::srgb(1.0, 0.6, 0.0),
+        );
+    }
+}
 
-    // AI chooses the best action based on current health
-    let ability = logic::choose_monster_action(&monster, &player, &monster_abilities.abilities);
+/// Runs `action` for the current combatant through [`logic::reduce`], records it
+/// in the replay, logs what happened and moves on to the next turn
+///
+/// Returns the turn's event, or `None` if the action was rejected.
+fn dispatch(
+    encounter: &mut logic::Encounter,
+    replay: &mut logic::Replay,
+    action: &logic::Action,
+    combat_state: &mut CombatState,
+    combat_log: &mut CombatLog,
+) -> Option<logic::CombatEvent> {
+    let (next, events) = match replay.play(encounter, action) {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("Rejected {:?}: {}", action, err);
+            return None;
+        }
+    };
 
-    // Check if this is a heal ability (negative power)
-    let is_heal = ability.power < 0;
+    for event in &events {
+        if event.ability_used == logic::PASS {
+            combat_log.add(format!(
+                "{} is stunned and cannot act!",
+                event.attacker_name
+            ));
+        } else {
+            log_outcomes(combat_log, event);
+        }
+        log_status_events(combat_log, &event.status_events);
+    }
 
-    let event = if is_heal {
-        // Healing: monster heals itself
-        let event = logic::compute_attack(&monster, &monster, &ability);
-        monster.hp = event.defender_hp_after.min(monster.stats.hp); // Cap at max HP
-        event
-    } else {
-        // Attack: monster attacks player
-        logic::compute_attack(&monster, &player, &ability)
-    };
+    // Report the target's HP and anyone defeated this turn
+    if let logic::Action::UseAbility { target, .. } = action {
+        let character = next.combatants[target.0].effective_character();
+        if character.hp > 0 {
+            combat_log.add(format!(
+                "{} HP: {} / {}",
+                character.name, character.hp, character.stats.hp
+            ));
+        }
+    }
+    for (before, after) in encounter.combatants.iter().zip(&next.combatants) {
+        if before.is_alive() && !after.is_alive() {
+            combat_log.add(format!("{} has been defeated!", after.character.name));
+        }
+    }
+    for event in &events {
+        log_xp_grants(combat_log, &event.xp_grants);
+    }
 
-    // Activate cooldown for the ability used
-    monster_abilities.activate_ability(&ability.name);
+    *encounter = next;
+    advance_turn(encounter, combat_state, combat_log);
+    events.into_iter().next()
+}
 
-    // Log the action
-    if is_heal {
-        let heal_amount = -event.damage; // Negative damage = healing
-        combat_log.add(format!(
-            "{} uses {} and heals {} HP!",
-            monster.name, ability.name, heal_amount
-        ));
+/// Spawns a damage or heal number at the target, shakes it when hurt and flashes
+/// the actor
+fn animate_action(
+    commands: &mut Commands,
+    combatants: &Query<(Entity, &logic::CombatantId, &Transform, &Sprite)>,
+    actor: logic::CombatantId,
+    target: logic::CombatantId,
+    event: &logic::CombatEvent,
+    damage_color: Color,
+) {
+    let is_heal = event
+        .outcomes
+        .iter()
+        .any(|outcome| matches!(outcome, logic::EffectOutcome::Healed { .. }));
+    let find = |id: logic::CombatantId| combatants.iter().find(|(_, c, _, _)| **c == id);
 
-        // Spawn heal number at monster position
+    if let Some((entity, _, transform, _)) = find(target) {
         spawn_damage_number(
-            &mut commands,
-            -heal_amount, // Display as negative to show healing
-            _monster_transform.translation + Vec3::new(0.0, 30.0, 10.0),
-            Color::srgb(0.2, 1.0, 0.2), // Green for healing
+            commands,
+            event.effective_amount, // HP actually lost, so heals show as negative numbers
+            transform.translation + Vec3::new(0.0, 30.0, 10.0),
+            if is_heal {
+                Color::srgb(0.2, 1.0, 0.2) // Green for healing
+            } else {
+                damage_color
+            },
         );
 
-        // Add flash effect to monster (green tint for heal)
-        commands.entity(monster_entity).insert(AttackFlash {
+        if !is_heal {
+            // Add hit shake to target
+            commands.entity(entity).insert(HitShake {
+                timer: Timer::from_seconds(0.3, TimerMode::Once),
+                original_position: transform.translation,
+                intensity: 5.0,
+            });
+        }
+    }
+
+    if let Some((entity, _, _, sprite)) = find(actor) {
+        // Add attack flash to the actor
+        commands.entity(entity).insert(AttackFlash {
             timer: Timer::from_seconds(0.15, TimerMode::Once),
-            original_color: monster_sprite.color,
+            original_color: sprite.color,
         });
-    } else {
-        combat_log.add(format!(
-            "{} uses {} on {} for {} damage!",
-            event.attacker_name, ability.name, event.defender_name, event.damage
-        ));
+    }
+}
 
-        // Apply damage to player
-        player.hp = event.defender_hp_after;
+/// Starts the next combatant's turn, or ends combat once a team is defeated
+fn advance_turn(
+    encounter: &logic::Encounter,
+    combat_state: &mut CombatState,
+    combat_log: &mut CombatLog,
+) {
+    let outcome = encounter.outcome();
+    if outcome != logic::EncounterOutcome::Ongoing {
+        *combat_state = CombatState::GameOver { outcome };
+        return;
+    }
 
-        // Spawn damage number at player position
-        spawn_damage_number(
-            &mut commands,
-            event.damage,
-            player_transform.translation + Vec3::new(0.0, 30.0, 10.0),
-            Color::srgb(1.0, 0.6, 0.0), // Orange damage numbers for monster attacking
-        );
+    let Some(next) = encounter.current().and_then(|id| encounter.combatant(id)) else {
+        return;
+    };
 
-        // Add attack flash to monster
-        commands.entity(monster_entity).insert(AttackFlash {
-            timer: Timer::from_seconds(0.15, TimerMode::Once),
-            original_color: monster_sprite.color,
-        });
+    *combat_state = match next.team {
+        logic::Team::Allies => CombatState::PlayerTurn,
+        logic::Team::Enemies => CombatState::MonsterTurn,
+    };
+    combat_log.add(format!("--- {}'s Turn ---", next.character.name));
+}
 
-        // Add hit shake to player
-        commands.entity(player_entity).insert(HitShake {
-            timer: Timer::from_seconds(0.3, TimerMode::Once),
-            original_position: player_transform.translation,
-            intensity: 5.0,
-        });
+/// Adds a combat log line when an attack's element hit a weakness, resistance or immunity
+fn log_effectiveness(combat_log: &mut CombatLog, event: &logic::CombatEvent) {
+    if let Some(label) = event.effectiveness.label() {
+        combat_log.add(format!("It's {}!", label));
+    }
+}
 
-        // Log HP status
-        combat_log.add(format!(
-            "{} HP: {} / {}",
-            player.name, player.hp, player.stats.hp
-        ));
+/// Adds a combat log line for each damage, heal or miss the ability produced
+///
+/// Status changes are logged separately from the event's status events.
+fn log_outcomes(combat_log: &mut CombatLog, event: &logic::CombatEvent) {
+    for outcome in &event.outcomes {
+        match outcome {
+            logic::EffectOutcome::Damaged { amount } => {
+                combat_log.add(format!(
+                    "{} uses {} on {} for {} damage!",
+                    event.attacker_name, event.ability_used, event.defender_name, amount
+                ));
+                log_effectiveness(combat_log, event);
+            }
+            logic::EffectOutcome::Healed { amount } => combat_log.add(format!(
+                "{} uses {} and heals {} for {} HP!",
+                event.attacker_name, event.ability_used, event.defender_name, amount
+            )),
+            logic::EffectOutcome::Evaded => combat_log.add(format!(
+                "{} uses {} but {} evades!",
+                event.attacker_name, event.ability_used, event.defender_name
+            )),
+            logic::EffectOutcome::StatusApplied { .. }
+            | logic::EffectOutcome::StatusIgnored { .. } => {}
+        }
     }
 
-    // Log monster HP if healed
-    if is_heal {
-        combat_log.add(format!(
-            "{} HP: {} / {}",
-            monster.name, monster.hp, monster.stats.hp
-        ));
+    if event.killing_blow && event.overkill > 0 {
+        combat_log.add(format!("Overkill by {}!", event.overkill));
     }
+}
 
-    // Check if player is defeated
-    if player.hp <= 0 {
-        combat_log.add(format!("{} has been defeated!", player.name));
-        *combat_state = CombatState::GameOver {
-            winner: monster.name.clone(),
+/// Adds combat log lines for XP earned, levels gained and abilities learned
+fn log_xp_grants(combat_log: &mut CombatLog, grants: &[logic::XpGrant]) {
+    for grant in grants {
+        combat_log.add(format!("{} gains {} XP", grant.recipient, grant.amount));
+        if grant.levels_gained > 0 {
+            combat_log.add(format!(
+                "{} reaches level {}!",
+                grant.recipient, grant.level_after
+            ));
+        }
+        for ability in &grant.unlocked {
+            combat_log.add(format!("{} learns {}!", grant.recipient, ability));
+        }
+    }
+}
+
+/// System that logs status effects ticking or expiring on the combat clock
+fn log_real_time_statuses(
+    mut statuses: EventReader<clock::RealTimeStatus>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    let events: Vec<_> = statuses.read().map(|status| status.0.clone()).collect();
+    log_status_events(&mut combat_log, &events);
+}
+
+/// Adds a combat log line for each status effect change
+fn log_status_events(combat_log: &mut CombatLog, events: &[logic::StatusEvent]) {
+    for event in events {
+        let message = match event {
+            logic::StatusEvent::Applied {
+                target,
+                kind,
+                stacks,
+            } => format!("{} is afflicted with {:?} (x{})", target, kind, stacks),
+            logic::StatusEvent::Ticked {
+                target,
+                kind,
+                amount,
+                hp_after,
+            } if *amount < 0 => format!(
+                "{} recovers {} HP from {:?} (HP: {})",
+                target, -amount, kind, hp_after
+            ),
+            logic::StatusEvent::Ticked {
+                target,
+                kind,
+                amount,
+                hp_after,
+            } => format!(
+                "{} takes {} {:?} damage (HP: {})",
+                target, amount, kind, hp_after
+            ),
+            logic::StatusEvent::Absorbed { target, amount } => {
+                format!("{}'s shield absorbs {} damage", target, amount)
+            }
+            logic::StatusEvent::Expired { target, kind } => {
+                format!("{}'s {:?} wears off", target, kind)
+            }
         };
-    } else {
-        // Switch back to player's turn
-        *combat_state = CombatState::PlayerTurn;
-        combat_log.add("--- Player's Turn ---".to_string());
+        combat_log.add(message);
     }
 }
 
@@ -521,6 +908,7 @@ fn check_game_over(
     combat_state: Res<CombatState>,
     mut commands: Commands,
     mut combat_log: ResMut<CombatLog>,
+    replay: Res<logic::Replay>,
     existing_message: Query<Entity, With<GameOverMessage>>,
 ) {
     // Only log once when entering game over state
@@ -528,19 +916,21 @@ fn check_game_over(
         return;
     }
 
-    if let CombatState::GameOver { winner } = &*combat_state {
+    if let CombatState::GameOver { outcome } = &*combat_state {
+        let victory = *outcome == logic::EncounterOutcome::Victory;
         combat_log.add("=== GAME OVER ===".to_string());
-        combat_log.add(format!("{} wins!", winner));
+        combat_log.add(if victory {
+            "The party wins!".to_string()
+        } else {
+            "The monsters win!".to_string()
+        });
+        save_replay(&replay, &mut combat_log);
         combat_log.add("Close the window to exit.".to_string());
 
         // Spawn game over message (only if not already spawned)
         if existing_message.is_empty() {
-            let message_text = if winner == "Hero" {
-                "VICTORY!"
-            } else {
-                "DEFEAT!"
-            };
-            let message_color = if winner == "Hero" {
+            let message_text = if victory { "VICTORY!" } else { "DEFEAT!" };
+            let message_color = if victory {
                 Color::srgb(0.2, 1.0, 0.2) // Green for victory
             } else {
                 Color::srgb(1.0, 0.2, 0.2) // Red for defeat
@@ -560,6 +950,22 @@ fn check_game_over(
     }
 }
 
+/// Writes the finished fight to [`REPLAY_PATH`] so it can be attached to a bug
+/// report; on the web, where there is no file system, it goes to the console
+fn save_replay(replay: &logic::Replay, combat_log: &mut CombatLog) {
+    #[cfg(not(target_arch = "wasm32"))]
+    match std::fs::write(REPLAY_PATH, replay.to_json()) {
+        Ok(()) => combat_log.add(format!("Replay saved to {}", REPLAY_PATH)),
+        Err(err) => warn!("Could not save the replay: {}", err),
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        info!("Replay:\n{}", replay.to_json());
+        combat_log.add("Replay written to the browser console".to_string());
+    }
+}
+
 /// System that displays the current turn indicator
 fn display_turn_indicator(combat_state: Res<CombatState>) {
     // Only log when state changes
@@ -570,15 +976,15 @@ fn display_turn_indicator(combat_state: Res<CombatState>) {
     match &*combat_state {
         CombatState::PlayerTurn => {
             println!("\n>>> YOUR TURN <<<");
-            println!("Press SPACE to attack!");
+            println!("Press TAB to switch target, SPACE to attack, P to drink a potion!");
         }
         CombatState::MonsterTurn => {
             println!("\n>>> MONSTER'S TURN <<<");
             println!("Monster is preparing to attack...");
         }
-        CombatState::GameOver { winner } => {
+        CombatState::GameOver { outcome } => {
             println!("\n>>> GAME OVER <<<");
-            println!("Winner: {}", winner);
+            println!("Outcome: {:?}", outcome);
         }
     }
 }
@@ -727,3 +1133,95 @@ fn animate_hit_shake(
         }
     }
 }
+
+/// System that copies combatant state from the encounter onto their entities
+fn sync_characters(
+    encounter: Res<logic::Encounter>,
+    mut characters: Query<(&logic::CombatantId, &mut logic::Character, &mut Sprite)>,
+) {
+    if !encounter.is_changed() {
+        return;
+    }
+
+    for (id, mut character, mut sprite) in characters.iter_mut() {
+        if let Some(combatant) = encounter.combatant(*id) {
+            // Show the stats the combatant fights with, equipment included
+            *character = combatant.effective_character();
+
+            // Grey out defeated combatants
+            if !combatant.is_alive() {
+                sprite.color = Color::srgb(0.3, 0.3, 0.3);
+            }
+        }
+    }
+}
+
+/// System that rolls and logs the loot of monsters defeated since the last frame
+fn drop_loot(
+    mut commands: Commands,
+    encounter: Res<logic::Encounter>,
+    loot: Res<logic::LootCatalog>,
+    mut rng: ResMut<LootRng>,
+    mut combat_log: ResMut<CombatLog>,
+    monsters: Query<(Entity, &logic::CombatantId, &Drops), Without<Looted>>,
+) {
+    if !encounter.is_changed() {
+        return;
+    }
+
+    for (entity, id, drops) in monsters.iter() {
+        let Some(combatant) = encounter.combatant(*id) else {
+            continue;
+        };
+        if combatant.is_alive() {
+            continue;
+        }
+
+        let name = &combatant.character.name;
+        match loot.roll(&drops.0, &mut rng.0) {
+            Ok(items) if items.is_empty() => combat_log.add(format!("{} dropped nothing", name)),
+            Ok(items) => {
+                let items: Vec<String> = items.iter().map(describe_loot).collect();
+                combat_log.add(format!("{} dropped {}", name, items.join(", ")));
+            }
+            Err(err) => warn!("No loot for {}: {}", name, err),
+        }
+        commands.entity(entity).insert(Looted);
+    }
+}
+
+/// "2x Potion" or "Ruby Ring (Rare)"; common drops show no rarity
+fn describe_loot(loot: &logic::Loot) -> String {
+    let mut text = loot.item.name.clone();
+    if loot.count > 1 {
+        text = format!("{}x {}", loot.count, text);
+    }
+    if loot.rarity != logic::Rarity::Common {
+        text = format!("{} ({:?})", text, loot.rarity);
+    }
+    text
+}
+
+/// System that shows the target marker above the selected enemy during the player's turn
+fn update_target_marker(
+    combat_state: Res<CombatState>,
+    selected: Res<SelectedTarget>,
+    combatants: Query<(&logic::CombatantId, &Transform), Without<TargetMarker>>,
+    mut markers: Query<(&mut Transform, &mut Visibility), With<TargetMarker>>,
+) {
+    let target = combatants
+        .iter()
+        .find(|(id, _)| Some(**id) == selected.0)
+        .filter(|_| *combat_state == CombatState::PlayerTurn);
+
+    for (mut transform, mut visibility) in markers.iter_mut() {
+        match target {
+            Some((_, target_transform)) => {
+                transform.translation =
+                    target_transform.translation.with_z(5.0) + Vec3::new(0.0, 75.0, 0.0);
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Tests for the combat clock ticking real-time cooldowns and status effects
+//!
+//! The fixed-step tests drive `FixedUpdate` directly, one schedule run per
+//! tick, so they don't depend on how fast the test machine runs.
+
+use app::clock::{CombatClock, CombatClockPlugin, RealTimeStatus};
+use bevy::prelude::*;
+use bevy_wasm_fsharp_ref_logic as logic;
+use std::time::Duration;
+
+/// Index of Powerful Attack, with its 3 second cooldown, in the default set
+const POWERFUL_ATTACK: usize = 1;
+
+/// A hero with real-time cooldowns, Powerful Attack just used, and two
+/// seconds of poison dealing 2 damage a second
+fn poisoned_hero() -> logic::Encounter {
+    let mut hero = logic::Combatant::new(
+        logic::Team::Allies,
+        logic::Character::new_player("Hero"),
+        logic::AbilitySet::player_default(),
+    );
+    hero.abilities.abilities[POWERFUL_ATTACK].use_ability();
+    hero.effects.apply(
+        "Hero",
+        &logic::StatusEffect {
+            kind: logic::StatusKind::Poison,
+            magnitude: 2,
+            duration: logic::EffectDuration::Seconds(logic::Millis::from_secs(2)),
+            stacking: logic::StackingRule::Refresh,
+        },
+    );
+    logic::Encounter::new(vec![hero])
+}
+
+fn create_clock_app(clock: CombatClock) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, CombatClockPlugin))
+        .insert_resource(clock)
+        .insert_resource(poisoned_hero());
+    app
+}
+
+fn run_fixed_ticks(app: &mut App, ticks: u32) {
+    for _ in 0..ticks {
+        app.world_mut().run_schedule(FixedUpdate);
+    }
+}
+
+fn hero(app: &App) -> &logic::Combatant {
+    &app.world().resource::<logic::Encounter>().combatants[0]
+}
+
+fn hero_hp(app: &App) -> i32 {
+    hero(app).character.hp
+}
+
+#[test]
+fn test_fixed_ticks_advance_cooldowns_and_effects() {
+    let mut app = create_clock_app(CombatClock::fixed(logic::Millis(20)));
+    let start_hp = hero(&app).character.hp;
+
+    // 149 ticks of 20 ms fall just short of the 3 second cooldown
+    run_fixed_ticks(&mut app, 149);
+    assert!(!hero(&app).abilities.abilities[POWERFUL_ATTACK].is_ready());
+
+    run_fixed_ticks(&mut app, 1);
+    let hero = hero(&app);
+    assert!(hero.abilities.abilities[POWERFUL_ATTACK].is_ready());
+
+    // Poison ticked once per second for two seconds, then wore off
+    assert_eq!(hero.character.hp, start_hp - 4);
+    assert!(hero.effects.effects.is_empty());
+
+    let events = app.world().resource::<Events<RealTimeStatus>>();
+    let statuses: Vec<_> = events.iter_current_update_events().collect();
+    assert_eq!(statuses.len(), 3);
+    assert!(matches!(
+        statuses[2].0,
+        logic::StatusEvent::Expired {
+            kind: logic::StatusKind::Poison,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_regen_heals_up_to_effective_max_hp() {
+    // Armor raises the hero's max HP by 10%
+    let mut hero = logic::Combatant::new(
+        logic::Team::Allies,
+        logic::Character::new_player("Hero"),
+        logic::AbilitySet::player_default(),
+    )
+    .with_inventory(logic::Inventory::new(vec![logic::Item::equipment(
+        "Leather Armor",
+        logic::EquipmentSlot::Armor,
+        vec![logic::StatModifier::Percent {
+            stat: logic::StatKind::MaxHp,
+            percent: 10,
+        }],
+    )]));
+    hero.equip("Leather Armor").unwrap();
+    let max_hp = hero.effective_stats().hp;
+    assert!(max_hp > hero.character.stats.hp);
+
+    hero.character.hp = max_hp - 15;
+    hero.effects.apply(
+        "Hero",
+        &logic::StatusEffect {
+            kind: logic::StatusKind::Regen,
+            magnitude: 10,
+            duration: logic::EffectDuration::Seconds(logic::Millis::from_secs(2)),
+            stacking: logic::StackingRule::Refresh,
+        },
+    );
+
+    let mut app = create_clock_app(CombatClock::fixed(logic::Millis(20)));
+    app.insert_resource(logic::Encounter::new(vec![hero]));
+    run_fixed_ticks(&mut app, 50);
+
+    // One second heals 10, the next is capped at the armored max HP
+    assert_eq!(hero_hp(&app), max_hp - 5);
+    run_fixed_ticks(&mut app, 50);
+    assert_eq!(hero_hp(&app), max_hp);
+}
+
+#[test]
+fn test_frame_time_carries_sub_millisecond_remainder() {
+    let mut clock = CombatClock::frame_time();
+
+    // 4000 frames at 4000 fps add up to exactly one second
+    let elapsed = (0..4000)
+        .map(|_| clock.advance(Duration::from_micros(250)))
+        .fold(logic::Millis::ZERO, logic::Millis::saturating_add);
+    assert_eq!(elapsed, logic::Millis::SECOND);
+
+    // A 16.6 ms frame advances 16 ms and carries the rest to the next
+    assert_eq!(
+        clock.advance(Duration::from_micros(16_600)),
+        logic::Millis(16)
+    );
+    assert_eq!(clock.advance(Duration::from_micros(400)), logic::Millis(1));
+}
+
+#[test]
+fn test_clock_mode_switches_at_runtime() {
+    let mut app = create_clock_app(CombatClock::fixed(logic::Millis(20)));
+    app.update();
+    assert_eq!(
+        app.world().resource::<Time<Fixed>>().timestep(),
+        Duration::from_millis(20)
+    );
+
+    app.insert_resource(CombatClock::fixed(logic::Millis(50)));
+    app.update();
+    assert_eq!(
+        app.world().resource::<Time<Fixed>>().timestep(),
+        Duration::from_millis(50)
+    );
+
+    // Without a fixed step, fixed ticks no longer advance anything
+    app.insert_resource(CombatClock::frame_time());
+    let before = hero(&app).clone();
+    run_fixed_ticks(&mut app, 10);
+    assert_eq!(hero(&app), &before);
+}
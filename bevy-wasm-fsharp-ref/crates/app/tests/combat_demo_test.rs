@@ -0,0 +1,36 @@
+//! Tests for the combat demo plugin running headlessly
+
+use app::CombatDemoPlugin;
+use bevy::prelude::*;
+use bevy_wasm_fsharp_ref_logic as logic;
+
+fn create_demo_app(bestiary: logic::Bestiary) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, CombatDemoPlugin { seed: 7 }))
+        .init_resource::<ButtonInput<KeyCode>>()
+        .insert_resource(bestiary);
+    app
+}
+
+#[test]
+fn test_demo_starts_an_encounter() {
+    let mut app = create_demo_app(logic::Bestiary::builtin());
+    app.update();
+
+    let encounter = app.world().resource::<logic::Encounter>();
+    assert!(encounter
+        .combatants
+        .iter()
+        .any(|c| c.team == logic::Team::Enemies));
+}
+
+#[test]
+fn test_empty_bestiary_does_not_start_a_fight() {
+    let bestiary = logic::Bestiary::new(Vec::new(), Vec::new()).unwrap();
+    let mut app = create_demo_app(bestiary);
+    app.update();
+    app.update();
+
+    assert!(!app.world().contains_resource::<logic::Encounter>());
+    assert!(!app.world().contains_resource::<logic::Replay>());
+}
@@ -1,7 +1,6 @@
-/// Integration tests for the turn-based combat system
-/// Tests the complete combat flow including turns, game over, and logging
+//! Integration tests for the turn-based combat system
+//! Tests the complete combat flow including turns, game over, and logging
 
-use bevy::prelude::*;
 use bevy_wasm_fsharp_ref_logic as logic;
 
 // Note: These would be proper integration tests if we exposed the combat types
@@ -52,7 +51,7 @@ mod combat_tests {
 
         // Calculate damage
         let ability = logic::Ability::basic_attack();
-        let damage_per_hit = (player.stats.attack + ability.power - monster.stats.defense).max(1);
+        let damage_per_hit = (player.stats.attack + ability.power() - monster.stats.defense).max(1);
 
         assert_eq!(damage_per_hit, 14);
 
@@ -84,7 +83,7 @@ mod combat_tests {
 
         // Calculate damage
         let ability = logic::Ability::basic_attack();
-        let damage_per_hit = (monster.stats.attack + ability.power - player.stats.defense).max(1);
+        let damage_per_hit = (monster.stats.attack + ability.power() - player.stats.defense).max(1);
 
         assert_eq!(damage_per_hit, 9);
 
@@ -163,6 +162,7 @@ mod combat_tests {
                 hp: 10,
                 attack: 1,
                 defense: 0,
+                ..Default::default()
             },
         };
 
@@ -173,6 +173,7 @@ mod combat_tests {
                 hp: 100,
                 attack: 1,
                 defense: 100,
+                ..Default::default()
             },
         };
 
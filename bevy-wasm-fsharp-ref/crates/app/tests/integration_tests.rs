@@ -25,19 +25,14 @@ struct Player;
 struct Monster;
 
 /// Resource tracking the current state of combat (duplicated from lib.rs for tests)
-#[derive(Resource, Debug, Clone, PartialEq)]
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
 enum CombatState {
+    #[default]
     PlayerTurn,
     MonsterTurn,
     GameOver { winner: String },
 }
 
-impl Default for CombatState {
-    fn default() -> Self {
-        CombatState::PlayerTurn
-    }
-}
-
 /// Resource for tracking combat events and messages (duplicated from lib.rs for tests)
 #[derive(Resource, Default)]
 struct CombatLog {
@@ -0,0 +1,40 @@
+//! # Balance Simulator
+//!
+//! Runs thousands of seeded duels between every pair of entrants in a [`Roster`]
+//! and aggregates the results into a [`BalanceReport`]:
+//!
+//! - Win-rate matrix and overall win rate per entrant
+//! - Average duel length per matchup
+//! - Direct damage, healing, crits and misses per ability
+//! - Time-to-kill distribution (rounds needed to win) per entrant
+//!
+//! Duels run on the pure logic crate (`reduce_with_rng` and the AI policies), with
+//! no Bevy app or window, so the simulator runs anywhere, including CI.
+//!
+//! # Examples
+//!
+//! ```
+//! use balance::{run, Roster, SimConfig};
+//! use bevy_wasm_fsharp_ref_logic::AbilityCatalog;
+//!
+//! let entrants = Roster::builtin()
+//!     .entrants(&AbilityCatalog::builtin())
+//!     .unwrap();
+//! let config = SimConfig {
+//!     duels: 4,
+//!     ..Default::default()
+//! };
+//!
+//! let report = run(&entrants[..3], &config);
+//!
+//! assert_eq!(report.win_rates.len(), 3);
+//! assert_eq!(report.matchups.len(), 3);
+//! ```
+
+pub mod report;
+pub mod roster;
+pub mod sim;
+
+pub use report::{AbilityStats, BalanceReport, MatchupStats, TimeToKill};
+pub use roster::{Entrant, Loadout, Roster, RosterError, Template};
+pub use sim::{duel, run, AbilityTally, DuelResult, SimConfig};
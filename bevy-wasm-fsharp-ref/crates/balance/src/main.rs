@@ -0,0 +1,140 @@
+//! Command-line entry point of the balance simulator.
+//!
+//! ```bash
+//! # Print the JSON report for the built-in roster
+//! cargo run -p balance --release
+//!
+//! # Write report.json and CSV tables for a custom roster
+//! cargo run -p balance --release -- --roster my_roster.json --duels 1000 --out target/balance
+//!
+//! # Compare damage formulas
+//! cargo run -p balance --release -- --formula PercentArmor
+//! ```
+
+use std::process::ExitCode;
+
+use balance::{run, Roster, SimConfig};
+use bevy_wasm_fsharp_ref_logic::{AbilityCatalog, DamageModel};
+
+const USAGE: &str = "\
+Usage: balance [OPTIONS]
+
+Options:
+  --duels <N>       Duels per pair of entrants (default 200)
+  --seed <N>        Seed of the first duel (default 0)
+  --max-turns <N>   Turns before a duel is a draw (default 200)
+  --formula <NAME>  Damage formula: FlatDefense, PercentArmor or AttackRatio
+                    (default FlatDefense)
+  --roster <FILE>   JSON roster (default: built-in roster)
+  --catalog <FILE>  JSON or RON ability catalog (default: built-in catalog)
+  --out <DIR>       Write report.json and CSV tables to DIR instead of
+                    printing the JSON report
+  -h, --help        Print this help";
+
+/// Parsed command-line options.
+#[derive(Debug, Default)]
+struct Options {
+    config: SimConfig,
+    roster: Option<String>,
+    catalog: Option<String>,
+    out: Option<String>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(flag) = args.next() {
+        if flag == "-h" || flag == "--help" {
+            return Ok(None);
+        }
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match flag.as_str() {
+            "--duels" => options.config.duels = parse_number(&flag, value()?)?,
+            "--seed" => options.config.seed = parse_number(&flag, value()?)?,
+            "--max-turns" => options.config.max_turns = parse_number(&flag, value()?)?,
+            "--formula" => options.config.formula = parse_formula(value()?)?,
+            "--roster" => options.roster = Some(value()?),
+            "--catalog" => options.catalog = Some(value()?),
+            "--out" => options.out = Some(value()?),
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    Ok(Some(options))
+}
+
+fn parse_formula(value: String) -> Result<DamageModel, String> {
+    DamageModel::ALL
+        .into_iter()
+        .find(|model| format!("{:?}", model) == value)
+        .ok_or_else(|| format!("--formula expects a formula name, got \"{}\"", value))
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: String) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got \"{}\"", flag, value))
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match simulate(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn simulate(options: &Options) -> Result<(), String> {
+    let catalog = match &options.catalog {
+        Some(path) => AbilityCatalog::from_file(path).map_err(|e| e.to_string())?,
+        None => AbilityCatalog::builtin(),
+    };
+    let roster = match &options.roster {
+        Some(path) => Roster::from_file(path).map_err(|e| e.to_string())?,
+        None => Roster::builtin(),
+    };
+    let entrants = roster.entrants(&catalog).map_err(|e| e.to_string())?;
+
+    eprintln!(
+        "Running {} duels for each of {} pairs of entrants...",
+        options.config.duels,
+        entrants.len() * (entrants.len() - 1) / 2
+    );
+    let report = run(&entrants, &options.config);
+
+    // Summary on stderr, so stdout stays valid JSON
+    let mut ranking: Vec<(f64, &str)> = report
+        .overall_win_rates
+        .iter()
+        .zip(&report.entrants)
+        .map(|(&rate, name)| (rate, name.as_str()))
+        .collect();
+    ranking.sort_by(|a, b| b.0.total_cmp(&a.0));
+    eprintln!("\nOverall win rates:");
+    for (rate, name) in &ranking {
+        eprintln!("  {:>6.1}%  {}", rate * 100.0, name);
+    }
+
+    match &options.out {
+        Some(dir) => {
+            report.write_to(dir).map_err(|e| e.to_string())?;
+            eprintln!("\nWrote report.json and CSV tables to {}", dir);
+        }
+        None => println!("{}", report.to_json()),
+    }
+    Ok(())
+}
@@ -0,0 +1,370 @@
+//! Aggregated results of a balance run, as JSON or CSV.
+//!
+//! A [`BalanceReport`] serializes to one JSON document. [`BalanceReport::csv_tables`]
+//! splits it into flat tables for spreadsheets:
+//!
+//! | File | One row per |
+//! |------|-------------|
+//! | `win_rates.csv` | entrant, with its win rate against every other entrant |
+//! | `matchups.csv` | pair of entrants |
+//! | `abilities.csv` | ability of each entrant |
+//! | `time_to_kill.csv` | entrant |
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sim::{AbilityTally, SimConfig};
+
+/// Results of every duel between two entrants.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchupStats {
+    pub first: String,
+    pub second: String,
+    pub duels: u32,
+    pub first_wins: u32,
+    pub second_wins: u32,
+    pub draws: u32,
+    pub total_turns: u64,
+}
+
+impl MatchupStats {
+    /// Mean turns per duel, counting both sides' turns.
+    pub fn average_turns(&self) -> f64 {
+        ratio(self.total_turns as f64, self.duels)
+    }
+}
+
+/// Direct damage and healing of one ability of one entrant, over all its duels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbilityStats {
+    pub entrant: String,
+    pub ability: String,
+    pub uses: u32,
+    pub damage: i64,
+    pub healing: i64,
+    pub crits: u32,
+    pub misses: u32,
+    pub damage_per_use: f64,
+}
+
+impl AbilityStats {
+    pub(crate) fn new(entrant: String, ability: String, tally: AbilityTally) -> Self {
+        Self {
+            entrant,
+            ability,
+            uses: tally.uses,
+            damage: tally.damage,
+            healing: tally.healing,
+            crits: tally.crits,
+            misses: tally.misses,
+            damage_per_use: ratio(tally.damage as f64, tally.uses),
+        }
+    }
+}
+
+/// How many rounds an entrant needed to win its duels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeToKill {
+    pub entrant: String,
+    pub kills: u32,
+    pub mean_rounds: f64,
+    pub min_rounds: u32,
+    pub median_rounds: u32,
+    pub p90_rounds: u32,
+    pub max_rounds: u32,
+
+    /// Number of wins that took each number of rounds.
+    pub histogram: BTreeMap<u32, u32>,
+}
+
+impl TimeToKill {
+    pub(crate) fn new(entrant: String, mut rounds: Vec<u32>) -> Self {
+        rounds.sort_unstable();
+        let kills = rounds.len() as u32;
+        let percentile = |p: usize| match rounds.len() {
+            0 => 0,
+            len => rounds[((len - 1) * p) / 100],
+        };
+
+        let mut histogram = BTreeMap::new();
+        for &r in &rounds {
+            *histogram.entry(r).or_insert(0) += 1;
+        }
+
+        Self {
+            entrant,
+            kills,
+            mean_rounds: ratio(rounds.iter().map(|&r| f64::from(r)).sum(), kills),
+            min_rounds: percentile(0),
+            median_rounds: percentile(50),
+            p90_rounds: percentile(90),
+            max_rounds: percentile(100),
+            histogram,
+        }
+    }
+}
+
+/// Everything measured in one balance run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceReport {
+    pub config: SimConfig,
+
+    /// Entrant names, in matrix order.
+    pub entrants: Vec<String>,
+
+    /// `win_rates[i][j]` is the share of duels entrant `i` won against entrant `j`.
+    /// The diagonal is `None`: entrants do not fight themselves.
+    pub win_rates: Vec<Vec<Option<f64>>>,
+
+    /// Share of all its duels each entrant won.
+    pub overall_win_rates: Vec<f64>,
+
+    /// `average_turns[i][j]` is the mean duel length between `i` and `j`.
+    pub average_turns: Vec<Vec<Option<f64>>>,
+
+    pub matchups: Vec<MatchupStats>,
+    pub abilities: Vec<AbilityStats>,
+    pub time_to_kill: Vec<TimeToKill>,
+}
+
+impl BalanceReport {
+    pub(crate) fn new(
+        config: SimConfig,
+        entrants: Vec<String>,
+        matchups: Vec<MatchupStats>,
+        abilities: Vec<AbilityStats>,
+        time_to_kill: Vec<TimeToKill>,
+    ) -> Self {
+        let count = entrants.len();
+        let index = |name: &str| entrants.iter().position(|e| e == name).unwrap_or(0);
+
+        let mut win_rates = vec![vec![None; count]; count];
+        let mut average_turns = vec![vec![None; count]; count];
+        let mut wins = vec![0u32; count];
+        let mut duels = vec![0u32; count];
+
+        for matchup in &matchups {
+            let (a, b) = (index(&matchup.first), index(&matchup.second));
+            win_rates[a][b] = Some(ratio(matchup.first_wins as f64, matchup.duels));
+            win_rates[b][a] = Some(ratio(matchup.second_wins as f64, matchup.duels));
+            average_turns[a][b] = Some(matchup.average_turns());
+            average_turns[b][a] = Some(matchup.average_turns());
+
+            wins[a] += matchup.first_wins;
+            wins[b] += matchup.second_wins;
+            duels[a] += matchup.duels;
+            duels[b] += matchup.duels;
+        }
+
+        let overall_win_rates = wins
+            .iter()
+            .zip(&duels)
+            .map(|(&w, &d)| ratio(f64::from(w), d))
+            .collect();
+
+        Self {
+            config,
+            entrants,
+            win_rates,
+            overall_win_rates,
+            average_turns,
+            matchups,
+            abilities,
+            time_to_kill,
+        }
+    }
+
+    /// The report as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports always serialize")
+    }
+
+    /// The report as CSV tables, keyed by file name.
+    pub fn csv_tables(&self) -> Vec<(&'static str, String)> {
+        let mut win_rates = String::from("entrant,overall");
+        for name in &self.entrants {
+            write!(win_rates, ",{}", csv_field(name)).unwrap();
+        }
+        win_rates.push('\n');
+        for (i, row) in self.win_rates.iter().enumerate() {
+            write!(
+                win_rates,
+                "{},{:.4}",
+                csv_field(&self.entrants[i]),
+                self.overall_win_rates[i]
+            )
+            .unwrap();
+            for rate in row {
+                match rate {
+                    Some(rate) => write!(win_rates, ",{:.4}", rate).unwrap(),
+                    None => win_rates.push(','),
+                }
+            }
+            win_rates.push('\n');
+        }
+
+        let mut matchups =
+            String::from("first,second,duels,first_wins,second_wins,draws,average_turns\n");
+        for m in &self.matchups {
+            writeln!(
+                matchups,
+                "{},{},{},{},{},{},{:.2}",
+                csv_field(&m.first),
+                csv_field(&m.second),
+                m.duels,
+                m.first_wins,
+                m.second_wins,
+                m.draws,
+                m.average_turns()
+            )
+            .unwrap();
+        }
+
+        let mut abilities =
+            String::from("entrant,ability,uses,damage,healing,crits,misses,damage_per_use\n");
+        for a in &self.abilities {
+            writeln!(
+                abilities,
+                "{},{},{},{},{},{},{},{:.2}",
+                csv_field(&a.entrant),
+                csv_field(&a.ability),
+                a.uses,
+                a.damage,
+                a.healing,
+                a.crits,
+                a.misses,
+                a.damage_per_use
+            )
+            .unwrap();
+        }
+
+        let mut time_to_kill = String::from(
+            "entrant,kills,mean_rounds,min_rounds,median_rounds,p90_rounds,max_rounds\n",
+        );
+        for t in &self.time_to_kill {
+            writeln!(
+                time_to_kill,
+                "{},{},{:.2},{},{},{},{}",
+                csv_field(&t.entrant),
+                t.kills,
+                t.mean_rounds,
+                t.min_rounds,
+                t.median_rounds,
+                t.p90_rounds,
+                t.max_rounds
+            )
+            .unwrap();
+        }
+
+        vec![
+            ("win_rates.csv", win_rates),
+            ("matchups.csv", matchups),
+            ("abilities.csv", abilities),
+            ("time_to_kill.csv", time_to_kill),
+        ]
+    }
+
+    /// Writes `report.json` and every CSV table into `dir`, creating it if needed.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("report.json"), self.to_json())?;
+        for (name, table) in self.csv_tables() {
+            std::fs::write(dir.join(name), table)?;
+        }
+        Ok(())
+    }
+}
+
+fn ratio(value: f64, count: u32) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        value / f64::from(count)
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matchup(first: &str, second: &str, first_wins: u32, second_wins: u32) -> MatchupStats {
+        MatchupStats {
+            first: first.to_string(),
+            second: second.to_string(),
+            duels: 10,
+            first_wins,
+            second_wins,
+            draws: 10 - first_wins - second_wins,
+            total_turns: 80,
+        }
+    }
+
+    fn report() -> BalanceReport {
+        BalanceReport::new(
+            SimConfig::default(),
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            vec![
+                matchup("A", "B", 7, 3),
+                matchup("A", "C", 5, 4),
+                matchup("B", "C", 2, 8),
+            ],
+            Vec::new(),
+            vec![TimeToKill::new("A".to_string(), vec![4, 2, 3, 3])],
+        )
+    }
+
+    #[test]
+    fn test_win_rate_matrix_is_filled_from_both_sides() {
+        let report = report();
+
+        assert_eq!(report.win_rates[0][1], Some(0.7));
+        assert_eq!(report.win_rates[1][0], Some(0.3));
+        assert_eq!(report.win_rates[1][1], None);
+        assert_eq!(report.overall_win_rates[2], 0.6);
+        assert_eq!(report.average_turns[2][1], Some(8.0));
+    }
+
+    #[test]
+    fn test_time_to_kill_percentiles() {
+        let ttk = &report().time_to_kill[0];
+
+        assert_eq!(ttk.kills, 4);
+        assert_eq!(ttk.mean_rounds, 3.0);
+        assert_eq!(
+            (ttk.min_rounds, ttk.median_rounds, ttk.max_rounds),
+            (2, 3, 4)
+        );
+        assert_eq!(ttk.histogram[&3], 2);
+    }
+
+    #[test]
+    fn test_csv_tables() {
+        let tables = report().csv_tables();
+        let win_rates = &tables[0].1;
+
+        assert_eq!(win_rates.lines().next(), Some("entrant,overall,A,B,C"));
+        assert_eq!(win_rates.lines().nth(1), Some("A,0.6000,,0.7000,0.5000"));
+        assert_eq!(tables[1].1.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_csv_fields_are_quoted() {
+        assert_eq!(csv_field("Warrior/Basic"), "Warrior/Basic");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,227 @@
+//! The characters, loadouts and AI policies to test against each other.
+//!
+//! A [`Roster`] lists character templates, ability loadouts and AI policies. Every
+//! combination of the three becomes one [`Entrant`] named
+//! `"Template/Loadout/Policy"`, so adding a template to a roster with three
+//! loadouts and three policies adds nine entrants.
+//!
+//! The built-in roster ([`Roster::builtin`]) is embedded from `data/roster.json`.
+//!
+//! # Data Format
+//!
+//! ```json
+//! {
+//!   "templates": [
+//!     { "name": "Warrior", "stats": { "hp": 30, "attack": 10, "defense": 2, "speed": 5 } }
+//!   ],
+//!   "loadouts": [
+//!     { "name": "Standard", "abilities": ["Basic Attack", "Powerful Attack", "Heal"] }
+//!   ],
+//!   "policies": ["Aggressive", "Smart"]
+//! }
+//! ```
+//!
+//! Stats use the logic crate's `Stats` format; ability names refer to an
+//! `AbilityCatalog`.
+
+use std::fmt;
+use std::path::Path;
+
+use bevy_wasm_fsharp_ref_logic::{
+    AbilityCatalog, AbilitySet, AiPersonality, AiPolicy, CatalogError, Character, CooldownMode,
+    Stats,
+};
+use serde::{Deserialize, Serialize};
+
+const BUILTIN_ROSTER: &str = include_str!("../data/roster.json");
+
+/// A named set of base stats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub stats: Stats,
+}
+
+/// A named list of catalog abilities.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Loadout {
+    pub name: String,
+    pub abilities: Vec<String>,
+}
+
+/// Templates, loadouts and policies to combine into entrants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Roster {
+    pub templates: Vec<Template>,
+    pub loadouts: Vec<Loadout>,
+    pub policies: Vec<AiPersonality>,
+}
+
+/// One combination of template, loadout and policy.
+#[derive(Debug, Clone)]
+pub struct Entrant {
+    /// `"Template/Loadout/Policy"`.
+    pub name: String,
+    pub character: Character,
+    pub abilities: AbilitySet,
+    pub policy: AiPersonality,
+}
+
+/// Errors from loading a roster or building its entrants.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RosterError {
+    /// The roster file could not be read.
+    Io { path: String, message: String },
+
+    /// The roster is not valid JSON or does not match the expected shape.
+    Parse { message: String },
+
+    /// The roster has no templates, loadouts or policies, so no entrants.
+    Empty { field: &'static str },
+
+    /// A loadout names an ability that is not in the catalog.
+    Catalog {
+        loadout: String,
+        source: CatalogError,
+    },
+}
+
+impl fmt::Display for RosterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RosterError::Io { path, message } => {
+                write!(f, "failed to read roster \"{}\": {}", path, message)
+            }
+            RosterError::Parse { message } => write!(f, "invalid roster: {}", message),
+            RosterError::Empty { field } => write!(f, "roster has no {}", field),
+            RosterError::Catalog { loadout, source } => {
+                write!(f, "loadout \"{}\": {}", loadout, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RosterError {}
+
+impl Roster {
+    /// Parses a JSON roster.
+    pub fn from_json(source: &str) -> Result<Self, RosterError> {
+        serde_json::from_str(source).map_err(|e| RosterError::Parse {
+            message: e.to_string(),
+        })
+    }
+
+    /// Loads a JSON roster file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RosterError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| RosterError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        Self::from_json(&source)
+    }
+
+    /// The roster embedded from `data/roster.json`.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: the embedded data is checked by the crate's tests.
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_ROSTER).expect("built-in roster is valid")
+    }
+
+    /// Every template × loadout × policy combination, with turn-based cooldowns
+    /// from `catalog`. Entrants are ordered by template, then loadout, then policy.
+    pub fn entrants(&self, catalog: &AbilityCatalog) -> Result<Vec<Entrant>, RosterError> {
+        if self.templates.is_empty() {
+            return Err(RosterError::Empty { field: "templates" });
+        }
+        if self.loadouts.is_empty() {
+            return Err(RosterError::Empty { field: "loadouts" });
+        }
+        if self.policies.is_empty() {
+            return Err(RosterError::Empty { field: "policies" });
+        }
+
+        let loadouts = self
+            .loadouts
+            .iter()
+            .map(|loadout| {
+                let names: Vec<&str> = loadout.abilities.iter().map(String::as_str).collect();
+                catalog
+                    .ability_set(&names, CooldownMode::Turns)
+                    .map(|set| (loadout, set))
+                    .map_err(|source| RosterError::Catalog {
+                        loadout: loadout.name.clone(),
+                        source,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut entrants = Vec::new();
+        for template in &self.templates {
+            for (loadout, abilities) in &loadouts {
+                for &policy in &self.policies {
+                    entrants.push(Entrant {
+                        name: format!("{}/{}/{}", template.name, loadout.name, policy.name()),
+                        character: Character {
+                            name: template.name.clone(),
+                            hp: template.stats.hp,
+                            stats: template.stats.clone(),
+                        },
+                        abilities: abilities.clone(),
+                        policy,
+                    });
+                }
+            }
+        }
+        Ok(entrants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_roster_builds_with_builtin_catalog() {
+        let roster = Roster::builtin();
+        let entrants = roster.entrants(&AbilityCatalog::builtin()).unwrap();
+
+        assert_eq!(
+            entrants.len(),
+            roster.templates.len() * roster.loadouts.len() * roster.policies.len()
+        );
+        assert_eq!(entrants[0].name, "Warrior/Basic/Aggressive");
+        assert_eq!(entrants[0].character.hp, 30);
+    }
+
+    #[test]
+    fn test_unknown_ability_names_the_loadout() {
+        let roster = Roster::from_json(
+            r#"{
+                "templates": [{"name": "A", "stats": {"hp": 10, "attack": 1, "defense": 0}}],
+                "loadouts": [{"name": "Broken", "abilities": ["Meteor"]}],
+                "policies": ["Smart"]
+            }"#,
+        )
+        .unwrap();
+
+        let err = roster.entrants(&AbilityCatalog::builtin()).unwrap_err();
+        assert!(matches!(err, RosterError::Catalog { ref loadout, .. } if loadout == "Broken"));
+    }
+
+    #[test]
+    fn test_empty_roster_is_rejected() {
+        let roster = Roster {
+            templates: Vec::new(),
+            loadouts: Vec::new(),
+            policies: Vec::new(),
+        };
+
+        assert_eq!(
+            roster.entrants(&AbilityCatalog::builtin()).unwrap_err(),
+            RosterError::Empty { field: "templates" }
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Seeded duels between roster entrants.
+//!
+//! Every duel is an [`Encounter`] of one entrant against another, played turn by
+//! turn through the logic crate's [`reduce_with_rng`]. Each side's AI policy picks
+//! its actions with [`choose_ai_action`]. Nothing touches Bevy, so thousands of
+//! duels run in well under a second in release builds.
+//!
+//! Duel `n` of a run rolls from `SeededRng::new(seed + n)`, so a run is fully
+//! determined by its [`SimConfig`] and entrant list.
+
+use std::collections::BTreeMap;
+
+use bevy_wasm_fsharp_ref_logic::{
+    choose_ai_action, reduce_with_rng, Combatant, DamageModel, EffectOutcome, Encounter,
+    EncounterOutcome, SeededRng, Team, PASS,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::report::{AbilityStats, BalanceReport, MatchupStats, TimeToKill};
+use crate::roster::Entrant;
+
+/// How many duels to run and how they are seeded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimConfig {
+    /// Duels per pair of entrants. Sides alternate who is listed first, which
+    /// breaks speed ties.
+    pub duels: u32,
+
+    /// Seed of the first duel.
+    pub seed: u64,
+
+    /// Turns after which a duel is declared a draw.
+    pub max_turns: u32,
+
+    /// Damage formula every duel uses.
+    #[serde(default)]
+    pub formula: DamageModel,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            duels: 200,
+            seed: 0,
+            max_turns: 200,
+            formula: DamageModel::default(),
+        }
+    }
+}
+
+/// Totals for one ability used by one side of a duel.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AbilityTally {
+    pub uses: u32,
+    pub damage: i64,
+    pub healing: i64,
+    pub crits: u32,
+    pub misses: u32,
+}
+
+/// The result of one duel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuelResult {
+    /// `Some(0)` if the first entrant won, `Some(1)` for the second, `None` for a
+    /// draw at the turn limit.
+    pub winner: Option<usize>,
+
+    /// Turns taken by both sides.
+    pub turns: u32,
+
+    /// Rounds started, so the round in which the duel ended.
+    pub rounds: u32,
+
+    /// Per side, the direct damage and healing of each ability used.
+    pub abilities: [BTreeMap<String, AbilityTally>; 2],
+}
+
+/// Plays `first` (as the allies) against `second` (as the enemies), resolving
+/// damage with `formula`.
+///
+/// With `first_listed` false the second entrant is listed first in the
+/// encounter, so it wins speed ties.
+pub fn duel(
+    first: &Entrant,
+    second: &Entrant,
+    first_listed: bool,
+    max_turns: u32,
+    formula: DamageModel,
+    rng: &mut SeededRng,
+) -> DuelResult {
+    let allies = Combatant::new(
+        Team::Allies,
+        first.character.clone(),
+        first.abilities.clone(),
+    );
+    let enemies = Combatant::new(
+        Team::Enemies,
+        second.character.clone(),
+        second.abilities.clone(),
+    );
+    let mut encounter = Encounter::new(if first_listed {
+        vec![allies, enemies]
+    } else {
+        vec![enemies, allies]
+    })
+    .with_formula(formula);
+
+    let mut abilities: [BTreeMap<String, AbilityTally>; 2] = Default::default();
+    let mut turns = 0;
+
+    while turns < max_turns {
+        let Some(actor) = encounter.current() else {
+            break;
+        };
+        let side = match encounter.combatants[actor.0].team {
+            Team::Allies => 0,
+            Team::Enemies => 1,
+        };
+        let policy = if side == 0 {
+            first.policy
+        } else {
+            second.policy
+        };
+        let Some(action) = choose_ai_action(&encounter, &policy) else {
+            break;
+        };
+        let (next, events) =
+            reduce_with_rng(&encounter, &action, rng).expect("AI actions are always valid");
+
+        for event in events.iter().filter(|e| e.ability_used != PASS) {
+            let tally = abilities[side]
+                .entry(event.ability_used.clone())
+                .or_default();
+            tally.uses += 1;
+            for outcome in &event.outcomes {
+                match outcome {
+                    EffectOutcome::Damaged { amount } => tally.damage += i64::from(*amount),
+                    EffectOutcome::Healed { amount } => tally.healing += i64::from(*amount),
+                    _ => {}
+                }
+            }
+            // Count the HP actually removed, like healing counts the HP restored
+            tally.damage -= i64::from(event.overkill);
+            tally.crits += u32::from(event.critical);
+            tally.misses += u32::from(event.missed);
+        }
+
+        encounter = next;
+        turns += 1;
+    }
+
+    DuelResult {
+        winner: match encounter.outcome() {
+            EncounterOutcome::Ongoing => None,
+            EncounterOutcome::Victory => Some(0),
+            EncounterOutcome::Defeat => Some(1),
+        },
+        turns,
+        rounds: encounter.round,
+        abilities,
+    }
+}
+
+/// Runs `config.duels` duels for every pair of distinct entrants.
+pub fn run(entrants: &[Entrant], config: &SimConfig) -> BalanceReport {
+    let count = entrants.len();
+    let mut matchups = Vec::new();
+    let mut abilities: BTreeMap<(usize, String), AbilityTally> = BTreeMap::new();
+    let mut kills: Vec<Vec<u32>> = vec![Vec::new(); count];
+    let mut seed = config.seed;
+
+    for a in 0..count {
+        for b in a + 1..count {
+            let mut stats = MatchupStats {
+                first: entrants[a].name.clone(),
+                second: entrants[b].name.clone(),
+                duels: config.duels,
+                ..Default::default()
+            };
+
+            for n in 0..config.duels {
+                let mut rng = SeededRng::new(seed);
+                seed = seed.wrapping_add(1);
+
+                let result = duel(
+                    &entrants[a],
+                    &entrants[b],
+                    n % 2 == 0,
+                    config.max_turns,
+                    config.formula,
+                    &mut rng,
+                );
+                stats.total_turns += u64::from(result.turns);
+                match result.winner {
+                    Some(0) => {
+                        stats.first_wins += 1;
+                        kills[a].push(result.rounds);
+                    }
+                    Some(_) => {
+                        stats.second_wins += 1;
+                        kills[b].push(result.rounds);
+                    }
+                    None => stats.draws += 1,
+                }
+
+                for (entrant, tallies) in [a, b].into_iter().zip(result.abilities) {
+                    for (name, tally) in tallies {
+                        let total = abilities.entry((entrant, name)).or_default();
+                        total.uses += tally.uses;
+                        total.damage += tally.damage;
+                        total.healing += tally.healing;
+                        total.crits += tally.crits;
+                        total.misses += tally.misses;
+                    }
+                }
+            }
+            matchups.push(stats);
+        }
+    }
+
+    BalanceReport::new(
+        config.clone(),
+        entrants.iter().map(|e| e.name.clone()).collect(),
+        matchups,
+        abilities
+            .into_iter()
+            .map(|((entrant, ability), tally)| {
+                AbilityStats::new(entrants[entrant].name.clone(), ability, tally)
+            })
+            .collect(),
+        entrants
+            .iter()
+            .zip(kills)
+            .map(|(entrant, rounds)| TimeToKill::new(entrant.name.clone(), rounds))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Roster;
+    use bevy_wasm_fsharp_ref_logic::AbilityCatalog;
+
+    fn entrants() -> Vec<Entrant> {
+        Roster::builtin()
+            .entrants(&AbilityCatalog::builtin())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_duel_ends_with_a_winner() {
+        let entrants = entrants();
+        let result = duel(
+            &entrants[0],
+            &entrants[1],
+            true,
+            200,
+            DamageModel::FlatDefense,
+            &mut SeededRng::new(1),
+        );
+
+        assert!(result.winner.is_some());
+        assert!(result.turns > 0);
+        assert!(result.abilities[0]["Basic Attack"].uses > 0);
+    }
+
+    #[test]
+    fn test_turn_limit_declares_a_draw() {
+        let entrants = entrants();
+        let result = duel(
+            &entrants[0],
+            &entrants[1],
+            true,
+            1,
+            DamageModel::FlatDefense,
+            &mut SeededRng::new(1),
+        );
+
+        assert_eq!(result.winner, None);
+        assert_eq!(result.turns, 1);
+    }
+
+    #[test]
+    fn test_runs_are_reproducible() {
+        let entrants = &entrants()[..4];
+        let config = SimConfig {
+            duels: 10,
+            seed: 42,
+            max_turns: 100,
+            formula: DamageModel::FlatDefense,
+        };
+
+        let first = serde_json::to_string(&run(entrants, &config)).unwrap();
+        let second = serde_json::to_string(&run(entrants, &config)).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_formula_changes_the_outcome() {
+        let mut entrants = entrants();
+        entrants[1].character.stats.defense = 12;
+        let play = |formula| {
+            duel(
+                &entrants[0],
+                &entrants[1],
+                true,
+                200,
+                formula,
+                &mut SeededRng::new(1),
+            )
+        };
+
+        // 15 power against 12 defense: 3 damage flat, 225 / 27 = 8 by ratio
+        let flat = play(DamageModel::FlatDefense);
+        let ratio = play(DamageModel::AttackRatio);
+        let per_use = |result: &DuelResult| {
+            let tally = &result.abilities[0]["Basic Attack"];
+            tally.damage / i64::from(tally.uses)
+        };
+        assert_eq!(per_use(&flat), 3);
+        assert_eq!(per_use(&ratio), 8);
+    }
+
+    #[test]
+    fn test_every_pair_plays_every_duel() {
+        let entrants = &entrants()[..4];
+        let config = SimConfig {
+            duels: 6,
+            ..Default::default()
+        };
+
+        let report = run(entrants, &config);
+
+        assert_eq!(report.matchups.len(), 6);
+        for matchup in &report.matchups {
+            assert_eq!(matchup.first_wins + matchup.second_wins + matchup.draws, 6);
+        }
+    }
+}
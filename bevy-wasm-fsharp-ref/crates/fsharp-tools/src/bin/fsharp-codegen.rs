@@ -0,0 +1,101 @@
+//! `fsharp-codegen`: generates the logic crate's domain types from `fsharp/Domain.fs`.
+//!
+//! ```text
+//! fsharp-codegen [--root DIR] [--check]
+//! ```
+//!
+//! Writes `crates/logic-fsharp/src/domain.rs` and the TypeScript declarations in
+//! `crates/logic-js/src/domain.d.ts`. With `--check`, writes nothing and exits
+//! with status 1 if either file is out of date.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use fsharp_tools::codegen::{self, CodegenError, DOMAIN_SOURCE};
+use fsharp_tools::typescript;
+
+const USAGE: &str = "\
+Usage: fsharp-codegen [OPTIONS]
+
+Generates crates/logic-fsharp/src/domain.rs and crates/logic-js/src/domain.d.ts
+from the record and union types in fsharp/Domain.fs.
+
+Options:
+  --root <DIR>    Repository root (default: the workspace this tool was built in)
+  --check         Only check that the generated files are up to date
+  -h, --help      Print this help";
+
+fn main() -> ExitCode {
+    let mut root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let mut check = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--root" => match args.next() {
+                Some(dir) => root = PathBuf::from(dir),
+                None => {
+                    eprintln!("--root needs a value\n\n{}", USAGE);
+                    return ExitCode::from(2);
+                }
+            },
+            "--check" => check = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            other => {
+                eprintln!("unknown argument: {}\n\n{}", other, USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let source = match std::fs::read_to_string(root.join(DOMAIN_SOURCE)) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {}", DOMAIN_SOURCE, e);
+            return ExitCode::from(2);
+        }
+    };
+
+    type Generator = fn(&str, &str) -> Result<String, CodegenError>;
+    let outputs: [(&str, Generator); 2] = [
+        (codegen::GENERATED_FILE, codegen::generate_source),
+        (typescript::GENERATED_FILE, typescript::generate_source),
+    ];
+
+    let mut stale = false;
+    for (file, generate) in outputs {
+        let generated = match generate(&source, DOMAIN_SOURCE) {
+            Ok(generated) => generated,
+            Err(e) => {
+                eprintln!("error: {}: {}", DOMAIN_SOURCE, e);
+                return ExitCode::from(2);
+            }
+        };
+
+        let target = root.join(file);
+        let current = std::fs::read_to_string(&target).unwrap_or_default();
+        if current == generated {
+            println!("{} is up to date.", file);
+        } else if check {
+            eprintln!(
+                "{} is out of date with {}; run `cargo run -p fsharp-tools --bin fsharp-codegen`.",
+                file, DOMAIN_SOURCE
+            );
+            stale = true;
+        } else if let Err(e) = std::fs::write(&target, generated) {
+            eprintln!("error: cannot write {}: {}", file, e);
+            return ExitCode::from(2);
+        } else {
+            println!("Wrote {}", file);
+        }
+    }
+
+    if stale {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
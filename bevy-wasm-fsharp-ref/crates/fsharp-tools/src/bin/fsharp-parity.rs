@@ -0,0 +1,72 @@
+//! `fsharp-parity`: checks the F# reference sources against the Rust logic crate.
+//!
+//! ```text
+//! fsharp-parity [--root DIR] [--type NAME]...
+//! ```
+//!
+//! Prints a report and exits with status 1 if any definition differs.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use fsharp_tools::parity::{check, DEFAULT_TYPES};
+
+const USAGE: &str = "\
+Usage: fsharp-parity [OPTIONS]
+
+Compares the F# records, unions and let bindings in fsharp/ with the Rust logic
+crate and reports every difference.
+
+Options:
+  --root <DIR>    Repository root (default: the workspace this tool was built in)
+  --type <NAME>   Type to compare; repeat for several (default: Stats, Character,
+                  Ability, AbilitySlot, CombatEvent, AbilityType)
+  -h, --help      Print this help";
+
+fn main() -> ExitCode {
+    let mut root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let mut types: Vec<String> = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next().unwrap_or_else(|| {
+                eprintln!("{} needs a value\n\n{}", name, USAGE);
+                std::process::exit(2);
+            })
+        };
+        match arg.as_str() {
+            "--root" => root = PathBuf::from(value("--root")),
+            "--type" => types.push(value("--type")),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            other => {
+                eprintln!("unknown argument: {}\n\n{}", other, USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let types: Vec<&str> = if types.is_empty() {
+        DEFAULT_TYPES.to_vec()
+    } else {
+        types.iter().map(String::as_str).collect()
+    };
+
+    match check(&root, &types) {
+        Ok(report) => {
+            println!("{}", report);
+            if report.is_ok() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
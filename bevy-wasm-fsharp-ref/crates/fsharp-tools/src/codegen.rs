@@ -0,0 +1,680 @@
+//! Rust code generation from F# record and union types.
+//!
+//! [`generate`] turns every type in an F# module into the equivalent Rust item:
+//!
+//! | F# | Rust |
+//! |----|------|
+//! | Record `{ CritChance: int }` | `pub struct` with `pub crit_chance: i32` |
+//! | Union without payloads | `pub enum` with unit variants |
+//! | Union case `of max: int * remaining: int` | Struct variant `{ max: i32, remaining: i32 }` |
+//! | Union case `of int` | Tuple variant `(i32)` |
+//! | Single-case union `type Id = Id of int` | Newtype `pub struct Id(pub i32)`, serialized transparently |
+//!
+//! Field types map as in `docs/fsharp-rust-type-mapping.md` (`int` → `i32`,
+//! `float32` → `f32`, `unativeint` → `usize`, `T list` → `Vec<T>`,
+//! `Map<K, V>` → `BTreeMap<K, V>`, ...).
+//!
+//! # Derives
+//!
+//! Every item derives `Debug`, `Clone`, `PartialEq`, `Serialize` and
+//! `Deserialize`. The rest follows from the field types, so it never needs to be
+//! spelled out:
+//!
+//! - `Copy` for unions whose payloads are all `Copy`; records are never `Copy`
+//! - `Eq` unless a float is reachable from the fields
+//! - `PartialOrd`, `Ord` and `Hash` for unions without payloads and for newtypes
+//!   over integers, strings or such unions, so they can key maps and sets
+//! - `Default` for records and newtypes whose fields all have defaults, and for
+//!   unions whose first case has no payload (it becomes the `#[default]`)
+//!
+//! Two attributes, defined in `Domain.fs`, carry what the types cannot say:
+//!
+//! - `[<Component>]` on a type derives `bevy::prelude::Component` when the logic
+//!   crate's `bevy` feature is on
+//! - `[<SerdeDefault>]` on a record field adds `#[serde(default)]`, so data
+//!   files may leave it out
+//!
+//! `///` doc comments are copied over, followed by an `# F# Equivalent` section
+//! with the F# definition.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::fsharp::{self, Case, Field, Module, TypeDef, TypeKind};
+use crate::parity::snake_case;
+use crate::TypeExpr;
+
+/// The F# file the domain types are generated from, relative to the repository root.
+pub const DOMAIN_SOURCE: &str = "fsharp/Domain.fs";
+
+/// Where the generated Rust module is written, relative to the repository root.
+pub const GENERATED_FILE: &str = "crates/logic-fsharp/src/domain.rs";
+
+/// Rust's `max_width`, used to lay out the output the way rustfmt would.
+const MAX_WIDTH: usize = 100;
+
+/// The longest derive list rustfmt keeps on the `#[derive(...)]` line.
+const DERIVE_WIDTH: usize = 85;
+
+/// The longest derive list rustfmt keeps on one line of its own; longer lists
+/// get one derive per line.
+const DERIVE_BLOCK_WIDTH: usize = 96;
+
+/// rustfmt's `struct_variant_width`: struct variants with longer field lists are
+/// written one field per line.
+const STRUCT_VARIANT_WIDTH: usize = 35;
+
+/// Why a module could not be turned into Rust.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodegenError {
+    /// The F# source does not parse.
+    Parse { source: fsharp::ParseError },
+
+    /// A field has a type that is neither an F# primitive nor defined in the module.
+    UnknownType {
+        line: usize,
+        owner: String,
+        ty: String,
+    },
+
+    /// A union case mixes named and unnamed fields, which Rust variants cannot.
+    MixedFields { line: usize, case: String },
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::Parse { source } => write!(f, "{}", source),
+            CodegenError::UnknownType { line, owner, ty } => {
+                write!(f, "line {}: {} uses unknown type `{}`", line, owner, ty)
+            }
+            CodegenError::MixedFields { line, case } => write!(
+                f,
+                "line {}: union case {} mixes named and unnamed fields",
+                line, case
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {}
+
+/// Parses F# source and generates the Rust module for its types.
+///
+/// `source_name` is mentioned in the generated header.
+pub fn generate_source(source: &str, source_name: &str) -> Result<String, CodegenError> {
+    let module = fsharp::parse(source).map_err(|source| CodegenError::Parse { source })?;
+    generate(&module, source_name)
+}
+
+/// Generates a Rust module with one item per type in `module`, in order.
+pub fn generate(module: &Module, source_name: &str) -> Result<String, CodegenError> {
+    let mut generator = Generator {
+        module,
+        traits: BTreeMap::new(),
+        uses_map: false,
+        uses_set: false,
+        uses_string: false,
+        uses_vec: false,
+    };
+
+    let mut items = Vec::new();
+    for def in &module.types {
+        items.push(generator.item(def)?);
+    }
+
+    let mut out = format!(
+        "// @generated by fsharp-codegen from {}. Do not edit by hand.\n\
+         \n\
+         //! Domain types generated from `{}`.\n\
+         //!\n\
+         //! Every record and union in the F# domain module becomes a struct or enum\n\
+         //! here, so the two can never drift. Behaviour lives in hand-written `impl`\n\
+         //! blocks in the other modules. To change a type, edit the F# and run:\n\
+         //!\n\
+         //! ```text\n\
+         //! cargo run -p fsharp-tools --bin fsharp-codegen\n\
+         //! ```\n\n",
+        source_name, source_name
+    );
+    match (generator.uses_map, generator.uses_set) {
+        (true, true) => out.push_str("use alloc::collections::{BTreeMap, BTreeSet};\n"),
+        (true, false) => out.push_str("use alloc::collections::BTreeMap;\n"),
+        (false, true) => out.push_str("use alloc::collections::BTreeSet;\n"),
+        (false, false) => {}
+    }
+    if generator.uses_string {
+        out.push_str("use alloc::string::String;\n");
+    }
+    if generator.uses_vec {
+        out.push_str("use alloc::vec::Vec;\n");
+    }
+    if generator.uses_map || generator.uses_set || generator.uses_string || generator.uses_vec {
+        out.push('\n');
+    }
+    out.push_str("use serde::{Deserialize, Serialize};\n");
+
+    for item in items {
+        out.push('\n');
+        out.push_str(&item);
+    }
+    Ok(out)
+}
+
+/// Which optional traits a type supports.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Traits {
+    copy: bool,
+    eq: bool,
+    /// `PartialOrd`, `Ord` and `Hash`.
+    key: bool,
+    default: bool,
+}
+
+impl Traits {
+    const ALL: Traits = Traits {
+        copy: true,
+        eq: true,
+        key: true,
+        default: true,
+    };
+
+    const NONE: Traits = Traits {
+        copy: false,
+        eq: false,
+        key: false,
+        default: false,
+    };
+}
+
+struct Generator<'a> {
+    module: &'a Module,
+    /// Derived traits per type name; `None` while the type is being computed,
+    /// which cuts recursive types short.
+    traits: BTreeMap<String, Option<Traits>>,
+    uses_map: bool,
+    uses_set: bool,
+    uses_string: bool,
+    uses_vec: bool,
+}
+
+impl Generator<'_> {
+    fn item(&mut self, def: &TypeDef) -> Result<String, CodegenError> {
+        let traits = self.type_traits(def)?;
+        let mut derives = vec!["Debug", "Clone"];
+        if traits.copy {
+            derives.push("Copy");
+        }
+        derives.push("PartialEq");
+        if traits.eq {
+            derives.push("Eq");
+        }
+        if traits.key {
+            derives.extend(["PartialOrd", "Ord", "Hash"]);
+        }
+        if traits.default {
+            derives.push("Default");
+        }
+        derives.extend(["Serialize", "Deserialize"]);
+
+        let mut out = String::new();
+        write_docs(&mut out, "", &def.docs);
+        if !def.docs.is_empty() {
+            out.push_str("///\n");
+        }
+        out.push_str("/// # F# Equivalent\n///\n/// ```fsharp\n");
+        for line in fsharp_source(def).lines() {
+            out.push_str(&format!("/// {}\n", line));
+        }
+        out.push_str("/// ```\n");
+        out.push_str(&derive_attribute(&derives));
+        if def.attributes.iter().any(|a| a == "Component") {
+            out.push_str("#[cfg_attr(feature = \"bevy\", derive(bevy::prelude::Component))]\n");
+        }
+
+        match &def.kind {
+            TypeKind::Record(fields) => {
+                out.push_str(&format!("pub struct {} {{\n", def.name));
+                let spaced = fields.iter().any(|f| !f.docs.is_empty());
+                for (i, field) in fields.iter().enumerate() {
+                    if spaced && i > 0 {
+                        out.push('\n');
+                    }
+                    write_docs(&mut out, "    ", &field.docs);
+                    if field.attributes.iter().any(|a| a == "SerdeDefault") {
+                        out.push_str("    #[serde(default)]\n");
+                    }
+                    let name = field.name.as_deref().unwrap_or_default();
+                    let ty = self.rust_type(def, &field.ty)?;
+                    out.push_str(&format!("    pub {}: {},\n", rust_ident(name), ty));
+                }
+                out.push_str("}\n");
+            }
+            TypeKind::Union(cases) if is_newtype(def) => {
+                let ty = self.rust_type(def, &cases[0].fields[0].ty)?;
+                out.push_str("#[serde(transparent)]\n");
+                out.push_str(&format!("pub struct {}(pub {});\n", def.name, ty));
+            }
+            TypeKind::Union(cases) => {
+                out.push_str(&format!("pub enum {} {{\n", def.name));
+                let spaced = cases.iter().any(|c| !c.docs.is_empty());
+                for (i, case) in cases.iter().enumerate() {
+                    if spaced && i > 0 {
+                        out.push('\n');
+                    }
+                    write_docs(&mut out, "    ", &case.docs);
+                    if i == 0 && traits.default {
+                        out.push_str("    #[default]\n");
+                    }
+                    out.push_str(&self.variant(def, case)?);
+                }
+                out.push_str("}\n");
+            }
+        }
+        Ok(out)
+    }
+
+    fn variant(&mut self, def: &TypeDef, case: &Case) -> Result<String, CodegenError> {
+        let named = case.fields.iter().filter(|f| f.name.is_some()).count();
+        if named == 0 {
+            if case.fields.is_empty() {
+                return Ok(format!("    {},\n", case.name));
+            }
+            let types = case
+                .fields
+                .iter()
+                .map(|f| self.rust_type(def, &f.ty))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(format!("    {}({}),\n", case.name, types.join(", ")));
+        }
+        if named != case.fields.len() {
+            return Err(CodegenError::MixedFields {
+                line: def.line,
+                case: format!("{}.{}", def.name, case.name),
+            });
+        }
+
+        let fields = case
+            .fields
+            .iter()
+            .map(|f| {
+                let name = snake_case(f.name.as_deref().unwrap_or_default());
+                Ok(format!(
+                    "{}: {}",
+                    rust_ident(&name),
+                    self.rust_type(def, &f.ty)?
+                ))
+            })
+            .collect::<Result<Vec<_>, CodegenError>>()?;
+
+        let body = fields.join(", ");
+        let one_line = format!("    {} {{ {} }},\n", case.name, body);
+        if body.len() <= STRUCT_VARIANT_WIDTH && one_line.len() <= MAX_WIDTH + 1 {
+            return Ok(one_line);
+        }
+        let mut out = format!("    {} {{\n", case.name);
+        for field in fields {
+            out.push_str(&format!("        {},\n", field));
+        }
+        out.push_str("    },\n");
+        Ok(out)
+    }
+
+    fn rust_type(&mut self, def: &TypeDef, ty: &TypeExpr) -> Result<String, CodegenError> {
+        let unknown = || CodegenError::UnknownType {
+            line: def.line,
+            owner: def.name.clone(),
+            ty: ty.fsharp(),
+        };
+
+        let args = ty
+            .args
+            .iter()
+            .map(|arg| self.rust_type(def, arg))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(primitive) = primitive(&ty.name) {
+            return if args.is_empty() {
+                self.uses_string |= primitive.0 == "String";
+                Ok(primitive.0.to_string())
+            } else {
+                Err(unknown())
+            };
+        }
+
+        let wrapper = match (ty.name.as_str(), args.len()) {
+            ("list" | "array" | "seq" | "ResizeArray", 1) => {
+                self.uses_vec = true;
+                "Vec"
+            }
+            ("option", 1) => "Option",
+            ("Map", 2) => {
+                self.uses_map = true;
+                "BTreeMap"
+            }
+            ("Set", 1) => {
+                self.uses_set = true;
+                "BTreeSet"
+            }
+            (name, 0) if self.module.type_def(name).is_some() => return Ok(name.to_string()),
+            _ => return Err(unknown()),
+        };
+        Ok(format!("{}<{}>", wrapper, args.join(", ")))
+    }
+
+    fn type_traits(&mut self, def: &TypeDef) -> Result<Traits, CodegenError> {
+        match self.traits.get(&def.name) {
+            Some(Some(traits)) => return Ok(*traits),
+            Some(None) => return Ok(Traits::NONE),
+            None => {}
+        }
+        self.traits.insert(def.name.clone(), None);
+
+        let traits = match &def.kind {
+            TypeKind::Record(fields) => {
+                let fields = self.fields_traits(def, fields)?;
+                Traits {
+                    copy: false,
+                    key: false,
+                    ..fields
+                }
+            }
+            TypeKind::Union(cases) if is_newtype(def) => {
+                let inner = self.expr_traits(def, &cases[0].fields[0].ty)?;
+                let scalar = inner.key && cases[0].fields[0].ty.args.is_empty();
+                Traits {
+                    key: scalar,
+                    ..inner
+                }
+            }
+            TypeKind::Union(cases) => {
+                let mut traits = Traits {
+                    key: cases.iter().all(|c| c.fields.is_empty()),
+                    default: cases[0].fields.is_empty(),
+                    ..Traits::ALL
+                };
+                for case in cases {
+                    let payload = self.fields_traits(def, &case.fields)?;
+                    traits.copy &= payload.copy;
+                    traits.eq &= payload.eq;
+                }
+                traits
+            }
+        };
+
+        self.traits.insert(def.name.clone(), Some(traits));
+        Ok(traits)
+    }
+
+    /// The traits every field supports.
+    fn fields_traits(&mut self, def: &TypeDef, fields: &[Field]) -> Result<Traits, CodegenError> {
+        let mut traits = Traits::ALL;
+        for field in fields {
+            let field = self.expr_traits(def, &field.ty)?;
+            traits.copy &= field.copy;
+            traits.eq &= field.eq;
+            traits.key &= field.key;
+            traits.default &= field.default;
+        }
+        Ok(traits)
+    }
+
+    fn expr_traits(&mut self, def: &TypeDef, ty: &TypeExpr) -> Result<Traits, CodegenError> {
+        if let Some((_, traits)) = primitive(&ty.name) {
+            return Ok(traits);
+        }
+        let mut args = Traits::ALL;
+        for arg in &ty.args {
+            let arg = self.expr_traits(def, arg)?;
+            args.copy &= arg.copy;
+            args.eq &= arg.eq;
+            args.key &= arg.key;
+        }
+
+        Ok(match ty.name.as_str() {
+            "option" => Traits {
+                default: true,
+                ..args
+            },
+            "list" | "array" | "seq" | "ResizeArray" | "Map" | "Set" => Traits {
+                copy: false,
+                key: false,
+                default: true,
+                ..args
+            },
+            name => match self.module.type_def(name) {
+                Some(other) => {
+                    let other = other.clone();
+                    self.type_traits(&other)?
+                }
+                None => {
+                    return Err(CodegenError::UnknownType {
+                        line: def.line,
+                        owner: def.name.clone(),
+                        ty: ty.fsharp(),
+                    })
+                }
+            },
+        })
+    }
+}
+
+/// F# primitive types, their Rust equivalents and the traits those support.
+pub(crate) fn primitive(name: &str) -> Option<(&'static str, Traits)> {
+    let float = Traits {
+        eq: false,
+        key: false,
+        ..Traits::ALL
+    };
+    let string = Traits {
+        copy: false,
+        ..Traits::ALL
+    };
+
+    Some(match name {
+        "int" | "int32" => ("i32", Traits::ALL),
+        "int64" => ("i64", Traits::ALL),
+        "int16" => ("i16", Traits::ALL),
+        "sbyte" | "int8" => ("i8", Traits::ALL),
+        "uint32" | "uint" => ("u32", Traits::ALL),
+        "uint64" => ("u64", Traits::ALL),
+        "uint16" => ("u16", Traits::ALL),
+        "byte" | "uint8" => ("u8", Traits::ALL),
+        "nativeint" => ("isize", Traits::ALL),
+        "unativeint" => ("usize", Traits::ALL),
+        "bool" => ("bool", Traits::ALL),
+        "char" => ("char", Traits::ALL),
+        "float" | "double" => ("f64", float),
+        "float32" | "single" => ("f32", float),
+        "string" => ("String", string),
+        _ => return None,
+    })
+}
+
+/// `type CombatantId = CombatantId of unativeint`
+pub(crate) fn is_newtype(def: &TypeDef) -> bool {
+    match &def.kind {
+        TypeKind::Union(cases) => {
+            cases.len() == 1
+                && cases[0].name == def.name
+                && cases[0].fields.len() == 1
+                && cases[0].fields[0].name.is_none()
+        }
+        TypeKind::Record(_) => false,
+    }
+}
+
+fn write_docs(out: &mut String, indent: &str, docs: &[String]) {
+    for line in docs {
+        if line.is_empty() {
+            out.push_str(&format!("{}///\n", indent));
+        } else {
+            out.push_str(&format!("{}/// {}\n", indent, line));
+        }
+    }
+}
+
+/// `#[derive(...)]`, wrapped the way rustfmt wraps long attribute lists.
+fn derive_attribute(derives: &[&str]) -> String {
+    let list = derives.join(", ");
+    if list.len() <= DERIVE_WIDTH {
+        format!("#[derive({})]\n", list)
+    } else if list.len() <= DERIVE_BLOCK_WIDTH {
+        format!("#[derive(\n    {},\n)]\n", list)
+    } else {
+        let lines: String = derives.iter().map(|d| format!("    {},\n", d)).collect();
+        format!("#[derive(\n{})]\n", lines)
+    }
+}
+
+fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn",
+        "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+        "async", "await", "dyn",
+    ];
+    let name = snake_case(name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// The F# definition without docs and attributes, for the generated docs.
+fn fsharp_source(def: &TypeDef) -> String {
+    let field = |f: &Field| match &f.name {
+        Some(name) => format!("{}: {}", name, f.ty.fsharp()),
+        None => f.ty.fsharp(),
+    };
+
+    match &def.kind {
+        TypeKind::Record(fields) => {
+            let mut out = format!("type {} =\n", def.name);
+            for (i, f) in fields.iter().enumerate() {
+                let open = if i == 0 { "    { " } else { "      " };
+                let close = if i + 1 == fields.len() { " }" } else { "" };
+                out.push_str(&format!("{}{}{}\n", open, field(f), close));
+            }
+            out
+        }
+        TypeKind::Union(cases) if is_newtype(def) => {
+            format!(
+                "type {} = {} of {}\n",
+                def.name,
+                cases[0].name,
+                field(&cases[0].fields[0])
+            )
+        }
+        TypeKind::Union(cases) => {
+            let mut out = format!("type {} =\n", def.name);
+            for case in cases {
+                out.push_str(&format!("    | {}", case.name));
+                if !case.fields.is_empty() {
+                    let fields: Vec<String> = case.fields.iter().map(field).collect();
+                    out.push_str(&format!(" of {}", fields.join(" * ")));
+                }
+                out.push('\n');
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_types(source: &str) -> String {
+        let out = generate_source(source, "Domain.fs").unwrap();
+        // Skip the header and imports
+        let start = out.find("\n///").unwrap() + 1;
+        out[start..].to_string()
+    }
+
+    #[test]
+    fn test_record() {
+        let out = generate_types(
+            "/// Combat stats.\n\
+             [<Component>]\n\
+             type Stats =\n    { /// Max HP.\n      Hp: int\n      [<SerdeDefault>]\n      CritChance: int }\n",
+        );
+
+        assert_eq!(
+            out,
+            "/// Combat stats.\n\
+             ///\n\
+             /// # F# Equivalent\n\
+             ///\n\
+             /// ```fsharp\n\
+             /// type Stats =\n\
+             ///     { Hp: int\n\
+             ///       CritChance: int }\n\
+             /// ```\n\
+             #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]\n\
+             #[cfg_attr(feature = \"bevy\", derive(bevy::prelude::Component))]\n\
+             pub struct Stats {\n    /// Max HP.\n    pub hp: i32,\n\n    #[serde(default)]\n    pub crit_chance: i32,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_unions() {
+        let out = generate_types(
+            "type Element =\n    | Physical\n    | Fire\n\
+             type Cooldown =\n    | Turns of max: int * remaining: int\n    | Seconds of float32\n\
+             type StatusEvent =\n    | Applied of target: string * kind: Element * stacks: uint32\n",
+        );
+
+        assert!(out.contains(
+            "#[derive(\n    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,\n)]\n\
+             pub enum Element {\n    #[default]\n    Physical,\n    Fire,\n}\n"
+        ));
+        assert!(out.contains(
+            "#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]\n\
+             pub enum Cooldown {\n    Turns { max: i32, remaining: i32 },\n    Seconds(f32),\n}\n"
+        ));
+        assert!(out.contains(
+            "pub enum StatusEvent {\n    Applied {\n        target: String,\n        kind: Element,\n        stacks: u32,\n    },\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_newtypes_and_collections() {
+        let out = generate_source(
+            "type Element =\n    | Fire\n\
+             type Resistances = Resistances of Map<Element, int>\n\
+             type Id = Id of unativeint\n\
+             type Party =\n    { Members: Id list\n      Leader: Id option }\n",
+            "Domain.fs",
+        )
+        .unwrap();
+
+        assert!(
+            out.contains("use alloc::collections::BTreeMap;\nuse alloc::vec::Vec;\n\nuse serde::")
+        );
+        assert!(out.contains(
+            "#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]\n\
+             #[serde(transparent)]\npub struct Resistances(pub BTreeMap<Element, i32>);\n"
+        ));
+        assert!(out.contains(
+            "PartialOrd, Ord, Hash, Default, Serialize, Deserialize,\n)]\n\
+             #[serde(transparent)]\npub struct Id(pub usize);\n"
+        ));
+        assert!(out.contains("    pub members: Vec<Id>,\n    pub leader: Option<Id>,\n"));
+    }
+
+    #[test]
+    fn test_unknown_types_are_errors() {
+        let err = generate_source("type A =\n    { Amount: decimal }\n", "Domain.fs").unwrap_err();
+
+        assert_eq!(
+            err,
+            CodegenError::UnknownType {
+                line: 1,
+                owner: "A".to_string(),
+                ty: "decimal".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Rust values that F# bindings can be compared against.
+//!
+//! F# bindings name their Rust counterpart in a doc line, e.g.
+//! `/// Corresponds to Rust: Ability::heal()`. This module evaluates those
+//! expressions against the real logic crate and serializes the result, so the
+//! comparison always sees what the Rust code actually returns.
+
+use bevy_wasm_fsharp_ref_logic::{
+    Ability, AbilitySet, AbilitySlot, PercentArmorFormula, COMBAT_EVENT_VERSION, MAX_LEVEL,
+};
+use serde_json::Value;
+
+/// Every Rust expression [`rust_value`] can evaluate.
+pub const KNOWN: &[&str] = &[
+    "Ability::basic_attack()",
+    "Ability::powerful_attack()",
+    "Ability::heal()",
+    "Ability::quick_strike()",
+    "AbilitySlot::basic_attack_turns()",
+    "AbilitySlot::powerful_attack_turns()",
+    "AbilitySlot::heal_turns()",
+    "AbilitySet::player_default()",
+    "AbilitySet::monster_default()",
+    "AbilitySet::monster_turn_based()",
+    "COMBAT_EVENT_VERSION",
+    "PercentArmorFormula::DEFAULT_ARMOR_SCALE",
+    "MAX_LEVEL",
+];
+
+/// Evaluates a Rust constructor expression and serializes its value, or `None`
+/// if the expression is not in [`KNOWN`].
+pub fn rust_value(expr: &str) -> Option<Value> {
+    let value = match expr {
+        "Ability::basic_attack()" => serde_json::to_value(Ability::basic_attack()),
+        "Ability::powerful_attack()" => serde_json::to_value(Ability::powerful_attack()),
+        "Ability::heal()" => serde_json::to_value(Ability::heal()),
+        "Ability::quick_strike()" => serde_json::to_value(Ability::quick_strike()),
+        "AbilitySlot::basic_attack_turns()" => {
+            serde_json::to_value(AbilitySlot::basic_attack_turns())
+        }
+        "AbilitySlot::powerful_attack_turns()" => {
+            serde_json::to_value(AbilitySlot::powerful_attack_turns())
+        }
+        "AbilitySlot::heal_turns()" => serde_json::to_value(AbilitySlot::heal_turns()),
+        "AbilitySet::player_default()" => serde_json::to_value(AbilitySet::player_default()),
+        "AbilitySet::monster_default()" => serde_json::to_value(AbilitySet::monster_default()),
+        "AbilitySet::monster_turn_based()" => {
+            serde_json::to_value(AbilitySet::monster_turn_based())
+        }
+        "COMBAT_EVENT_VERSION" => serde_json::to_value(COMBAT_EVENT_VERSION),
+        "PercentArmorFormula::DEFAULT_ARMOR_SCALE" => {
+            serde_json::to_value(PercentArmorFormula::DEFAULT_ARMOR_SCALE)
+        }
+        "MAX_LEVEL" => serde_json::to_value(MAX_LEVEL),
+        _ => return None,
+    };
+    Some(value.expect("logic types always serialize"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_known_expression_evaluates() {
+        for expr in KNOWN {
+            assert!(rust_value(expr).is_some(), "{}", expr);
+        }
+        assert_eq!(rust_value("Ability::meteor()"), None);
+    }
+
+    #[test]
+    fn test_values_are_serialized_with_rust_field_names() {
+        let heal = rust_value("Ability::heal()").unwrap();
+        assert_eq!(heal["effects"][0]["Heal"]["amount"], 8);
+    }
+}
@@ -0,0 +1,917 @@
+//! A small parser for the subset of F# used in `fsharp/`.
+//!
+//! It understands three kinds of top-level declarations:
+//!
+//! - Record types: `type Stats = { Hp: int; Attack: int }`
+//! - Union types: `type Cooldown = | Turns of max: int * remaining: int | ...`
+//! - Value bindings built from literals, records, lists, union cases and other
+//!   bindings: `let basicAttack = { Name = "Basic Attack"; Power = 5 }`
+//!
+//! Functions (`let f x = ...`), classes (`type X() = ...`), `open`, `module` and
+//! anything else are skipped. Top-level declarations must start in column 0 and
+//! continue on indented lines, as in the rest of the F# sources.
+//!
+//! `///` doc comments and `[<Attribute>]`s are kept on types, record fields and
+//! union cases, so the Rust code generator can carry them over.
+//!
+//! A `/// Corresponds to Rust: Ability::heal()` doc line on a value binding is
+//! recorded in [`Binding::rust`], so the binding can be compared with the Rust
+//! constructor it mirrors.
+
+use std::fmt;
+
+use crate::TypeExpr;
+
+/// A parsed F# source file (or several merged together).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Module {
+    pub types: Vec<TypeDef>,
+    pub bindings: Vec<Binding>,
+}
+
+/// A record or union type definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDef {
+    pub name: String,
+    pub kind: TypeKind,
+    pub line: usize,
+
+    /// `///` doc comment lines, without the slashes and leading space.
+    pub docs: Vec<String>,
+
+    /// Attribute names, e.g. `Component` for `[<Component>]`.
+    pub attributes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeKind {
+    Record(Vec<Field>),
+    Union(Vec<Case>),
+}
+
+/// A record field or union case field. Union case fields may be unnamed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: Option<String>,
+    pub ty: TypeExpr,
+    pub docs: Vec<String>,
+    pub attributes: Vec<String>,
+}
+
+/// A union case and its payload fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub docs: Vec<String>,
+}
+
+/// A top-level value binding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub value: Expr,
+    pub line: usize,
+
+    /// The Rust expression named in a `Corresponds to Rust:` doc line.
+    pub rust: Option<String>,
+}
+
+/// A constant F# expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    /// A union case without payload or a reference to another binding, possibly
+    /// qualified (`Cooldown.Turns`).
+    Ident(String),
+    /// A union case applied to arguments: `Cooldown.Turns(3, 0)`.
+    Call(String, Vec<Expr>),
+    List(Vec<Expr>),
+    Record(Vec<(String, Expr)>),
+    /// Anything else (arithmetic, function calls, ...), kept as source text.
+    Unsupported(String),
+}
+
+/// A syntax error with its 1-based line number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Module {
+    /// Looks up a type definition by name.
+    pub fn type_def(&self, name: &str) -> Option<&TypeDef> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// Looks up a value binding by name.
+    pub fn binding(&self, name: &str) -> Option<&Binding> {
+        self.bindings.iter().find(|b| b.name == name)
+    }
+
+    /// Appends the declarations of `other`, e.g. to resolve `GameLogic.fs`
+    /// bindings against `Domain.fs` types.
+    pub fn merge(&mut self, other: Module) {
+        self.types.extend(other.types);
+        self.bindings.extend(other.bindings);
+    }
+}
+
+/// Parses an F# source file.
+pub fn parse(source: &str) -> Result<Module, ParseError> {
+    let tokens = tokenize(source)?;
+    let mut module = Module::default();
+    let mut docs: Vec<String> = Vec::new();
+    let mut attributes: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let token = &tokens[i];
+        match &token.tok {
+            Tok::Doc(text) => {
+                docs.push(text.clone());
+                i += 1;
+                continue;
+            }
+            Tok::Attr(names) => {
+                attributes.extend(names.iter().cloned());
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        // A declaration runs until the next token in column 0
+        let end = (i + 1..tokens.len())
+            .find(|&j| tokens[j].col == 0)
+            .unwrap_or(tokens.len());
+
+        if token.col == 0 && token.is_ident("type") {
+            let decl = Parser {
+                tokens: &tokens[i..end],
+                pos: 0,
+            };
+            if let Some(mut def) = decl.type_def()? {
+                def.docs = std::mem::take(&mut docs);
+                def.attributes = std::mem::take(&mut attributes);
+                module.types.push(def);
+            }
+        } else if token.col == 0 && token.is_ident("let") {
+            // Expressions never contain doc comments or attributes
+            let decl_tokens: Vec<Token> = tokens[i..end]
+                .iter()
+                .filter(|t| !matches!(t.tok, Tok::Doc(_) | Tok::Attr(_)))
+                .cloned()
+                .collect();
+            let decl = Parser {
+                tokens: &decl_tokens,
+                pos: 0,
+            };
+            if let Some(mut binding) = decl.binding()? {
+                binding.rust = docs
+                    .iter()
+                    .find_map(|doc| doc.trim().strip_prefix("Corresponds to Rust:"))
+                    .map(|rust| rust.trim().to_string());
+                module.bindings.push(binding);
+            }
+        }
+
+        docs.clear();
+        attributes.clear();
+        i = end;
+    }
+
+    Ok(module)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Sym(char),
+    Doc(String),
+    /// The names in an attribute list: `[<Component; SerdeDefault>]`.
+    Attr(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    line: usize,
+    col: usize,
+}
+
+impl Token {
+    fn is_ident(&self, name: &str) -> bool {
+        matches!(&self.tok, Tok::Ident(n) if n == name)
+    }
+
+    fn is_sym(&self, c: char) -> bool {
+        self.tok == Tok::Sym(c)
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let (mut i, mut line, mut line_start) = (0, 1, 0);
+    let at = |j: usize| chars.get(j).copied();
+
+    while i < chars.len() {
+        let c = chars[i];
+        let col = i - line_start;
+
+        if c == '\n' {
+            i += 1;
+            line += 1;
+            line_start = i;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && at(i + 1) == Some('/') {
+            let end = (i..chars.len())
+                .find(|&j| chars[j] == '\n')
+                .unwrap_or(chars.len());
+            if at(i + 2) == Some('/') {
+                let text: String = chars[i + 3..end].iter().collect();
+                let text = text.strip_prefix(' ').unwrap_or(&text).trim_end();
+                tokens.push(Token {
+                    tok: Tok::Doc(text.to_string()),
+                    line,
+                    col,
+                });
+            }
+            i = end;
+        } else if c == '(' && at(i + 1) == Some('*') && at(i + 2) != Some(')') {
+            let start_line = line;
+            i += 2;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(ParseError {
+                            line: start_line,
+                            message: "unterminated comment".to_string(),
+                        })
+                    }
+                    Some('*') if at(i + 1) == Some(')') => break,
+                    Some('\n') => {
+                        line += 1;
+                        line_start = i + 1;
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '[' && at(i + 1) == Some('<') {
+            let start = i + 2;
+            let end = (start..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '>' && chars[j + 1] == ']')
+                .ok_or(ParseError {
+                    line,
+                    message: "unterminated attribute".to_string(),
+                })?;
+            let text: String = chars[start..end].iter().collect();
+            let names = text
+                .split(';')
+                .map(|attr| {
+                    // Drop constructor arguments: Obsolete("...") -> Obsolete
+                    let name = attr.split('(').next().unwrap_or_default().trim();
+                    name.strip_suffix("Attribute").unwrap_or(name).to_string()
+                })
+                .filter(|name| !name.is_empty())
+                .collect();
+            tokens.push(Token {
+                tok: Tok::Attr(names),
+                line,
+                col,
+            });
+            i = end + 2;
+        } else if c == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None | Some('\n') => {
+                        return Err(ParseError {
+                            line,
+                            message: "unterminated string".to_string(),
+                        })
+                    }
+                    Some('"') => break,
+                    Some('\\') => {
+                        text.push(match chars.get(i + 1) {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some(&other) => other,
+                            None => '\\',
+                        });
+                        i += 2;
+                    }
+                    Some(&other) => {
+                        text.push(other);
+                        i += 1;
+                    }
+                }
+            }
+            i += 1;
+            tokens.push(Token {
+                tok: Tok::Str(text),
+                line,
+                col,
+            });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                i += 1;
+            }
+            let is_float =
+                chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit());
+            if is_float {
+                i += 1;
+                while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            // Type suffixes: 1.5f (float32), 3L (int64), 2u (uint32), ...
+            let suffix_start = i;
+            while chars.get(i).is_some_and(|c| c.is_ascii_alphabetic()) {
+                i += 1;
+            }
+            let suffix: String = chars[suffix_start..i].iter().collect();
+            let is_float = is_float || suffix == "f" || suffix == "F";
+            let tok = if is_float {
+                text.parse().map(Tok::Float).ok()
+            } else {
+                text.parse().map(Tok::Int).ok()
+            };
+            tokens.push(Token {
+                tok: tok.ok_or_else(|| ParseError {
+                    line,
+                    message: format!("invalid number {}", text),
+                })?,
+                line,
+                col,
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            loop {
+                match chars.get(i) {
+                    Some(c) if c.is_alphanumeric() || *c == '_' || *c == '\'' => i += 1,
+                    // Dotted names: Cooldown.Turns, attacker.Stats.Attack
+                    Some('.') if chars.get(i + 1).is_some_and(|c| c.is_alphabetic()) => i += 1,
+                    _ => break,
+                }
+            }
+            tokens.push(Token {
+                tok: Tok::Ident(chars[start..i].iter().collect()),
+                line,
+                col,
+            });
+        } else {
+            tokens.push(Token {
+                tok: Tok::Sym(c),
+                line,
+                col,
+            });
+            i += 1;
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn line(&self) -> usize {
+        self.peek()
+            .or(self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(0)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError {
+            line: self.line(),
+            message: message.into(),
+        })
+    }
+
+    fn at_sym(&self, c: char) -> bool {
+        self.peek().is_some_and(|t| t.is_sym(c))
+    }
+
+    fn eat_sym(&mut self, c: char) -> bool {
+        let found = self.at_sym(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_sym(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat_sym(c) {
+            Ok(())
+        } else {
+            self.error(format!("expected '{}'", c))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, ParseError> {
+        match self.peek().map(|t| &t.tok) {
+            Some(Tok::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => self.error("expected an identifier"),
+        }
+    }
+
+    /// `Ident :` starts a new field in a record type or union case.
+    fn at_field_start(&self) -> bool {
+        matches!(self.peek().map(|t| &t.tok), Some(Tok::Ident(_)))
+            && self.peek_at(1).is_some_and(|t| t.is_sym(':'))
+    }
+
+    // ---- type Name = ... ----
+
+    fn type_def(mut self) -> Result<Option<TypeDef>, ParseError> {
+        let line = self.line();
+        self.next(); // type
+        let name = self.ident()?;
+        if !self.eat_sym('=') {
+            // Classes such as attribute definitions: `type ComponentAttribute() = ...`
+            return Ok(None);
+        }
+        let docs = self.docs_and_attributes().0;
+
+        let kind = if self.eat_sym('{') {
+            TypeKind::Record(self.record_fields()?)
+        } else if self.at_sym('|')
+            || self
+                .peek_at(1)
+                .is_some_and(|t| t.is_sym('|') || t.is_ident("of"))
+        {
+            TypeKind::Union(self.union_cases(docs)?)
+        } else {
+            // Aliases and other types are not compared
+            return Ok(None);
+        };
+
+        Ok(Some(TypeDef {
+            name,
+            kind,
+            line,
+            docs: Vec::new(),
+            attributes: Vec::new(),
+        }))
+    }
+
+    /// Consumes doc comments and attributes in front of a field or case.
+    fn docs_and_attributes(&mut self) -> (Vec<String>, Vec<String>) {
+        let (mut docs, mut attributes) = (Vec::new(), Vec::new());
+        loop {
+            match self.peek().map(|t| &t.tok) {
+                Some(Tok::Doc(text)) => docs.push(text.clone()),
+                Some(Tok::Attr(names)) => attributes.extend(names.iter().cloned()),
+                _ => return (docs, attributes),
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn record_fields(&mut self) -> Result<Vec<Field>, ParseError> {
+        let mut fields = Vec::new();
+        loop {
+            let (docs, attributes) = self.docs_and_attributes();
+            if self.eat_sym('}') {
+                break;
+            }
+            let name = self.ident()?;
+            self.expect_sym(':')?;
+            let ty = self.type_expr(&[';', '}'])?;
+            fields.push(Field {
+                name: Some(name),
+                ty,
+                docs,
+                attributes,
+            });
+            self.eat_sym(';');
+        }
+        Ok(fields)
+    }
+
+    /// `first_docs` are the docs in front of the first case, which come after
+    /// the `=`.
+    fn union_cases(&mut self, first_docs: Vec<String>) -> Result<Vec<Case>, ParseError> {
+        let mut cases = Vec::new();
+        let mut docs = first_docs;
+        while self.peek().is_some() {
+            docs.extend(self.docs_and_attributes().0);
+            self.eat_sym('|');
+            let name = self.ident()?;
+            let mut fields = Vec::new();
+
+            if self.peek().is_some_and(|t| t.is_ident("of")) {
+                self.next();
+                loop {
+                    let name = if self.at_field_start() {
+                        let name = self.ident()?;
+                        self.next(); // :
+                        Some(name)
+                    } else {
+                        None
+                    };
+                    let ty = self.type_expr(&['*', '|'])?;
+                    fields.push(Field {
+                        name,
+                        ty,
+                        docs: Vec::new(),
+                        attributes: Vec::new(),
+                    });
+                    if !self.eat_sym('*') {
+                        break;
+                    }
+                }
+            }
+            cases.push(Case {
+                name,
+                fields,
+                docs: std::mem::take(&mut docs),
+            });
+        }
+        Ok(cases)
+    }
+
+    /// Parses a type up to (not including) one of `stops` at nesting depth 0, or
+    /// the start of the next record field.
+    fn type_expr(&mut self, stops: &[char]) -> Result<TypeExpr, ParseError> {
+        let mut ty = if self.eat_sym('(') {
+            let inner = self.type_expr(&[')'])?;
+            self.expect_sym(')')?;
+            inner
+        } else {
+            let name = self.ident()?;
+            let mut args = Vec::new();
+            if self.eat_sym('<') {
+                loop {
+                    args.push(self.type_expr(&[',', '>'])?);
+                    if self.eat_sym('>') {
+                        break;
+                    }
+                    self.expect_sym(',')?;
+                }
+            }
+            TypeExpr::new(name, args)
+        };
+
+        // Postfix type application: `StatusEffect list`, `int option`
+        while let Some(Tok::Ident(suffix)) = self.peek().map(|t| &t.tok) {
+            if self.at_field_start() {
+                break;
+            }
+            ty = TypeExpr::new(suffix.clone(), vec![ty]);
+            self.pos += 1;
+        }
+
+        match self.peek() {
+            None => Ok(ty),
+            Some(t) if stops.iter().any(|&c| t.is_sym(c)) => Ok(ty),
+            // The docs or attributes of the next field or case
+            Some(t) if matches!(t.tok, Tok::Doc(_) | Tok::Attr(_)) => Ok(ty),
+            Some(_) if self.at_field_start() => Ok(ty),
+            Some(_) => self.error("unsupported type syntax"),
+        }
+    }
+
+    // ---- let name = ... ----
+
+    fn binding(mut self) -> Result<Option<Binding>, ParseError> {
+        let line = self.line();
+        self.next(); // let
+        if self.peek().is_some_and(|t| t.is_ident("private")) {
+            self.next();
+        }
+        let name = self.ident()?;
+
+        // Skip a type annotation; anything else before `=` means a function
+        if self.eat_sym(':') {
+            while self.peek().is_some_and(|t| !t.is_sym('=')) {
+                self.next();
+            }
+        }
+        if !self.eat_sym('=') {
+            return Ok(None);
+        }
+
+        let value = self.expr()?;
+        Ok(Some(Binding {
+            name,
+            value,
+            line,
+            rust: None,
+        }))
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        let Some(token) = self.next().cloned() else {
+            return self.error("expected an expression");
+        };
+
+        let expr = match token.tok {
+            Tok::Int(n) => Expr::Int(n),
+            Tok::Float(x) => Expr::Float(x),
+            Tok::Str(s) => Expr::Str(s),
+            Tok::Sym('-') => match self.next().map(|t| t.tok.clone()) {
+                Some(Tok::Int(n)) => Expr::Int(-n),
+                Some(Tok::Float(x)) => Expr::Float(-x),
+                _ => return self.unsupported(start),
+            },
+            Tok::Sym('{') => self.record_expr(start)?,
+            Tok::Sym('[') => self.list_expr()?,
+            Tok::Sym('(') => {
+                let inner = self.expr()?;
+                self.expect_sym(')')?;
+                inner
+            }
+            Tok::Ident(name) if name == "true" => Expr::Bool(true),
+            Tok::Ident(name) if name == "false" => Expr::Bool(false),
+            Tok::Ident(name) => {
+                // A union case applied to a parenthesised argument list
+                let applied = self
+                    .peek()
+                    .is_some_and(|t| t.is_sym('(') && t.line == token.line);
+                if applied {
+                    self.next();
+                    let mut args = Vec::new();
+                    while !self.eat_sym(')') {
+                        args.push(self.expr()?);
+                        self.eat_sym(',');
+                    }
+                    Expr::Call(name, args)
+                } else {
+                    Expr::Ident(name)
+                }
+            }
+            _ => return self.unsupported(start),
+        };
+
+        // Operators or function application on the same line are not constant
+        let continues = self.peek().is_some_and(|t| {
+            t.line == self.tokens[self.pos - 1].line
+                && ![';', ',', ')', ']', '}'].iter().any(|&c| t.is_sym(c))
+        });
+        if continues {
+            return self.unsupported(start);
+        }
+        Ok(expr)
+    }
+
+    fn record_expr(&mut self, start: usize) -> Result<Expr, ParseError> {
+        let mut fields = Vec::new();
+        while !self.eat_sym('}') {
+            let name = self.ident()?;
+            if name == "with" || !self.eat_sym('=') {
+                // Copy-and-update and other forms
+                return self.unsupported(start);
+            }
+            fields.push((name, self.expr()?));
+            self.eat_sym(';');
+        }
+        Ok(Expr::Record(fields))
+    }
+
+    fn list_expr(&mut self) -> Result<Expr, ParseError> {
+        let mut items = Vec::new();
+        while !self.eat_sym(']') {
+            items.push(self.expr()?);
+            self.eat_sym(';');
+        }
+        Ok(Expr::List(items))
+    }
+
+    /// Skips the rest of the expression on its line, up to a separator or closing
+    /// bracket of the enclosing expression, and records its source text.
+    fn unsupported(&mut self, start: usize) -> Result<Expr, ParseError> {
+        let line = self.tokens[start].line;
+        self.pos = start;
+        let mut depth = 0usize;
+        while let Some(t) = self.peek().filter(|t| t.line == line) {
+            match t.tok {
+                Tok::Sym('(' | '[' | '{') => depth += 1,
+                Tok::Sym(')' | ']' | '}') if depth == 0 => break,
+                Tok::Sym(')' | ']' | '}') => depth -= 1,
+                Tok::Sym(';' | ',') if depth == 0 => break,
+                _ => {}
+            }
+            self.next();
+        }
+        let text = self.tokens[start..self.pos]
+            .iter()
+            .map(|t| match &t.tok {
+                Tok::Ident(s) => s.clone(),
+                Tok::Int(n) => n.to_string(),
+                Tok::Float(x) => x.to_string(),
+                Tok::Str(s) => format!("{:?}", s),
+                Tok::Sym(c) => c.to_string(),
+                Tok::Doc(_) | Tok::Attr(_) => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(Expr::Unsupported(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_type() {
+        let module = parse(
+            "type Stats =\n    { Hp: int\n      Resistances: Map<Element, int>\n      Effects: StatusEffect list }\n",
+        )
+        .unwrap();
+
+        let TypeKind::Record(fields) = &module.types[0].kind else {
+            panic!("expected a record");
+        };
+        assert_eq!(fields[0].name.as_deref(), Some("Hp"));
+        assert_eq!(fields[0].ty, TypeExpr::named("int"));
+        assert_eq!(fields[1].ty.to_string(), "Map<Element, int>");
+        assert_eq!(fields[2].ty.to_string(), "list<StatusEffect>");
+    }
+
+    #[test]
+    fn test_single_line_record_type() {
+        let module = parse("type Character = { Name: string; Hp: int }").unwrap();
+
+        let TypeKind::Record(fields) = &module.types[0].kind else {
+            panic!("expected a record");
+        };
+        assert_eq!(fields.len(), 2);
+    }
+
+    #[test]
+    fn test_union_type() {
+        let module = parse(
+            "type Cooldown =\n    | Turns of max: int * remaining: int\n    | Seconds of float\n    | Off\n",
+        )
+        .unwrap();
+
+        let TypeKind::Union(cases) = &module.types[0].kind else {
+            panic!("expected a union");
+        };
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].fields[1].name.as_deref(), Some("remaining"));
+        assert_eq!(cases[1].fields[0].name, None);
+        assert!(cases[2].fields.is_empty());
+    }
+
+    #[test]
+    fn test_value_bindings() {
+        let module = parse(
+            r#"
+/// A heal.
+/// Corresponds to Rust: Ability::heal()
+let healAbility =
+    { Name = "Heal"
+      Power = -8
+      Tags = [ Fire; Ice ]
+      Cooldown = Cooldown.Turns(4, 0) }
+
+let isReady (cooldown: Cooldown) : bool = true
+let total = 1 + 2
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(module.bindings.len(), 2);
+        let heal = module.binding("healAbility").unwrap();
+        assert_eq!(heal.rust.as_deref(), Some("Ability::heal()"));
+        assert_eq!(heal.line, 4);
+        assert_eq!(
+            heal.value,
+            Expr::Record(vec![
+                ("Name".to_string(), Expr::Str("Heal".to_string())),
+                ("Power".to_string(), Expr::Int(-8)),
+                (
+                    "Tags".to_string(),
+                    Expr::List(vec![
+                        Expr::Ident("Fire".to_string()),
+                        Expr::Ident("Ice".to_string())
+                    ])
+                ),
+                (
+                    "Cooldown".to_string(),
+                    Expr::Call(
+                        "Cooldown.Turns".to_string(),
+                        vec![Expr::Int(4), Expr::Int(0)]
+                    )
+                ),
+            ])
+        );
+        assert!(matches!(
+            module.binding("total").unwrap().value,
+            Expr::Unsupported(_)
+        ));
+    }
+
+    #[test]
+    fn test_docs_and_attributes() {
+        let module = parse(
+            r#"
+open System
+
+type ComponentAttribute() =
+    inherit Attribute()
+
+/// A character.
+[<Component>]
+type Character =
+    { /// Display name.
+      Name: string
+      [<SerdeDefault>]
+      Level: int }
+
+type Element =
+    /// Untyped damage.
+    | Physical
+    /// Burns.
+    | Fire
+
+type CombatantId = CombatantId of unativeint
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(module.types.len(), 3);
+        let character = module.type_def("Character").unwrap();
+        assert_eq!(character.docs, vec!["A character."]);
+        assert_eq!(character.attributes, vec!["Component"]);
+        let TypeKind::Record(fields) = &character.kind else {
+            panic!("expected a record");
+        };
+        assert_eq!(fields[0].docs, vec!["Display name."]);
+        assert_eq!(fields[1].attributes, vec!["SerdeDefault"]);
+
+        let TypeKind::Union(cases) = &module.type_def("Element").unwrap().kind else {
+            panic!("expected a union");
+        };
+        assert_eq!(cases[0].docs, vec!["Untyped damage."]);
+        assert_eq!(cases[1].docs, vec!["Burns."]);
+
+        let TypeKind::Union(cases) = &module.type_def("CombatantId").unwrap().kind else {
+            panic!("expected a union");
+        };
+        assert_eq!(cases[0].fields[0].ty, TypeExpr::named("unativeint"));
+    }
+
+    #[test]
+    fn test_numeric_suffixes() {
+        let module = parse("let a = 1.5f\nlet b = 3L\n").unwrap();
+
+        assert_eq!(module.binding("a").unwrap().value, Expr::Float(1.5));
+        assert_eq!(module.binding("b").unwrap().value, Expr::Int(3));
+    }
+
+    #[test]
+    fn test_errors_report_the_line() {
+        let err = parse("type A =\n    { Hp int }").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+}
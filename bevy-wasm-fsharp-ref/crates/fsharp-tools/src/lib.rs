@@ -0,0 +1,91 @@
+//! # F# Tools
+//!
+//! Keeps the F# reference sources in `fsharp/` honest about the Rust logic crate
+//! they document. The `fsharp-parity` binary parses the F# records, unions and
+//! let bindings and compares them with the Rust definitions:
+//!
+//! - Field names and types of the core combat model, from `Stats` and `Ability`
+//!   to `AbilityEffect` and `CombatEvent` (see [`parity::DEFAULT_TYPES`])
+//! - Constant values such as `healAbility` against the Rust constructor named in
+//!   their `/// Corresponds to Rust:` doc line
+//!
+//! Every difference is reported with its path on both sides, so drift such as a
+//! heal of the wrong size shows up as
+//! `healAbility.Effects[0].Heal.amount <-> Ability::heal().effects[0].Heal.amount`.
+//!
+//! # Examples
+//!
+//! ```
+//! use fsharp_tools::parity::check_sources;
+//!
+//! let fsharp = "type Stats =\n    { Hp: int\n      Attack: int }\n";
+//! let rust = "pub struct Stats { pub hp: i32, pub attack: u32 }";
+//!
+//! let report = check_sources(&[("Domain.fs", fsharp)], &[("lib.rs", rust)], &["Stats"]).unwrap();
+//!
+//! assert_eq!(report.mismatches.len(), 1);
+//! assert_eq!(report.mismatches[0].rust_path, "Stats.attack");
+//! assert_eq!(report.mismatches[0].rust, "u32");
+//! ```
+
+use std::fmt;
+
+pub mod codegen;
+pub mod constants;
+pub mod fsharp;
+pub mod parity;
+pub mod rust;
+pub mod typescript;
+
+pub use parity::{check, check_sources, CheckError, Mismatch, ParityReport};
+
+/// A type as written in a field declaration, reduced to a name and its type
+/// arguments: `Map<Element, int>`, `StatusEffect list`, `Vec<StatusEffect>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeExpr {
+    pub name: String,
+    pub args: Vec<TypeExpr>,
+}
+
+impl TypeExpr {
+    pub fn new(name: impl Into<String>, args: Vec<TypeExpr>) -> Self {
+        Self {
+            name: name.into(),
+            args,
+        }
+    }
+
+    /// A type without arguments.
+    pub fn named(name: &str) -> Self {
+        Self::new(name, Vec::new())
+    }
+
+    /// The type as F# writes it: `StatusEffect list`, `Map<Element, int>`.
+    pub fn fsharp(&self) -> String {
+        match (self.name.as_str(), self.args.as_slice()) {
+            ("list" | "option" | "array" | "seq", [arg]) => {
+                format!("{} {}", arg.fsharp(), self.name)
+            }
+            (_, []) => self.name.clone(),
+            (name, args) => format!(
+                "{}<{}>",
+                name,
+                args.iter()
+                    .map(TypeExpr::fsharp)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.args.is_empty() {
+            let args: Vec<String> = self.args.iter().map(ToString::to_string).collect();
+            write!(f, "<{}>", args.join(", "))?;
+        }
+        Ok(())
+    }
+}
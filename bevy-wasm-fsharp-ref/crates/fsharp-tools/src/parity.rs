@@ -0,0 +1,749 @@
+//! Comparison of F# definitions with their Rust counterparts.
+//!
+//! Two kinds of checks produce [`Mismatch`]es:
+//!
+//! - **Types**: each F# record must be a Rust struct with the same fields
+//!   (`PascalCase` → `snake_case`) and equivalent field types; each F# union must
+//!   be a Rust enum with the same cases and payloads
+//! - **Values**: each F# binding with a `Corresponds to Rust:` doc line must
+//!   equal the serialized result of that Rust expression (see
+//!   [`constants`](crate::constants)), field by field
+//!
+//! Primitive types map as documented in `docs/fsharp-rust-type-mapping.md`:
+//!
+//! | F# | Rust |
+//! |----|------|
+//! | `int` | `i32` |
+//! | `int64` | `i64` |
+//! | `float` | `f64` |
+//! | `float32` | `f32` |
+//! | `string` | `String` |
+//! | `bool` | `bool` |
+//! | `T list` | `Vec<T>` |
+//! | `T option` | `Option<T>` |
+//! | `Map<K, V>` | `BTreeMap<K, V>` or `HashMap<K, V>` |
+//!
+//! Rust newtypes such as `Resistances(BTreeMap<Element, i32>)` compare as the
+//! type they wrap.
+
+use std::fmt;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::fsharp::{self, Expr, Module};
+use crate::rust::{self, Definitions, ItemKind};
+use crate::TypeExpr;
+
+/// The types compared by default: the core combat model.
+pub const DEFAULT_TYPES: &[&str] = &[
+    "Stats",
+    "Character",
+    "Targeting",
+    "AbilityEffect",
+    "Ability",
+    "AbilitySlot",
+    "EffectOutcome",
+    "CombatEvent",
+    "AbilityType",
+];
+
+/// One difference between the F# and Rust definitions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    /// Where the difference is, in F# names: `healAbility.Effects[0].Heal.amount`, `Stats.Speed`.
+    pub fsharp_path: String,
+
+    /// The same place in Rust names: `Ability::heal().effects[0].Heal.amount`, `Stats.speed`.
+    pub rust_path: String,
+
+    /// What F# has there.
+    pub fsharp: String,
+
+    /// What Rust has there.
+    pub rust: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} <-> {}", self.fsharp_path, self.rust_path)?;
+        writeln!(f, "    F#:   {}", self.fsharp)?;
+        write!(f, "    Rust: {}", self.rust)
+    }
+}
+
+/// Everything a parity check looked at and every difference it found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParityReport {
+    /// Names of the types compared.
+    pub types: Vec<String>,
+
+    /// Names of the F# bindings compared with Rust values.
+    pub bindings: Vec<String>,
+
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ParityReport {
+    /// Whether the definitions match.
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for ParityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Compared {} types ({}) and {} values ({})",
+            self.types.len(),
+            self.types.join(", "),
+            self.bindings.len(),
+            self.bindings.join(", ")
+        )?;
+
+        if self.is_ok() {
+            return write!(f, "F# and Rust definitions match.");
+        }
+        writeln!(f, "\n{} mismatches:\n", self.mismatches.len())?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "{}\n", mismatch)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the named types and every F# binding that names a Rust counterpart.
+///
+/// `rust_value` evaluates the Rust expressions; [`crate::constants::rust_value`]
+/// is the usual choice.
+pub fn compare(
+    fsharp: &Module,
+    rust: &Definitions,
+    types: &[&str],
+    rust_value: impl Fn(&str) -> Option<Value>,
+) -> ParityReport {
+    let mut report = ParityReport::default();
+
+    for &name in types {
+        report.types.push(name.to_string());
+        compare_type(fsharp, rust, name, &mut report.mismatches);
+    }
+
+    for binding in &fsharp.bindings {
+        let Some(expr) = &binding.rust else {
+            continue;
+        };
+        report.bindings.push(binding.name.clone());
+
+        match rust_value(expr) {
+            Some(value) => Differ {
+                module: fsharp,
+                out: &mut report.mismatches,
+            }
+            .diff(&binding.name, expr, &binding.value, &value),
+            None => report.mismatches.push(Mismatch {
+                fsharp_path: binding.name.clone(),
+                rust_path: expr.clone(),
+                fsharp: format!("binding on line {}", binding.line),
+                rust: "no known Rust value (see fsharp_tools::constants::KNOWN)".to_string(),
+            }),
+        }
+    }
+
+    report
+}
+
+/// The F# sources compared with Rust, relative to the repository root.
+pub const FSHARP_SOURCES: &[&str] = &["fsharp/Domain.fs", "fsharp/GameLogic.fs"];
+
+/// The directory whose `.rs` files hold the Rust definitions.
+pub const RUST_SOURCES: &str = "crates/logic-fsharp/src";
+
+/// Why a parity check could not run.
+#[derive(Debug)]
+pub enum CheckError {
+    /// A source file could not be read.
+    Io { path: String, message: String },
+
+    /// An F# file could not be parsed.
+    FSharp {
+        file: String,
+        source: fsharp::ParseError,
+    },
+
+    /// A Rust file could not be parsed.
+    Rust {
+        file: String,
+        source: rust::ParseError,
+    },
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::Io { path, message } => write!(f, "cannot read {}: {}", path, message),
+            CheckError::FSharp { file, source } => write!(f, "{}: {}", file, source),
+            CheckError::Rust { file, source } => write!(f, "{}: {}", file, source),
+        }
+    }
+}
+
+impl std::error::Error for CheckError {}
+
+/// Parses `(file name, source)` pairs and [`compare`]s them against the Rust
+/// values of [`constants::rust_value`](crate::constants::rust_value).
+pub fn check_sources(
+    fsharp_files: &[(&str, &str)],
+    rust_files: &[(&str, &str)],
+    types: &[&str],
+) -> Result<ParityReport, CheckError> {
+    let mut module = Module::default();
+    for (file, source) in fsharp_files {
+        module.merge(fsharp::parse(source).map_err(|source| CheckError::FSharp {
+            file: file.to_string(),
+            source,
+        })?);
+    }
+
+    let mut definitions = Definitions::default();
+    for (file, source) in rust_files {
+        definitions
+            .parse_file(source)
+            .map_err(|source| CheckError::Rust {
+                file: file.to_string(),
+                source,
+            })?;
+    }
+
+    Ok(compare(
+        &module,
+        &definitions,
+        types,
+        crate::constants::rust_value,
+    ))
+}
+
+/// Checks the repository at `root`: [`FSHARP_SOURCES`] against every `.rs` file
+/// in [`RUST_SOURCES`].
+pub fn check(root: &Path, types: &[&str]) -> Result<ParityReport, CheckError> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).map_err(|e| CheckError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })
+    };
+
+    let mut fsharp_files = Vec::new();
+    for file in FSHARP_SOURCES {
+        fsharp_files.push((file.to_string(), read(&root.join(file))?));
+    }
+
+    let rust_dir = root.join(RUST_SOURCES);
+    let entries = std::fs::read_dir(&rust_dir).map_err(|e| CheckError::Io {
+        path: rust_dir.display().to_string(),
+        message: e.to_string(),
+    })?;
+    let mut rust_paths: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    rust_paths.sort();
+    let mut rust_files = Vec::new();
+    for path in rust_paths {
+        let source = read(&path)?;
+        rust_files.push((path.display().to_string(), source));
+    }
+
+    check_sources(&borrowed(&fsharp_files), &borrowed(&rust_files), types)
+}
+
+fn borrowed(files: &[(String, String)]) -> Vec<(&str, &str)> {
+    files
+        .iter()
+        .map(|(file, source)| (file.as_str(), source.as_str()))
+        .collect()
+}
+
+/// `DefenderHpAfter` → `defender_hp_after`, `maxStacks` → `max_stacks`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let ends_acronym = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if after_lower || ends_acronym {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn compare_type(fsharp: &Module, rust: &Definitions, name: &str, out: &mut Vec<Mismatch>) {
+    let missing = |fs: &str, rs: &str| Mismatch {
+        fsharp_path: name.to_string(),
+        rust_path: name.to_string(),
+        fsharp: fs.to_string(),
+        rust: rs.to_string(),
+    };
+
+    let (fs_def, rs_item) = match (fsharp.type_def(name), rust.item(name)) {
+        (Some(fs), Some(rs)) => (fs, rs),
+        (None, None) => return out.push(missing("not defined", "not defined")),
+        (None, Some(_)) => return out.push(missing("not defined", "defined")),
+        (Some(_), None) => return out.push(missing("defined", "not defined")),
+    };
+
+    match (&fs_def.kind, &rs_item.kind) {
+        (fsharp::TypeKind::Record(fs_fields), ItemKind::Struct(rs_fields)) => {
+            compare_fields(rust, name, name, fs_fields, rs_fields, out);
+        }
+        (fsharp::TypeKind::Union(cases), ItemKind::Enum(variants)) => {
+            for case in cases {
+                let rs_path = format!("{}::{}", name, case.name);
+                let fs_path = format!("{}.{}", name, case.name);
+                match variants.iter().find(|v| v.name == case.name) {
+                    Some(variant) => {
+                        compare_fields(rust, &fs_path, &rs_path, &case.fields, &variant.fields, out)
+                    }
+                    None => out.push(Mismatch {
+                        fsharp_path: fs_path,
+                        rust_path: rs_path,
+                        fsharp: "case defined".to_string(),
+                        rust: "no such variant".to_string(),
+                    }),
+                }
+            }
+            for variant in variants {
+                if !cases.iter().any(|c| c.name == variant.name) {
+                    out.push(Mismatch {
+                        fsharp_path: format!("{}.{}", name, variant.name),
+                        rust_path: format!("{}::{}", name, variant.name),
+                        fsharp: "no such case".to_string(),
+                        rust: "variant defined".to_string(),
+                    });
+                }
+            }
+        }
+        // Single-case unions are newtypes: `type Id = Id of int` is `struct Id(i32)`
+        (fsharp::TypeKind::Union(cases), ItemKind::Tuple(types))
+            if cases.len() == 1 && cases[0].name == name =>
+        {
+            let rs_fields: Vec<rust::Field> = types
+                .iter()
+                .map(|ty| rust::Field {
+                    name: None,
+                    ty: ty.clone(),
+                })
+                .collect();
+            compare_fields(rust, name, name, &cases[0].fields, &rs_fields, out);
+        }
+        (fs_kind, rs_kind) => out.push(missing(
+            match fs_kind {
+                fsharp::TypeKind::Record(_) => "record",
+                fsharp::TypeKind::Union(_) => "union",
+            },
+            match rs_kind {
+                ItemKind::Struct(_) => "struct",
+                ItemKind::Tuple(_) => "tuple struct",
+                ItemKind::Enum(_) => "enum",
+            },
+        )),
+    }
+}
+
+/// Compares record fields or union case payloads. Named fields are matched by
+/// name, unnamed ones by position.
+fn compare_fields(
+    rust: &Definitions,
+    fs_owner: &str,
+    rs_owner: &str,
+    fs_fields: &[fsharp::Field],
+    rs_fields: &[rust::Field],
+    out: &mut Vec<Mismatch>,
+) {
+    let mut matched = vec![false; rs_fields.len()];
+
+    for (index, fs_field) in fs_fields.iter().enumerate() {
+        let rs_name = fs_field.name.as_deref().map(snake_case);
+        let found = match &rs_name {
+            Some(rs_name) => rs_fields
+                .iter()
+                .position(|f| f.name.as_deref() == Some(rs_name.as_str())),
+            None => (index < rs_fields.len() && rs_fields[index].name.is_none()).then_some(index),
+        };
+
+        let fs_path = format!(
+            "{}.{}",
+            fs_owner,
+            fs_field.name.clone().unwrap_or_else(|| index.to_string())
+        );
+        let rs_path = format!(
+            "{}.{}",
+            rs_owner,
+            rs_name.unwrap_or_else(|| index.to_string())
+        );
+        let fs_type = fs_field.ty.fsharp();
+
+        match found {
+            None => out.push(Mismatch {
+                fsharp_path: fs_path,
+                rust_path: rs_path,
+                fsharp: fs_type,
+                rust: "no such field".to_string(),
+            }),
+            Some(position) => {
+                matched[position] = true;
+                let rs_type = &rs_fields[position].ty;
+                if !same_type(rust, &fs_field.ty, rs_type) {
+                    out.push(Mismatch {
+                        fsharp_path: fs_path,
+                        rust_path: rs_path,
+                        fsharp: fs_type,
+                        rust: rs_type.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    for (position, rs_field) in rs_fields.iter().enumerate() {
+        if matched[position] {
+            continue;
+        }
+        let name = rs_field
+            .name
+            .clone()
+            .unwrap_or_else(|| position.to_string());
+        out.push(Mismatch {
+            fsharp_path: format!("{}.{}", fs_owner, pascal_case(&name)),
+            rust_path: format!("{}.{}", rs_owner, name),
+            fsharp: "no such field".to_string(),
+            rust: rs_field.ty.to_string(),
+        });
+    }
+}
+
+fn same_type(rust: &Definitions, fs: &TypeExpr, rs: &TypeExpr) -> bool {
+    let matches = |rs: &TypeExpr| {
+        let names = match fs.name.as_str() {
+            "int" | "int32" => rs.name == "i32",
+            "int64" => rs.name == "i64",
+            "uint32" | "uint" => rs.name == "u32",
+            "uint64" => rs.name == "u64",
+            "nativeint" => rs.name == "isize",
+            "unativeint" => rs.name == "usize",
+            "float" | "double" => rs.name == "f64",
+            "float32" | "single" => rs.name == "f32",
+            "string" => rs.name == "String",
+            "list" | "array" | "seq" => rs.name == "Vec",
+            "option" => rs.name == "Option",
+            "Map" => rs.name == "BTreeMap" || rs.name == "HashMap",
+            "Set" => rs.name == "BTreeSet" || rs.name == "HashSet",
+            other => rs.name == other,
+        };
+        names
+            && fs.args.len() == rs.args.len()
+            && fs
+                .args
+                .iter()
+                .zip(&rs.args)
+                .all(|(f, r)| same_type(rust, f, r))
+    };
+
+    matches(rs) || matches(rust.unwrap_newtype(rs))
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Walks an F# value and the serialized Rust value side by side.
+struct Differ<'a> {
+    module: &'a Module,
+    out: &'a mut Vec<Mismatch>,
+}
+
+impl Differ<'_> {
+    fn report(&mut self, fs_path: &str, rs_path: &str, fsharp: String, rust: &Value) {
+        self.out.push(Mismatch {
+            fsharp_path: fs_path.to_string(),
+            rust_path: rs_path.to_string(),
+            fsharp,
+            rust: if rust.is_null() {
+                "no such field".to_string()
+            } else {
+                rust.to_string()
+            },
+        });
+    }
+
+    fn diff(&mut self, fs_path: &str, rs_path: &str, expr: &Expr, value: &Value) {
+        match expr {
+            Expr::Int(n) => {
+                if value.as_f64() != Some(*n as f64) {
+                    self.report(fs_path, rs_path, n.to_string(), value);
+                }
+            }
+            Expr::Float(x) => {
+                let close = value
+                    .as_f64()
+                    .is_some_and(|v| (v - x).abs() <= 1e-6 * x.abs().max(1.0));
+                if !close {
+                    self.report(fs_path, rs_path, x.to_string(), value);
+                }
+            }
+            Expr::Str(s) => {
+                if value.as_str() != Some(s) {
+                    self.report(fs_path, rs_path, format!("{:?}", s), value);
+                }
+            }
+            Expr::Bool(b) => {
+                if value.as_bool() != Some(*b) {
+                    self.report(fs_path, rs_path, b.to_string(), value);
+                }
+            }
+            Expr::Record(fields) => {
+                for (name, field) in fields {
+                    let rs_name = snake_case(name);
+                    let field_value = value.get(&rs_name).unwrap_or(&Value::Null);
+                    self.diff(
+                        &format!("{}.{}", fs_path, name),
+                        &format!("{}.{}", rs_path, rs_name),
+                        field,
+                        field_value,
+                    );
+                }
+            }
+            Expr::List(items) => {
+                let rs_items = value.as_array().map(Vec::as_slice).unwrap_or(&[]);
+                if !value.is_array() || rs_items.len() != items.len() {
+                    let fs = format!("list of {} items", items.len());
+                    return self.report(fs_path, rs_path, fs, value);
+                }
+                for (index, (item, rs_item)) in items.iter().zip(rs_items).enumerate() {
+                    self.diff(
+                        &format!("{}[{}]", fs_path, index),
+                        &format!("{}[{}]", rs_path, index),
+                        item,
+                        rs_item,
+                    );
+                }
+            }
+            Expr::Ident(name) => match self.module.binding(name) {
+                Some(binding) => self.diff(fs_path, rs_path, &binding.value.clone(), value),
+                None => self.diff_case(fs_path, rs_path, name, &[], value),
+            },
+            Expr::Call(name, args) => self.diff_case(fs_path, rs_path, name, args, value),
+            Expr::Unsupported(text) => {
+                self.report(fs_path, rs_path, format!("non-constant `{}`", text), value)
+            }
+        }
+    }
+
+    /// Union cases serialize as `"Case"`, `{"Case": value}` or
+    /// `{"Case": {"field": value, ...}}`.
+    fn diff_case(
+        &mut self,
+        fs_path: &str,
+        rs_path: &str,
+        name: &str,
+        args: &[Expr],
+        value: &Value,
+    ) {
+        let (union, case_name) = match name.rsplit_once('.') {
+            Some((union, case)) => (Some(union), case),
+            None => (None, name),
+        };
+        let case = self
+            .module
+            .types
+            .iter()
+            .filter(|t| union.is_none_or(|u| t.name == u))
+            .filter_map(|t| match &t.kind {
+                fsharp::TypeKind::Union(cases) => Some(cases),
+                fsharp::TypeKind::Record(_) => None,
+            })
+            .flatten()
+            .find(|c| c.name == case_name && c.fields.len() == args.len());
+
+        let Some(case) = case else {
+            let fs = format!("unknown union case or binding `{}`", name);
+            return self.report(fs_path, rs_path, fs, value);
+        };
+
+        if args.is_empty() {
+            if value.as_str() != Some(case_name) {
+                self.report(fs_path, rs_path, case_name.to_string(), value);
+            }
+            return;
+        }
+
+        let Some(payload) = value.get(case_name) else {
+            return self.report(fs_path, rs_path, case_name.to_string(), value);
+        };
+        let fs_path = format!("{}.{}", fs_path, case_name);
+        let rs_path = format!("{}.{}", rs_path, case_name);
+
+        if let [arg] = args {
+            if case.fields[0].name.is_none() {
+                return self.diff(&fs_path, &rs_path, arg, payload);
+            }
+        }
+        for (index, (field, arg)) in case.fields.iter().zip(args).enumerate() {
+            match &field.name {
+                Some(name) => {
+                    let rs_name = snake_case(name);
+                    self.diff(
+                        &format!("{}.{}", fs_path, name),
+                        &format!("{}.{}", rs_path, rs_name),
+                        arg,
+                        payload.get(&rs_name).unwrap_or(&Value::Null),
+                    );
+                }
+                None => self.diff(
+                    &format!("{}.{}", fs_path, index),
+                    &format!("{}.{}", rs_path, index),
+                    arg,
+                    payload.get(index).unwrap_or(&Value::Null),
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn defs(source: &str) -> Definitions {
+        let mut defs = Definitions::default();
+        defs.parse_file(source).unwrap();
+        defs
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("DefenderHpAfter"), "defender_hp_after");
+        assert_eq!(snake_case("maxStacks"), "max_stacks");
+        assert_eq!(snake_case("Hp"), "hp");
+        assert_eq!(snake_case("HTTPServer"), "http_server");
+        assert_eq!(pascal_case("defender_hp_after"), "DefenderHpAfter");
+    }
+
+    #[test]
+    fn test_matching_types() {
+        let fs = fsharp::parse(
+            "type Stats =\n    { Hp: int\n      Resistances: Map<Element, int>\n      Tags: string list }\n\
+             type Cooldown =\n    | Turns of max: int * remaining: int\n    | Off\n\
+             type CombatantId = CombatantId of unativeint\n",
+        )
+        .unwrap();
+        let rs = defs(
+            "pub struct Resistances(pub BTreeMap<Element, i32>);\n\
+             pub struct Stats { pub hp: i32, pub resistances: Resistances, pub tags: Vec<String> }\n\
+             pub enum Cooldown { Turns { max: i32, remaining: i32 }, Off }\n\
+             pub struct CombatantId(pub usize);",
+        );
+
+        let report = compare(&fs, &rs, &["Stats", "Cooldown", "CombatantId"], |_| None);
+        assert!(report.is_ok(), "{}", report);
+    }
+
+    #[test]
+    fn test_type_differences() {
+        let fs = fsharp::parse(
+            "type Stats =\n    { Hp: int\n      Speed: int }\n\
+             type Kind =\n    | A of int\n    | B\n",
+        )
+        .unwrap();
+        let rs = defs(
+            "pub struct Stats { pub hp: u32, pub defense: i32 }\n\
+             pub enum Kind { A(f32), C }",
+        );
+
+        let report = compare(&fs, &rs, &["Stats", "Kind", "Missing"], |_| None);
+        let found: Vec<(&str, &str, &str)> = report
+            .mismatches
+            .iter()
+            .map(|m| (m.rust_path.as_str(), m.fsharp.as_str(), m.rust.as_str()))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("Stats.hp", "int", "u32"),
+                ("Stats.speed", "int", "no such field"),
+                ("Stats.defense", "no such field", "i32"),
+                ("Kind::A.0", "int", "f32"),
+                ("Kind::B", "case defined", "no such variant"),
+                ("Kind::C", "no such case", "variant defined"),
+                ("Missing", "not defined", "not defined"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_value_differences_name_both_paths() {
+        let fs = fsharp::parse(
+            "type Cooldown =\n    | Turns of max: int * remaining: int\n\
+             /// Corresponds to Rust: Ability::heal()\n\
+             let healAbility =\n    { Name = \"Heal\"\n      Power = 10\n      Cooldown = Cooldown.Turns(4, 0) }\n",
+        )
+        .unwrap();
+        let rust_value = |expr: &str| {
+            (expr == "Ability::heal()").then(|| {
+                json!({"name": "Heal", "power": -8, "cooldown": {"Turns": {"max": 4, "remaining": 1}}})
+            })
+        };
+
+        let report = compare(&fs, &Definitions::default(), &[], rust_value);
+
+        assert_eq!(report.bindings, vec!["healAbility"]);
+        assert_eq!(
+            report.mismatches,
+            vec![
+                Mismatch {
+                    fsharp_path: "healAbility.Power".to_string(),
+                    rust_path: "Ability::heal().power".to_string(),
+                    fsharp: "10".to_string(),
+                    rust: "-8".to_string(),
+                },
+                Mismatch {
+                    fsharp_path: "healAbility.Cooldown.Turns.remaining".to_string(),
+                    rust_path: "Ability::heal().cooldown.Turns.remaining".to_string(),
+                    fsharp: "0".to_string(),
+                    rust: "1".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_rust_expression_is_a_mismatch() {
+        let fs =
+            fsharp::parse("/// Corresponds to Rust: Ability::meteor()\nlet meteor = 1\n").unwrap();
+
+        let report = compare(&fs, &Definitions::default(), &[], |_| None);
+
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].rust_path, "Ability::meteor()");
+    }
+}
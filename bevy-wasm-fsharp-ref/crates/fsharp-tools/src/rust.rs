@@ -0,0 +1,216 @@
+//! Struct and enum definitions read from Rust source files.
+//!
+//! Only top-level items are collected; inline modules (such as `mod tests`) are
+//! skipped. Field types are reduced to [`TypeExpr`]s: the last path segment and
+//! its generic arguments, so `std::collections::BTreeMap<Element, i32>` becomes
+//! `BTreeMap<Element, i32>`.
+
+use std::fmt;
+
+use crate::TypeExpr;
+
+/// Every struct and enum found in a set of source files.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Definitions {
+    pub items: Vec<Item>,
+}
+
+/// A struct or enum definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub name: String,
+    pub kind: ItemKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemKind {
+    /// A struct with named fields.
+    Struct(Vec<Field>),
+    /// A tuple struct, such as a newtype.
+    Tuple(Vec<TypeExpr>),
+    Enum(Vec<Variant>),
+}
+
+/// A named struct field or enum variant field. Tuple variant fields are unnamed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: Option<String>,
+    pub ty: TypeExpr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+/// A Rust syntax error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Definitions {
+    /// Adds the structs and enums of one source file.
+    pub fn parse_file(&mut self, source: &str) -> Result<(), ParseError> {
+        let file = syn::parse_file(source).map_err(|e| ParseError {
+            line: e.span().start().line,
+            message: e.to_string(),
+        })?;
+
+        for item in file.items {
+            match item {
+                syn::Item::Struct(item) => self.items.push(Item {
+                    name: item.ident.to_string(),
+                    kind: match item.fields {
+                        syn::Fields::Unnamed(fields) => ItemKind::Tuple(
+                            fields.unnamed.iter().map(|f| type_expr(&f.ty)).collect(),
+                        ),
+                        fields => ItemKind::Struct(convert_fields(&fields)),
+                    },
+                }),
+                syn::Item::Enum(item) => self.items.push(Item {
+                    name: item.ident.to_string(),
+                    kind: ItemKind::Enum(
+                        item.variants
+                            .iter()
+                            .map(|v| Variant {
+                                name: v.ident.to_string(),
+                                fields: convert_fields(&v.fields),
+                            })
+                            .collect(),
+                    ),
+                }),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Looks up a struct or enum by name.
+    pub fn item(&self, name: &str) -> Option<&Item> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// Resolves single-field tuple structs to the type they wrap, so
+    /// `Resistances(BTreeMap<Element, i32>)` compares as its map.
+    pub fn unwrap_newtype<'a>(&'a self, ty: &'a TypeExpr) -> &'a TypeExpr {
+        match self.item(&ty.name).map(|item| &item.kind) {
+            Some(ItemKind::Tuple(inner)) if inner.len() == 1 && ty.args.is_empty() => &inner[0],
+            _ => ty,
+        }
+    }
+}
+
+fn convert_fields(fields: &syn::Fields) -> Vec<Field> {
+    fields
+        .iter()
+        .map(|f| Field {
+            name: f.ident.as_ref().map(|i| i.to_string()),
+            ty: type_expr(&f.ty),
+        })
+        .collect()
+}
+
+fn type_expr(ty: &syn::Type) -> TypeExpr {
+    match ty {
+        syn::Type::Path(path) => {
+            let Some(segment) = path.path.segments.last() else {
+                return TypeExpr::named("?");
+            };
+            let args = match &segment.arguments {
+                syn::PathArguments::AngleBracketed(generic) => generic
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(type_expr(ty)),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            TypeExpr::new(segment.ident.to_string(), args)
+        }
+        syn::Type::Reference(reference) => type_expr(&reference.elem),
+        syn::Type::Slice(slice) => TypeExpr::new("Vec", vec![type_expr(&slice.elem)]),
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => TypeExpr::named("()"),
+        _ => TypeExpr::named("?"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Definitions {
+        let mut defs = Definitions::default();
+        defs.parse_file(source).unwrap();
+        defs
+    }
+
+    #[test]
+    fn test_structs_and_enums() {
+        let defs = parse(
+            r#"
+            #[derive(Debug)]
+            pub struct Ability {
+                /// Doc comments and attributes are ignored.
+                #[serde(default)]
+                pub status_effects: Vec<crate::StatusEffect>,
+            }
+
+            pub enum Cooldown {
+                Turns { max: i32, remaining: i32 },
+                Seconds(f32),
+                Off,
+            }
+
+            #[cfg(test)]
+            mod tests {
+                struct Hidden;
+            }
+            "#,
+        );
+
+        assert_eq!(defs.items.len(), 2);
+        let ItemKind::Struct(fields) = &defs.item("Ability").unwrap().kind else {
+            panic!("expected a struct");
+        };
+        assert_eq!(fields[0].ty.to_string(), "Vec<StatusEffect>");
+
+        let ItemKind::Enum(variants) = &defs.item("Cooldown").unwrap().kind else {
+            panic!("expected an enum");
+        };
+        assert_eq!(variants[0].fields[1].name.as_deref(), Some("remaining"));
+        assert_eq!(variants[1].fields[0].name, None);
+        assert!(variants[2].fields.is_empty());
+    }
+
+    #[test]
+    fn test_newtypes_unwrap() {
+        let defs = parse("pub struct Resistances(pub BTreeMap<Element, i32>);");
+
+        let ty = TypeExpr::named("Resistances");
+        assert_eq!(
+            defs.unwrap_newtype(&ty).to_string(),
+            "BTreeMap<Element, i32>"
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors_report_the_line() {
+        let err = Definitions::default()
+            .parse_file("pub struct A {}\npub struct B {")
+            .unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+}
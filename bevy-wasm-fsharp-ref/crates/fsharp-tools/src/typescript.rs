@@ -0,0 +1,278 @@
+//! TypeScript declarations for the domain types in `fsharp/Domain.fs`.
+//!
+//! The `logic-js` bindings pass domain values to JavaScript through serde, so
+//! each declaration describes the JSON shape serde gives the Rust type that
+//! [`codegen`](crate::codegen) generates from the same F#:
+//!
+//! - Records become interfaces with `snake_case` fields; `[<SerdeDefault>]`
+//!   fields are optional
+//! - Unions without payloads become string literal types, and the other cases
+//!   are externally tagged objects such as `{ Turns: { max: number; remaining: number } }`
+//! - Single-case newtypes are their payload type
+//! - Numbers are `number`, `'T option` is `T | null`, lists and sets are arrays
+//!   and maps are objects keyed by the map's key type
+//!
+//! `///` doc comments are copied over as JSDoc.
+
+use crate::codegen::{is_newtype, primitive, CodegenError};
+use crate::fsharp::{self, Case, Field, Module, TypeDef, TypeKind};
+use crate::parity::snake_case;
+use crate::TypeExpr;
+
+/// Where the generated declarations are written, relative to the repository root.
+pub const GENERATED_FILE: &str = "crates/logic-js/src/domain.d.ts";
+
+/// Parses F# source and generates the TypeScript declarations for its types.
+///
+/// `source_name` is mentioned in the generated header.
+pub fn generate_source(source: &str, source_name: &str) -> Result<String, CodegenError> {
+    let module = fsharp::parse(source).map_err(|source| CodegenError::Parse { source })?;
+    generate(&module, source_name)
+}
+
+/// Generates one exported TypeScript type per type in `module`, in order.
+pub fn generate(module: &Module, source_name: &str) -> Result<String, CodegenError> {
+    let mut out = format!(
+        "// @generated by fsharp-codegen from {}. Do not edit by hand.\n",
+        source_name
+    );
+    for def in &module.types {
+        out.push('\n');
+        out.push_str(&declaration(module, def)?);
+    }
+    Ok(out)
+}
+
+fn declaration(module: &Module, def: &TypeDef) -> Result<String, CodegenError> {
+    let mut out = String::new();
+    write_docs(&mut out, "", &def.docs);
+
+    match &def.kind {
+        TypeKind::Record(fields) => {
+            out.push_str(&format!("export interface {} {{\n", def.name));
+            for field in fields {
+                write_docs(&mut out, "    ", &field.docs);
+                let optional = if field.attributes.iter().any(|a| a == "SerdeDefault") {
+                    "?"
+                } else {
+                    ""
+                };
+                out.push_str(&format!(
+                    "    {}{}: {};\n",
+                    field_name(field),
+                    optional,
+                    ts_type(module, def, &field.ty)?
+                ));
+            }
+            out.push_str("}\n");
+        }
+        TypeKind::Union(cases) if is_newtype(def) => {
+            let ty = ts_type(module, def, &cases[0].fields[0].ty)?;
+            out.push_str(&format!("export type {} = {};\n", def.name, ty));
+        }
+        TypeKind::Union(cases) => {
+            out.push_str(&format!("export type {} =\n", def.name));
+            for case in cases {
+                out.push_str(&format!("    | {}\n", variant(module, def, case)?));
+            }
+            out.pop();
+            out.push_str(";\n");
+        }
+    }
+    Ok(out)
+}
+
+/// `"Fire"`, `{ Seconds: number }` or `{ Turns: { max: number; remaining: number } }`.
+fn variant(module: &Module, def: &TypeDef, case: &Case) -> Result<String, CodegenError> {
+    let named = case.fields.iter().filter(|f| f.name.is_some()).count();
+    if named == 0 {
+        let types = case
+            .fields
+            .iter()
+            .map(|f| ts_type(module, def, &f.ty))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(match types.len() {
+            0 => format!("\"{}\"", case.name),
+            1 => format!("{{ {}: {} }}", case.name, types[0]),
+            _ => format!("{{ {}: [{}] }}", case.name, types.join(", ")),
+        });
+    }
+    if named != case.fields.len() {
+        return Err(CodegenError::MixedFields {
+            line: def.line,
+            case: format!("{}.{}", def.name, case.name),
+        });
+    }
+
+    let fields = case
+        .fields
+        .iter()
+        .map(|f| {
+            Ok(format!(
+                "{}: {}",
+                field_name(f),
+                ts_type(module, def, &f.ty)?
+            ))
+        })
+        .collect::<Result<Vec<_>, CodegenError>>()?;
+    Ok(format!("{{ {}: {{ {} }} }}", case.name, fields.join("; ")))
+}
+
+fn ts_type(module: &Module, def: &TypeDef, ty: &TypeExpr) -> Result<String, CodegenError> {
+    let unknown = || CodegenError::UnknownType {
+        line: def.line,
+        owner: def.name.clone(),
+        ty: ty.fsharp(),
+    };
+
+    let args = ty
+        .args
+        .iter()
+        .map(|arg| ts_type(module, def, arg))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some((rust, _)) = primitive(&ty.name) {
+        if !args.is_empty() {
+            return Err(unknown());
+        }
+        return Ok(match rust {
+            "String" | "char" => "string",
+            "bool" => "boolean",
+            _ => "number",
+        }
+        .to_string());
+    }
+
+    Ok(match (ty.name.as_str(), args.as_slice()) {
+        ("list" | "array" | "seq" | "ResizeArray" | "Set", [item]) => {
+            if item.contains(' ') {
+                format!("({})[]", item)
+            } else {
+                format!("{}[]", item)
+            }
+        }
+        ("option", [item]) => format!("{} | null", item),
+        ("Map", [key, value]) => format!("Partial<Record<{}, {}>>", key, value),
+        (name, []) if module.type_def(name).is_some() => name.to_string(),
+        _ => return Err(unknown()),
+    })
+}
+
+fn field_name(field: &Field) -> String {
+    snake_case(field.name.as_deref().unwrap_or_default())
+}
+
+fn write_docs(out: &mut String, indent: &str, docs: &[String]) {
+    match docs {
+        [] => {}
+        [line] => out.push_str(&format!("{}/** {} */\n", indent, line)),
+        lines => {
+            out.push_str(&format!("{}/**\n", indent));
+            for line in lines {
+                if line.is_empty() {
+                    out.push_str(&format!("{} *\n", indent));
+                } else {
+                    out.push_str(&format!("{} * {}\n", indent, line));
+                }
+            }
+            out.push_str(&format!("{} */\n", indent));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_types(source: &str) -> String {
+        let out = generate_source(source, "Domain.fs").unwrap();
+        // Skip the header
+        let start = out.find("\n\n").unwrap() + 2;
+        out[start..].to_string()
+    }
+
+    #[test]
+    fn test_record() {
+        let out = generate_types(
+            "/// Combat stats.\n\
+             [<Component>]\n\
+             type Stats =\n    { /// Max HP.\n      Hp: int\n      [<SerdeDefault>]\n      CritChance: int\n      Name: string option }\n",
+        );
+
+        assert_eq!(
+            out,
+            "/** Combat stats. */\n\
+             export interface Stats {\n    \
+                 /** Max HP. */\n    \
+                 hp: number;\n    \
+                 crit_chance?: number;\n    \
+                 name: string | null;\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_unions() {
+        let out = generate_types(
+            "type Element =\n    | Physical\n    | Fire\n\
+             type Cooldown =\n    | Turns of max: int * remaining: int\n    | Seconds of float32\n\
+             type Outcome =\n    | Evaded\n    | Split of int * bool\n",
+        );
+
+        assert!(out.contains("export type Element =\n    | \"Physical\"\n    | \"Fire\";\n"));
+        assert!(out.contains(
+            "export type Cooldown =\n    \
+                 | { Turns: { max: number; remaining: number } }\n    \
+                 | { Seconds: number };\n"
+        ));
+        assert!(out.contains("    | \"Evaded\"\n    | { Split: [number, boolean] };\n"));
+    }
+
+    #[test]
+    fn test_newtypes_and_containers() {
+        let out = generate_types(
+            "type Element =\n    | Fire\n\
+             type Resistances = Resistances of Map<Element, int>\n\
+             type Id = Id of unativeint\n\
+             type Party =\n    { Members: Id list\n      Leader: Id option\n      Tags: Set<string>\n      Slots: int option list }\n",
+        );
+
+        assert!(out.contains("export type Resistances = Partial<Record<Element, number>>;\n"));
+        assert!(out.contains("export type Id = number;\n"));
+        assert!(out.contains(
+            "    members: Id[];\n    leader: Id | null;\n    tags: string[];\n    slots: (number | null)[];\n"
+        ));
+    }
+
+    #[test]
+    fn test_multi_line_docs() {
+        let out = generate_types("/// First.\n///\n/// Second.\ntype Element =\n    | Fire\n");
+
+        assert!(out.starts_with("/**\n * First.\n *\n * Second.\n */\nexport type Element =\n"));
+    }
+
+    #[test]
+    fn test_unknown_types_are_errors() {
+        let err = generate_source("type A =\n    { Amount: decimal }\n", "Domain.fs").unwrap_err();
+
+        assert_eq!(
+            err,
+            CodegenError::UnknownType {
+                line: 1,
+                owner: "A".to_string(),
+                ty: "decimal".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_mixed_fields() {
+        let err = generate_source(
+            "type Event =\n    | Hit of target: string * int\n",
+            "Domain.fs",
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, CodegenError::MixedFields { .. }));
+    }
+}
@@ -0,0 +1,39 @@
+//! Checks that the generated domain types in the logic crate, and their
+//! TypeScript declarations in `logic-js`, are up to date with `fsharp/Domain.fs`.
+
+use std::path::PathBuf;
+
+use fsharp_tools::codegen::{generate_source, DOMAIN_SOURCE, GENERATED_FILE};
+use fsharp_tools::typescript;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+#[test]
+fn test_generated_domain_is_up_to_date() {
+    let source = std::fs::read_to_string(repo_root().join(DOMAIN_SOURCE)).unwrap();
+    let generated = std::fs::read_to_string(repo_root().join(GENERATED_FILE)).unwrap();
+
+    let expected = generate_source(&source, DOMAIN_SOURCE).unwrap();
+    assert!(
+        expected == generated,
+        "{} is out of date with {}; run `cargo run -p fsharp-tools --bin fsharp-codegen`",
+        GENERATED_FILE,
+        DOMAIN_SOURCE
+    );
+}
+
+#[test]
+fn test_typescript_declarations_are_up_to_date() {
+    let source = std::fs::read_to_string(repo_root().join(DOMAIN_SOURCE)).unwrap();
+    let generated = std::fs::read_to_string(repo_root().join(typescript::GENERATED_FILE)).unwrap();
+
+    let expected = typescript::generate_source(&source, DOMAIN_SOURCE).unwrap();
+    assert!(
+        expected == generated,
+        "{} is out of date with {}; run `cargo run -p fsharp-tools --bin fsharp-codegen`",
+        typescript::GENERATED_FILE,
+        DOMAIN_SOURCE
+    );
+}
@@ -0,0 +1,77 @@
+//! Checks the F# reference sources in this repository against the Rust logic
+//! crate, so drift fails `cargo test` instead of waiting for a manual script run.
+
+use std::path::PathBuf;
+
+use fsharp_tools::parity::{
+    check, check_sources, ParityReport, DEFAULT_TYPES, FSHARP_SOURCES, RUST_SOURCES,
+};
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// Checks the repository's sources after `edit` has been applied to the F# ones.
+fn check_edited(edit: impl Fn(&str, String) -> String, types: &[&str]) -> ParityReport {
+    let fsharp: Vec<(&str, String)> = FSHARP_SOURCES
+        .iter()
+        .map(|&file| {
+            let source = std::fs::read_to_string(repo_root().join(file)).unwrap();
+            (file, edit(file, source))
+        })
+        .collect();
+    let rust: Vec<(String, String)> = std::fs::read_dir(repo_root().join(RUST_SOURCES))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|path| {
+            let source = std::fs::read_to_string(&path).unwrap();
+            (path.display().to_string(), source)
+        })
+        .collect();
+
+    let fsharp: Vec<(&str, &str)> = fsharp.iter().map(|(f, s)| (*f, s.as_str())).collect();
+    let rust: Vec<(&str, &str)> = rust.iter().map(|(f, s)| (f.as_str(), s.as_str())).collect();
+    check_sources(&fsharp, &rust, types).unwrap()
+}
+
+#[test]
+fn test_fsharp_sources_match_rust() {
+    let report = check(&repo_root(), DEFAULT_TYPES).unwrap();
+
+    assert_eq!(report.types.len(), DEFAULT_TYPES.len());
+    assert!(report.bindings.contains(&"healAbility".to_string()));
+    assert!(report.is_ok(), "{}", report);
+}
+
+#[test]
+fn test_heal_amount_drift_is_reported() {
+    let report = check_edited(
+        |file, source| {
+            if file.ends_with("GameLogic.fs") {
+                source.replacen("AbilityEffect.Heal(8)", "AbilityEffect.Heal(10)", 1)
+            } else {
+                source
+            }
+        },
+        &["Ability"],
+    );
+
+    let heal = &report.mismatches[0];
+    assert_eq!(heal.fsharp_path, "healAbility.Effects[0].Heal.amount");
+    assert_eq!(heal.rust_path, "Ability::heal().effects[0].Heal.amount");
+    assert_eq!((heal.fsharp.as_str(), heal.rust.as_str()), ("10", "8"));
+}
+
+#[test]
+fn test_field_type_drift_is_reported() {
+    let report = check_edited(
+        |_, source| source.replacen("Speed: int", "Speed: float", 1),
+        &["Stats"],
+    );
+
+    assert_eq!(report.mismatches.len(), 1);
+    assert_eq!(report.mismatches[0].rust_path, "Stats.speed");
+    assert_eq!(report.mismatches[0].fsharp, "float");
+    assert_eq!(report.mismatches[0].rust, "i32");
+}
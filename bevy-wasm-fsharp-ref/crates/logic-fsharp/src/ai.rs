@@ -0,0 +1,474 @@
+//! Pluggable monster AI policies.
+//!
+//! An [`AiPolicy`] picks the [`Ability`] a computer-controlled character uses on its
+//! turn. Policies see both combatants and the caster's [`AbilitySlot`]s, including
+//! their cooldowns, and only choose abilities that are ready. Like
+//! [`choose_monster_action`], every policy falls back to [`Ability::basic_attack`]
+//! when nothing is ready.
+//!
+//! Built-in policies:
+//!
+//! | Policy | Behaviour |
+//! |--------|-----------|
+//! | [`ThresholdAi`] | The classic [`choose_monster_action`] HP thresholds |
+//! | [`AggressiveAi`] | Always the hardest-hitting ready attack, never heals |
+//! | [`DefensiveAi`] | Heals below half HP, otherwise the cheapest attack |
+//! | [`BalancedAi`] | Pressures healthy opponents, finishes off wounded ones |
+//! | [`SmartAi`] | Takes kills, heals when the next hit could be fatal |
+//! | [`Planner`] | Searches future turns, see [`planner`](crate::planner) |
+//!
+//! [`AiPersonality`] names each built-in policy so a personality can be stored in
+//! data or attached to an entity as a component.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type AiPersonality =
+//!     | Threshold
+//!     | Aggressive
+//!     | Defensive
+//!     | Balanced
+//!     | Smart
+//!     | Tactician
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let mut monster = Character::new_monster("Goblin");
+//! monster.hp = 4;
+//! let player = Character::new_player("Hero");
+//! let abilities = AbilitySet::monster_turn_based();
+//!
+//! // Aggressive monsters attack no matter what; defensive ones heal first
+//! let aggressive = AiPersonality::Aggressive.choose(&monster, &player, &abilities.abilities);
+//! let defensive = AiPersonality::Defensive.choose(&monster, &player, &abilities.abilities);
+//!
+//! assert_eq!(aggressive.name, "Powerful Attack");
+//! assert_eq!(defensive.name, "Heal");
+//! ```
+
+use crate::{
+    choose_monster_action, compute_attack, Ability, AbilitySlot, AbilityType, AiPersonality,
+    Character, Cooldown, Planner,
+};
+
+/// Decides which ability a computer-controlled character uses on its turn.
+pub trait AiPolicy {
+    /// Display name of the policy.
+    fn name(&self) -> &str;
+
+    /// Chooses an ability for `actor` to use against `opponent`.
+    ///
+    /// Only abilities in `abilities` that are ready should be chosen. Heals are
+    /// used on the actor itself; everything else targets the opponent.
+    fn choose(&self, actor: &Character, opponent: &Character, abilities: &[AbilitySlot])
+        -> Ability;
+}
+
+/// Abilities whose cooldown has finished.
+fn ready(abilities: &[AbilitySlot]) -> impl Iterator<Item = &AbilitySlot> {
+    abilities.iter().filter(|slot| slot.is_ready())
+}
+
+/// Ready abilities that target the opponent.
+fn ready_attacks(abilities: &[AbilitySlot]) -> impl Iterator<Item = &AbilitySlot> {
+    ready(abilities).filter(|slot| slot.ability_type != AbilityType::Heal)
+}
+
+/// The first ready heal, if any.
+fn ready_heal(abilities: &[AbilitySlot]) -> Option<&AbilitySlot> {
+    ready(abilities).find(|slot| slot.ability_type == AbilityType::Heal)
+}
+
+/// Damage `slot` would deal to `opponent`, ignoring randomness and status effects.
+fn expected_damage(actor: &Character, opponent: &Character, slot: &AbilitySlot) -> i32 {
+    compute_attack(actor, opponent, &slot.ability).damage
+}
+
+/// The ready attack dealing the most damage to `opponent`.
+///
+/// Ties go to the ability listed first.
+fn strongest_attack<'a>(
+    actor: &Character,
+    opponent: &Character,
+    abilities: &'a [AbilitySlot],
+) -> Option<&'a AbilitySlot> {
+    ready_attacks(abilities).fold(None, |best: Option<&AbilitySlot>, slot| match best {
+        Some(b)
+            if expected_damage(actor, opponent, b) >= expected_damage(actor, opponent, slot) =>
+        {
+            Some(b)
+        }
+        _ => Some(slot),
+    })
+}
+
+/// HP as a whole percentage of max HP.
+fn hp_percent(character: &Character) -> i32 {
+    if character.stats.hp <= 0 {
+        0
+    } else {
+        character.hp * 100 / character.stats.hp
+    }
+}
+
+/// The original HP-threshold AI, see [`choose_monster_action`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThresholdAi;
+
+impl AiPolicy for ThresholdAi {
+    fn name(&self) -> &str {
+        "Threshold"
+    }
+
+    fn choose(
+        &self,
+        actor: &Character,
+        opponent: &Character,
+        abilities: &[AbilitySlot],
+    ) -> Ability {
+        choose_monster_action(actor, opponent, abilities)
+    }
+}
+
+/// Always uses the ready attack that deals the most damage. Never heals.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AggressiveAi;
+
+impl AiPolicy for AggressiveAi {
+    fn name(&self) -> &str {
+        "Aggressive"
+    }
+
+    fn choose(
+        &self,
+        actor: &Character,
+        opponent: &Character,
+        abilities: &[AbilitySlot],
+    ) -> Ability {
+        strongest_attack(actor, opponent, abilities)
+            .map(|slot| slot.ability.clone())
+            .unwrap_or_else(Ability::basic_attack)
+    }
+}
+
+/// Heals below half HP; otherwise uses the ready attack with the shortest
+/// cooldown, keeping stronger abilities in reserve.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefensiveAi;
+
+impl AiPolicy for DefensiveAi {
+    fn name(&self) -> &str {
+        "Defensive"
+    }
+
+    fn choose(
+        &self,
+        actor: &Character,
+        _opponent: &Character,
+        abilities: &[AbilitySlot],
+    ) -> Ability {
+        if hp_percent(actor) < 50 {
+            if let Some(heal) = ready_heal(abilities) {
+                return heal.ability.clone();
+            }
+        }
+
+        ready_attacks(abilities)
+            .min_by(|a, b| {
+                let (a_max, b_max) = (max_cooldown(a), max_cooldown(b));
+                a_max.total_cmp(&b_max)
+            })
+            .map(|slot| slot.ability.clone())
+            .unwrap_or_else(Ability::basic_attack)
+    }
+}
+
+/// A cooldown's full length, for comparing slots within one mode.
+fn max_cooldown(slot: &AbilitySlot) -> f32 {
+    match slot.cooldown {
+        Cooldown::Turns { max, .. } => max as f32,
+        Cooldown::Seconds { max, .. } => max.as_secs_f32(),
+    }
+}
+
+/// Adapts to the opponent's HP: a powerful attack while they are above 70%,
+/// the strongest available attack to finish them below 30%, and a basic attack
+/// in between.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BalancedAi;
+
+impl AiPolicy for BalancedAi {
+    fn name(&self) -> &str {
+        "Balanced"
+    }
+
+    fn choose(
+        &self,
+        actor: &Character,
+        opponent: &Character,
+        abilities: &[AbilitySlot],
+    ) -> Ability {
+        let opponent_hp = hp_percent(opponent);
+        let of_type = |ability_type| {
+            ready(abilities)
+                .find(|slot| slot.ability_type == ability_type)
+                .map(|slot| slot.ability.clone())
+        };
+
+        let choice = if opponent_hp > 70 {
+            of_type(AbilityType::PowerfulAttack).or_else(|| of_type(AbilityType::BasicAttack))
+        } else if opponent_hp < 30 {
+            strongest_attack(actor, opponent, abilities).map(|slot| slot.ability.clone())
+        } else {
+            of_type(AbilityType::BasicAttack)
+        };
+
+        choice.unwrap_or_else(Ability::basic_attack)
+    }
+}
+
+/// Weighs both combatants:
+///
+/// 1. If a ready attack can defeat the opponent this turn, use the one with the
+///    shortest cooldown
+/// 2. If the opponent's basic attack could defeat the actor next turn, heal
+/// 3. Otherwise use the ready attack that deals the most damage
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmartAi;
+
+impl AiPolicy for SmartAi {
+    fn name(&self) -> &str {
+        "Smart"
+    }
+
+    fn choose(
+        &self,
+        actor: &Character,
+        opponent: &Character,
+        abilities: &[AbilitySlot],
+    ) -> Ability {
+        let lethal = ready_attacks(abilities)
+            .filter(|slot| expected_damage(actor, opponent, slot) >= opponent.hp)
+            .min_by(|a, b| max_cooldown(a).total_cmp(&max_cooldown(b)));
+        if let Some(slot) = lethal {
+            return slot.ability.clone();
+        }
+
+        let threat = compute_attack(opponent, actor, &Ability::basic_attack()).damage;
+        if actor.hp <= threat {
+            if let Some(heal) = ready_heal(abilities) {
+                return heal.ability.clone();
+            }
+        }
+
+        strongest_attack(actor, opponent, abilities)
+            .map(|slot| slot.ability.clone())
+            .unwrap_or_else(Ability::basic_attack)
+    }
+}
+
+/// The planner behind [`AiPersonality::Tactician`].
+static TACTICIAN: Planner = Planner::HARD;
+
+impl AiPersonality {
+    /// All built-in personalities.
+    pub const ALL: [AiPersonality; 6] = [
+        AiPersonality::Threshold,
+        AiPersonality::Aggressive,
+        AiPersonality::Defensive,
+        AiPersonality::Balanced,
+        AiPersonality::Smart,
+        AiPersonality::Tactician,
+    ];
+
+    /// The policy implementing this personality.
+    pub fn policy(&self) -> &'static dyn AiPolicy {
+        match self {
+            AiPersonality::Threshold => &ThresholdAi,
+            AiPersonality::Aggressive => &AggressiveAi,
+            AiPersonality::Defensive => &DefensiveAi,
+            AiPersonality::Balanced => &BalancedAi,
+            AiPersonality::Smart => &SmartAi,
+            AiPersonality::Tactician => &TACTICIAN,
+        }
+    }
+}
+
+impl AiPolicy for AiPersonality {
+    fn name(&self) -> &str {
+        self.policy().name()
+    }
+
+    fn choose(
+        &self,
+        actor: &Character,
+        opponent: &Character,
+        abilities: &[AbilitySlot],
+    ) -> Ability {
+        self.policy().choose(actor, opponent, abilities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::{AbilitySet, Element, Resistances};
+
+    fn monster_at(hp: i32) -> Character {
+        let mut monster = Character::new_monster("Goblin");
+        monster.hp = hp;
+        monster
+    }
+
+    fn player_at(hp: i32) -> Character {
+        let mut player = Character::new_player("Hero");
+        player.hp = hp;
+        player
+    }
+
+    fn abilities() -> Vec<AbilitySlot> {
+        AbilitySet::monster_turn_based().abilities
+    }
+
+    #[test]
+    fn test_threshold_matches_choose_monster_action() {
+        let player = player_at(30);
+
+        for hp in [2, 10, 20] {
+            let monster = monster_at(hp);
+            assert_eq!(
+                ThresholdAi.choose(&monster, &player, &abilities()).name,
+                choose_monster_action(&monster, &player, &abilities()).name
+            );
+        }
+    }
+
+    #[test]
+    fn test_aggressive_never_heals() {
+        let chosen = AggressiveAi.choose(&monster_at(1), &player_at(30), &abilities());
+
+        assert_eq!(chosen.name, "Powerful Attack");
+    }
+
+    #[test]
+    fn test_aggressive_respects_cooldowns() {
+        let mut abilities = abilities();
+        abilities[1].use_ability();
+
+        let chosen = AggressiveAi.choose(&monster_at(20), &player_at(30), &abilities);
+
+        assert_eq!(chosen.name, "Basic Attack");
+    }
+
+    #[test]
+    fn test_aggressive_accounts_for_resistances() {
+        let mut player = player_at(30);
+        player.stats.resistances = Resistances::default().with(Element::Fire, 300);
+        let mut abilities = abilities();
+        abilities.push(AbilitySlot::new(
+            Ability {
+                element: Element::Fire,
+                ..Ability::damage("Ember", 1)
+            },
+            AbilityType::BasicAttack,
+            Cooldown::turns(0),
+        ));
+
+        let chosen = AggressiveAi.choose(&monster_at(20), &player, &abilities);
+
+        // Ember: (6 + 1 - 2) * 300% = 15, Powerful Attack: 6 + 12 - 2 = 16
+        assert_eq!(chosen.name, "Powerful Attack");
+
+        player.stats.resistances = Resistances::default().with(Element::Fire, 400);
+        let chosen = AggressiveAi.choose(&monster_at(20), &player, &abilities);
+        assert_eq!(chosen.name, "Ember"); // 5 * 400% = 20
+    }
+
+    #[test]
+    fn test_defensive_heals_below_half() {
+        let chosen = DefensiveAi.choose(&monster_at(9), &player_at(30), &abilities());
+
+        assert_eq!(chosen.name, "Heal");
+    }
+
+    #[test]
+    fn test_defensive_prefers_cheap_attacks() {
+        let chosen = DefensiveAi.choose(&monster_at(20), &player_at(30), &abilities());
+
+        assert_eq!(chosen.name, "Basic Attack");
+    }
+
+    #[test]
+    fn test_balanced_depends_on_opponent_hp() {
+        let monster = monster_at(20);
+
+        assert_eq!(
+            BalancedAi
+                .choose(&monster, &player_at(30), &abilities())
+                .name,
+            "Powerful Attack"
+        );
+        assert_eq!(
+            BalancedAi
+                .choose(&monster, &player_at(15), &abilities())
+                .name,
+            "Basic Attack"
+        );
+        assert_eq!(
+            BalancedAi
+                .choose(&monster, &player_at(5), &abilities())
+                .name,
+            "Powerful Attack"
+        );
+    }
+
+    #[test]
+    fn test_smart_takes_cheapest_kill() {
+        // Basic attack deals 6 + 5 - 2 = 9, enough to finish the player
+        let chosen = SmartAi.choose(&monster_at(20), &player_at(9), &abilities());
+
+        assert_eq!(chosen.name, "Basic Attack");
+    }
+
+    #[test]
+    fn test_smart_heals_when_next_hit_is_lethal() {
+        // Player's basic attack deals 10 + 5 - 1 = 14
+        let chosen = SmartAi.choose(&monster_at(14), &player_at(30), &abilities());
+
+        assert_eq!(chosen.name, "Heal");
+    }
+
+    #[test]
+    fn test_smart_attacks_when_safe() {
+        let chosen = SmartAi.choose(&monster_at(20), &player_at(30), &abilities());
+
+        assert_eq!(chosen.name, "Powerful Attack");
+    }
+
+    #[test]
+    fn test_all_policies_fall_back_when_nothing_is_ready() {
+        let mut abilities = abilities();
+        for slot in &mut abilities {
+            slot.cooldown = Cooldown::Turns {
+                max: 3,
+                remaining: 3,
+            };
+        }
+
+        for personality in AiPersonality::ALL {
+            let chosen = personality.choose(&monster_at(5), &player_at(30), &abilities);
+            assert_eq!(chosen.name, "Basic Attack", "{}", personality.name());
+        }
+    }
+
+    #[test]
+    fn test_personality_dispatches_to_policy() {
+        assert_eq!(AiPersonality::Smart.name(), "Smart");
+        assert_eq!(AiPersonality::default(), AiPersonality::Threshold);
+    }
+}
@@ -0,0 +1,558 @@
+//! Monster templates and encounters, loaded from data.
+//!
+//! A [`Bestiary`] holds [`MonsterTemplate`]s, each with base stats, ability
+//! catalog names, the [`AiPersonality`](crate::AiPersonality) that picks its
+//! actions and an optional loot table, and [`EncounterTemplate`]s naming the
+//! monsters that are fought together. Playable archetypes (Warrior, Mage,
+//! Rogue...) are the [`CharacterClass`](crate::CharacterClass)es of a
+//! [`ClassCatalog`](crate::ClassCatalog), built with
+//! [`ClassCatalog::spawn`](crate::ClassCatalog::spawn).
+//!
+//! Templates are looked up by ID and turned into a [`Character`] plus its
+//! [`AbilitySet`] with [`Bestiary::spawn`], or into a whole enemy team with
+//! [`Bestiary::spawn_encounter`].
+//!
+//! The built-in bestiary ([`Bestiary::builtin`]) is embedded from
+//! `data/bestiary.ron`.
+//!
+//! # Data Format
+//!
+//! ```ron
+//! (
+//!     monsters: [
+//!         (
+//!             id: "goblin",
+//!             name: "Goblin",
+//!             stats: (hp: 20, attack: 6, defense: 1, speed: 4),
+//!             abilities: ["Basic Attack", "Powerful Attack", "Heal"],
+//!             personality: Balanced,
+//!             loot: Some("Goblin"),
+//!         ),
+//!     ],
+//!     encounters: [
+//!         (id: "goblins", name: "Goblin Pair", monsters: ["goblin", "goblin"]),
+//!     ],
+//! )
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! # // The built-in catalogs need `std`
+//! # #[cfg(feature = "std")]
+//! # {
+//! let bestiary = Bestiary::builtin();
+//! let abilities = AbilityCatalog::builtin();
+//!
+//! let (bat, loadout) = bestiary.spawn("bat", &abilities, CooldownMode::Turns).unwrap();
+//! assert_eq!(bat.name, "Bat");
+//! assert_eq!(bat.hp, 12);
+//! assert_eq!(loadout.abilities.len(), 2);
+//!
+//! // Repeated monsters get letters to tell them apart
+//! let pit = bestiary
+//!     .spawn_encounter("slime_pit", &abilities, CooldownMode::Turns)
+//!     .unwrap();
+//! let names: Vec<&str> = pit.iter().map(|(c, _)| c.character.name.as_str()).collect();
+//! assert_eq!(names, ["Slime A", "Slime B", "Slime C"]);
+//! # }
+//! ```
+
+use alloc::collections::BTreeSet;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::{
+    AbilityCatalog, AbilitySet, CatalogError, Character, Combatant, CooldownMode,
+    EncounterTemplate, LootCatalog, MonsterTemplate, Team,
+};
+
+/// Source of the built-in bestiary.
+#[cfg(feature = "std")]
+const BUILTIN_BESTIARY: &str = include_str!("../data/bestiary.ron");
+
+impl MonsterTemplate {
+    /// A monster of this kind at full HP.
+    pub fn character(&self) -> Character {
+        Character {
+            name: self.name.clone(),
+            hp: self.stats.hp,
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// The monster's loadout, with cooldowns counting in `mode`.
+    pub fn ability_set(
+        &self,
+        catalog: &AbilityCatalog,
+        mode: CooldownMode,
+    ) -> Result<AbilitySet, CatalogError> {
+        let names: Vec<&str> = self.abilities.iter().map(String::as_str).collect();
+        catalog.ability_set(&names, mode)
+    }
+}
+
+/// Errors produced while loading or querying a [`Bestiary`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BestiaryError {
+    /// The bestiary file could not be read.
+    Io { path: String, message: String },
+
+    /// The file extension is not `.json` or `.ron`.
+    UnsupportedFormat { path: String },
+
+    /// The data could not be deserialized.
+    Parse {
+        format: &'static str,
+        message: String,
+    },
+
+    /// A monster or encounter has an empty ID.
+    EmptyId { index: usize },
+
+    /// Two monsters or two encounters share an ID.
+    DuplicateId { id: String },
+
+    /// A monster or encounter failed validation.
+    InvalidTemplate { id: String, reason: String },
+
+    /// A lookup or an encounter named a monster that is not in the bestiary.
+    UnknownMonster { id: String },
+
+    /// A lookup named an encounter that is not in the bestiary.
+    UnknownEncounter { id: String },
+
+    /// A monster's abilities are not all in the ability catalog.
+    Ability(CatalogError),
+}
+
+impl fmt::Display for BestiaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BestiaryError::Io { path, message } => {
+                write!(f, "failed to read bestiary {}: {}", path, message)
+            }
+            BestiaryError::UnsupportedFormat { path } => write!(
+                f,
+                "unsupported bestiary format {} (expected .json or .ron)",
+                path
+            ),
+            BestiaryError::Parse { format, message } => {
+                write!(f, "invalid {} bestiary: {}", format, message)
+            }
+            BestiaryError::EmptyId { index } => write!(f, "template #{} has an empty ID", index),
+            BestiaryError::DuplicateId { id } => {
+                write!(f, "template \"{}\" is defined more than once", id)
+            }
+            BestiaryError::InvalidTemplate { id, reason } => {
+                write!(f, "template \"{}\" is invalid: {}", id, reason)
+            }
+            BestiaryError::UnknownMonster { id } => {
+                write!(f, "monster \"{}\" is not in the bestiary", id)
+            }
+            BestiaryError::UnknownEncounter { id } => {
+                write!(f, "encounter \"{}\" is not in the bestiary", id)
+            }
+            BestiaryError::Ability(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for BestiaryError {}
+
+/// A validated collection of monster and encounter templates.
+///
+/// Construct with [`from_json`](Self::from_json), [`from_ron`](Self::from_ron),
+/// [`from_file`](Self::from_file) or [`new`](Self::new); all of them validate every
+/// template, so a bestiary value is always well-formed. Ability and loot table
+/// names live in other catalogs and are checked with
+/// [`check_abilities`](Self::check_abilities) and [`check_loot`](Self::check_loot).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct Bestiary {
+    monsters: Vec<MonsterTemplate>,
+    encounters: Vec<EncounterTemplate>,
+}
+
+/// On-disk shape of a bestiary file.
+#[derive(Deserialize)]
+struct BestiaryFile {
+    monsters: Vec<MonsterTemplate>,
+    #[serde(default)]
+    encounters: Vec<EncounterTemplate>,
+}
+
+impl Bestiary {
+    /// Validates the templates and builds a bestiary.
+    pub fn new(
+        monsters: Vec<MonsterTemplate>,
+        encounters: Vec<EncounterTemplate>,
+    ) -> Result<Self, BestiaryError> {
+        check_ids(monsters.iter().map(|m| m.id.as_str()))?;
+        check_ids(encounters.iter().map(|e| e.id.as_str()))?;
+
+        for monster in &monsters {
+            let invalid = |reason: &str| BestiaryError::InvalidTemplate {
+                id: monster.id.clone(),
+                reason: reason.to_string(),
+            };
+            if monster.stats.hp <= 0 {
+                return Err(invalid("stats.hp must be positive"));
+            }
+            if monster.abilities.is_empty() {
+                return Err(invalid("a monster needs at least one ability"));
+            }
+        }
+
+        let bestiary = Self {
+            monsters,
+            encounters,
+        };
+        for encounter in &bestiary.encounters {
+            if encounter.monsters.is_empty() {
+                return Err(BestiaryError::InvalidTemplate {
+                    id: encounter.id.clone(),
+                    reason: "an encounter needs at least one monster".to_string(),
+                });
+            }
+            for id in &encounter.monsters {
+                bestiary.require(id)?;
+            }
+        }
+        Ok(bestiary)
+    }
+
+    /// Parses and validates a JSON bestiary.
+    pub fn from_json(source: &str) -> Result<Self, BestiaryError> {
+        let file: BestiaryFile =
+            serde_json::from_str(source).map_err(|e| BestiaryError::Parse {
+                format: "JSON",
+                message: e.to_string(),
+            })?;
+        Self::new(file.monsters, file.encounters)
+    }
+
+    /// Parses and validates a RON bestiary.
+    #[cfg(feature = "std")]
+    pub fn from_ron(source: &str) -> Result<Self, BestiaryError> {
+        let file: BestiaryFile = ron::from_str(source).map_err(|e| BestiaryError::Parse {
+            format: "RON",
+            message: e.to_string(),
+        })?;
+        Self::new(file.monsters, file.encounters)
+    }
+
+    /// Loads a bestiary file, choosing the format from its `.json` or `.ron`
+    /// extension.
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, BestiaryError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        let source = std::fs::read_to_string(path).map_err(|e| BestiaryError::Io {
+            path: display.clone(),
+            message: e.to_string(),
+        })?;
+
+        match extension {
+            Some("json") => Self::from_json(&source),
+            Some("ron") => Self::from_ron(&source),
+            _ => Err(BestiaryError::UnsupportedFormat { path: display }),
+        }
+    }
+
+    /// The bestiary embedded in the crate from `data/bestiary.ron`.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: the embedded data is checked by the crate's tests.
+    #[cfg(feature = "std")]
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_BESTIARY).expect("built-in bestiary is valid")
+    }
+
+    /// All monster templates, in file order.
+    pub fn monsters(&self) -> &[MonsterTemplate] {
+        &self.monsters
+    }
+
+    /// All encounter templates, in file order.
+    pub fn encounters(&self) -> &[EncounterTemplate] {
+        &self.encounters
+    }
+
+    /// Looks up a monster template by ID.
+    pub fn get(&self, id: &str) -> Option<&MonsterTemplate> {
+        self.monsters.iter().find(|monster| monster.id == id)
+    }
+
+    /// Looks up a monster template by ID, failing with
+    /// [`BestiaryError::UnknownMonster`].
+    pub fn require(&self, id: &str) -> Result<&MonsterTemplate, BestiaryError> {
+        self.get(id)
+            .ok_or_else(|| BestiaryError::UnknownMonster { id: id.to_string() })
+    }
+
+    /// Looks up an encounter template by ID, failing with
+    /// [`BestiaryError::UnknownEncounter`].
+    pub fn encounter(&self, id: &str) -> Result<&EncounterTemplate, BestiaryError> {
+        self.encounters
+            .iter()
+            .find(|encounter| encounter.id == id)
+            .ok_or_else(|| BestiaryError::UnknownEncounter { id: id.to_string() })
+    }
+
+    /// Builds the monster `id`: its character, at full HP, and its loadout.
+    pub fn spawn(
+        &self,
+        id: &str,
+        abilities: &AbilityCatalog,
+        mode: CooldownMode,
+    ) -> Result<(Character, AbilitySet), BestiaryError> {
+        let monster = self.require(id)?;
+        let loadout = monster
+            .ability_set(abilities, mode)
+            .map_err(BestiaryError::Ability)?;
+        Ok((monster.character(), loadout))
+    }
+
+    /// Builds the enemy team of the encounter `id`, each combatant with the
+    /// template it was spawned from.
+    ///
+    /// Monsters that appear more than once are told apart by a letter, e.g.
+    /// "Wolf A" and "Wolf B".
+    pub fn spawn_encounter(
+        &self,
+        id: &str,
+        abilities: &AbilityCatalog,
+        mode: CooldownMode,
+    ) -> Result<Vec<(Combatant, &MonsterTemplate)>, BestiaryError> {
+        let encounter = self.encounter(id)?;
+
+        let mut team = Vec::new();
+        for (index, monster_id) in encounter.monsters.iter().enumerate() {
+            let monster = self.require(monster_id)?;
+            let (mut character, loadout) = self.spawn(monster_id, abilities, mode)?;
+
+            let repeats = encounter.monsters.iter().filter(|id| *id == monster_id);
+            if repeats.count() > 1 {
+                let earlier = encounter.monsters[..index]
+                    .iter()
+                    .filter(|id| *id == monster_id)
+                    .count();
+                character.name = format!("{} {}", character.name, letter(earlier));
+            }
+
+            team.push((Combatant::new(Team::Enemies, character, loadout), monster));
+        }
+        Ok(team)
+    }
+
+    /// Checks that every monster's abilities are in `catalog`.
+    pub fn check_abilities(&self, catalog: &AbilityCatalog) -> Result<(), BestiaryError> {
+        for monster in &self.monsters {
+            monster
+                .ability_set(catalog, CooldownMode::Turns)
+                .map_err(BestiaryError::Ability)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that every monster's loot table is in `catalog`.
+    pub fn check_loot(&self, catalog: &LootCatalog) -> Result<(), BestiaryError> {
+        for monster in &self.monsters {
+            if let Some(table) = &monster.loot {
+                if catalog.get(table).is_none() {
+                    return Err(BestiaryError::InvalidTemplate {
+                        id: monster.id.clone(),
+                        reason: format!("loot table \"{}\" is not in the catalog", table),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fails on the first empty or repeated ID.
+fn check_ids<'a>(ids: impl Iterator<Item = &'a str>) -> Result<(), BestiaryError> {
+    let mut seen = BTreeSet::new();
+    for (index, id) in ids.enumerate() {
+        if id.trim().is_empty() {
+            return Err(BestiaryError::EmptyId { index });
+        }
+        if !seen.insert(id) {
+            return Err(BestiaryError::DuplicateId { id: id.to_string() });
+        }
+    }
+    Ok(())
+}
+
+/// "A" for the first copy of a monster, "B" for the second...
+fn letter(index: usize) -> String {
+    match u8::try_from(index).ok().filter(|&i| i < 26) {
+        Some(i) => char::from(b'A' + i).to_string(),
+        None => (index + 1).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AiPersonality, Stats};
+    #[cfg(feature = "std")]
+    use crate::{ClassCatalog, ClassError, Element};
+
+    fn monster(id: &str) -> MonsterTemplate {
+        MonsterTemplate {
+            id: id.to_string(),
+            name: id.to_string(),
+            stats: Stats {
+                hp: 10,
+                ..Default::default()
+            },
+            abilities: vec!["Basic Attack".to_string()],
+            personality: AiPersonality::Aggressive,
+            loot: None,
+        }
+    }
+
+    fn encounter(id: &str, monsters: &[&str]) -> EncounterTemplate {
+        EncounterTemplate {
+            id: id.to_string(),
+            name: id.to_string(),
+            monsters: monsters.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_builtin_bestiary_matches_the_other_catalogs() {
+        let bestiary = Bestiary::builtin();
+
+        assert_eq!(bestiary.check_abilities(&AbilityCatalog::builtin()), Ok(()));
+        assert_eq!(bestiary.check_loot(&LootCatalog::builtin()), Ok(()));
+        assert_eq!(
+            bestiary.require("slime").unwrap().character(),
+            Character::new_monster("Slime")
+        );
+        assert_eq!(
+            bestiary
+                .require("cultist")
+                .unwrap()
+                .stats
+                .resistances
+                .multiplier(Element::Fire),
+            50
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_spawn_encounter_keeps_templates_and_names_unique() {
+        let bestiary = Bestiary::builtin();
+        let team = bestiary
+            .spawn_encounter("war_band", &AbilityCatalog::builtin(), CooldownMode::Turns)
+            .unwrap();
+
+        let names: Vec<&str> = team
+            .iter()
+            .map(|(c, _)| c.character.name.as_str())
+            .collect();
+        assert_eq!(names, ["Orc", "Goblin", "Cultist"]);
+        assert_eq!(team[0].1.personality, AiPersonality::Defensive);
+        assert!(team.iter().all(|(c, _)| c.team == Team::Enemies));
+        assert_eq!(team[2].0.abilities.abilities[1].ability.name, "Fire Bolt");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_archetypes_spawn_with_their_unlocked_abilities() {
+        let classes = ClassCatalog::builtin();
+        let abilities = AbilityCatalog::builtin();
+
+        let (rogue, loadout) = classes
+            .spawn("Rogue", "Vex", 2, &abilities, CooldownMode::Turns)
+            .unwrap();
+
+        assert_eq!(rogue.name, "Vex");
+        assert_eq!(rogue.stats.attack, 11);
+        assert_eq!(rogue.hp, 25);
+        let names: Vec<&str> = loadout
+            .abilities
+            .iter()
+            .map(|slot| slot.ability.name.as_str())
+            .collect();
+        assert_eq!(names, ["Basic Attack", "Quick Strike", "Venom Strike"]);
+        assert_eq!(
+            classes
+                .spawn("Paladin", "Uther", 1, &abilities, CooldownMode::Turns)
+                .unwrap_err(),
+            ClassError::UnknownClass {
+                name: "Paladin".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_bestiaries_are_rejected() {
+        assert_eq!(
+            Bestiary::new(vec![monster("a"), monster("a")], Vec::new()),
+            Err(BestiaryError::DuplicateId {
+                id: "a".to_string()
+            })
+        );
+        assert_eq!(
+            Bestiary::new(vec![monster("a")], vec![encounter("e", &["a", "b"])]),
+            Err(BestiaryError::UnknownMonster {
+                id: "b".to_string()
+            })
+        );
+        assert!(matches!(
+            Bestiary::new(vec![monster("a")], vec![encounter("e", &[])]),
+            Err(BestiaryError::InvalidTemplate { .. })
+        ));
+
+        let mut unarmed = monster("a");
+        unarmed.abilities.clear();
+        assert!(matches!(
+            Bestiary::new(vec![unarmed], Vec::new()),
+            Err(BestiaryError::InvalidTemplate { .. })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unknown_abilities_surface_on_spawn() {
+        let mut caster = monster("caster");
+        caster.abilities.push("Meteor".to_string());
+        let bestiary = Bestiary::new(vec![caster], Vec::new()).unwrap();
+
+        assert_eq!(
+            bestiary.spawn("caster", &AbilityCatalog::builtin(), CooldownMode::Turns),
+            Err(BestiaryError::Ability(CatalogError::UnknownAbility {
+                name: "Meteor".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_json_bestiary() {
+        let bestiary = Bestiary::from_json(
+            r#"{"monsters": [{"id": "imp", "name": "Imp",
+                "stats": {"hp": 8, "attack": 4, "defense": 0},
+                "abilities": ["Fire Bolt"], "personality": "Smart"}]}"#,
+        )
+        .unwrap();
+
+        assert!(bestiary.encounters().is_empty());
+        assert_eq!(bestiary.require("imp").unwrap().loot, None);
+    }
+}
@@ -0,0 +1,669 @@
+//! Data-driven ability definitions.
+//!
+//! An [`AbilityCatalog`] is a validated list of [`AbilityDef`]s loaded from JSON
+//! or RON. Each definition carries everything needed to build the runtime types:
+//! an [`Ability`] for damage resolution, or an [`AbilitySlot`] whose cooldown
+//! counts turns or seconds depending on the [`CooldownMode`] of the game.
+//!
+//! The built-in catalog ([`AbilityCatalog::builtin`]) is embedded from
+//! `data/abilities.ron` and reproduces the hardcoded constructors such as
+//! [`Ability::basic_attack`] and [`AbilitySet::player_default`].
+//!
+//! # Data Format
+//!
+//! ```ron
+//! (
+//!     abilities: [
+//!         (
+//!             name: "Venom Strike",
+//!             type: BasicAttack,
+//!             element: Poison,
+//!             cooldown: (turns: 2, seconds: 2.0),
+//!             targeting: Enemy,
+//!             effects: [
+//!                 Damage(power: 2),
+//!                 ApplyStatus((kind: Poison, magnitude: 2, duration: Turns(3))),
+//!             ],
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! `effects` lists the [`AbilityEffect`]s in resolution order. `element`,
+//! `cooldown` and `targeting` are optional and default to physical damage, no
+//! cooldown and a single enemy.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let catalog = AbilityCatalog::from_json(
+//!     r#"{"abilities": [
+//!         {"name": "Slash", "type": "BasicAttack", "cooldown": {"turns": 1},
+//!          "effects": [{"Damage": {"power": 7}}]}
+//!     ]}"#,
+//! )
+//! .expect("valid catalog");
+//!
+//! let slash = catalog.ability("Slash").unwrap();
+//! assert_eq!(slash.power(), 7);
+//!
+//! let loadout = catalog.ability_set(&["Slash"], CooldownMode::Turns).unwrap();
+//! assert_eq!(loadout.abilities[0].cooldown, Cooldown::turns(1));
+//! ```
+
+use alloc::collections::BTreeSet;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::{
+    Ability, AbilityEffect, AbilitySet, AbilitySlot, AbilityType, Cooldown, CooldownMode,
+    EffectDuration, Element, StackingRule, Targeting,
+};
+
+/// Source of the built-in catalog.
+#[cfg(feature = "std")]
+const BUILTIN_CATALOG: &str = include_str!("../data/abilities.ron");
+
+/// Cooldown of an ability in both combat modes.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct CooldownDef {
+    /// Turns to wait after use in turn-based combat.
+    #[serde(default)]
+    pub turns: i32,
+
+    /// Seconds to wait after use in real-time combat.
+    #[serde(default)]
+    pub seconds: f32,
+}
+
+impl CooldownDef {
+    /// A ready [`Cooldown`] for the given game mode.
+    pub fn cooldown(&self, mode: CooldownMode) -> Cooldown {
+        match mode {
+            CooldownMode::Turns => Cooldown::turns(self.turns),
+            CooldownMode::Seconds => Cooldown::seconds(self.seconds),
+        }
+    }
+}
+
+/// A single ability definition as stored in a catalog file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbilityDef {
+    /// Display name. Must be unique within a catalog.
+    pub name: String,
+
+    /// Classification used by the monster AI.
+    #[serde(rename = "type")]
+    pub ability_type: AbilityType,
+
+    /// Damage element.
+    #[serde(default)]
+    pub element: Element,
+
+    /// Cooldown in turns and seconds.
+    #[serde(default)]
+    pub cooldown: CooldownDef,
+
+    /// Who the ability can be aimed at.
+    #[serde(default)]
+    pub targeting: Targeting,
+
+    /// What the ability does to each target, in order.
+    pub effects: Vec<AbilityEffect>,
+}
+
+impl AbilityDef {
+    /// Builds the runtime [`Ability`].
+    pub fn to_ability(&self) -> Ability {
+        Ability {
+            name: self.name.clone(),
+            element: self.element,
+            target: self.targeting,
+            effects: self.effects.clone(),
+        }
+    }
+
+    /// Builds a ready [`AbilitySlot`] whose cooldown counts in `mode`.
+    pub fn to_slot(&self, mode: CooldownMode) -> AbilitySlot {
+        AbilitySlot::new(
+            self.to_ability(),
+            self.ability_type,
+            self.cooldown.cooldown(mode),
+        )
+    }
+
+    /// Checks the definition for values the combat code cannot handle.
+    pub fn validate(&self) -> Result<(), CatalogError> {
+        let invalid = |reason: String| CatalogError::InvalidAbility {
+            name: self.name.clone(),
+            reason,
+        };
+
+        if self.cooldown.turns < 0 {
+            return Err(invalid(format!(
+                "cooldown.turns must not be negative (got {})",
+                self.cooldown.turns
+            )));
+        }
+        if !self.cooldown.seconds.is_finite() || self.cooldown.seconds < 0.0 {
+            return Err(invalid(format!(
+                "cooldown.seconds must be a non-negative number (got {})",
+                self.cooldown.seconds
+            )));
+        }
+
+        if self.effects.is_empty() {
+            return Err(invalid("must have at least one effect".to_string()));
+        }
+
+        let heals = self
+            .effects
+            .iter()
+            .any(|effect| matches!(effect, AbilityEffect::Heal { .. }));
+        if (self.ability_type == AbilityType::Heal) != heals {
+            return Err(invalid(format!(
+                "type {:?} does not match its effects (only Heal abilities have Heal effects)",
+                self.ability_type
+            )));
+        }
+
+        for effect in &self.effects {
+            match effect {
+                AbilityEffect::Damage { power } => {
+                    if *power < 0 {
+                        return Err(invalid(format!(
+                            "Damage power must not be negative (got {}); use a Heal effect",
+                            power
+                        )));
+                    }
+                    if !self.targeting.is_offensive() {
+                        return Err(invalid(format!(
+                            "damaging ability cannot target {:?}",
+                            self.targeting
+                        )));
+                    }
+                }
+                AbilityEffect::Heal { amount } => {
+                    if *amount <= 0 {
+                        return Err(invalid(format!(
+                            "Heal amount must be positive (got {})",
+                            amount
+                        )));
+                    }
+                    if self.targeting.is_offensive() {
+                        return Err(invalid(format!(
+                            "healing ability cannot target {:?}",
+                            self.targeting
+                        )));
+                    }
+                }
+                AbilityEffect::Shield { .. } if self.targeting.is_offensive() => {
+                    return Err(invalid(format!(
+                        "shielding ability cannot target {:?}",
+                        self.targeting
+                    )));
+                }
+                AbilityEffect::Shield { .. } | AbilityEffect::ApplyStatus(_) => {}
+            }
+        }
+
+        for effect in self.effects.iter().filter_map(AbilityEffect::status_effect) {
+            if effect.magnitude < 0 {
+                return Err(invalid(format!(
+                    "{:?} effect magnitude must not be negative (got {})",
+                    effect.kind, effect.magnitude
+                )));
+            }
+            let positive = match effect.duration {
+                EffectDuration::Turns(turns) => turns > 0,
+                EffectDuration::Seconds(duration) => !duration.is_zero(),
+            };
+            if !positive {
+                return Err(invalid(format!(
+                    "{:?} effect duration must be positive (got {:?})",
+                    effect.kind, effect.duration
+                )));
+            }
+            if effect.stacking == (StackingRule::Stack { max_stacks: 0 }) {
+                return Err(invalid(format!(
+                    "{:?} effect max_stacks must be at least 1",
+                    effect.kind
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Errors produced while loading or querying an [`AbilityCatalog`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CatalogError {
+    /// The catalog file could not be read.
+    Io { path: String, message: String },
+
+    /// The file extension is not `.json` or `.ron`.
+    UnsupportedFormat { path: String },
+
+    /// The data could not be deserialized.
+    Parse {
+        format: &'static str,
+        message: String,
+    },
+
+    /// An ability has an empty name.
+    EmptyName { index: usize },
+
+    /// Two abilities share a name.
+    DuplicateName { name: String },
+
+    /// An ability definition failed validation.
+    InvalidAbility { name: String, reason: String },
+
+    /// A lookup named an ability that is not in the catalog.
+    UnknownAbility { name: String },
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io { path, message } => {
+                write!(f, "failed to read ability catalog {}: {}", path, message)
+            }
+            CatalogError::UnsupportedFormat { path } => write!(
+                f,
+                "unsupported ability catalog format {} (expected .json or .ron)",
+                path
+            ),
+            CatalogError::Parse { format, message } => {
+                write!(f, "invalid {} ability catalog: {}", format, message)
+            }
+            CatalogError::EmptyName { index } => {
+                write!(f, "ability #{} has an empty name", index)
+            }
+            CatalogError::DuplicateName { name } => {
+                write!(f, "ability \"{}\" is defined more than once", name)
+            }
+            CatalogError::InvalidAbility { name, reason } => {
+                write!(f, "ability \"{}\" is invalid: {}", name, reason)
+            }
+            CatalogError::UnknownAbility { name } => {
+                write!(f, "ability \"{}\" is not in the catalog", name)
+            }
+        }
+    }
+}
+
+impl core::error::Error for CatalogError {}
+
+/// A validated collection of ability definitions.
+///
+/// Construct with [`from_json`](Self::from_json), [`from_ron`](Self::from_ron),
+/// [`from_file`](Self::from_file) or [`new`](Self::new); all of them validate every
+/// definition, so a catalog value is always well-formed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct AbilityCatalog {
+    abilities: Vec<AbilityDef>,
+}
+
+/// On-disk shape of a catalog file.
+#[derive(Deserialize)]
+struct CatalogFile {
+    abilities: Vec<AbilityDef>,
+}
+
+impl AbilityCatalog {
+    /// Validates the definitions and builds a catalog.
+    pub fn new(abilities: Vec<AbilityDef>) -> Result<Self, CatalogError> {
+        let mut names = BTreeSet::new();
+
+        for (index, def) in abilities.iter().enumerate() {
+            if def.name.trim().is_empty() {
+                return Err(CatalogError::EmptyName { index });
+            }
+            if !names.insert(def.name.as_str()) {
+                return Err(CatalogError::DuplicateName {
+                    name: def.name.clone(),
+                });
+            }
+            def.validate()?;
+        }
+
+        Ok(Self { abilities })
+    }
+
+    /// Parses and validates a JSON catalog.
+    pub fn from_json(source: &str) -> Result<Self, CatalogError> {
+        let file: CatalogFile = serde_json::from_str(source).map_err(|e| CatalogError::Parse {
+            format: "JSON",
+            message: e.to_string(),
+        })?;
+        Self::new(file.abilities)
+    }
+
+    /// Parses and validates a RON catalog.
+    #[cfg(feature = "std")]
+    pub fn from_ron(source: &str) -> Result<Self, CatalogError> {
+        let file: CatalogFile = ron::from_str(source).map_err(|e| CatalogError::Parse {
+            format: "RON",
+            message: e.to_string(),
+        })?;
+        Self::new(file.abilities)
+    }
+
+    /// Loads a catalog file, choosing the format from its `.json` or `.ron` extension.
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        let source = std::fs::read_to_string(path).map_err(|e| CatalogError::Io {
+            path: display.clone(),
+            message: e.to_string(),
+        })?;
+
+        match extension {
+            Some("json") => Self::from_json(&source),
+            Some("ron") => Self::from_ron(&source),
+            _ => Err(CatalogError::UnsupportedFormat { path: display }),
+        }
+    }
+
+    /// The catalog embedded in the crate from `data/abilities.ron`.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: the embedded data is checked by the crate's tests.
+    #[cfg(feature = "std")]
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_CATALOG).expect("built-in ability catalog is valid")
+    }
+
+    /// All definitions, in file order.
+    pub fn abilities(&self) -> &[AbilityDef] {
+        &self.abilities
+    }
+
+    /// Looks up a definition by name.
+    pub fn get(&self, name: &str) -> Option<&AbilityDef> {
+        self.abilities.iter().find(|def| def.name == name)
+    }
+
+    /// Looks up a definition by name, failing with [`CatalogError::UnknownAbility`].
+    pub fn require(&self, name: &str) -> Result<&AbilityDef, CatalogError> {
+        self.get(name).ok_or_else(|| CatalogError::UnknownAbility {
+            name: name.to_string(),
+        })
+    }
+
+    /// Builds the runtime [`Ability`] with the given name.
+    pub fn ability(&self, name: &str) -> Result<Ability, CatalogError> {
+        self.require(name).map(AbilityDef::to_ability)
+    }
+
+    /// Builds an [`AbilitySet`] from the named abilities, in order, with
+    /// cooldowns counting in `mode`.
+    pub fn ability_set(
+        &self,
+        names: &[&str],
+        mode: CooldownMode,
+    ) -> Result<AbilitySet, CatalogError> {
+        let abilities = names
+            .iter()
+            .map(|name| self.require(name).map(|def| def.to_slot(mode)))
+            .collect::<Result<_, _>>()?;
+        Ok(AbilitySet { abilities })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StatusEffect;
+    #[cfg(feature = "std")]
+    use crate::StatusKind;
+
+    fn def(name: &str, effect: AbilityEffect, ability_type: AbilityType) -> AbilityDef {
+        AbilityDef {
+            name: name.into(),
+            ability_type,
+            element: Element::Physical,
+            cooldown: CooldownDef::default(),
+            targeting: Targeting::Enemy,
+            effects: vec![effect],
+        }
+    }
+
+    fn damage(power: i32) -> AbilityEffect {
+        AbilityEffect::Damage { power }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_builtin_catalog_matches_constructors() {
+        let catalog = AbilityCatalog::builtin();
+
+        for expected in [
+            Ability::basic_attack(),
+            Ability::powerful_attack(),
+            Ability::heal(),
+            Ability::quick_strike(),
+        ] {
+            let ability = catalog.ability(&expected.name).expect("Should exist");
+            assert_eq!(ability, expected);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_builtin_catalog_matches_player_default_cooldowns() {
+        let catalog = AbilityCatalog::builtin();
+        let from_catalog = catalog
+            .ability_set(
+                &["Basic Attack", "Powerful Attack", "Heal", "Quick Strike"],
+                CooldownMode::Seconds,
+            )
+            .expect("Should build");
+        let hardcoded = AbilitySet::player_default();
+
+        for (a, b) in from_catalog.abilities.iter().zip(&hardcoded.abilities) {
+            assert_eq!(a.ability.name, b.ability.name);
+            assert_eq!(a.ability_type, b.ability_type);
+            assert_eq!(a.cooldown, b.cooldown);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_builtin_catalog_matches_turn_based_monster_set() {
+        let catalog = AbilityCatalog::builtin();
+        let from_catalog = catalog
+            .ability_set(
+                &["Basic Attack", "Powerful Attack", "Heal"],
+                CooldownMode::Turns,
+            )
+            .expect("Should build");
+        let hardcoded = AbilitySet::monster_turn_based();
+
+        for (a, b) in from_catalog.abilities.iter().zip(&hardcoded.abilities) {
+            assert_eq!(a.ability.name, b.ability.name);
+            assert_eq!(a.ability_type, b.ability_type);
+            assert_eq!(a.cooldown, b.cooldown);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_one_definition_drives_both_modes() {
+        let catalog = AbilityCatalog::builtin();
+        let def = catalog.get("Powerful Attack").unwrap();
+
+        assert_eq!(
+            def.to_slot(CooldownMode::Turns).cooldown,
+            Cooldown::turns(3)
+        );
+        assert_eq!(
+            def.to_slot(CooldownMode::Seconds).cooldown,
+            Cooldown::seconds(3.0)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_json_and_ron_produce_same_catalog() {
+        let json = r#"{"abilities": [{
+            "name": "Venom Strike", "type": "BasicAttack", "element": "Poison",
+            "cooldown": {"turns": 2, "seconds": 2.0},
+            "effects": [
+                {"Damage": {"power": 2}},
+                {"ApplyStatus": {"kind": "Poison", "magnitude": 2, "duration": {"Turns": 3}}}
+            ]
+        }]}"#;
+        let ron = r#"(abilities: [(
+            name: "Venom Strike", type: BasicAttack, element: Poison,
+            cooldown: (turns: 2, seconds: 2.0),
+            effects: [
+                Damage(power: 2),
+                ApplyStatus((kind: Poison, magnitude: 2, duration: Turns(3))),
+            ],
+        )])"#;
+
+        let from_json = AbilityCatalog::from_json(json).expect("Should parse JSON");
+        let from_ron = AbilityCatalog::from_ron(ron).expect("Should parse RON");
+
+        assert_eq!(from_json, from_ron);
+        let venom = from_json.ability("Venom Strike").unwrap();
+        assert_eq!(
+            venom.status_effects().next().unwrap().kind,
+            StatusKind::Poison
+        );
+        assert_eq!(venom.target, Targeting::Enemy);
+    }
+
+    #[test]
+    fn test_parse_error_names_format() {
+        let err = AbilityCatalog::from_json("{").unwrap_err();
+
+        assert!(matches!(err, CatalogError::Parse { format: "JSON", .. }));
+    }
+
+    #[test]
+    fn test_duplicate_names_rejected() {
+        let err = AbilityCatalog::new(vec![
+            def("Slash", damage(4), AbilityType::BasicAttack),
+            def("Slash", damage(6), AbilityType::BasicAttack),
+        ])
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            CatalogError::DuplicateName {
+                name: "Slash".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_empty_name_rejected() {
+        let err =
+            AbilityCatalog::new(vec![def(" ", damage(4), AbilityType::BasicAttack)]).unwrap_err();
+
+        assert_eq!(err, CatalogError::EmptyName { index: 0 });
+    }
+
+    #[test]
+    fn test_negative_cooldown_rejected() {
+        let mut slash = def("Slash", damage(4), AbilityType::BasicAttack);
+        slash.cooldown.turns = -1;
+
+        let err = AbilityCatalog::new(vec![slash]).unwrap_err();
+
+        assert!(matches!(err, CatalogError::InvalidAbility { ref name, .. } if name == "Slash"));
+    }
+
+    #[test]
+    fn test_heal_type_must_match_effects() {
+        let mut mend = def("Mend", damage(8), AbilityType::Heal);
+        mend.targeting = Targeting::Caster;
+
+        let err = AbilityCatalog::new(vec![mend]).unwrap_err();
+
+        assert!(err.to_string().contains("does not match its effects"));
+    }
+
+    #[test]
+    fn test_heal_cannot_target_enemy() {
+        let mend = def("Mend", AbilityEffect::Heal { amount: 8 }, AbilityType::Heal);
+
+        let err = AbilityCatalog::new(vec![mend]).unwrap_err();
+
+        assert!(err.to_string().contains("cannot target Enemy"));
+    }
+
+    #[test]
+    fn test_negative_damage_rejected() {
+        let err = AbilityCatalog::new(vec![def("Drain", damage(-8), AbilityType::BasicAttack)])
+            .unwrap_err();
+
+        assert!(err.to_string().contains("use a Heal effect"));
+    }
+
+    #[test]
+    fn test_ability_without_effects_rejected() {
+        let mut idle = def("Idle", damage(0), AbilityType::BasicAttack);
+        idle.effects.clear();
+
+        let err = AbilityCatalog::new(vec![idle]).unwrap_err();
+
+        assert!(err.to_string().contains("at least one effect"));
+    }
+
+    #[test]
+    fn test_effect_duration_must_be_positive() {
+        let mut slash = def("Slash", damage(4), AbilityType::BasicAttack);
+        slash
+            .effects
+            .push(AbilityEffect::ApplyStatus(StatusEffect::poison(2, 0)));
+
+        let err = AbilityCatalog::new(vec![slash]).unwrap_err();
+
+        assert!(err.to_string().contains("duration must be positive"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unknown_ability_lookup() {
+        let catalog = AbilityCatalog::builtin();
+
+        let err = catalog
+            .ability_set(&["Basic Attack", "Meteor"], CooldownMode::Turns)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            CatalogError::UnknownAbility {
+                name: "Meteor".into()
+            }
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_file_rejects_unknown_extension() {
+        let path = std::env::temp_dir().join("grimware_catalog_test.toml");
+        std::fs::write(&path, "").unwrap();
+
+        let err = AbilityCatalog::from_file(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+
+        assert!(matches!(err, CatalogError::UnsupportedFormat { .. }));
+    }
+}
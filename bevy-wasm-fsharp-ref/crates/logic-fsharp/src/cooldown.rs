@@ -0,0 +1,202 @@
+//! Ability cooldowns for turn-based and real-time combat.
+//!
+//! A [`Cooldown`] counts down either whole turns or seconds. Both modes share one
+//! API, so [`AbilitySlot`](crate::AbilitySlot), [`AbilitySet`](crate::AbilitySet)
+//! and [`choose_monster_action`](crate::choose_monster_action) work the same way in
+//! either game mode:
+//!
+//! - [`Cooldown::tick_turn`] advances turn-based cooldowns by one turn
+//! - [`Cooldown::tick`] advances real-time cooldowns by a [`Millis`] delta
+//!
+//! Each tick only affects cooldowns of its own mode, so a game loop can call the
+//! one that matches its clock without checking the mode first. This mirrors
+//! [`StatusEffects::tick_turn`](crate::StatusEffects::tick_turn) and
+//! [`StatusEffects::tick`](crate::StatusEffects::tick).
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type CooldownMode =
+//!     | Turns
+//!     | Seconds
+//!
+//! type Cooldown =
+//!     | Turns of max: int * remaining: int
+//!     | Seconds of max: Millis * remaining: Millis
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::{Cooldown, Millis};
+//!
+//! let mut turns = Cooldown::turns(2);
+//! let mut seconds = Cooldown::seconds(1.5);
+//!
+//! turns.trigger();
+//! seconds.trigger();
+//!
+//! // Real-time ticks leave turn cooldowns alone, and vice versa
+//! turns.tick(Millis::from_secs(10));
+//! seconds.tick_turn();
+//! assert!(!turns.is_ready());
+//! assert!(!seconds.is_ready());
+//!
+//! turns.tick_turn();
+//! turns.tick_turn();
+//! seconds.tick(Millis(1500));
+//! assert!(turns.is_ready());
+//! assert!(seconds.is_ready());
+//! ```
+
+use crate::{Cooldown, CooldownMode, Millis};
+
+impl Cooldown {
+    /// A ready turn-based cooldown lasting `max` turns after use.
+    pub fn turns(max: i32) -> Self {
+        Cooldown::Turns { max, remaining: 0 }
+    }
+
+    /// A ready real-time cooldown lasting `max` seconds after use, rounded to
+    /// whole milliseconds.
+    pub fn seconds(max: f32) -> Self {
+        Cooldown::Seconds {
+            max: Millis::from_secs_f32(max),
+            remaining: Millis::ZERO,
+        }
+    }
+
+    /// The clock this cooldown counts against.
+    pub fn mode(&self) -> CooldownMode {
+        match self {
+            Cooldown::Turns { .. } => CooldownMode::Turns,
+            Cooldown::Seconds { .. } => CooldownMode::Seconds,
+        }
+    }
+
+    /// Whether the cooldown has finished.
+    pub fn is_ready(&self) -> bool {
+        match *self {
+            Cooldown::Turns { remaining, .. } => remaining <= 0,
+            Cooldown::Seconds { remaining, .. } => remaining.is_zero(),
+        }
+    }
+
+    /// Restarts the cooldown after the ability is used.
+    pub fn trigger(&mut self) {
+        match self {
+            Cooldown::Turns { max, remaining } => *remaining = *max,
+            Cooldown::Seconds { max, remaining } => *remaining = *max,
+        }
+    }
+
+    /// Advances a turn-based cooldown by one turn. Real-time cooldowns are unchanged.
+    pub fn tick_turn(&mut self) {
+        if let Cooldown::Turns { remaining, .. } = self {
+            *remaining = (*remaining - 1).max(0);
+        }
+    }
+
+    /// Advances a real-time cooldown by `delta`. Turn-based cooldowns are unchanged.
+    pub fn tick(&mut self, delta: Millis) {
+        if let Cooldown::Seconds { remaining, .. } = self {
+            *remaining = remaining.saturating_sub(delta);
+        }
+    }
+
+    /// Fraction of the cooldown still remaining (0.0 = ready, 1.0 = just used).
+    pub fn progress(&self) -> f32 {
+        let (remaining, max) = match *self {
+            Cooldown::Turns { max, remaining } => (remaining as f32, max as f32),
+            Cooldown::Seconds { max, remaining } => (remaining.0 as f32, max.0 as f32),
+        };
+        if max <= 0.0 {
+            0.0
+        } else {
+            (remaining / max).clamp(0.0, 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_cooldowns_are_ready() {
+        assert!(Cooldown::turns(3).is_ready());
+        assert!(Cooldown::seconds(3.0).is_ready());
+    }
+
+    #[test]
+    fn test_turn_cooldown_counts_down() {
+        let mut cooldown = Cooldown::turns(3);
+
+        cooldown.trigger();
+        assert_eq!(
+            cooldown,
+            Cooldown::Turns {
+                max: 3,
+                remaining: 3
+            }
+        );
+
+        cooldown.tick_turn();
+        cooldown.tick_turn();
+        assert!(!cooldown.is_ready());
+
+        cooldown.tick_turn();
+        assert!(cooldown.is_ready());
+    }
+
+    #[test]
+    fn test_turn_cooldown_does_not_go_negative() {
+        let mut cooldown = Cooldown::turns(0);
+
+        cooldown.tick_turn();
+
+        assert_eq!(
+            cooldown,
+            Cooldown::Turns {
+                max: 0,
+                remaining: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_ticks_only_affect_matching_mode() {
+        let mut turns = Cooldown::turns(1);
+        let mut seconds = Cooldown::seconds(1.0);
+        turns.trigger();
+        seconds.trigger();
+
+        turns.tick(Millis::from_secs(5));
+        seconds.tick_turn();
+
+        assert!(!turns.is_ready());
+        assert!(!seconds.is_ready());
+    }
+
+    #[test]
+    fn test_progress_in_both_modes() {
+        let mut turns = Cooldown::turns(4);
+        let mut seconds = Cooldown::seconds(4.0);
+        turns.trigger();
+        seconds.trigger();
+
+        turns.tick_turn();
+        turns.tick_turn();
+        seconds.tick(Millis(2000));
+
+        assert_eq!(turns.progress(), 0.5);
+        assert_eq!(seconds.progress(), 0.5);
+        assert_eq!(Cooldown::turns(0).progress(), 0.0);
+    }
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(Cooldown::turns(1).mode(), CooldownMode::Turns);
+        assert_eq!(Cooldown::seconds(1.0).mode(), CooldownMode::Seconds);
+    }
+}
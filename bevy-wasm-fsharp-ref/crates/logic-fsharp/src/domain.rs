@@ -0,0 +1,1359 @@
+// @generated by fsharp-codegen from fsharp/Domain.fs. Do not edit by hand.
+
+//! Domain types generated from `fsharp/Domain.fs`.
+//!
+//! Every record and union in the F# domain module becomes a struct or enum
+//! here, so the two can never drift. Behaviour lives in hand-written `impl`
+//! blocks in the other modules. To change a type, edit the F# and run:
+//!
+//! ```text
+//! cargo run -p fsharp-tools --bin fsharp-codegen
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+/// The damage type of an ability.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Element =
+///     | Physical
+///     | Fire
+///     | Ice
+///     | Lightning
+///     | Poison
+///     | Holy
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Element {
+    /// Untyped weapon damage. The default for every ability.
+    #[default]
+    Physical,
+
+    Fire,
+
+    Ice,
+
+    Lightning,
+
+    Poison,
+
+    Holy,
+}
+
+/// How well an attack's element matched the defender's resistances.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Effectiveness =
+///     | Normal
+///     | SuperEffective
+///     | Resisted
+///     | Immune
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Effectiveness {
+    /// Neutral damage.
+    #[default]
+    Normal,
+
+    /// The defender is weak to the element.
+    SuperEffective,
+
+    /// The defender resists the element.
+    Resisted,
+
+    /// The defender is immune to the element and took no damage.
+    Immune,
+}
+
+/// The built-in damage formulas.
+///
+/// Dispatches to the matching formula, so an encounter or a data file can name
+/// the formula it uses wherever a `DamageFormula` is expected.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type DamageModel =
+///     | FlatDefense
+///     | PercentArmor
+///     | AttackRatio
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum DamageModel {
+    /// `FlatDefenseFormula`: `max(1, attack + power - defense)`, the default
+    #[default]
+    FlatDefense,
+
+    /// `PercentArmorFormula`: each point of defense blocks a shrinking share of damage
+    PercentArmor,
+
+    /// `AttackRatioFormula`: `attack^2 / (attack + defense)`
+    AttackRatio,
+}
+
+/// A character's damage multipliers per element, in percent.
+///
+/// Elements without an entry take neutral (100%) damage. Serializes as a plain
+/// map, e.g. `{"Fire": 200, "Ice": 50}`.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Resistances = Resistances of Map<Element, int>
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Resistances(pub BTreeMap<Element, i32>);
+
+/// Character combat statistics.
+///
+/// Stats define a character's combat capabilities: maximum health, offensive
+/// power and defensive resilience. These values are used in damage calculations
+/// via `compute_attack`.
+///
+/// The three random stats default to 0, which keeps combat fully deterministic.
+/// Resistances default to empty, meaning every element deals neutral damage.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Stats =
+///     { Hp: int
+///       Attack: int
+///       Defense: int
+///       CritChance: int
+///       EvadeChance: int
+///       DamageVariance: int
+///       Resistances: Resistances
+///       Speed: int }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Stats {
+    /// Maximum hit points. When current HP reaches 0, the character is defeated.
+    pub hp: i32,
+
+    /// Attack power. Added to ability power when calculating damage.
+    pub attack: i32,
+
+    /// Damage reduction. Subtracted from incoming damage (minimum 1 damage always applies).
+    pub defense: i32,
+
+    /// Critical hit chance in percent (0-100). Critical hits deal
+    /// `CRIT_DAMAGE_PERCENT` of normal damage.
+    #[serde(default)]
+    pub crit_chance: i32,
+
+    /// Evasion chance in percent (0-100). An evaded attack deals no damage.
+    #[serde(default)]
+    pub evade_chance: i32,
+
+    /// Damage variance in percent. Outgoing damage is scaled by a random
+    /// factor in `[100 - variance, 100 + variance]` percent.
+    #[serde(default)]
+    pub damage_variance: i32,
+
+    /// Incoming damage multipliers per element, in percent. Elements without
+    /// an entry take 100% damage.
+    #[serde(default)]
+    pub resistances: Resistances,
+
+    /// Initiative. Higher speed acts earlier in an encounter round.
+    #[serde(default)]
+    pub speed: i32,
+}
+
+/// A game character (player or monster).
+///
+/// Characters are the core entities in the combat system. Each character has a
+/// name, current health and base statistics. Current HP can differ from max HP
+/// (`stats.hp`) after taking damage; a character is defeated when `hp <= 0`.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Character =
+///     { Name: string
+///       Hp: int
+///       Stats: Stats }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct Character {
+    /// Display name of the character.
+    pub name: String,
+
+    /// Current hit points. When this reaches 0 or below, the character is defeated.
+    pub hp: i32,
+
+    /// Base combat statistics (max HP, attack, defense).
+    pub stats: Stats,
+}
+
+/// A real-time duration in whole milliseconds.
+///
+/// Real-time cooldowns and status effects count down in integer milliseconds
+/// rather than floating-point seconds, so every platform ticks them identically.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Millis = Millis of uint32
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Millis(pub u32);
+
+/// The kind of a status effect.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StatusKind =
+///     | Poison
+///     | Stun
+///     | Shield
+///     | Regen
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum StatusKind {
+    /// Deals `magnitude` damage per tick, ignoring defense and shields.
+    #[default]
+    Poison,
+
+    /// The affected character loses its turns while the effect lasts.
+    Stun,
+
+    /// Absorbs up to `magnitude` incoming attack damage.
+    Shield,
+
+    /// Restores `magnitude` HP per tick, up to max HP.
+    Regen,
+}
+
+/// How long a status effect lasts.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type EffectDuration =
+///     | Turns of int
+///     | Seconds of Millis
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectDuration {
+    /// Lasts this many turns. Ticked by `StatusEffects::tick_turn`.
+    Turns(i32),
+
+    /// Lasts this long in real time. Ticked by `StatusEffects::tick`.
+    Seconds(Millis),
+}
+
+/// What happens when an effect is applied to a character that already has it.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StackingRule =
+///     | Refresh
+///     | Stack of maxStacks: uint32
+///     | Ignore
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StackingRule {
+    /// Reset the duration; magnitude stays the same.
+    #[default]
+    Refresh,
+
+    /// Add a stack (up to `max_stacks`) and reset the duration. Ticks and
+    /// shields scale with the stack count.
+    Stack { max_stacks: u32 },
+
+    /// Keep the existing effect and discard the new one.
+    Ignore,
+}
+
+/// A status effect definition, as carried by an `Ability`.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StatusEffect =
+///     { Kind: StatusKind
+///       Magnitude: int
+///       Duration: EffectDuration
+///       Stacking: StackingRule }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    /// What the effect does.
+    pub kind: StatusKind,
+
+    /// Damage or healing per tick, or shield points. Unused by `Stun`.
+    pub magnitude: i32,
+
+    /// How long the effect lasts.
+    pub duration: EffectDuration,
+
+    /// How re-application interacts with an existing effect of the same kind.
+    #[serde(default)]
+    pub stacking: StackingRule,
+}
+
+/// A status effect currently affecting a character.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type ActiveEffect =
+///     { Effect: StatusEffect
+///       Remaining: EffectDuration
+///       Stacks: uint32
+///       ShieldRemaining: int
+///       TickProgress: Millis }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    /// The definition this effect was applied from.
+    pub effect: StatusEffect,
+
+    /// Time left before the effect expires.
+    pub remaining: EffectDuration,
+
+    /// Number of stacks (at least 1).
+    pub stacks: u32,
+
+    /// Shield points left; only meaningful for `Shield`.
+    pub shield_remaining: i32,
+
+    /// Time accumulated toward the next per-second tick.
+    pub tick_progress: Millis,
+}
+
+/// Something that happened to a character's status effects.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StatusEvent =
+///     | Applied of target: string * kind: StatusKind * stacks: uint32
+///     | Ticked of target: string * kind: StatusKind * amount: int * hpAfter: int
+///     | Absorbed of target: string * amount: int
+///     | Expired of target: string * kind: StatusKind
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusEvent {
+    /// An effect was applied or re-applied. `stacks` is the resulting count.
+    Applied {
+        target: String,
+        kind: StatusKind,
+        stacks: u32,
+    },
+
+    /// A damage or healing effect ticked. `amount` is positive for damage and
+    /// negative for healing, matching `CombatEvent::damage`.
+    Ticked {
+        target: String,
+        kind: StatusKind,
+        amount: i32,
+        hp_after: i32,
+    },
+
+    /// A shield absorbed incoming damage.
+    Absorbed { target: String, amount: i32 },
+
+    /// An effect ran out (or a shield was depleted) and was removed.
+    Expired { target: String, kind: StatusKind },
+}
+
+/// The status effects currently affecting one character.
+///
+/// This is a companion component: spawn it on the same entity as the
+/// `Character` it belongs to.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StatusEffects =
+///     { Effects: ActiveEffect list }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct StatusEffects {
+    /// Active effects, at most one entry per `StatusKind`.
+    pub effects: Vec<ActiveEffect>,
+}
+
+/// Who an ability can be aimed at.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Targeting =
+///     | Enemy
+///     | AllEnemies
+///     | Caster
+///     | Ally
+///     | AllAllies
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Targeting {
+    /// A single opponent.
+    #[default]
+    Enemy,
+
+    /// Every opponent.
+    AllEnemies,
+
+    /// The character using the ability.
+    Caster,
+
+    /// A single ally, possibly the caster.
+    Ally,
+
+    /// Every ally, including the caster.
+    AllAllies,
+}
+
+/// One thing an ability does to each of its targets.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type AbilityEffect =
+///     | Damage of power: int
+///     | Heal of amount: int
+///     | Shield of points: int * duration: EffectDuration
+///     | ApplyStatus of StatusEffect
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AbilityEffect {
+    /// Deals damage of the ability's element: the caster's attack plus `power`,
+    /// minus the target's defense.
+    Damage { power: i32 },
+
+    /// Restores `amount` HP, up to the target's max HP.
+    Heal { amount: i32 },
+
+    /// Raises a shield that absorbs up to `points` attack damage.
+    Shield {
+        points: i32,
+        duration: EffectDuration,
+    },
+
+    /// Applies a status effect.
+    ApplyStatus(StatusEffect),
+}
+
+/// A combat ability or attack.
+///
+/// Abilities represent actions that characters can perform in combat. Each
+/// ability has a target rule and a list of effects, resolved in order against
+/// every target; `element` is checked against the target's resistances.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Ability =
+///     { Name: string
+///       Element: Element
+///       Target: Targeting
+///       Effects: AbilityEffect list }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Ability {
+    /// Display name of the ability.
+    pub name: String,
+
+    /// Damage type, checked against the defender's resistances.
+    #[serde(default)]
+    pub element: Element,
+
+    /// Who the ability can be aimed at.
+    #[serde(default)]
+    pub target: Targeting,
+
+    /// What the ability does to each target.
+    pub effects: Vec<AbilityEffect>,
+}
+
+/// Ability type enumeration for AI decision making.
+///
+/// Classifies abilities into categories to help the AI choose appropriate actions.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type AbilityType =
+///     | BasicAttack
+///     | PowerfulAttack
+///     | Heal
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum AbilityType {
+    #[default]
+    BasicAttack,
+    PowerfulAttack,
+    Heal,
+}
+
+/// The clock a cooldown counts against.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type CooldownMode =
+///     | Turns
+///     | Seconds
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum CooldownMode {
+    /// Whole turns, for turn-based combat.
+    #[default]
+    Turns,
+
+    /// Seconds, for real-time combat.
+    Seconds,
+}
+
+/// A cooldown timer in turns or seconds.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Cooldown =
+///     | Turns of max: int * remaining: int
+///     | Seconds of max: Millis * remaining: Millis
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cooldown {
+    /// Counts down one per `tick_turn`.
+    Turns { max: i32, remaining: i32 },
+
+    /// Counts down by `delta` per `tick`.
+    Seconds { max: Millis, remaining: Millis },
+}
+
+/// An ability slot with cooldown tracking.
+///
+/// Pairs an `Ability` with its `AbilityType` classification and a `Cooldown`.
+/// The same slot type is used for real-time and turn-based combat; only the
+/// cooldown's mode differs.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type AbilitySlot =
+///     { Ability: Ability
+///       AbilityType: AbilityType
+///       Cooldown: Cooldown }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct AbilitySlot {
+    /// The ability contained in this slot.
+    pub ability: Ability,
+
+    /// Type classification for AI decision making.
+    pub ability_type: AbilityType,
+
+    /// Cooldown in turns or seconds.
+    pub cooldown: Cooldown,
+}
+
+/// A set of abilities for a character.
+///
+/// Contains multiple ability slots that can be used in combat.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type AbilitySet =
+///     { Abilities: AbilitySlot list }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct AbilitySet {
+    /// List of ability slots.
+    pub abilities: Vec<AbilitySlot>,
+}
+
+/// The built-in AI personalities.
+///
+/// Dispatches to the matching policy, so it can be stored in data files or
+/// attached to a monster entity and used wherever an `AiPolicy` is expected.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type AiPersonality =
+///     | Threshold
+///     | Aggressive
+///     | Defensive
+///     | Balanced
+///     | Smart
+///     | Tactician
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub enum AiPersonality {
+    /// `ThresholdAi`
+    #[default]
+    Threshold,
+
+    /// `AggressiveAi`
+    Aggressive,
+
+    /// `DefensiveAi`
+    Defensive,
+
+    /// `BalancedAi`
+    Balanced,
+
+    /// `SmartAi`
+    Smart,
+
+    /// `Planner::HARD`, the hard difficulty
+    Tactician,
+}
+
+/// What one effect of an ability did to its target.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type EffectOutcome =
+///     | Damaged of amount: int
+///     | Healed of amount: int
+///     | StatusApplied of kind: StatusKind * stacks: uint32
+///     | StatusIgnored of kind: StatusKind
+///     | Evaded
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectOutcome {
+    /// A `Damage` effect dealt `amount` damage, after defense, resistances, shields
+    /// and critical hits. Damage past 0 HP counts towards the event's `Overkill`.
+    Damaged { amount: i32 },
+
+    /// A `Heal` effect restored `amount` HP. Healing beyond max HP is lost.
+    Healed { amount: i32 },
+
+    /// A `Shield` or `ApplyStatus` effect took hold; `stacks` is the resulting count.
+    StatusApplied { kind: StatusKind, stacks: u32 },
+
+    /// A `Shield` or `ApplyStatus` effect was discarded because of `StackingRule.Ignore`.
+    StatusIgnored { kind: StatusKind },
+
+    /// The target evaded the ability, so none of its effects applied.
+    Evaded,
+}
+
+/// Total XP needed to reach each level.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type LevelCurve =
+///     | Linear of step: uint32
+///     | Quadratic of scale: uint32
+///     | Table of totals: uint32 list
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LevelCurve {
+    /// Every level costs the same: level `n` needs `step * (n - 1)` XP in total.
+    Linear { step: u32 },
+
+    /// Levels get steeper: level `n` needs `scale * (n - 1)^2` XP in total.
+    Quadratic { scale: u32 },
+
+    /// `totals[i]` is the total XP needed for level `i + 2`. The last entry
+    /// reaches the maximum level.
+    Table { totals: Vec<u32> },
+}
+
+/// Stats gained with every level above 1.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StatGrowth =
+///     { Hp: int
+///       Attack: int
+///       Defense: int
+///       Speed: int }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StatGrowth {
+    /// Max HP per level.
+    pub hp: i32,
+
+    /// Attack per level.
+    pub attack: i32,
+
+    /// Defense per level.
+    pub defense: i32,
+
+    /// Speed per level.
+    #[serde(default)]
+    pub speed: i32,
+}
+
+/// An ability a class learns on reaching `Level`.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type AbilityUnlock =
+///     { Level: uint32
+///       Ability: string }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AbilityUnlock {
+    pub level: u32,
+
+    /// Ability catalog name.
+    pub ability: String,
+}
+
+/// A character class: level 1 stats, growth, level curve and ability unlocks.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type CharacterClass =
+///     { Name: string
+///       Stats: Stats
+///       Growth: StatGrowth
+///       Curve: LevelCurve
+///       Unlocks: AbilityUnlock list
+///       XpReward: uint32
+///       Personality: AiPersonality }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharacterClass {
+    /// Display name. Must be unique within a class catalog.
+    pub name: String,
+
+    /// Stats at level 1.
+    pub stats: Stats,
+
+    /// Stats gained per level.
+    pub growth: StatGrowth,
+
+    /// XP needed for each level.
+    pub curve: LevelCurve,
+
+    /// Abilities learned by level, in the order they are learned.
+    #[serde(default)]
+    pub unlocks: Vec<AbilityUnlock>,
+
+    /// XP for defeating a level 1 member of the class. Scales with level.
+    pub xp_reward: u32,
+
+    /// How members of the class fight when the AI controls them.
+    #[serde(default)]
+    pub personality: AiPersonality,
+}
+
+/// A character's class, level and experience.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Progression =
+///     { Class: CharacterClass
+///       Level: uint32
+///       Xp: uint32 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct Progression {
+    pub class: CharacterClass,
+
+    /// Current level, starting at 1.
+    pub level: u32,
+
+    /// Total XP earned.
+    pub xp: u32,
+}
+
+/// XP one character earned from a combat action.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type XpGrant =
+///     { Recipient: string
+///       Amount: uint32
+///       LevelAfter: uint32
+///       LevelsGained: uint32
+///       Unlocked: string list }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct XpGrant {
+    /// Name of the character who earned the XP.
+    pub recipient: String,
+
+    /// XP earned.
+    pub amount: u32,
+
+    /// Level after the XP was added.
+    pub level_after: u32,
+
+    /// Levels gained.
+    #[serde(default)]
+    pub levels_gained: u32,
+
+    /// Abilities unlocked by the levels gained.
+    #[serde(default)]
+    pub unlocked: Vec<String>,
+}
+
+/// The result of a combat action.
+///
+/// A `CombatEvent` records what happened when one character used an ability on
+/// another: the names of both participants, the net damage dealt, the defender's
+/// remaining HP afterwards, which ability was used and what each of its effects
+/// did. Events are immutable records; the defender's HP must be updated from
+/// `defender_hp_after`, which is always clamped between 0 and max HP.
+///
+/// `Damage = EffectiveAmount + Overkill - Overheal` always holds.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type CombatEvent =
+///     { Version: uint32
+///       AttackerName: string
+///       DefenderName: string
+///       Damage: int
+///       DefenderHpAfter: int
+///       EffectiveAmount: int
+///       Overkill: int
+///       Overheal: int
+///       KillingBlow: bool
+///       AbilityUsed: string
+///       Critical: bool
+///       Missed: bool
+///       Effectiveness: Effectiveness
+///       StatusEvents: StatusEvent list
+///       Outcomes: EffectOutcome list
+///       XpGrants: XpGrant list }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CombatEvent {
+    /// Version of the serialized event format. Logs written before events
+    /// were versioned have no version and read as 0.
+    #[serde(default)]
+    pub version: u32,
+
+    /// Name of the character who performed the attack.
+    pub attacker_name: String,
+
+    /// Name of the character who received the attack.
+    pub defender_name: String,
+
+    /// Net damage dealt: damage minus healing, before HP is clamped.
+    /// Negative damage means healing.
+    pub damage: i32,
+
+    /// Defender's remaining HP after the ability, between 0 and max HP.
+    /// If this is 0, the defender is defeated.
+    pub defender_hp_after: i32,
+
+    /// HP the defender actually lost; negative for HP actually restored.
+    #[serde(default)]
+    pub effective_amount: i32,
+
+    /// Damage dealt past 0 HP.
+    #[serde(default)]
+    pub overkill: i32,
+
+    /// Healing past max HP.
+    #[serde(default)]
+    pub overheal: i32,
+
+    /// Whether this event took the defender from above 0 HP to 0.
+    #[serde(default)]
+    pub killing_blow: bool,
+
+    /// Name of the ability that was used.
+    pub ability_used: String,
+
+    /// Whether the attack was a critical hit.
+    #[serde(default)]
+    pub critical: bool,
+
+    /// Whether the defender evaded the attack (no damage dealt).
+    #[serde(default)]
+    pub missed: bool,
+
+    /// How the ability's element matched the defender's resistances.
+    #[serde(default)]
+    pub effectiveness: Effectiveness,
+
+    /// Status effects applied, absorbed or expired as part of this action.
+    #[serde(default)]
+    pub status_events: Vec<StatusEvent>,
+
+    /// What each of the ability's effects did, in resolution order.
+    #[serde(default)]
+    pub outcomes: Vec<EffectOutcome>,
+
+    /// XP earned for the characters defeated this turn.
+    #[serde(default)]
+    pub xp_grants: Vec<XpGrant>,
+}
+
+/// A stat that equipment can modify.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StatKind =
+///     | MaxHp
+///     | Attack
+///     | Defense
+///     | Speed
+///     | CritChance
+///     | EvadeChance
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum StatKind {
+    /// `Stats.Hp`, the maximum hit points.
+    #[default]
+    MaxHp,
+
+    Attack,
+
+    Defense,
+
+    Speed,
+
+    CritChance,
+
+    EvadeChance,
+}
+
+/// A change an equipped item makes to one of its wearer's stats.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type StatModifier =
+///     | Flat of stat: StatKind * amount: int
+///     | Percent of stat: StatKind * percent: int
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatModifier {
+    /// Adds `amount` to the stat.
+    Flat { stat: StatKind, amount: i32 },
+
+    /// Scales the stat by `percent` percent (`20` is +20%), after every `Flat`
+    /// modifier. Percentages on the same stat add up.
+    Percent { stat: StatKind, percent: i32 },
+}
+
+/// Where an item is worn. A combatant wears at most one item per slot.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type EquipmentSlot =
+///     | Weapon
+///     | Armor
+///     | Accessory
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum EquipmentSlot {
+    #[default]
+    Weapon,
+    Armor,
+    Accessory,
+}
+
+/// What an item does.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type ItemKind =
+///     | Equipment of slot: EquipmentSlot * modifiers: StatModifier list
+///     | Consumable of ability: Ability
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemKind {
+    /// Worn in `slot`, modifying the wearer's stats while equipped.
+    Equipment {
+        slot: EquipmentSlot,
+        modifiers: Vec<StatModifier>,
+    },
+
+    /// Used up to resolve `ability`'s effects, e.g. a potion's `Heal`.
+    Consumable { ability: Ability },
+}
+
+/// Something a character can carry.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Item =
+///     { Name: string
+///       Kind: ItemKind }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Item {
+    /// Display name, unique within an inventory's lookups.
+    pub name: String,
+
+    pub kind: ItemKind,
+}
+
+/// A character's items: a bag of carried items and the equipment being worn.
+///
+/// Equipped items modify the wearer's effective stats; its base `Stats` never change.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Inventory =
+///     { Items: Item list
+///       Equipped: Map<EquipmentSlot, Item> }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct Inventory {
+    /// Carried items, consumables included, in pickup order.
+    #[serde(default)]
+    pub items: Vec<Item>,
+
+    /// Worn equipment by slot.
+    #[serde(default)]
+    pub equipped: BTreeMap<EquipmentSlot, Item>,
+}
+
+/// How rare a drop is. Loot entries without a weight use their rarity's default
+/// weight.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Rarity =
+///     | Common
+///     | Uncommon
+///     | Rare
+///     | Epic
+///     | Legendary
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+/// What a loot entry yields when it is picked.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type LootDrop =
+///     | Nothing
+///     | Item of item: Item * min: uint32 * max: uint32
+///     | Table of table: string
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LootDrop {
+    /// No drop. Gives a table a chance of dropping nothing.
+    #[default]
+    Nothing,
+
+    /// Between `min` and `max` copies of `item`.
+    Item { item: Item, min: u32, max: u32 },
+
+    /// Everything a roll on the named table yields.
+    Table { table: String },
+}
+
+/// One possible outcome of a loot table.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type LootEntry =
+///     { Drop: LootDrop
+///       Weight: uint32 option
+///       Guaranteed: bool
+///       Rarity: Rarity }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LootEntry {
+    pub drop: LootDrop,
+
+    /// Relative chance among the table's weighted entries. Defaults to the
+    /// weight of `Rarity`.
+    #[serde(default)]
+    pub weight: Option<u32>,
+
+    /// Dropped on every roll of the table, on top of its weighted picks.
+    #[serde(default)]
+    pub guaranteed: bool,
+
+    #[serde(default)]
+    pub rarity: Rarity,
+}
+
+/// Weighted loot, e.g. what a monster drops when defeated.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type LootTable =
+///     { Name: string
+///       Rolls: uint32
+///       Entries: LootEntry list }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LootTable {
+    pub name: String,
+
+    /// Number of weighted picks per roll. Picks are made with replacement.
+    pub rolls: u32,
+
+    pub entries: Vec<LootEntry>,
+}
+
+/// Items rolled from a loot table.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Loot =
+///     { Item: Item
+///       Count: uint32
+///       Rarity: Rarity }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Loot {
+    pub item: Item,
+
+    pub count: u32,
+
+    /// The rarity of the entry that dropped the item.
+    pub rarity: Rarity,
+}
+
+/// A kind of monster in the bestiary.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type MonsterTemplate =
+///     { Id: string
+///       Name: string
+///       Stats: Stats
+///       Abilities: string list
+///       Personality: AiPersonality
+///       Loot: string option }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MonsterTemplate {
+    /// Template ID, e.g. `"goblin"`. Must be unique within a bestiary.
+    pub id: String,
+
+    /// Display name of spawned monsters.
+    pub name: String,
+
+    pub stats: Stats,
+
+    /// Ability catalog names, in slot order.
+    pub abilities: Vec<String>,
+
+    pub personality: AiPersonality,
+
+    /// Loot table rolled when the monster is defeated.
+    #[serde(default)]
+    pub loot: Option<String>,
+}
+
+/// A group of monsters from the bestiary that is fought together.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type EncounterTemplate =
+///     { Id: string
+///       Name: string
+///       Monsters: string list }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct EncounterTemplate {
+    /// Template ID. Must be unique within a bestiary.
+    pub id: String,
+
+    /// Display name of the encounter.
+    pub name: String,
+
+    /// Monster template IDs, one per spawned monster.
+    pub monsters: Vec<String>,
+}
+
+/// The side a combatant fights for.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Team =
+///     | Allies
+///     | Enemies
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub enum Team {
+    /// The player's party.
+    #[default]
+    Allies,
+
+    /// The monsters.
+    Enemies,
+}
+
+/// Identifies a combatant within an `Encounter`.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type CombatantId = CombatantId of unativeint
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+#[serde(transparent)]
+pub struct CombatantId(pub usize);
+
+/// A character taking part in an encounter, with its abilities and status effects.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Combatant =
+///     { Id: CombatantId
+///       Team: Team
+///       Character: Character
+///       Abilities: AbilitySet
+///       Effects: StatusEffects
+///       Progression: Progression option
+///       Inventory: Inventory }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Combatant {
+    /// Assigned by `Encounter::new`.
+    pub id: CombatantId,
+
+    pub team: Team,
+
+    pub character: Character,
+
+    pub abilities: AbilitySet,
+
+    #[serde(default)]
+    pub effects: StatusEffects,
+
+    /// Class and level, for combatants that earn XP. Defeating a combatant
+    /// without one is worth its max HP in XP.
+    #[serde(default)]
+    pub progression: Option<Progression>,
+
+    /// Carried and worn items. Equipment modifies the stats the combatant
+    /// fights with.
+    #[serde(default)]
+    pub inventory: Inventory,
+}
+
+/// How an encounter has ended, from the allies' point of view.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type EncounterOutcome =
+///     | Ongoing
+///     | Victory
+///     | Defeat
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum EncounterOutcome {
+    /// Both teams still have living combatants.
+    #[default]
+    Ongoing,
+
+    /// Every enemy has been defeated.
+    Victory,
+
+    /// Every ally has been defeated.
+    Defeat,
+}
+
+/// What the current combatant does with its turn.
+///
+/// # F# Equivalent
+///
+/// ```fsharp
+/// type Action =
+///     | UseAbility of ability: string * target: CombatantId
+///     | UseItem of item: string * target: CombatantId
+///     | Pass
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// Use one of the actor's ready abilities on `target`.
+    UseAbility {
+        ability: String,
+        target: CombatantId,
+    },
+
+    /// Use up one of the actor's consumable items on `target`.
+    UseItem { item: String, target: CombatantId },
+
+    /// Do nothing this turn. The only action available to a stunned combatant.
+    Pass,
+}
@@ -0,0 +1,189 @@
+//! What abilities do, and who they can be aimed at.
+//!
+//! An [`Ability`] no longer encodes healing as negative power. Instead it carries
+//! a [`Targeting`] rule and a list of [`AbilityEffect`]s, which are resolved in
+//! order against each target:
+//!
+//! | Effect | Resolved by | Outcome |
+//! |--------|-------------|---------|
+//! | [`AbilityEffect::Damage`] | [`compute_attack`](crate::compute_attack) | [`EffectOutcome::Damaged`] |
+//! | [`AbilityEffect::Heal`] | [`compute_attack`](crate::compute_attack) | [`EffectOutcome::Healed`] |
+//! | [`AbilityEffect::Shield`] | [`apply_ability_status`](crate::apply_ability_status) | [`EffectOutcome::StatusApplied`] |
+//! | [`AbilityEffect::ApplyStatus`] | [`apply_ability_status`](crate::apply_ability_status) | [`EffectOutcome::StatusApplied`] |
+//!
+//! The outcomes are recorded in [`CombatEvent::outcomes`](crate::CombatEvent::outcomes).
+//! Healing is capped at the target's max HP by the logic crate, so callers only
+//! ever copy `defender_hp_after` back onto the character.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type Targeting =
+//!     | Enemy
+//!     | AllEnemies
+//!     | Caster
+//!     | Ally
+//!     | AllAllies
+//!
+//! type AbilityEffect =
+//!     | Damage of power: int
+//!     | Heal of amount: int
+//!     | Shield of points: int * duration: EffectDuration
+//!     | ApplyStatus of StatusEffect
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let mut hero = Character::new_player("Hero");
+//! hero.hp = 25;
+//!
+//! let heal = Ability::heal();
+//! assert_eq!(heal.target, Targeting::Ally);
+//! assert_eq!(heal.effects, vec![AbilityEffect::Heal { amount: 8 }]);
+//!
+//! // Only 5 of the 8 HP fit under the hero's max HP of 30
+//! let event = compute_attack(&hero, &hero, &heal);
+//! assert_eq!(event.defender_hp_after, 30);
+//! assert_eq!(event.outcomes, vec![EffectOutcome::Healed { amount: 5 }]);
+//! ```
+
+use crate::{Ability, AbilityEffect, Element, StackingRule, StatusEffect, StatusKind, Targeting};
+
+impl Targeting {
+    /// Whether the ability is aimed at the caster's opponents.
+    pub fn is_offensive(&self) -> bool {
+        matches!(self, Targeting::Enemy | Targeting::AllEnemies)
+    }
+
+    /// Whether the ability hits every living member of the targeted team.
+    pub fn is_area(&self) -> bool {
+        matches!(self, Targeting::AllEnemies | Targeting::AllAllies)
+    }
+}
+
+impl AbilityEffect {
+    /// The status effect this effect applies, if any.
+    ///
+    /// A [`Shield`](AbilityEffect::Shield) is a [`StatusKind::Shield`] effect whose
+    /// magnitude is the shield's points; re-applying it refreshes the shield.
+    pub fn status_effect(&self) -> Option<StatusEffect> {
+        match self {
+            AbilityEffect::Shield { points, duration } => Some(StatusEffect {
+                kind: StatusKind::Shield,
+                magnitude: *points,
+                duration: *duration,
+                stacking: StackingRule::Refresh,
+            }),
+            AbilityEffect::ApplyStatus(effect) => Some(effect.clone()),
+            AbilityEffect::Damage { .. } | AbilityEffect::Heal { .. } => None,
+        }
+    }
+}
+
+/// Building and inspecting abilities by their effects.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_wasm_fsharp_ref_logic::*;
+///
+/// let fireball = Ability {
+///     element: Element::Fire,
+///     ..Ability::damage("Fireball", 15)
+/// };
+/// assert_eq!(fireball.power(), 15);
+/// assert!(!fireball.heals());
+///
+/// let mend = Ability::healing("Mend", 10);
+/// assert_eq!(mend.power(), 0);
+/// assert!(mend.heals());
+/// ```
+impl Ability {
+    /// A physical attack on a single enemy with one [`Damage`](AbilityEffect::Damage)
+    /// effect of the given power.
+    pub fn damage(name: &str, power: i32) -> Self {
+        Self {
+            name: name.into(),
+            element: Element::Physical,
+            target: Targeting::Enemy,
+            effects: vec![AbilityEffect::Damage { power }],
+        }
+    }
+
+    /// A single-ally heal restoring `amount` HP.
+    pub fn healing(name: &str, amount: i32) -> Self {
+        Self {
+            name: name.into(),
+            element: Element::Physical,
+            target: Targeting::Ally,
+            effects: vec![AbilityEffect::Heal { amount }],
+        }
+    }
+
+    /// The total power of the ability's damage effects, or 0 if it deals no damage.
+    pub fn power(&self) -> i32 {
+        self.effects
+            .iter()
+            .map(|effect| match effect {
+                AbilityEffect::Damage { power } => *power,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Whether any of the ability's effects restores HP.
+    pub fn heals(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| matches!(effect, AbilityEffect::Heal { .. }))
+    }
+
+    /// The status effects the ability applies, including shields.
+    pub fn status_effects(&self) -> impl Iterator<Item = StatusEffect> + '_ {
+        self.effects.iter().filter_map(AbilityEffect::status_effect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EffectDuration;
+
+    #[test]
+    fn test_shield_effect_is_a_refreshing_shield_status() {
+        let shield = AbilityEffect::Shield {
+            points: 6,
+            duration: EffectDuration::Turns(2),
+        };
+
+        assert_eq!(shield.status_effect(), Some(StatusEffect::shield(6, 2)));
+        assert_eq!(AbilityEffect::Heal { amount: 3 }.status_effect(), None);
+    }
+
+    #[test]
+    fn test_power_sums_damage_effects() {
+        let ability = Ability {
+            name: "Double Strike".into(),
+            effects: vec![
+                AbilityEffect::Damage { power: 3 },
+                AbilityEffect::ApplyStatus(StatusEffect::stun(1)),
+                AbilityEffect::Damage { power: 4 },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(ability.power(), 7);
+        assert_eq!(ability.status_effects().count(), 1);
+    }
+
+    #[test]
+    fn test_targeting_rules() {
+        assert!(Targeting::AllEnemies.is_offensive());
+        assert!(Targeting::AllEnemies.is_area());
+        assert!(!Targeting::Caster.is_offensive());
+        assert!(!Targeting::Ally.is_area());
+    }
+}
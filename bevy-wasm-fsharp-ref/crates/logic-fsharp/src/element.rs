@@ -0,0 +1,153 @@
+//! Elemental damage types and per-character resistances.
+//!
+//! Every [`Ability`](crate::Ability) deals damage of one [`Element`]. Each character's
+//! [`Stats`](crate::Stats) carry a [`Resistances`] table mapping elements to a damage
+//! multiplier in percent. Taken across all characters, these tables form the
+//! resistance matrix that [`compute_attack`](crate::compute_attack) consults.
+//!
+//! | Multiplier | Meaning | [`Effectiveness`] |
+//! |------------|---------|-------------------|
+//! | `> 100` | Weakness | `SuperEffective` |
+//! | `100` (or absent) | Neutral | `Normal` |
+//! | `1..100` | Resistance | `Resisted` |
+//! | `0` | Immunity | `Immune` |
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type Element =
+//!     | Physical
+//!     | Fire
+//!     | Ice
+//!     | Lightning
+//!     | Poison
+//!     | Holy
+//!
+//! type Effectiveness =
+//!     | Normal
+//!     | SuperEffective
+//!     | Resisted
+//!     | Immune
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let player = Character::new_player("Hero");
+//! let mut slime = Character::new_monster("Ice Slime");
+//! slime.stats.resistances = Resistances::default()
+//!     .with(Element::Fire, 200)
+//!     .with(Element::Ice, 0);
+//!
+//! let fireball = Ability {
+//!     element: Element::Fire,
+//!     ..Ability::damage("Fireball", 5)
+//! };
+//!
+//! let event = compute_attack(&player, &slime, &fireball);
+//! assert_eq!(event.effectiveness, Effectiveness::SuperEffective);
+//! assert_eq!(event.damage, 28); // (10 + 5 - 1) * 200%
+//! ```
+
+use crate::{Effectiveness, Element, Resistances};
+
+impl Element {
+    /// All elements, in declaration order.
+    pub const ALL: [Element; 6] = [
+        Element::Physical,
+        Element::Fire,
+        Element::Ice,
+        Element::Lightning,
+        Element::Poison,
+        Element::Holy,
+    ];
+}
+
+/// Neutral damage multiplier, in percent.
+pub const NEUTRAL_MULTIPLIER: i32 = 100;
+
+impl Resistances {
+    /// Returns a copy with the multiplier for `element` set to `percent`.
+    pub fn with(mut self, element: Element, percent: i32) -> Self {
+        self.0.insert(element, percent);
+        self
+    }
+
+    /// The damage multiplier for `element`, in percent. Never negative.
+    pub fn multiplier(&self, element: Element) -> i32 {
+        self.0
+            .get(&element)
+            .copied()
+            .unwrap_or(NEUTRAL_MULTIPLIER)
+            .max(0)
+    }
+}
+
+impl Effectiveness {
+    /// Classifies a damage multiplier in percent.
+    pub fn from_multiplier(percent: i32) -> Self {
+        match percent {
+            p if p <= 0 => Effectiveness::Immune,
+            p if p < NEUTRAL_MULTIPLIER => Effectiveness::Resisted,
+            NEUTRAL_MULTIPLIER => Effectiveness::Normal,
+            _ => Effectiveness::SuperEffective,
+        }
+    }
+
+    /// Short human-readable label, or `None` for neutral hits.
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            Effectiveness::Normal => None,
+            Effectiveness::SuperEffective => Some("super effective"),
+            Effectiveness::Resisted => Some("resisted"),
+            Effectiveness::Immune => Some("immune"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_element_is_neutral() {
+        let resistances = Resistances::default();
+
+        for element in Element::ALL {
+            assert_eq!(resistances.multiplier(element), NEUTRAL_MULTIPLIER);
+        }
+    }
+
+    #[test]
+    fn test_negative_multiplier_clamps_to_immune() {
+        let resistances = Resistances::default().with(Element::Holy, -50);
+
+        assert_eq!(resistances.multiplier(Element::Holy), 0);
+    }
+
+    #[test]
+    fn test_effectiveness_from_multiplier() {
+        assert_eq!(Effectiveness::from_multiplier(0), Effectiveness::Immune);
+        assert_eq!(Effectiveness::from_multiplier(50), Effectiveness::Resisted);
+        assert_eq!(Effectiveness::from_multiplier(100), Effectiveness::Normal);
+        assert_eq!(
+            Effectiveness::from_multiplier(150),
+            Effectiveness::SuperEffective
+        );
+    }
+
+    #[test]
+    fn test_resistances_serialize_as_map() {
+        let resistances = Resistances::default()
+            .with(Element::Fire, 200)
+            .with(Element::Ice, 50);
+
+        let json = serde_json::to_string(&resistances).expect("Should serialize");
+        assert_eq!(json, r#"{"Fire":200,"Ice":50}"#);
+
+        let deserialized: Resistances = serde_json::from_str(&json).expect("Should deserialize");
+        assert_eq!(deserialized, resistances);
+    }
+}
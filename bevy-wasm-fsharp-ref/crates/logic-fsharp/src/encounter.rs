@@ -0,0 +1,621 @@
+//! Party-vs-party encounters.
+//!
+//! An [`Encounter`] pits any number of [`Team::Allies`] against any number of
+//! [`Team::Enemies`]. It tracks:
+//!
+//! - **Turn order**: each round, living combatants act from highest to lowest
+//!   [`Stats::speed`](crate::Stats::speed) with equipment, see [`turn_order`]
+//! - **Targeting**: each ability's [`Targeting`] rule picks living opponents,
+//!   living teammates or the caster itself, see [`Encounter::targets`]
+//! - **Outcome**: the allies win once every enemy is defeated and lose once every
+//!   ally is, see [`Encounter::outcome`]
+//! - **Damage formula**: the [`DamageModel`] every attack uses, see
+//!   [`Encounter::with_formula`]
+//!
+//! Combatants are addressed by [`CombatantId`], their index in
+//! [`Encounter::combatants`].
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type Team =
+//!     | Allies
+//!     | Enemies
+//!
+//! type Combatant =
+//!     { Id: int
+//!       Team: Team
+//!       Character: Character
+//!       Abilities: AbilitySet }
+//!
+//! type EncounterOutcome =
+//!     | Ongoing
+//!     | Victory
+//!     | Defeat
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let mut encounter = Encounter::new(vec![
+//!     Combatant::new(Team::Allies, Character::new_player("Hero"), AbilitySet::player_default()),
+//!     Combatant::new(Team::Enemies, Character::new_monster("Slime"), AbilitySet::monster_turn_based()),
+//!     Combatant::new(Team::Enemies, Character::new_monster("Bat"), AbilitySet::monster_turn_based()),
+//! ]);
+//!
+//! // The hero is fastest, and can attack either monster
+//! let hero = encounter.current().unwrap();
+//! assert_eq!(hero, CombatantId(0));
+//! assert_eq!(
+//!     encounter.targets(hero, Targeting::Enemy),
+//!     vec![CombatantId(1), CombatantId(2)]
+//! );
+//!
+//! encounter.end_turn();
+//! assert_eq!(encounter.current(), Some(CombatantId(1)));
+//! assert_eq!(encounter.outcome(), EncounterOutcome::Ongoing);
+//! ```
+
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::{
+    AbilitySet, Character, Combatant, CombatantId, DamageModel, EncounterOutcome, Inventory,
+    InventoryError, Progression, StatusEffects, Targeting, Team,
+};
+
+impl Team {
+    /// The team this team fights against.
+    pub fn opponent(self) -> Team {
+        match self {
+            Team::Allies => Team::Enemies,
+            Team::Enemies => Team::Allies,
+        }
+    }
+}
+
+impl fmt::Display for CombatantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+impl Combatant {
+    /// A combatant with no status effects. Its id is assigned when the encounter
+    /// is created.
+    pub fn new(team: Team, character: Character, abilities: AbilitySet) -> Self {
+        Self {
+            id: CombatantId(0),
+            team,
+            character,
+            abilities,
+            effects: StatusEffects::default(),
+            progression: None,
+            inventory: Inventory::default(),
+        }
+    }
+
+    /// Sets the combatant's class, level and XP. See [`crate::progression`].
+    pub fn with_progression(mut self, progression: Progression) -> Self {
+        self.progression = Some(progression);
+        self
+    }
+
+    /// Sets the combatant's items. See [`crate::inventory`].
+    pub fn with_inventory(mut self, inventory: Inventory) -> Self {
+        self.inventory = inventory;
+        self
+    }
+
+    /// Whether the combatant can still act and be targeted.
+    pub fn is_alive(&self) -> bool {
+        self.character.hp > 0
+    }
+}
+
+/// Reasons an action is not allowed in an encounter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncounterError {
+    /// No combatant has this id.
+    UnknownCombatant { id: CombatantId },
+
+    /// The target has already been defeated.
+    TargetDefeated { id: CombatantId },
+
+    /// The ability cannot target this combatant's team.
+    WrongTeam { id: CombatantId, expected: Team },
+
+    /// The ability can only target its user.
+    NotCaster { id: CombatantId },
+
+    /// The encounter has already been won or lost.
+    EncounterOver,
+
+    /// The acting combatant is stunned and can only pass.
+    Stunned { id: CombatantId },
+
+    /// The acting combatant has no ability with this name.
+    UnknownAbility { name: String },
+
+    /// The ability is still on cooldown.
+    AbilityNotReady { name: String },
+
+    /// The acting combatant cannot use the item.
+    Inventory(InventoryError),
+}
+
+impl fmt::Display for EncounterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncounterError::UnknownCombatant { id } => {
+                write!(f, "combatant {} is not in the encounter", id)
+            }
+            EncounterError::TargetDefeated { id } => {
+                write!(f, "combatant {} has already been defeated", id)
+            }
+            EncounterError::WrongTeam { id, expected } => {
+                write!(f, "combatant {} is not on the {:?} team", id, expected)
+            }
+            EncounterError::NotCaster { id } => {
+                write!(f, "the ability can only target its user, not {}", id)
+            }
+            EncounterError::EncounterOver => write!(f, "the encounter is over"),
+            EncounterError::Stunned { id } => {
+                write!(f, "combatant {} is stunned and can only pass", id)
+            }
+            EncounterError::UnknownAbility { name } => write!(f, "unknown ability '{}'", name),
+            EncounterError::AbilityNotReady { name } => {
+                write!(f, "ability '{}' is on cooldown", name)
+            }
+            EncounterError::Inventory(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for EncounterError {}
+
+/// The order living combatants act in for one round.
+///
+/// Sorted by descending speed, including equipment (see
+/// [`Combatant::effective_stats`]). Ties keep encounter order, so allies (listed
+/// first) act before enemies of the same speed.
+pub fn turn_order(combatants: &[Combatant]) -> Vec<CombatantId> {
+    let mut living: Vec<&Combatant> = combatants.iter().filter(|c| c.is_alive()).collect();
+    living.sort_by_key(|c| (-c.effective_stats().speed, c.id));
+    living.into_iter().map(|c| c.id).collect()
+}
+
+/// The team an ability aimed by `targeting` affects, for a caster on `team`.
+fn target_team(team: Team, targeting: Targeting) -> Team {
+    if targeting.is_offensive() {
+        team.opponent()
+    } else {
+        team
+    }
+}
+
+/// N allies against M enemies, with rounds ordered by speed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct Encounter {
+    /// Everyone in the encounter, indexed by [`CombatantId`].
+    pub combatants: Vec<Combatant>,
+
+    /// Current round, starting at 1.
+    pub round: u32,
+
+    /// Turn order of the current round.
+    pub order: Vec<CombatantId>,
+
+    /// Index into `order` of the next combatant to act.
+    pub turn: usize,
+
+    /// Formula for the base damage of every hit.
+    #[serde(default)]
+    pub formula: DamageModel,
+}
+
+impl Encounter {
+    /// Starts round 1. Combatant ids are assigned in list order.
+    pub fn new(mut combatants: Vec<Combatant>) -> Self {
+        for (index, combatant) in combatants.iter_mut().enumerate() {
+            combatant.id = CombatantId(index);
+        }
+        let order = turn_order(&combatants);
+
+        Self {
+            combatants,
+            round: 1,
+            order,
+            turn: 0,
+            formula: DamageModel::default(),
+        }
+    }
+
+    /// Resolves every attack with `formula` instead of the default
+    /// [`DamageModel::FlatDefense`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_wasm_fsharp_ref_logic::*;
+    ///
+    /// let encounter = Encounter::new(vec![
+    ///     Combatant::new(Team::Allies, Character::new_player("Hero"), AbilitySet::monster_turn_based()),
+    ///     Combatant::new(Team::Enemies, Character::new_monster("Slime"), AbilitySet::monster_turn_based()),
+    /// ])
+    /// .with_formula(DamageModel::PercentArmor);
+    ///
+    /// let action = Action::UseAbility {
+    ///     ability: "Basic Attack".to_string(),
+    ///     target: CombatantId(1),
+    /// };
+    /// let (_, events) = reduce(&encounter, &action).unwrap();
+    ///
+    /// // (10 + 5) * 10 / (10 + 1), instead of 10 + 5 - 1
+    /// assert_eq!(events[0].damage, 13);
+    /// ```
+    pub fn with_formula(mut self, formula: DamageModel) -> Self {
+        self.formula = formula;
+        self
+    }
+
+    /// Looks up a combatant.
+    pub fn combatant(&self, id: CombatantId) -> Option<&Combatant> {
+        self.combatants.get(id.0)
+    }
+
+    /// Looks up a combatant for modification.
+    pub fn combatant_mut(&mut self, id: CombatantId) -> Option<&mut Combatant> {
+        self.combatants.get_mut(id.0)
+    }
+
+    /// Living members of `team`, in encounter order.
+    pub fn living(&self, team: Team) -> impl Iterator<Item = &Combatant> {
+        self.combatants
+            .iter()
+            .filter(move |c| c.team == team && c.is_alive())
+    }
+
+    /// The living combatant whose turn it is, or `None` once the encounter is over.
+    pub fn current(&self) -> Option<CombatantId> {
+        if self.outcome() != EncounterOutcome::Ongoing {
+            return None;
+        }
+        self.next_living_from(self.turn)
+            .map(|index| self.order[index])
+    }
+
+    /// Ends the current combatant's turn. After the last combatant of a round,
+    /// starts the next round with a freshly computed turn order.
+    pub fn end_turn(&mut self) {
+        self.turn = match self.next_living_from(self.turn) {
+            Some(index) => index + 1,
+            None => self.order.len(),
+        };
+
+        if self.next_living_from(self.turn).is_none() {
+            self.round += 1;
+            self.order = turn_order(&self.combatants);
+            self.turn = 0;
+        }
+    }
+
+    fn next_living_from(&self, start: usize) -> Option<usize> {
+        (start..self.order.len()).find(|&index| {
+            self.combatant(self.order[index])
+                .is_some_and(Combatant::is_alive)
+        })
+    }
+
+    /// Valid targets for `actor` using an ability aimed by `targeting`: living
+    /// opponents for [`Targeting::Enemy`] and [`Targeting::AllEnemies`], living
+    /// teammates (including the actor) for [`Targeting::Ally`] and
+    /// [`Targeting::AllAllies`], and the actor alone for [`Targeting::Caster`].
+    pub fn targets(&self, actor: CombatantId, targeting: Targeting) -> Vec<CombatantId> {
+        match self.combatant(actor) {
+            Some(combatant) if targeting == Targeting::Caster => Some(combatant.id)
+                .filter(|_| combatant.is_alive())
+                .into_iter()
+                .collect(),
+            Some(combatant) => {
+                let team = target_team(combatant.team, targeting);
+                self.living(team).map(|c| c.id).collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Checks that `target` is a valid target for `actor` using an ability aimed
+    /// by `targeting`. For area abilities, `target` may be any member of the
+    /// targeted team.
+    pub fn check_target(
+        &self,
+        actor: CombatantId,
+        targeting: Targeting,
+        target: CombatantId,
+    ) -> Result<(), EncounterError> {
+        let team = self
+            .combatant(actor)
+            .ok_or(EncounterError::UnknownCombatant { id: actor })?
+            .team;
+        let combatant = self
+            .combatant(target)
+            .ok_or(EncounterError::UnknownCombatant { id: target })?;
+        let expected = target_team(team, targeting);
+
+        if targeting == Targeting::Caster && target != actor {
+            Err(EncounterError::NotCaster { id: target })
+        } else if combatant.team != expected {
+            Err(EncounterError::WrongTeam {
+                id: target,
+                expected,
+            })
+        } else if !combatant.is_alive() {
+            Err(EncounterError::TargetDefeated { id: target })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Everyone an ability aimed by `targeting` at `target` affects: the living
+    /// members of `target`'s team for area abilities, otherwise `target` alone.
+    pub fn affected(&self, targeting: Targeting, target: CombatantId) -> Vec<CombatantId> {
+        match self.combatant(target) {
+            Some(combatant) if targeting.is_area() => {
+                self.living(combatant.team).map(|c| c.id).collect()
+            }
+            Some(_) => vec![target],
+            None => Vec::new(),
+        }
+    }
+
+    /// The living opponent of `actor` with the least HP. Ties go to the lowest id.
+    pub fn weakest_target(&self, actor: CombatantId) -> Option<CombatantId> {
+        let team = self.combatant(actor)?.team.opponent();
+        self.living(team)
+            .min_by_key(|c| (c.character.hp, c.id))
+            .map(|c| c.id)
+    }
+
+    /// Whether either team has been wiped out.
+    pub fn outcome(&self) -> EncounterOutcome {
+        if self.living(Team::Allies).next().is_none() {
+            EncounterOutcome::Defeat
+        } else if self.living(Team::Enemies).next().is_none() {
+            EncounterOutcome::Victory
+        } else {
+            EncounterOutcome::Ongoing
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_speed(mut character: Character, speed: i32) -> Character {
+        character.stats.speed = speed;
+        character
+    }
+
+    fn party() -> Encounter {
+        Encounter::new(vec![
+            Combatant::new(
+                Team::Allies,
+                with_speed(Character::new_player("Hero"), 5),
+                AbilitySet::player_default(),
+            ),
+            Combatant::new(
+                Team::Allies,
+                with_speed(Character::new_player("Cleric"), 2),
+                AbilitySet::player_default(),
+            ),
+            Combatant::new(
+                Team::Enemies,
+                with_speed(Character::new_monster("Bat"), 9),
+                AbilitySet::monster_turn_based(),
+            ),
+            Combatant::new(
+                Team::Enemies,
+                with_speed(Character::new_monster("Slime"), 2),
+                AbilitySet::monster_turn_based(),
+            ),
+        ])
+    }
+
+    fn kill(encounter: &mut Encounter, id: usize) {
+        encounter.combatants[id].character.hp = 0;
+    }
+
+    #[test]
+    fn test_ids_follow_list_order() {
+        let encounter = party();
+
+        for (index, combatant) in encounter.combatants.iter().enumerate() {
+            assert_eq!(combatant.id, CombatantId(index));
+        }
+    }
+
+    #[test]
+    fn test_turn_order_by_speed_then_id() {
+        let encounter = party();
+
+        assert_eq!(
+            encounter.order,
+            vec![
+                CombatantId(2),
+                CombatantId(0),
+                CombatantId(1),
+                CombatantId(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_end_turn_advances_and_starts_new_round() {
+        let mut encounter = party();
+        let mut acted = Vec::new();
+
+        for _ in 0..4 {
+            acted.push(encounter.current().unwrap());
+            encounter.end_turn();
+        }
+
+        assert_eq!(acted, encounter.order);
+        assert_eq!(encounter.round, 2);
+        assert_eq!(encounter.current(), Some(CombatantId(2)));
+    }
+
+    #[test]
+    fn test_defeated_combatants_are_skipped() {
+        let mut encounter = party();
+        encounter.end_turn(); // Bat acts
+        kill(&mut encounter, 1); // Cleric falls before acting
+
+        encounter.end_turn(); // Hero acts
+
+        assert_eq!(encounter.current(), Some(CombatantId(3)));
+    }
+
+    #[test]
+    fn test_new_round_recomputes_order() {
+        let mut encounter = party();
+        encounter.combatants[3].character.stats.speed = 20;
+
+        for _ in 0..4 {
+            encounter.end_turn();
+        }
+
+        assert_eq!(encounter.order[0], CombatantId(3));
+    }
+
+    #[test]
+    fn test_attack_targets_living_opponents() {
+        let mut encounter = party();
+        kill(&mut encounter, 2);
+
+        assert_eq!(
+            encounter.targets(CombatantId(0), Targeting::Enemy),
+            vec![CombatantId(3)]
+        );
+        assert_eq!(
+            encounter.targets(CombatantId(3), Targeting::AllEnemies),
+            vec![CombatantId(0), CombatantId(1)]
+        );
+    }
+
+    #[test]
+    fn test_heal_targets_own_team() {
+        let encounter = party();
+
+        assert_eq!(
+            encounter.targets(CombatantId(1), Targeting::Ally),
+            vec![CombatantId(0), CombatantId(1)]
+        );
+        assert_eq!(
+            encounter.targets(CombatantId(1), Targeting::Caster),
+            vec![CombatantId(1)]
+        );
+    }
+
+    #[test]
+    fn test_area_abilities_affect_the_whole_team() {
+        let mut encounter = party();
+        kill(&mut encounter, 2);
+
+        assert_eq!(
+            encounter.affected(Targeting::AllEnemies, CombatantId(3)),
+            vec![CombatantId(3)]
+        );
+        assert_eq!(
+            encounter.affected(Targeting::AllAllies, CombatantId(1)),
+            vec![CombatantId(0), CombatantId(1)]
+        );
+        assert_eq!(
+            encounter.affected(Targeting::Ally, CombatantId(1)),
+            vec![CombatantId(1)]
+        );
+    }
+
+    #[test]
+    fn test_check_target_errors() {
+        let mut encounter = party();
+        kill(&mut encounter, 3);
+        let hero = CombatantId(0);
+
+        assert_eq!(
+            encounter.check_target(hero, Targeting::Enemy, CombatantId(2)),
+            Ok(())
+        );
+        assert_eq!(
+            encounter.check_target(hero, Targeting::Enemy, CombatantId(1)),
+            Err(EncounterError::WrongTeam {
+                id: CombatantId(1),
+                expected: Team::Enemies
+            })
+        );
+        assert_eq!(
+            encounter.check_target(hero, Targeting::Enemy, CombatantId(3)),
+            Err(EncounterError::TargetDefeated { id: CombatantId(3) })
+        );
+        assert_eq!(
+            encounter.check_target(hero, Targeting::Enemy, CombatantId(9)),
+            Err(EncounterError::UnknownCombatant { id: CombatantId(9) })
+        );
+        assert_eq!(
+            encounter.check_target(hero, Targeting::Caster, CombatantId(1)),
+            Err(EncounterError::NotCaster { id: CombatantId(1) })
+        );
+        assert_eq!(
+            encounter.check_target(hero, Targeting::Caster, hero),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_weakest_target() {
+        let mut encounter = party();
+        encounter.combatants[3].character.hp = 5;
+
+        assert_eq!(
+            encounter.weakest_target(CombatantId(0)),
+            Some(CombatantId(3))
+        );
+        assert_eq!(
+            encounter.weakest_target(CombatantId(2)),
+            Some(CombatantId(0))
+        );
+    }
+
+    #[test]
+    fn test_outcome() {
+        let mut encounter = party();
+        assert_eq!(encounter.outcome(), EncounterOutcome::Ongoing);
+
+        kill(&mut encounter, 2);
+        kill(&mut encounter, 3);
+        assert_eq!(encounter.outcome(), EncounterOutcome::Victory);
+        assert_eq!(encounter.current(), None);
+
+        let mut encounter = party();
+        kill(&mut encounter, 0);
+        kill(&mut encounter, 1);
+        assert_eq!(encounter.outcome(), EncounterOutcome::Defeat);
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let encounter = party();
+
+        let json = serde_json::to_string(&encounter).unwrap();
+        let restored: Encounter = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.order, encounter.order);
+        assert_eq!(restored.combatants.len(), 4);
+    }
+}
@@ -0,0 +1,225 @@
+//! HP accounting for combat events, and the versioned event format.
+//!
+//! Every [`CombatEvent`] the logic crate produces is settled against the
+//! defender's HP before the ability and their max HP:
+//!
+//! - `defender_hp_after` is clamped between 0 and max HP
+//! - `effective_amount` is the HP the defender actually lost (negative if restored)
+//! - `overkill` is the damage dealt past 0 HP, `overheal` the healing past max HP
+//! - `killing_blow` is set when the event took the defender from above 0 HP to 0
+//!
+//! `damage` stays the net amount dealt, so `damage == effective_amount + overkill -
+//! overheal` holds for every event.
+//!
+//! # Versions
+//!
+//! Serialized events carry a [`version`](CombatEvent::version).
+//! [`CombatEvent::from_json`] reads every version and upgrades older events to
+//! [`COMBAT_EVENT_VERSION`]:
+//!
+//! | Version | Format |
+//! |---------|--------|
+//! | 0 | No `version` field. HP could drop below 0; no accounting fields |
+//! | 1 | Clamped HP with `effective_amount`, `overkill`, `overheal` and `killing_blow` |
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! let settle (hpBefore: int) (maxHp: int) (event: CombatEvent) : CombatEvent =
+//!     let floor = min hpBefore 0
+//!     let ceiling = max maxHp hpBefore
+//!     let rawAfter = hpBefore - event.Damage
+//!     let hpAfter = rawAfter |> max floor |> min ceiling
+//!     { event with
+//!         DefenderHpAfter = hpAfter
+//!         EffectiveAmount = hpBefore - hpAfter
+//!         Overkill = max 0 (floor - rawAfter)
+//!         Overheal = max 0 (rawAfter - ceiling)
+//!         KillingBlow = hpBefore > 0 && hpAfter <= 0 }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let player = Character::new_player("Hero");
+//! let mut monster = Character::new_monster("Slime");
+//! monster.hp = 5;
+//!
+//! // 14 damage against 5 HP
+//! let event = compute_attack(&player, &monster, &Ability::basic_attack());
+//! assert_eq!(event.damage, 14);
+//! assert_eq!(event.defender_hp_after, 0);
+//! assert_eq!(event.effective_amount, 5);
+//! assert_eq!(event.overkill, 9);
+//! assert!(event.killing_blow);
+//!
+//! // Logs written before events were versioned are upgraded on load
+//! let old = r#"{"attacker_name": "Hero", "defender_name": "Slime", "damage": 14,
+//!               "defender_hp_after": -9, "ability_used": "Basic Attack"}"#;
+//! let upgraded = CombatEvent::from_json(old).unwrap();
+//! assert_eq!(upgraded.version, COMBAT_EVENT_VERSION);
+//! assert_eq!(upgraded.defender_hp_after, 0);
+//! assert_eq!(upgraded.overkill, 9);
+//! ```
+
+use crate::{CombatEvent, EffectOutcome};
+
+/// The version of the [`CombatEvent`] format written by this crate.
+pub const COMBAT_EVENT_VERSION: u32 = 1;
+
+impl CombatEvent {
+    /// Reads a serialized event of any version, upgrading it to
+    /// [`COMBAT_EVENT_VERSION`].
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<Self>(json).map(Self::upgrade)
+    }
+
+    /// Upgrades an event read from an older log to [`COMBAT_EVENT_VERSION`].
+    ///
+    /// Version 0 events get clamped HP and their accounting fields, plus one
+    /// outcome if they had none. Their max HP was not recorded, so heals that
+    /// went past it cannot be told apart and report no overheal.
+    pub fn upgrade(mut self) -> Self {
+        if self.version >= COMBAT_EVENT_VERSION {
+            return self;
+        }
+
+        if self.outcomes.is_empty() {
+            let outcome = if self.missed {
+                Some(EffectOutcome::Evaded)
+            } else if self.damage > 0 {
+                Some(EffectOutcome::Damaged {
+                    amount: self.damage,
+                })
+            } else if self.damage < 0 {
+                Some(EffectOutcome::Healed {
+                    amount: -self.damage,
+                })
+            } else {
+                None
+            };
+            self.outcomes.extend(outcome);
+        }
+
+        // Unknown max HP: take the logged HP as the ceiling, so heals stay as logged
+        let hp_before = self.defender_hp_after + self.damage;
+        self.settle(hp_before, self.defender_hp_after);
+        self.version = COMBAT_EVENT_VERSION;
+        self
+    }
+
+    /// Clamps `defender_hp_after` and fills in the accounting fields from `damage`.
+    ///
+    /// HP never ends up outside `0..=max_hp`, except that a defender who was
+    /// already outside that range is not pushed further out.
+    pub(crate) fn settle(&mut self, hp_before: i32, max_hp: i32) {
+        let floor = hp_before.min(0);
+        let ceiling = max_hp.max(hp_before);
+        let raw_after = hp_before - self.damage;
+
+        self.defender_hp_after = raw_after.clamp(floor, ceiling);
+        self.effective_amount = hp_before - self.defender_hp_after;
+        self.overkill = (floor - raw_after).max(0);
+        self.overheal = (raw_after - ceiling).max(0);
+        self.killing_blow = hp_before > 0 && self.defender_hp_after <= 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_attack, Ability, Character};
+
+    fn assert_balanced(event: &CombatEvent) {
+        assert_eq!(
+            event.damage,
+            event.effective_amount + event.overkill - event.overheal,
+            "{:?}",
+            event
+        );
+    }
+
+    #[test]
+    fn test_overheal_is_reported() {
+        let mut hero = Character::new_player("Hero");
+        hero.hp = 27;
+
+        let event = compute_attack(&hero, &hero, &Ability::heal());
+
+        assert_eq!(event.damage, -8);
+        assert_eq!(event.defender_hp_after, 30);
+        assert_eq!(event.effective_amount, -3);
+        assert_eq!(event.overheal, 5);
+        assert!(!event.killing_blow);
+        assert_balanced(&event);
+    }
+
+    #[test]
+    fn test_hitting_a_defeated_character_is_not_a_killing_blow() {
+        let hero = Character::new_player("Hero");
+        let mut slime = Character::new_monster("Slime");
+        slime.hp = 0;
+
+        let event = compute_attack(&hero, &slime, &Ability::basic_attack());
+
+        assert_eq!(event.defender_hp_after, 0);
+        assert_eq!(event.effective_amount, 0);
+        assert_eq!(event.overkill, 14);
+        assert!(!event.killing_blow);
+        assert_balanced(&event);
+    }
+
+    #[test]
+    fn test_exact_kill_has_no_overkill() {
+        let hero = Character::new_player("Hero");
+        let mut slime = Character::new_monster("Slime");
+        slime.hp = 14;
+
+        let event = compute_attack(&hero, &slime, &Ability::basic_attack());
+
+        assert_eq!(event.defender_hp_after, 0);
+        assert_eq!(event.overkill, 0);
+        assert!(event.killing_blow);
+    }
+
+    #[test]
+    fn test_current_events_round_trip_unchanged() {
+        let hero = Character::new_player("Hero");
+        let slime = Character::new_monster("Slime");
+        let event = compute_attack(&hero, &slime, &Ability::powerful_attack());
+
+        let json = serde_json::to_string(&event).unwrap();
+
+        assert_eq!(event.version, COMBAT_EVENT_VERSION);
+        assert_eq!(CombatEvent::from_json(&json).unwrap(), event);
+    }
+
+    #[test]
+    fn test_legacy_heal_is_upgraded() {
+        let json = r#"{"attacker_name": "Slime", "defender_name": "Slime", "damage": -8,
+                       "defender_hp_after": 18, "ability_used": "Heal"}"#;
+
+        let event = CombatEvent::from_json(json).unwrap();
+
+        assert_eq!(event.defender_hp_after, 18);
+        assert_eq!(event.effective_amount, -8);
+        assert_eq!(event.overheal, 0);
+        assert_eq!(event.outcomes, vec![EffectOutcome::Healed { amount: 8 }]);
+        assert_balanced(&event);
+    }
+
+    #[test]
+    fn test_legacy_miss_is_upgraded() {
+        let json = r#"{"attacker_name": "Hero", "defender_name": "Slime", "damage": 0,
+                       "defender_hp_after": 20, "ability_used": "Basic Attack",
+                       "missed": true}"#;
+
+        let event = CombatEvent::from_json(json).unwrap();
+
+        assert_eq!(event.outcomes, vec![EffectOutcome::Evaded]);
+        assert!(!event.killing_blow);
+        assert_balanced(&event);
+    }
+}
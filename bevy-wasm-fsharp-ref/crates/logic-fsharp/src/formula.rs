@@ -0,0 +1,280 @@
+//! Pluggable damage formulas.
+//!
+//! A [`DamageFormula`] turns the attacker's attack, an ability's power and the
+//! defender's defense into the base damage of one hit. Resistances, variance and
+//! critical hits are applied on top by [`compute_attack_with_formula`] and
+//! [`compute_attack_with_formula_and_rng`], and every hit still deals at least 1
+//! damage unless the defender is immune.
+//!
+//! Built-in formulas, for `raw = attacker.attack + power`:
+//!
+//! | Formula | Base damage | Behaviour |
+//! |---------|-------------|-----------|
+//! | [`FlatDefenseFormula`] | `raw - defense` | The default; high defense walls off weak attacks |
+//! | [`PercentArmorFormula`] | `raw * scale / (scale + defense)` | Defense never blocks everything |
+//! | [`AttackRatioFormula`] | `raw^2 / (raw + defense)` | Strong attacks shrug off defense |
+//!
+//! Formulas see both characters, so custom ones can scale with any stat.
+//! [`DamageModel`] names each built-in formula so an [`Encounter`](crate::Encounter)
+//! can store the one it uses, see [`Encounter::with_formula`](crate::Encounter::with_formula).
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! let baseDamage (model: DamageModel) (attacker: Character) (defender: Character) (power: int) : int =
+//!     let raw = attacker.Stats.Attack + power
+//!     let defense = max 0 defender.Stats.Defense
+//!     let dmg =
+//!         match model with
+//!         | FlatDefense -> raw - defender.Stats.Defense
+//!         | PercentArmor -> raw * armorScale / (armorScale + defense)
+//!         | AttackRatio -> if raw <= 0 then 0 else raw * raw / (raw + defense)
+//!     max 1 dmg
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let hero = Character::new_player("Hero");
+//! let mut golem = Character::new_monster("Golem");
+//! golem.stats.defense = 10;
+//!
+//! // 10 attack + 5 power against 10 defense
+//! let basic = Ability::basic_attack();
+//! let flat = compute_attack_with_formula(&hero, &golem, &basic, &DamageModel::FlatDefense);
+//! let percent = compute_attack_with_formula(&hero, &golem, &basic, &DamageModel::PercentArmor);
+//! let ratio = compute_attack_with_formula(&hero, &golem, &basic, &DamageModel::AttackRatio);
+//!
+//! assert_eq!(flat.damage, 5); // 15 - 10
+//! assert_eq!(percent.damage, 7); // 15 * 10 / 20
+//! assert_eq!(ratio.damage, 9); // 225 / 25
+//! ```
+
+use crate::{Character, DamageModel};
+
+/// Computes the base damage of one hit.
+pub trait DamageFormula {
+    /// Display name of the formula.
+    fn name(&self) -> &str;
+
+    /// Damage `attacker` deals to `defender` with a hit of `power`, before
+    /// resistances, variance and critical hits.
+    ///
+    /// Results below 1 are raised to 1 by the caller.
+    fn base_damage(&self, attacker: &Character, defender: &Character, power: i32) -> i32;
+}
+
+/// `max(1, attack + power - defense)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlatDefenseFormula;
+
+impl DamageFormula for FlatDefenseFormula {
+    fn name(&self) -> &str {
+        "Flat Defense"
+    }
+
+    fn base_damage(&self, attacker: &Character, defender: &Character, power: i32) -> i32 {
+        attacker.stats.attack + power - defender.stats.defense
+    }
+}
+
+/// `(attack + power) * armor_scale / (armor_scale + defense)`.
+///
+/// Defense equal to `armor_scale` halves damage, three times `armor_scale`
+/// quarters it. Negative defense counts as 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentArmorFormula {
+    /// Defense that blocks half of the damage. Must be positive.
+    pub armor_scale: i32,
+}
+
+impl PercentArmorFormula {
+    /// The `armor_scale` of [`DamageModel::PercentArmor`].
+    pub const DEFAULT_ARMOR_SCALE: i32 = 10;
+}
+
+impl Default for PercentArmorFormula {
+    fn default() -> Self {
+        Self {
+            armor_scale: Self::DEFAULT_ARMOR_SCALE,
+        }
+    }
+}
+
+impl DamageFormula for PercentArmorFormula {
+    fn name(&self) -> &str {
+        "Percent Armor"
+    }
+
+    fn base_damage(&self, attacker: &Character, defender: &Character, power: i32) -> i32 {
+        let raw = i64::from(attacker.stats.attack + power);
+        let scale = i64::from(self.armor_scale.max(1));
+        let defense = i64::from(defender.stats.defense.max(0));
+        (raw * scale / (scale + defense)) as i32
+    }
+}
+
+/// `(attack + power)^2 / (attack + power + defense)`.
+///
+/// Damage approaches the raw attack as it grows past the defender's defense.
+/// Negative defense counts as 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttackRatioFormula;
+
+impl DamageFormula for AttackRatioFormula {
+    fn name(&self) -> &str {
+        "Attack Ratio"
+    }
+
+    fn base_damage(&self, attacker: &Character, defender: &Character, power: i32) -> i32 {
+        let raw = i64::from(attacker.stats.attack + power);
+        if raw <= 0 {
+            return 0;
+        }
+        let defense = i64::from(defender.stats.defense.max(0));
+        (raw * raw / (raw + defense)) as i32
+    }
+}
+
+const PERCENT_ARMOR: PercentArmorFormula = PercentArmorFormula {
+    armor_scale: PercentArmorFormula::DEFAULT_ARMOR_SCALE,
+};
+
+impl DamageModel {
+    /// All built-in formulas.
+    pub const ALL: [DamageModel; 3] = [
+        DamageModel::FlatDefense,
+        DamageModel::PercentArmor,
+        DamageModel::AttackRatio,
+    ];
+
+    /// The formula implementing this model.
+    pub fn formula(&self) -> &'static dyn DamageFormula {
+        match self {
+            DamageModel::FlatDefense => &FlatDefenseFormula,
+            DamageModel::PercentArmor => &PERCENT_ARMOR,
+            DamageModel::AttackRatio => &AttackRatioFormula,
+        }
+    }
+}
+
+impl DamageFormula for DamageModel {
+    fn name(&self) -> &str {
+        self.formula().name()
+    }
+
+    fn base_damage(&self, attacker: &Character, defender: &Character, power: i32) -> i32 {
+        self.formula().base_damage(attacker, defender, power)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::{compute_attack, compute_attack_with_formula, Ability, Stats};
+
+    fn fighter(attack: i32, defense: i32) -> Character {
+        Character {
+            name: "Fighter".to_string(),
+            hp: 100,
+            stats: Stats {
+                hp: 100,
+                attack,
+                defense,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_default_model_matches_compute_attack() {
+        let hero = Character::new_player("Hero");
+        let slime = Character::new_monster("Slime");
+
+        for ability in [Ability::basic_attack(), Ability::powerful_attack()] {
+            assert_eq!(
+                compute_attack_with_formula(&hero, &slime, &ability, &DamageModel::default()),
+                compute_attack(&hero, &slime, &ability)
+            );
+        }
+    }
+
+    #[test]
+    fn test_percent_armor_never_blocks_everything() {
+        let attacker = fighter(10, 0);
+        let wall = fighter(0, 1000);
+
+        // Flat defense floors at the minimum, percent armor still scales with power
+        let weak = PercentArmorFormula::default().base_damage(&attacker, &wall, 100);
+        let strong = PercentArmorFormula::default().base_damage(&attacker, &wall, 1000);
+        assert_eq!(FlatDefenseFormula.base_damage(&attacker, &wall, 100), -890);
+        assert_eq!(weak, 1); // 110 * 10 / 1010
+        assert_eq!(strong, 10); // 1010 * 10 / 1010
+    }
+
+    #[test]
+    fn test_percent_armor_scale_halves_damage() {
+        let formula = PercentArmorFormula { armor_scale: 20 };
+
+        assert_eq!(formula.base_damage(&fighter(40, 0), &fighter(0, 20), 0), 20);
+        assert_eq!(formula.base_damage(&fighter(40, 0), &fighter(0, 0), 0), 40);
+    }
+
+    #[test]
+    fn test_attack_ratio_favours_strong_attacks() {
+        let defender = fighter(0, 10);
+
+        assert_eq!(
+            AttackRatioFormula.base_damage(&fighter(10, 0), &defender, 0),
+            5
+        );
+        assert_eq!(
+            AttackRatioFormula.base_damage(&fighter(90, 0), &defender, 0),
+            81
+        );
+        assert_eq!(
+            AttackRatioFormula.base_damage(&fighter(0, 0), &defender, 0),
+            0
+        );
+    }
+
+    #[test]
+    fn test_negative_defense_counts_as_zero() {
+        let cursed = fighter(0, -5);
+
+        assert_eq!(
+            PercentArmorFormula::default().base_damage(&fighter(10, 0), &cursed, 0),
+            10
+        );
+        assert_eq!(
+            AttackRatioFormula.base_damage(&fighter(10, 0), &cursed, 0),
+            10
+        );
+    }
+
+    #[test]
+    fn test_models_dispatch_to_their_formula() {
+        let names: Vec<&str> = DamageModel::ALL.iter().map(|m| m.name()).collect();
+
+        assert_eq!(names, ["Flat Defense", "Percent Armor", "Attack Ratio"]);
+        assert_eq!(
+            serde_json::to_string(&DamageModel::PercentArmor).unwrap(),
+            "\"PercentArmor\""
+        );
+    }
+
+    #[test]
+    fn test_every_hit_deals_at_least_one_damage() {
+        let weakling = fighter(0, 0);
+        let wall = fighter(0, 1000);
+        let ability = Ability::damage("Poke", 0);
+
+        for model in DamageModel::ALL {
+            let event = compute_attack_with_formula(&weakling, &wall, &ability, &model);
+            assert_eq!(event.damage, 1, "{:?}", model);
+        }
+    }
+}
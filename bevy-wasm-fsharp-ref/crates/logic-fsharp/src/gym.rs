@@ -0,0 +1,722 @@
+//! A Gym-style reinforcement learning environment over the combat rules.
+//!
+//! [`CombatEnv`] is a one-on-one fight between an agent and a monster driven by an
+//! [`AiPersonality`], played through [`reduce_with_rng`] exactly like the game.
+//! It follows the usual `reset(seed) / step(action)` loop:
+//!
+//! - **Observations** are fixed-size `f32` vectors ([`Observation`]) describing both
+//!   combatants and the agent's ability slots. See [`CombatEnv::observation`] for
+//!   the layout
+//! - **Actions** are indices into the agent's [`AbilitySet`], at most
+//!   [`MAX_ACTIONS`] of them. Offensive abilities target the monster, the rest
+//!   the agent itself. [`CombatEnv::action_mask`] lists the ready ones; choosing
+//!   a missing or unready slot passes the turn and costs
+//!   [`INVALID_ACTION_REWARD`]
+//! - **Rewards** are the share of the monster's max HP taken minus the share of
+//!   the agent's max HP lost during the step, plus [`WIN_REWARD`] or
+//!   [`LOSS_REWARD`] when the fight ends
+//! - An episode is **done** when either side is defeated, or after
+//!   [`EnvConfig::max_steps`] agent actions
+//!
+//! Monster turns, and agent turns lost to stuns, are played inside `reset` and
+//! `step`, so every observation is taken at an agent decision.
+//!
+//! [`BatchEnv`] steps many environments at once and resets finished ones, for
+//! training loops that want a batch of observations per call. Nothing here
+//! touches a Bevy `App`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let mut env = CombatEnv::new(EnvConfig::default()).unwrap();
+//! let observation = env.reset(42);
+//! assert_eq!(observation.len(), OBSERVATION_SIZE);
+//!
+//! // Always use the first ready ability
+//! let mut total = 0.0;
+//! loop {
+//!     let action = env.action_mask().iter().position(|&ready| ready).unwrap();
+//!     let step = env.step(action);
+//!     total += step.reward;
+//!     if step.done {
+//!         break;
+//!     }
+//! }
+//! assert_ne!(env.outcome(), EncounterOutcome::Ongoing);
+//! ```
+
+use core::fmt;
+
+use crate::prelude::*;
+use crate::{
+    choose_ai_action, reduce_with_rng, AbilitySet, Action, AiPersonality, Character, Combatant,
+    CombatantId, Cooldown, DamageModel, Encounter, EncounterOutcome, SeededRng, StatusKind, Team,
+};
+
+/// Largest number of ability slots the agent can have.
+pub const MAX_ACTIONS: usize = 8;
+
+/// Features describing one combatant in an [`Observation`].
+pub const SIDE_FEATURES: usize = 9;
+
+/// Features describing one ability slot in an [`Observation`].
+pub const SLOT_FEATURES: usize = 3;
+
+/// Length of an [`Observation`]: both combatants, the agent's slots and the
+/// share of the step limit used.
+pub const OBSERVATION_SIZE: usize = 2 * SIDE_FEATURES + MAX_ACTIONS * SLOT_FEATURES + 1;
+
+/// Reward for defeating the monster.
+pub const WIN_REWARD: f32 = 1.0;
+
+/// Reward for being defeated.
+pub const LOSS_REWARD: f32 = -1.0;
+
+/// Reward for choosing a missing or unready ability slot.
+pub const INVALID_ACTION_REWARD: f32 = -0.1;
+
+/// A fixed-size numeric view of the fight from the agent's side.
+pub type Observation = [f32; OBSERVATION_SIZE];
+
+/// The agent's combatant in the environment's encounter.
+const AGENT: CombatantId = CombatantId(0);
+
+/// The monster's combatant in the environment's encounter.
+const MONSTER: CombatantId = CombatantId(1);
+
+/// Errors produced when building an environment.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvError {
+    /// The agent has no abilities to choose from.
+    NoAbilities,
+
+    /// The agent has more ability slots than [`MAX_ACTIONS`].
+    TooManyAbilities { count: usize },
+
+    /// A combatant starts the fight with no HP.
+    Defeated { name: String },
+
+    /// An ability uses a real-time cooldown, which never ticks in turn-based play.
+    RealTimeCooldown { ability: String },
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvError::NoAbilities => write!(f, "the agent has no abilities"),
+            EnvError::TooManyAbilities { count } => write!(
+                f,
+                "the agent has {} abilities, more than the {} actions supported",
+                count, MAX_ACTIONS
+            ),
+            EnvError::Defeated { name } => write!(f, "{} starts with no HP", name),
+            EnvError::RealTimeCooldown { ability } => write!(
+                f,
+                "ability \"{}\" has a real-time cooldown; use turn cooldowns",
+                ability
+            ),
+        }
+    }
+}
+
+impl core::error::Error for EnvError {}
+
+/// The fight a [`CombatEnv`] plays.
+#[derive(Debug, Clone)]
+pub struct EnvConfig {
+    /// The character the agent controls.
+    pub agent: Character,
+
+    /// The agent's ability slots, which are its actions.
+    pub agent_abilities: AbilitySet,
+
+    /// The opposing monster.
+    pub monster: Character,
+
+    /// The monster's ability slots.
+    pub monster_abilities: AbilitySet,
+
+    /// The policy choosing the monster's actions.
+    pub monster_policy: AiPersonality,
+
+    /// Formula for the base damage of every hit.
+    pub formula: DamageModel,
+
+    /// Agent actions after which an episode ends undecided.
+    pub max_steps: u32,
+}
+
+impl Default for EnvConfig {
+    /// A Hero against a Smart Slime, both with the turn-based monster abilities.
+    fn default() -> Self {
+        Self::new(
+            Character::new_player("Hero"),
+            AbilitySet::monster_turn_based(),
+            Character::new_monster("Slime"),
+            AbilitySet::monster_turn_based(),
+        )
+    }
+}
+
+impl EnvConfig {
+    /// A fight between `agent` and `monster`, with a Smart monster, the default
+    /// damage formula and a limit of 100 steps.
+    pub fn new(
+        agent: Character,
+        agent_abilities: AbilitySet,
+        monster: Character,
+        monster_abilities: AbilitySet,
+    ) -> Self {
+        Self {
+            agent,
+            agent_abilities,
+            monster,
+            monster_abilities,
+            monster_policy: AiPersonality::Smart,
+            formula: DamageModel::default(),
+            max_steps: 100,
+        }
+    }
+
+    /// Lets `policy` choose the monster's actions.
+    pub fn with_policy(mut self, policy: AiPersonality) -> Self {
+        self.monster_policy = policy;
+        self
+    }
+
+    /// Resolves every attack with `formula`.
+    pub fn with_formula(mut self, formula: DamageModel) -> Self {
+        self.formula = formula;
+        self
+    }
+
+    /// Ends episodes undecided after `max_steps` agent actions.
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Checks that the fight can be played as an environment.
+    pub fn validate(&self) -> Result<(), EnvError> {
+        let slots = &self.agent_abilities.abilities;
+        if slots.is_empty() {
+            return Err(EnvError::NoAbilities);
+        }
+        if slots.len() > MAX_ACTIONS {
+            return Err(EnvError::TooManyAbilities { count: slots.len() });
+        }
+        for character in [&self.agent, &self.monster] {
+            if character.hp <= 0 {
+                return Err(EnvError::Defeated {
+                    name: character.name.clone(),
+                });
+            }
+        }
+        let mut all_slots = slots.iter().chain(&self.monster_abilities.abilities);
+        if let Some(slot) = all_slots.find(|slot| matches!(slot.cooldown, Cooldown::Seconds { .. }))
+        {
+            return Err(EnvError::RealTimeCooldown {
+                ability: slot.ability.name.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The encounter at the start of an episode.
+    fn encounter(&self) -> Encounter {
+        Encounter::new(vec![
+            Combatant::new(
+                Team::Allies,
+                self.agent.clone(),
+                self.agent_abilities.clone(),
+            ),
+            Combatant::new(
+                Team::Enemies,
+                self.monster.clone(),
+                self.monster_abilities.clone(),
+            ),
+        ])
+        .with_formula(self.formula)
+    }
+}
+
+/// The result of one [`CombatEnv::step`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// The fight at the agent's next decision, or at its end.
+    pub observation: Observation,
+
+    /// Reward earned by the action.
+    pub reward: f32,
+
+    /// Whether the episode is over; call [`CombatEnv::reset`] to start another.
+    pub done: bool,
+
+    /// How the fight stands; `Ongoing` with `done` set means the step limit was hit.
+    pub outcome: EncounterOutcome,
+}
+
+/// A seeded one-on-one fight for training an agent against a monster policy.
+#[derive(Debug, Clone)]
+pub struct CombatEnv {
+    config: EnvConfig,
+    state: Encounter,
+    rng: SeededRng,
+    steps: u32,
+}
+
+impl CombatEnv {
+    /// Builds an environment, already reset with seed 0.
+    pub fn new(config: EnvConfig) -> Result<Self, EnvError> {
+        config.validate()?;
+        let mut env = Self {
+            state: config.encounter(),
+            config,
+            rng: SeededRng::new(0),
+            steps: 0,
+        };
+        env.reset(0);
+        Ok(env)
+    }
+
+    /// The fight this environment plays.
+    pub fn config(&self) -> &EnvConfig {
+        &self.config
+    }
+
+    /// The current encounter.
+    pub fn state(&self) -> &Encounter {
+        &self.state
+    }
+
+    /// Number of actions, one per agent ability slot.
+    pub fn action_count(&self) -> usize {
+        self.config.agent_abilities.abilities.len()
+    }
+
+    /// Starts a new episode whose rolls come from `seed`.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.state = self.config.encounter();
+        self.rng = SeededRng::new(seed);
+        self.steps = 0;
+        self.advance();
+        self.observation()
+    }
+
+    /// Uses the agent's ability slot `action`, then plays until the agent's next
+    /// decision or the end of the episode.
+    pub fn step(&mut self, action: usize) -> Step {
+        if self.is_done() {
+            return Step {
+                observation: self.observation(),
+                reward: 0.0,
+                done: true,
+                outcome: self.outcome(),
+            };
+        }
+
+        let before = self.hp_shares();
+        let mut reward = 0.0;
+        let action = self.agent_action(action).unwrap_or_else(|| {
+            reward += INVALID_ACTION_REWARD;
+            Action::Pass
+        });
+        self.play(&action);
+        self.steps += 1;
+        self.advance();
+
+        let after = self.hp_shares();
+        reward += (before[1] - after[1]) - (before[0] - after[0]);
+        let outcome = self.outcome();
+        reward += match outcome {
+            EncounterOutcome::Victory => WIN_REWARD,
+            EncounterOutcome::Defeat => LOSS_REWARD,
+            EncounterOutcome::Ongoing => 0.0,
+        };
+
+        Step {
+            observation: self.observation(),
+            reward,
+            done: self.is_done(),
+            outcome,
+        }
+    }
+
+    /// How the fight stands, from the agent's side.
+    pub fn outcome(&self) -> EncounterOutcome {
+        self.state.outcome()
+    }
+
+    /// Whether the episode is over.
+    pub fn is_done(&self) -> bool {
+        self.outcome() != EncounterOutcome::Ongoing || self.steps >= self.config.max_steps
+    }
+
+    /// Which actions are ready; missing slots are `false`.
+    pub fn action_mask(&self) -> [bool; MAX_ACTIONS] {
+        let mut mask = [false; MAX_ACTIONS];
+        for (ready, slot) in mask.iter_mut().zip(&self.agent().abilities.abilities) {
+            *ready = slot.is_ready();
+        }
+        mask
+    }
+
+    /// The fight as seen by the agent.
+    ///
+    /// | Offset | Features |
+    /// |--------|----------|
+    /// | 0 | Agent: HP / max HP, max HP, attack, defense, speed, crit chance / 100, evade chance / 100, stunned, poisoned |
+    /// | [`SIDE_FEATURES`] | The monster, in the same order |
+    /// | 2 × [`SIDE_FEATURES`] | Per agent slot: present, ready, cooldown left / cooldown (all 0 for missing slots) |
+    /// | [`OBSERVATION_SIZE`] − 1 | Steps taken / step limit |
+    ///
+    /// Stats are the [effective](Combatant::effective_stats) ones, equipment
+    /// included. Flags are 1.0 or 0.0.
+    pub fn observation(&self) -> Observation {
+        let mut observation = [0.0; OBSERVATION_SIZE];
+        let (sides, rest) = observation.split_at_mut(2 * SIDE_FEATURES);
+
+        for (features, id) in sides.chunks_mut(SIDE_FEATURES).zip([AGENT, MONSTER]) {
+            let combatant = &self.state.combatants[id.0];
+            let stats = combatant.effective_stats();
+            features.copy_from_slice(&[
+                share(combatant.character.hp, stats.hp),
+                stats.hp as f32,
+                stats.attack as f32,
+                stats.defense as f32,
+                stats.speed as f32,
+                stats.crit_chance as f32 / 100.0,
+                stats.evade_chance as f32 / 100.0,
+                flag(combatant.effects.is_stunned()),
+                flag(combatant.effects.get(StatusKind::Poison).is_some()),
+            ]);
+        }
+
+        let slots = &self.agent().abilities.abilities;
+        for (features, slot) in rest.chunks_mut(SLOT_FEATURES).zip(slots) {
+            features.copy_from_slice(&[1.0, flag(slot.is_ready()), slot.cooldown.progress()]);
+        }
+
+        observation[OBSERVATION_SIZE - 1] = if self.config.max_steps == 0 {
+            1.0
+        } else {
+            self.steps as f32 / self.config.max_steps as f32
+        };
+        observation
+    }
+
+    fn agent(&self) -> &Combatant {
+        &self.state.combatants[AGENT.0]
+    }
+
+    /// The action for ability slot `index`, if it exists and is ready.
+    fn agent_action(&self, index: usize) -> Option<Action> {
+        let slot = self.agent().abilities.abilities.get(index)?;
+        if !slot.is_ready() {
+            return None;
+        }
+        Some(Action::UseAbility {
+            ability: slot.ability.name.clone(),
+            target: if slot.ability.target.is_offensive() {
+                MONSTER
+            } else {
+                AGENT
+            },
+        })
+    }
+
+    /// Plays monster turns and stunned agent turns until the agent can decide.
+    fn advance(&mut self) {
+        while self.outcome() == EncounterOutcome::Ongoing {
+            let Some(actor) = self.state.current() else {
+                return;
+            };
+            let action = if actor == AGENT {
+                if !self.agent().effects.is_stunned() {
+                    return;
+                }
+                Action::Pass
+            } else {
+                choose_ai_action(&self.state, &self.config.monster_policy).unwrap_or(Action::Pass)
+            };
+            self.play(&action);
+        }
+    }
+
+    /// Plays `action`, passing instead if the rules reject it.
+    fn play(&mut self, action: &Action) {
+        let result = reduce_with_rng(&self.state, action, &mut self.rng)
+            .or_else(|_| reduce_with_rng(&self.state, &Action::Pass, &mut self.rng));
+        if let Ok((next, _)) = result {
+            self.state = next;
+        }
+    }
+
+    /// Each side's HP as a share of its effective max HP, agent first.
+    fn hp_shares(&self) -> [f32; 2] {
+        [AGENT, MONSTER].map(|id| {
+            let combatant = &self.state.combatants[id.0];
+            share(combatant.character.hp, combatant.effective_stats().hp)
+        })
+    }
+}
+
+fn share(hp: i32, max: i32) -> f32 {
+    if max <= 0 {
+        0.0
+    } else {
+        hp.max(0) as f32 / max as f32
+    }
+}
+
+fn flag(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Many [`CombatEnv`]s stepped together.
+///
+/// Environment `i` of a batch reset with seed `s` rolls from seed `s + i`.
+/// Environments that finish during [`step`](Self::step) are reset with the next
+/// unused seed: their [`Step`] reports the finished episode's reward, `done` and
+/// outcome, and its `observation` is the first of the new episode.
+#[derive(Debug, Clone)]
+pub struct BatchEnv {
+    envs: Vec<CombatEnv>,
+    next_seed: u64,
+}
+
+impl BatchEnv {
+    /// `count` copies of the fight in `config`, reset with seed 0.
+    pub fn new(config: EnvConfig, count: usize) -> Result<Self, EnvError> {
+        let env = CombatEnv::new(config)?;
+        let mut batch = Self {
+            envs: vec![env; count],
+            next_seed: 0,
+        };
+        batch.reset(0);
+        Ok(batch)
+    }
+
+    /// Number of environments.
+    pub fn len(&self) -> usize {
+        self.envs.len()
+    }
+
+    /// Whether the batch has no environments.
+    pub fn is_empty(&self) -> bool {
+        self.envs.is_empty()
+    }
+
+    /// The environments, in batch order.
+    pub fn envs(&self) -> &[CombatEnv] {
+        &self.envs
+    }
+
+    /// Starts a new episode in every environment.
+    pub fn reset(&mut self, seed: u64) -> Vec<Observation> {
+        self.next_seed = seed.wrapping_add(self.envs.len() as u64);
+        self.envs
+            .iter_mut()
+            .enumerate()
+            .map(|(index, env)| env.reset(seed.wrapping_add(index as u64)))
+            .collect()
+    }
+
+    /// Steps environment `i` with `actions[i]`.
+    ///
+    /// # Panics
+    ///
+    /// If `actions` is not one action per environment.
+    pub fn step(&mut self, actions: &[usize]) -> Vec<Step> {
+        assert_eq!(
+            actions.len(),
+            self.envs.len(),
+            "expected one action per environment"
+        );
+
+        self.envs
+            .iter_mut()
+            .zip(actions)
+            .map(|(env, &action)| {
+                let mut step = env.step(action);
+                if step.done {
+                    step.observation = env.reset(self.next_seed);
+                    self.next_seed = self.next_seed.wrapping_add(1);
+                }
+                step
+            })
+            .collect()
+    }
+
+    /// Which actions are ready in each environment.
+    pub fn action_masks(&self) -> Vec<[bool; MAX_ACTIONS]> {
+        self.envs.iter().map(CombatEnv::action_mask).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AbilitySlot, StatusEffect};
+
+    /// Index of Powerful Attack in the turn-based monster abilities.
+    const POWERFUL_ATTACK: usize = 1;
+
+    fn first_ready(env: &CombatEnv) -> usize {
+        env.action_mask().iter().position(|&ready| ready).unwrap()
+    }
+
+    fn play_out(env: &mut CombatEnv) -> (f32, u32) {
+        let (mut total, mut steps) = (0.0, 0);
+        loop {
+            let step = env.step(first_ready(env));
+            total += step.reward;
+            steps += 1;
+            if step.done {
+                return (total, steps);
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_episode() {
+        let mut a = CombatEnv::new(EnvConfig::default()).unwrap();
+        let mut b = CombatEnv::new(EnvConfig::default()).unwrap();
+
+        assert_eq!(a.reset(9), b.reset(9));
+        assert_eq!(play_out(&mut a), play_out(&mut b));
+        assert_eq!(a.observation(), b.observation());
+    }
+
+    #[test]
+    fn test_attacks_are_rewarded() {
+        let mut env = CombatEnv::new(EnvConfig::default()).unwrap();
+        let observation = env.reset(1);
+        assert_eq!(observation[0], 1.0);
+        assert_eq!(observation[SIDE_FEATURES], 1.0);
+
+        let step = env.step(POWERFUL_ATTACK);
+        assert!(step.reward > 0.0);
+        assert!(step.observation[SIDE_FEATURES] < 1.0);
+
+        // Powerful Attack is now cooling down
+        let slot = 2 * SIDE_FEATURES + POWERFUL_ATTACK * SLOT_FEATURES;
+        assert_eq!(step.observation[slot..slot + 2], [1.0, 0.0]);
+        assert!(!env.action_mask()[POWERFUL_ATTACK]);
+    }
+
+    #[test]
+    fn test_invalid_actions_pass_with_a_penalty() {
+        let mut env =
+            CombatEnv::new(EnvConfig::default().with_policy(AiPersonality::Defensive)).unwrap();
+        env.reset(3);
+        let monster_hp = env.state().combatants[1].character.hp;
+
+        let step = env.step(MAX_ACTIONS - 1);
+
+        assert!(step.reward <= INVALID_ACTION_REWARD);
+        assert_eq!(env.state().combatants[1].character.hp, monster_hp);
+        assert!(!env.action_mask()[MAX_ACTIONS - 1]);
+    }
+
+    #[test]
+    fn test_episodes_end_with_an_outcome_bonus() {
+        let mut env = CombatEnv::new(EnvConfig::default()).unwrap();
+        env.reset(5);
+        let (total, _) = play_out(&mut env);
+
+        match env.outcome() {
+            EncounterOutcome::Victory => assert!(total > WIN_REWARD),
+            EncounterOutcome::Defeat => assert!(total < LOSS_REWARD),
+            EncounterOutcome::Ongoing => panic!("the fight should be decided"),
+        }
+        let after = env.step(0);
+        assert!(after.done);
+        assert_eq!(after.reward, 0.0);
+    }
+
+    #[test]
+    fn test_step_limit_truncates() {
+        let mut config = EnvConfig::default().with_max_steps(2);
+        config.monster.stats.hp = 500;
+        config.monster.hp = 500;
+        let mut env = CombatEnv::new(config).unwrap();
+
+        assert!(!env.step(0).done);
+        let step = env.step(0);
+        assert!(step.done);
+        assert_eq!(step.outcome, EncounterOutcome::Ongoing);
+        assert_eq!(step.observation[OBSERVATION_SIZE - 1], 1.0);
+    }
+
+    #[test]
+    fn test_stunned_agents_lose_their_turn() {
+        let mut env = CombatEnv::new(EnvConfig::default()).unwrap();
+        assert_eq!(env.state().current(), Some(AGENT));
+        env.state.combatants[AGENT.0]
+            .effects
+            .apply("Hero", &StatusEffect::stun(1));
+
+        env.advance();
+
+        // The Hero passed, the Slime acted, and the Hero decides in round 2
+        assert_eq!(env.state().round, 2);
+        assert_eq!(env.state().current(), Some(AGENT));
+        assert!(!env.agent().effects.is_stunned());
+    }
+
+    #[test]
+    fn test_invalid_configs_are_rejected() {
+        let mut config = EnvConfig::default();
+        config.agent_abilities.abilities.clear();
+        assert_eq!(CombatEnv::new(config).unwrap_err(), EnvError::NoAbilities);
+
+        let mut config = EnvConfig::default();
+        config.agent_abilities.abilities = vec![AbilitySlot::basic_attack_turns(); 9];
+        assert_eq!(
+            CombatEnv::new(config).unwrap_err(),
+            EnvError::TooManyAbilities { count: 9 }
+        );
+
+        let config = EnvConfig {
+            monster_abilities: AbilitySet::monster_default(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            CombatEnv::new(config),
+            Err(EnvError::RealTimeCooldown { .. })
+        ));
+    }
+
+    #[test]
+    fn test_batch_resets_finished_environments() {
+        let mut batch = BatchEnv::new(EnvConfig::default(), 4).unwrap();
+        let observations = batch.reset(100);
+        assert_eq!(observations.len(), 4);
+
+        // Environment 2 of the batch plays the same episode as a lone env seeded 102
+        let mut lone = CombatEnv::new(EnvConfig::default()).unwrap();
+        assert_eq!(observations[2], lone.reset(102));
+
+        let mut finished = 0;
+        for _ in 0..200 {
+            let actions: Vec<usize> = batch.envs().iter().map(first_ready).collect();
+            let steps = batch.step(&actions);
+            for (env, step) in batch.envs().iter().zip(&steps) {
+                if step.done {
+                    finished += 1;
+                    assert_eq!(step.observation, env.observation());
+                    assert_eq!(env.steps, 0);
+                }
+            }
+        }
+        assert!(finished > 4);
+        assert!(batch.next_seed >= 104 + 4);
+    }
+}
@@ -0,0 +1,629 @@
+//! Items, equipment and inventories.
+//!
+//! Every [`Combatant`] carries an [`Inventory`]: a bag of [`Item`]s and the
+//! equipment it wears, at most one item per [`EquipmentSlot`]. Equipped items
+//! carry [`StatModifier`]s that change the stats the combatant fights with:
+//!
+//! 1. [`StatModifier::Flat`] amounts are added to the base stat
+//! 2. The sum of the stat's [`StatModifier::Percent`] bonuses then scales it
+//! 3. Max HP stays at least 1, and crit and evade chances stay within 0-100
+//!
+//! The result is a derived view, [`Combatant::effective_stats`]; the base
+//! [`Stats`] in [`Character::stats`] are never modified by equipment, so level
+//! ups and unequipping always start from the real values. The
+//! [`reduce`](crate::reduce) turn loop hands [`Combatant::effective_character`] to
+//! [`compute_attack`](crate::compute_attack), and orders turns by effective speed.
+//!
+//! Consumables wrap an [`Ability`], and [`Action::UseItem`](crate::Action::UseItem)
+//! resolves it exactly like an ability, so a potion heals through the same
+//! code path (and the same max HP cap) as the `Heal` ability.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! let applyModifiers (stats: Stats) (modifiers: StatModifier list) : Stats =
+//!     let flat stat = modifiers |> List.sumBy (function Flat (s, n) when s = stat -> n | _ -> 0)
+//!     let percent stat = modifiers |> List.sumBy (function Percent (s, p) when s = stat -> p | _ -> 0)
+//!     let effective stat value = (value + flat stat) * max 0 (100 + percent stat) / 100
+//!     { stats with
+//!         Hp = max 1 (effective MaxHp stats.Hp)
+//!         Attack = effective Attack stats.Attack
+//!         ... }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let mut hero = Combatant::new(
+//!     Team::Allies,
+//!     Character::new_player("Hero"),
+//!     AbilitySet::player_default(),
+//! );
+//! hero.inventory.add(Item::equipment(
+//!     "Iron Sword",
+//!     EquipmentSlot::Weapon,
+//!     vec![
+//!         StatModifier::Flat { stat: StatKind::Attack, amount: 4 },
+//!         StatModifier::Percent { stat: StatKind::Attack, percent: 50 },
+//!     ],
+//! ));
+//!
+//! hero.equip("Iron Sword").unwrap();
+//!
+//! assert_eq!(hero.effective_stats().attack, 21); // (10 + 4) * 150%
+//! assert_eq!(hero.character.stats.attack, 10); // Base stats are untouched
+//!
+//! hero.unequip(EquipmentSlot::Weapon).unwrap();
+//! assert_eq!(hero.effective_stats().attack, 10);
+//! ```
+
+use core::fmt;
+
+use crate::prelude::*;
+use crate::{
+    Ability, Character, Combatant, EquipmentSlot, Inventory, Item, ItemKind, StatKind,
+    StatModifier, Stats,
+};
+
+impl StatKind {
+    /// Every stat equipment can modify.
+    pub const ALL: [StatKind; 6] = [
+        StatKind::MaxHp,
+        StatKind::Attack,
+        StatKind::Defense,
+        StatKind::Speed,
+        StatKind::CritChance,
+        StatKind::EvadeChance,
+    ];
+
+    /// The stat's value in `stats`.
+    pub fn get(self, stats: &Stats) -> i32 {
+        match self {
+            StatKind::MaxHp => stats.hp,
+            StatKind::Attack => stats.attack,
+            StatKind::Defense => stats.defense,
+            StatKind::Speed => stats.speed,
+            StatKind::CritChance => stats.crit_chance,
+            StatKind::EvadeChance => stats.evade_chance,
+        }
+    }
+
+    fn get_mut(self, stats: &mut Stats) -> &mut i32 {
+        match self {
+            StatKind::MaxHp => &mut stats.hp,
+            StatKind::Attack => &mut stats.attack,
+            StatKind::Defense => &mut stats.defense,
+            StatKind::Speed => &mut stats.speed,
+            StatKind::CritChance => &mut stats.crit_chance,
+            StatKind::EvadeChance => &mut stats.evade_chance,
+        }
+    }
+}
+
+impl StatModifier {
+    /// The stat this modifier changes.
+    pub fn stat(&self) -> StatKind {
+        match *self {
+            StatModifier::Flat { stat, .. } | StatModifier::Percent { stat, .. } => stat,
+        }
+    }
+}
+
+/// `base` with `modifiers` applied: flat amounts first, then each stat's summed
+/// percentage bonus. Max HP stays at least 1 and crit and evade chances stay
+/// within 0-100.
+pub fn apply_modifiers<'a>(
+    base: &Stats,
+    modifiers: impl IntoIterator<Item = &'a StatModifier>,
+) -> Stats {
+    let mut stats = base.clone();
+    let mut bonuses = [0_i64; StatKind::ALL.len()];
+
+    for modifier in modifiers {
+        match *modifier {
+            StatModifier::Flat { stat, amount } => {
+                let value = stat.get_mut(&mut stats);
+                *value = value.saturating_add(amount);
+            }
+            StatModifier::Percent { stat, percent } => {
+                bonuses[stat as usize] += i64::from(percent);
+            }
+        }
+    }
+
+    for stat in StatKind::ALL {
+        let bonus = bonuses[stat as usize];
+        if bonus != 0 {
+            let value = stat.get_mut(&mut stats);
+            let scaled = i64::from(*value) * (100 + bonus).max(0) / 100;
+            *value = scaled.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+        }
+    }
+
+    stats.hp = stats.hp.max(1);
+    stats.crit_chance = stats.crit_chance.clamp(0, 100);
+    stats.evade_chance = stats.evade_chance.clamp(0, 100);
+    stats
+}
+
+impl Item {
+    /// An item worn in `slot`.
+    pub fn equipment(name: &str, slot: EquipmentSlot, modifiers: Vec<StatModifier>) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: ItemKind::Equipment { slot, modifiers },
+        }
+    }
+
+    /// A consumable named after the ability it resolves.
+    pub fn consumable(ability: Ability) -> Self {
+        Self {
+            name: ability.name.clone(),
+            kind: ItemKind::Consumable { ability },
+        }
+    }
+
+    /// A potion that heals an ally for 10 HP.
+    pub fn potion() -> Self {
+        Self::consumable(Ability::healing("Potion", 10))
+    }
+
+    /// The slot the item is worn in, or `None` for consumables.
+    pub fn slot(&self) -> Option<EquipmentSlot> {
+        match &self.kind {
+            ItemKind::Equipment { slot, .. } => Some(*slot),
+            ItemKind::Consumable { .. } => None,
+        }
+    }
+
+    /// The item's stat modifiers; empty for consumables.
+    pub fn modifiers(&self) -> &[StatModifier] {
+        match &self.kind {
+            ItemKind::Equipment { modifiers, .. } => modifiers,
+            ItemKind::Consumable { .. } => &[],
+        }
+    }
+}
+
+/// Reasons an inventory operation is not allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryError {
+    /// No carried item has this name.
+    UnknownItem { name: String },
+
+    /// The item cannot be equipped.
+    NotEquipment { name: String },
+
+    /// The item cannot be used up.
+    NotConsumable { name: String },
+
+    /// Nothing is equipped in the slot.
+    EmptySlot { slot: EquipmentSlot },
+
+    /// An equipped item is in a slot it does not belong to.
+    WrongSlot { name: String, slot: EquipmentSlot },
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InventoryError::UnknownItem { name } => write!(f, "no item named '{}'", name),
+            InventoryError::NotEquipment { name } => {
+                write!(f, "item '{}' cannot be equipped", name)
+            }
+            InventoryError::NotConsumable { name } => {
+                write!(f, "item '{}' cannot be used", name)
+            }
+            InventoryError::EmptySlot { slot } => write!(f, "nothing is equipped as {:?}", slot),
+            InventoryError::WrongSlot { name, slot } => {
+                write!(f, "item '{}' cannot be equipped as {:?}", name, slot)
+            }
+        }
+    }
+}
+
+impl core::error::Error for InventoryError {}
+
+impl Inventory {
+    /// An inventory carrying `items`, with nothing equipped.
+    pub fn new(items: Vec<Item>) -> Self {
+        Self {
+            items,
+            ..Default::default()
+        }
+    }
+
+    /// Puts an item in the bag.
+    pub fn add(&mut self, item: Item) {
+        self.items.push(item);
+    }
+
+    /// The first carried item named `name`.
+    pub fn get(&self, name: &str) -> Option<&Item> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// The item worn in `slot`.
+    pub fn equipped_in(&self, slot: EquipmentSlot) -> Option<&Item> {
+        self.equipped.get(&slot)
+    }
+
+    /// The modifiers of every equipped item.
+    pub fn modifiers(&self) -> impl Iterator<Item = &StatModifier> {
+        self.equipped.values().flat_map(Item::modifiers)
+    }
+
+    /// `base` as modified by the equipped items.
+    pub fn effective_stats(&self, base: &Stats) -> Stats {
+        apply_modifiers(base, self.modifiers())
+    }
+
+    /// Checks that every equipped item is equipment worn in its own slot, e.g.
+    /// after loading an inventory from a file.
+    pub fn validate(&self) -> Result<(), InventoryError> {
+        for (&slot, item) in &self.equipped {
+            match item.slot() {
+                None => {
+                    return Err(InventoryError::NotEquipment {
+                        name: item.name.clone(),
+                    })
+                }
+                Some(own) if own != slot => {
+                    return Err(InventoryError::WrongSlot {
+                        name: item.name.clone(),
+                        slot,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves a carried item into its slot. Whatever was worn there goes back
+    /// into the bag.
+    pub fn equip(&mut self, name: &str) -> Result<(), InventoryError> {
+        let index = self.index_of(name)?;
+        let slot = self.items[index]
+            .slot()
+            .ok_or_else(|| InventoryError::NotEquipment {
+                name: name.to_string(),
+            })?;
+
+        let item = self.items.remove(index);
+        if let Some(previous) = self.equipped.insert(slot, item) {
+            self.items.push(previous);
+        }
+        Ok(())
+    }
+
+    /// Moves the item worn in `slot` back into the bag.
+    pub fn unequip(&mut self, slot: EquipmentSlot) -> Result<(), InventoryError> {
+        let item = self
+            .equipped
+            .remove(&slot)
+            .ok_or(InventoryError::EmptySlot { slot })?;
+        self.items.push(item);
+        Ok(())
+    }
+
+    /// Removes a carried consumable and returns the ability it resolves.
+    pub fn take_consumable(&mut self, name: &str) -> Result<Ability, InventoryError> {
+        let index = self.index_of(name)?;
+        if !matches!(self.items[index].kind, ItemKind::Consumable { .. }) {
+            return Err(InventoryError::NotConsumable {
+                name: name.to_string(),
+            });
+        }
+
+        match self.items.remove(index).kind {
+            ItemKind::Consumable { ability } => Ok(ability),
+            ItemKind::Equipment { .. } => unreachable!("checked above"),
+        }
+    }
+
+    fn index_of(&self, name: &str) -> Result<usize, InventoryError> {
+        self.items
+            .iter()
+            .position(|item| item.name == name)
+            .ok_or_else(|| InventoryError::UnknownItem {
+                name: name.to_string(),
+            })
+    }
+}
+
+impl Combatant {
+    /// The stats the combatant fights with: its base stats as modified by its
+    /// equipment.
+    pub fn effective_stats(&self) -> Stats {
+        self.inventory.effective_stats(&self.character.stats)
+    }
+
+    /// The combatant's character with [`effective_stats`](Self::effective_stats),
+    /// as combat calculations should see it.
+    pub fn effective_character(&self) -> Character {
+        Character {
+            stats: self.effective_stats(),
+            ..self.character.clone()
+        }
+    }
+
+    /// Equips a carried item, see [`Inventory::equip`]. Current HP is capped at
+    /// the new effective max HP.
+    pub fn equip(&mut self, name: &str) -> Result<(), InventoryError> {
+        self.inventory.equip(name)?;
+        self.cap_hp();
+        Ok(())
+    }
+
+    /// Unequips the item worn in `slot`, see [`Inventory::unequip`]. Current HP
+    /// is capped at the new effective max HP.
+    pub fn unequip(&mut self, slot: EquipmentSlot) -> Result<(), InventoryError> {
+        self.inventory.unequip(slot)?;
+        self.cap_hp();
+        Ok(())
+    }
+
+    fn cap_hp(&mut self) {
+        self.character.hp = self.character.hp.min(self.effective_stats().hp);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reduce, AbilitySet, Action, CombatantId, Encounter, EncounterError, Team};
+
+    fn sword() -> Item {
+        Item::equipment(
+            "Sword",
+            EquipmentSlot::Weapon,
+            vec![StatModifier::Flat {
+                stat: StatKind::Attack,
+                amount: 5,
+            }],
+        )
+    }
+
+    fn plate() -> Item {
+        Item::equipment(
+            "Plate",
+            EquipmentSlot::Armor,
+            vec![
+                StatModifier::Flat {
+                    stat: StatKind::Defense,
+                    amount: 3,
+                },
+                StatModifier::Percent {
+                    stat: StatKind::MaxHp,
+                    percent: 50,
+                },
+                StatModifier::Percent {
+                    stat: StatKind::Speed,
+                    percent: -20,
+                },
+            ],
+        )
+    }
+
+    fn hero() -> Combatant {
+        Combatant::new(
+            Team::Allies,
+            Character::new_player("Hero"),
+            AbilitySet::player_default(),
+        )
+        .with_inventory(Inventory::new(vec![sword(), plate(), Item::potion()]))
+    }
+
+    fn slime() -> Combatant {
+        Combatant::new(
+            Team::Enemies,
+            Character::new_monster("Slime"),
+            AbilitySet::monster_turn_based(),
+        )
+    }
+
+    #[test]
+    fn test_modifiers_apply_flat_then_percent() {
+        let stats = apply_modifiers(
+            &Character::new_player("Hero").stats,
+            &[
+                StatModifier::Percent {
+                    stat: StatKind::Attack,
+                    percent: 10,
+                },
+                StatModifier::Flat {
+                    stat: StatKind::Attack,
+                    amount: 10,
+                },
+                StatModifier::Percent {
+                    stat: StatKind::Attack,
+                    percent: 15,
+                },
+                StatModifier::Flat {
+                    stat: StatKind::CritChance,
+                    amount: 150,
+                },
+                StatModifier::Percent {
+                    stat: StatKind::MaxHp,
+                    percent: -200,
+                },
+            ],
+        );
+
+        assert_eq!(stats.attack, 25); // (10 + 10) * 125%
+        assert_eq!(stats.crit_chance, 100);
+        assert_eq!(stats.hp, 1);
+    }
+
+    #[test]
+    fn test_equip_swaps_and_unequip_returns_items() {
+        let mut hero = hero();
+        let axe = Item::equipment("Axe", EquipmentSlot::Weapon, Vec::new());
+        hero.inventory.add(axe.clone());
+
+        hero.equip("Sword").unwrap();
+        hero.equip("Axe").unwrap();
+
+        assert_eq!(
+            hero.inventory.equipped_in(EquipmentSlot::Weapon),
+            Some(&axe)
+        );
+        assert!(hero.inventory.get("Sword").is_some());
+        assert!(hero.inventory.get("Axe").is_none());
+
+        hero.unequip(EquipmentSlot::Weapon).unwrap();
+        assert_eq!(hero.inventory.items.len(), 4);
+        assert_eq!(
+            hero.unequip(EquipmentSlot::Weapon),
+            Err(InventoryError::EmptySlot {
+                slot: EquipmentSlot::Weapon
+            })
+        );
+    }
+
+    #[test]
+    fn test_equip_rejects_unknown_items_and_consumables() {
+        let mut hero = hero();
+
+        assert_eq!(
+            hero.equip("Bow"),
+            Err(InventoryError::UnknownItem {
+                name: "Bow".to_string()
+            })
+        );
+        assert_eq!(
+            hero.equip("Potion"),
+            Err(InventoryError::NotEquipment {
+                name: "Potion".to_string()
+            })
+        );
+        assert_eq!(
+            hero.inventory.take_consumable("Sword"),
+            Err(InventoryError::NotConsumable {
+                name: "Sword".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_unequipping_max_hp_caps_current_hp() {
+        let mut hero = hero();
+        hero.equip("Plate").unwrap();
+        assert_eq!(hero.effective_stats().hp, 45);
+        hero.character.hp = 40;
+
+        hero.unequip(EquipmentSlot::Armor).unwrap();
+
+        assert_eq!(hero.character.hp, 30);
+        assert_eq!(hero.character.stats.hp, 30);
+    }
+
+    #[test]
+    fn test_validate_rejects_misplaced_equipment() {
+        let mut inventory = Inventory::default();
+        inventory.equipped.insert(EquipmentSlot::Armor, sword());
+
+        assert_eq!(
+            inventory.validate(),
+            Err(InventoryError::WrongSlot {
+                name: "Sword".to_string(),
+                slot: EquipmentSlot::Armor
+            })
+        );
+    }
+
+    #[test]
+    fn test_attacks_use_effective_stats() {
+        let mut hero = hero();
+        hero.equip("Sword").unwrap();
+        let mut slime = slime();
+        slime.inventory = Inventory::new(vec![plate()]);
+        slime.equip("Plate").unwrap();
+        let state = Encounter::new(vec![hero, slime]);
+
+        let (next, events) = reduce(
+            &state,
+            &Action::UseAbility {
+                ability: "Basic Attack".to_string(),
+                target: CombatantId(1),
+            },
+        )
+        .unwrap();
+
+        // (10 + 5) attack + 5 power - (1 + 3) defense
+        assert_eq!(events[0].damage, 16);
+        assert_eq!(next.combatants[1].character.hp, 4);
+        assert_eq!(next.combatants[0].character.stats.attack, 10);
+    }
+
+    #[test]
+    fn test_potion_heals_through_the_heal_path() {
+        let mut hero = hero();
+        hero.equip("Plate").unwrap();
+        hero.character.hp = 38;
+        let state = Encounter::new(vec![hero, slime()]);
+
+        let (next, events) = reduce(
+            &state,
+            &Action::UseItem {
+                item: "Potion".to_string(),
+                target: CombatantId(0),
+            },
+        )
+        .unwrap();
+
+        // Capped at the effective max HP of 45
+        assert_eq!(events[0].ability_used, "Potion");
+        assert_eq!(events[0].overheal, 3);
+        assert_eq!(next.combatants[0].character.hp, 45);
+        assert!(next.combatants[0].inventory.get("Potion").is_none());
+    }
+
+    #[test]
+    fn test_rejected_items_are_not_used_up() {
+        let state = Encounter::new(vec![hero(), slime()]);
+
+        let result = reduce(
+            &state,
+            &Action::UseItem {
+                item: "Potion".to_string(),
+                target: CombatantId(1),
+            },
+        );
+
+        assert!(matches!(result, Err(EncounterError::WrongTeam { .. })));
+        assert!(state.combatants[0].inventory.get("Potion").is_some());
+        assert_eq!(
+            reduce(
+                &state,
+                &Action::UseItem {
+                    item: "Sword".to_string(),
+                    target: CombatantId(0),
+                },
+            )
+            .unwrap_err(),
+            EncounterError::Inventory(InventoryError::NotConsumable {
+                name: "Sword".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_inventory_round_trips_through_json() {
+        let mut hero = hero();
+        hero.equip("Plate").unwrap();
+
+        let json = serde_json::to_string(&hero.inventory).unwrap();
+        let back: Inventory = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back, hero.inventory);
+        assert!(json.contains("\"Armor\":{\"name\":\"Plate\""));
+        assert_eq!(
+            serde_json::from_str::<Inventory>("{}").unwrap(),
+            Inventory::default()
+        );
+    }
+}
@@ -6,14 +6,16 @@
 //! ## Purpose
 //!
 //! This crate demonstrates how F# functional programming patterns can be transpiled to Rust
-//! and integrated with the Bevy game engine. Currently, this is a hand-written Rust implementation
-//! that mirrors F# source code. In the future, it will be replaced by code generated via the
+//! and integrated with the Bevy game engine. The domain types in [`domain`] are generated
+//! from `fsharp/Domain.fs`; the game logic is a hand-written Rust implementation that
+//! mirrors the F# source code. In the future, it will be replaced by code generated via the
 //! [fsrs](https://github.com/raibid-labs/fsrs) transpiler.
 //!
 //! ## F# Integration
 //!
 //! Types and functions in this crate mirror F# definitions in:
-//! - `fsharp/Domain.fs` - Domain types (Stats, Character, Ability, CombatEvent)
+//! - `fsharp/Domain.fs` - Domain types (Stats, Character, Ability, CombatEvent), generated
+//!   into [`domain`] by `cargo run -p fsharp-tools --bin fsharp-codegen`
 //! - `fsharp/GameLogic.fs` - Game logic functions (compute_attack)
 //!
 //! This dual implementation enables:
@@ -43,9 +45,22 @@
 //! The combat system is built around simple, deterministic rules:
 //!
 //! - Each [`Character`] has [`Stats`] (hp, attack, defense)
-//! - Characters use [`Ability`] actions to attack
-//! - Combat is resolved via [`compute_attack`], producing a [`CombatEvent`]
-//! - Damage formula: `max(1, attacker.attack + ability.power - defender.defense)`
+//! - Characters use [`Ability`] actions, each a list of [`AbilityEffect`]s (damage,
+//!   heal, shield, status) aimed by a [`Targeting`] rule
+//! - Combat is resolved via [`compute_attack`], producing a [`CombatEvent`] with
+//!   one typed [`EffectOutcome`] per effect
+//! - Damage formula: `max(1, attacker.attack + power - defender.defense)` by
+//!   default, or any other [`DamageFormula`]; healing is capped at the target's max HP
+//! - [`compute_attack_with_rng`] adds crits, evasion and damage variance, drawing
+//!   every roll from a seedable [`CombatRng`] so fights replay identically
+//! - Monsters pick abilities through an [`AiPolicy`]; [`AiPersonality`] names the
+//!   built-in strategies, and [`Planner`] searches future turns for the best move
+//! - An [`Encounter`] runs parties of allies against groups of enemies, ordering
+//!   turns by speed and validating every target
+//! - [`reduce`] runs one turn of an encounter as a pure function from state and
+//!   [`Action`] to the next state and its events
+//! - [`Lockstep`] keeps two peers' copies of an encounter in step over a
+//!   [`Transport`], exchanging only actions and comparing state [`checksum`]s
 //!
 //! ### Example Usage
 //!
@@ -70,7 +85,8 @@
 //!
 //! Types in this crate are designed to work seamlessly with Bevy's ECS:
 //!
-//! - [`Character`] derives `Component` for use in Bevy entities
+//! - [`Character`] derives `Component` for use in Bevy entities (with the `bevy`
+//!   feature)
 //! - All types derive `Serialize` and `Deserialize` for WASM bindings
 //! - Pure functions (like [`compute_attack`]) are called from Bevy systems
 //!
@@ -91,15 +107,59 @@
 //! }
 //! ```
 //!
+//! ## Cargo Features
+//!
+//! - `bevy` (default): `Component` and `Resource` derives for the app
+//! - `std` (default, implied by `bevy`): `from_file`, `from_ron`, the
+//!   `builtin()` catalogs and the channel and TCP lockstep transports
+//!
+//! Without either feature the crate is `no_std + alloc`; catalogs are then
+//! built with `from_json` or `new`.
+//!
 //! ## F# Type Mappings
 //!
 //! | F# Type | Rust Type | Notes |
 //! |---------|-----------|-------|
 //! | `int` | `i32` | Signed 32-bit integer |
+//! | `uint32` | `u32` | Unsigned 32-bit integer |
+//! | `float32` | `f32` | 32-bit float |
 //! | `string` | `String` | Heap-allocated string |
 //! | Record `{ Field: T }` | Struct `{ field: T }` | F# uses PascalCase, Rust uses snake_case |
+//! | Single-case union `Id of int` | Newtype `Id(i32)` | Serialized as the wrapped value |
 //! | Record with members | Struct with impl block | Methods become associated functions |
 //!
+//! Generated types are plain data; construct them with struct literals, filling
+//! optional fields from `Default`:
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! // Typical player stats
+//! let player_stats = Stats {
+//!     hp: 30,
+//!     attack: 10,
+//!     defense: 2,
+//!     ..Default::default()
+//! };
+//! let player = Character {
+//!     name: "Hero".to_string(),
+//!     hp: player_stats.hp,
+//!     stats: player_stats,
+//! };
+//!
+//! // Events are immutable records of what happened; the defender's HP must be
+//! // updated from `defender_hp_after`
+//! let monster = Character::new_monster("Slime");
+//! let event = compute_attack(&player, &monster, &Ability::basic_attack());
+//!
+//! println!("{} used {} on {} for {} damage! ({} HP remaining)",
+//!          event.attacker_name,
+//!          event.ability_used,
+//!          event.defender_name,
+//!          event.damage,
+//!          event.defender_hp_after);
+//! ```
+//!
 //! ## Design Principles
 //!
 //! 1. **Functional Core**: All logic functions are pure (no side effects)
@@ -111,86 +171,82 @@
 //!
 //! Planned features include:
 //! - Automatic fsrs transpilation from F# source
-//! - More complex combat abilities (multi-target, status effects)
+//! - More complex combat abilities (multi-target)
 //! - Character progression system
-//! - Monster AI behaviors
 //!
 //! See the [GitHub issues](https://github.com/raibid-labs/grimware) for active development work.
 
-use serde::{Deserialize, Serialize};
-
-/// Character combat statistics.
-///
-/// Stats define a character's combat capabilities: maximum health, offensive power,
-/// and defensive resilience. These values are used in damage calculations via [`compute_attack`].
-///
-/// # F# Equivalent
-///
-/// ```fsharp
-/// type Stats =
-///     { Hp: int
-///       Attack: int
-///       Defense: int }
-/// ```
-///
-/// # Fields
-///
-/// - `hp`: Maximum hit points (how much damage can be taken before defeat)
-/// - `attack`: Attack power (added to damage calculations)
-/// - `defense`: Damage reduction (subtracted from incoming damage)
-///
-/// # Examples
-///
-/// ```
-/// use bevy_wasm_fsharp_ref_logic::Stats;
-///
-/// // Typical player stats
-/// let player_stats = Stats {
-///     hp: 30,
-///     attack: 10,
-///     defense: 2,
-/// };
-///
-/// // Weaker monster stats
-/// let monster_stats = Stats {
-///     hp: 20,
-///     attack: 6,
-///     defense: 1,
-/// };
-/// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Stats {
-    /// Maximum hit points. When current HP reaches 0, the character is defeated.
-    pub hp: i32,
-
-    /// Attack power. Added to ability power when calculating damage.
-    pub attack: i32,
-
-    /// Damage reduction. Subtracted from incoming damage (minimum 1 damage always applies).
-    pub defense: i32,
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+pub mod ai;
+pub mod bestiary;
+pub mod catalog;
+pub mod cooldown;
+pub mod domain;
+pub mod effect;
+pub mod element;
+pub mod encounter;
+pub mod event;
+pub mod formula;
+pub mod gym;
+pub mod inventory;
+pub mod lockstep;
+pub mod loot;
+pub mod planner;
+pub mod progression;
+pub mod reducer;
+pub mod replay;
+pub mod rng;
+pub mod status;
+pub mod time;
+
+pub use ai::{AggressiveAi, AiPolicy, BalancedAi, DefensiveAi, SmartAi, ThresholdAi};
+pub use bestiary::{Bestiary, BestiaryError};
+pub use catalog::{AbilityCatalog, AbilityDef, CatalogError, CooldownDef};
+pub use domain::{
+    Ability, AbilityEffect, AbilitySet, AbilitySlot, AbilityType, AbilityUnlock, Action,
+    ActiveEffect, AiPersonality, Character, CharacterClass, CombatEvent, Combatant, CombatantId,
+    Cooldown, CooldownMode, DamageModel, EffectDuration, EffectOutcome, Effectiveness, Element,
+    EncounterOutcome, EncounterTemplate, EquipmentSlot, Inventory, Item, ItemKind, LevelCurve,
+    Loot, LootDrop, LootEntry, LootTable, Millis, MonsterTemplate, Progression, Rarity,
+    Resistances, StackingRule, StatGrowth, StatKind, StatModifier, Stats, StatusEffect,
+    StatusEffects, StatusEvent, StatusKind, Targeting, Team, XpGrant,
+};
+pub use element::NEUTRAL_MULTIPLIER;
+pub use encounter::{turn_order, Encounter, EncounterError};
+pub use event::COMBAT_EVENT_VERSION;
+pub use formula::{AttackRatioFormula, DamageFormula, FlatDefenseFormula, PercentArmorFormula};
+pub use gym::{
+    BatchEnv, CombatEnv, EnvConfig, EnvError, Observation, Step, INVALID_ACTION_REWARD,
+    LOSS_REWARD, MAX_ACTIONS, OBSERVATION_SIZE, SIDE_FEATURES, SLOT_FEATURES, WIN_REWARD,
+};
+pub use inventory::{apply_modifiers, InventoryError};
+pub use lockstep::{checksum, Lockstep, LockstepError, Transport, TurnInput};
+#[cfg(feature = "std")]
+pub use lockstep::{ChannelTransport, TcpTransport};
+pub use loot::{LootCatalog, LootError};
+pub use planner::{CandidateScore, DuelState, OpponentModel, Plan, Planner, WIN_SCORE};
+pub use progression::{xp_reward, ClassCatalog, ClassError, MAX_LEVEL};
+pub use reducer::{choose_ai_action, reduce, reduce_with_rng, PASS};
+pub use replay::{Replay, ReplayError, ReplayTurn, REPLAY_VERSION};
+pub use rng::{CombatRng, SeededRng};
+pub use status::apply_ability_status;
+
+use prelude::*;
+
+/// The `alloc` types and traits the std prelude would otherwise provide.
+mod prelude {
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
 }
 
-/// A game character (player or monster).
-///
-/// Characters are the core entities in the combat system. Each character has a name,
-/// current health, and base statistics. Characters can be either player-controlled
-/// or AI-controlled monsters.
-///
-/// # F# Equivalent
-///
-/// ```fsharp
-/// type Character =
-///     { Name: string
-///       Hp: int
-///       Stats: Stats }
-/// ```
-///
-/// # Design Notes
-///
-/// - Current HP (`hp`) can differ from max HP (`stats.hp`) after taking damage
-/// - Characters are defeated when `hp <= 0`
-/// - The `Stats` field contains base values that don't change during combat
-/// - This type derives `Component` for use in Bevy's ECS
+/// Constructors and queries for [`Character`].
 ///
 /// # Examples
 ///
@@ -209,36 +265,6 @@ pub struct Stats {
 /// assert_eq!(monster.hp, 20);
 /// assert_eq!(monster.stats.attack, 6);
 /// ```
-///
-/// # Bevy Integration
-///
-/// ```rust,ignore
-/// use bevy::prelude::*;
-/// use bevy_wasm_fsharp_ref_logic::Character;
-///
-/// #[derive(Component)]
-/// struct Player;
-///
-/// fn spawn_player(mut commands: Commands) {
-///     commands.spawn((
-///         Player,
-///         Character::new_player("Hero"),
-///         Transform::from_xyz(-100.0, 0.0, 0.0),
-///     ));
-/// }
-/// ```
-#[derive(Debug, Clone, Serialize, Deserialize, bevy::prelude::Component)]
-pub struct Character {
-    /// Display name of the character.
-    pub name: String,
-
-    /// Current hit points. When this reaches 0 or below, the character is defeated.
-    pub hp: i32,
-
-    /// Base combat statistics (max HP, attack, defense).
-    pub stats: Stats,
-}
-
 impl Character {
     /// Creates a new player character with default stats.
     ///
@@ -246,6 +272,7 @@ impl Character {
     /// - 30 HP (both current and max)
     /// - 10 Attack
     /// - 2 Defense
+    /// - 5 Speed
     ///
     /// # Arguments
     ///
@@ -271,6 +298,8 @@ impl Character {
                 hp: 30,
                 attack: 10,
                 defense: 2,
+                speed: 5,
+                ..Default::default()
             },
         }
     }
@@ -281,6 +310,7 @@ impl Character {
     /// - 20 HP (both current and max)
     /// - 6 Attack
     /// - 1 Defense
+    /// - 4 Speed
     ///
     /// # Arguments
     ///
@@ -306,55 +336,33 @@ impl Character {
                 hp: 20,
                 attack: 6,
                 defense: 1,
+                speed: 4,
+                ..Default::default()
             },
         }
     }
 }
 
-/// A combat ability or attack.
-///
-/// Abilities represent actions that characters can perform in combat. Each ability
-/// has a name (for display purposes) and a power value (used in damage calculations).
-///
-/// # F# Equivalent
-///
-/// ```fsharp
-/// type Ability =
-///     { Name: string
-///       Power: int }
-/// ```
-///
-/// # Design Notes
-///
-/// - Abilities are typically created via factory functions like [`Ability::basic_attack`]
-/// - Power is added to the attacker's attack stat when calculating damage
-/// - Future enhancements may add cooldowns, MP costs, or status effects
+/// The built-in abilities.
 ///
 /// # Examples
 ///
 /// ```
-/// use bevy_wasm_fsharp_ref_logic::Ability;
+/// use bevy_wasm_fsharp_ref_logic::*;
 ///
 /// // Use the built-in basic attack
 /// let attack = Ability::basic_attack();
 /// assert_eq!(attack.name, "Basic Attack");
-/// assert_eq!(attack.power, 5);
+/// assert_eq!(attack.power(), 5);
 ///
 /// // Create a custom ability
 /// let fireball = Ability {
 ///     name: "Fireball".to_string(),
-///     power: 15,
+///     element: Element::Fire,
+///     effects: vec![AbilityEffect::Damage { power: 15 }],
+///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Ability {
-    /// Display name of the ability.
-    pub name: String,
-
-    /// Base power of the ability. Added to the attacker's attack stat.
-    pub power: i32,
-}
-
 impl Ability {
     /// Creates the standard basic attack ability.
     ///
@@ -366,7 +374,9 @@ impl Ability {
     /// ```fsharp
     /// let basicAttack =
     ///     { Name = "Basic Attack"
-    ///       Power = 5 }
+    ///       Element = Physical
+    ///       Target = Enemy
+    ///       Effects = [ Damage(5) ] }
     /// ```
     ///
     /// # Examples
@@ -376,263 +386,314 @@ impl Ability {
     ///
     /// let ability = Ability::basic_attack();
     /// assert_eq!(ability.name, "Basic Attack");
-    /// assert_eq!(ability.power, 5);
+    /// assert_eq!(ability.power(), 5);
     /// ```
     pub fn basic_attack() -> Self {
-        Self {
-            name: "Basic Attack".into(),
-            power: 5,
-        }
+        Self::damage("Basic Attack", 5)
     }
 
     /// Creates a powerful attack ability with high damage and longer cooldown.
     pub fn powerful_attack() -> Self {
-        Self {
-            name: "Powerful Attack".into(),
-            power: 12,
-        }
+        Self::damage("Powerful Attack", 12)
     }
 
-    /// Creates a healing ability (negative power = heal).
+    /// Creates a healing ability that restores 8 HP to an ally.
     pub fn heal() -> Self {
-        Self {
-            name: "Heal".into(),
-            power: -8,
-        }
+        Self::healing("Heal", 8)
     }
 
     /// Creates a quick strike ability with low damage but very short cooldown.
     pub fn quick_strike() -> Self {
-        Self {
-            name: "Quick Strike".into(),
-            power: 3,
-        }
+        Self::damage("Quick Strike", 3)
     }
 }
 
-/// An ability slot with cooldown tracking.
+/// Cooldown tracking for [`AbilitySlot`].
 ///
-/// Represents an ability that can be used with a cooldown timer.
-/// The cooldown prevents the ability from being used repeatedly without delay.
+/// # Examples
 ///
-/// # F# Equivalent
+/// ```
+/// use bevy_wasm_fsharp_ref_logic::*;
 ///
-/// ```fsharp
-/// type AbilitySlot =
-///     { Ability: Ability
-///       CooldownMax: float
-///       CooldownCurrent: float }
+/// let mut slot = AbilitySlot::new(
+///     Ability::powerful_attack(),
+///     AbilityType::PowerfulAttack,
+///     Cooldown::turns(3),
+/// );
+///
+/// slot.use_ability();
+/// assert!(!slot.is_ready());
+///
+/// for _ in 0..3 {
+///     slot.tick_turn();
+/// }
+/// assert!(slot.is_ready());
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, bevy::prelude::Component)]
-pub struct AbilitySlot {
-    /// The ability contained in this slot.
-    pub ability: Ability,
-
-    /// Maximum cooldown time in seconds.
-    pub cooldown_max: f32,
-
-    /// Current cooldown remaining in seconds (0 = ready to use).
-    pub cooldown_current: f32,
-}
-
 impl AbilitySlot {
-    /// Creates a new ability slot with the specified ability and cooldown.
-    pub fn new(ability: Ability, cooldown_max: f32) -> Self {
+    /// Creates a ready ability slot.
+    pub fn new(ability: Ability, ability_type: AbilityType, cooldown: Cooldown) -> Self {
         Self {
             ability,
-            cooldown_max,
-            cooldown_current: 0.0,
+            ability_type,
+            cooldown,
         }
     }
 
+    /// Creates a basic attack with no turn cooldown.
+    ///
+    /// # F# Equivalent
+    ///
+    /// ```fsharp
+    /// let basicAttackSlot =
+    ///     { Ability = basicAttack
+    ///       AbilityType = BasicAttack
+    ///       Cooldown = Cooldown.Turns(0, 0) }
+    /// ```
+    pub fn basic_attack_turns() -> Self {
+        Self::new(
+            Ability::basic_attack(),
+            AbilityType::BasicAttack,
+            Cooldown::turns(0),
+        )
+    }
+
+    /// Creates a powerful attack with a 3-turn cooldown.
+    ///
+    /// # F# Equivalent
+    ///
+    /// ```fsharp
+    /// let powerfulAttackSlot =
+    ///     { Ability = powerfulAttack
+    ///       AbilityType = PowerfulAttack
+    ///       Cooldown = Cooldown.Turns(3, 0) }
+    /// ```
+    pub fn powerful_attack_turns() -> Self {
+        Self::new(
+            Ability::powerful_attack(),
+            AbilityType::PowerfulAttack,
+            Cooldown::turns(3),
+        )
+    }
+
+    /// Creates a heal with a 4-turn cooldown.
+    ///
+    /// # F# Equivalent
+    ///
+    /// ```fsharp
+    /// let healSlot =
+    ///     { Ability = healAbility
+    ///       AbilityType = Heal
+    ///       Cooldown = Cooldown.Turns(4, 0) }
+    /// ```
+    pub fn heal_turns() -> Self {
+        Self::new(Ability::heal(), AbilityType::Heal, Cooldown::turns(4))
+    }
+
     /// Checks if the ability is ready to use (cooldown finished).
     pub fn is_ready(&self) -> bool {
-        self.cooldown_current <= 0.0
+        self.cooldown.is_ready()
     }
 
     /// Triggers the ability's cooldown after use.
     pub fn use_ability(&mut self) {
-        self.cooldown_current = self.cooldown_max;
+        self.cooldown.trigger();
     }
 
-    /// Ticks down the cooldown by the given delta time.
-    pub fn tick(&mut self, delta: f32) {
-        self.cooldown_current = (self.cooldown_current - delta).max(0.0);
+    /// Ticks down a real-time cooldown by the given delta time.
+    pub fn tick(&mut self, delta: Millis) {
+        self.cooldown.tick(delta);
+    }
+
+    /// Ticks down a turn-based cooldown by one turn.
+    pub fn tick_turn(&mut self) {
+        self.cooldown.tick_turn();
     }
 
     /// Gets the cooldown progress as a percentage (0.0 to 1.0).
     /// 0.0 = ready, 1.0 = just used.
     pub fn cooldown_progress(&self) -> f32 {
-        if self.cooldown_max <= 0.0 {
-            0.0
-        } else {
-            (self.cooldown_current / self.cooldown_max).clamp(0.0, 1.0)
-        }
+        self.cooldown.progress()
     }
 }
 
-/// A set of abilities for a character.
-///
-/// Contains multiple ability slots that can be used in combat.
-///
-/// # F# Equivalent
-///
-/// ```fsharp
-/// type AbilitySet =
-///     { Abilities: AbilitySlot list }
-/// ```
-#[derive(Debug, Clone, Serialize, Deserialize, bevy::prelude::Component)]
-pub struct AbilitySet {
-    /// List of ability slots.
-    pub abilities: Vec<AbilitySlot>,
-}
-
 impl AbilitySet {
-    /// Creates a default player ability set with 4 abilities.
+    /// Creates a default real-time player ability set with 4 abilities.
     pub fn player_default() -> Self {
         Self {
             abilities: vec![
-                AbilitySlot::new(Ability::basic_attack(), 0.5),
-                AbilitySlot::new(Ability::powerful_attack(), 3.0),
-                AbilitySlot::new(Ability::heal(), 5.0),
-                AbilitySlot::new(Ability::quick_strike(), 0.2),
+                AbilitySlot::new(
+                    Ability::basic_attack(),
+                    AbilityType::BasicAttack,
+                    Cooldown::seconds(0.5),
+                ),
+                AbilitySlot::new(
+                    Ability::powerful_attack(),
+                    AbilityType::PowerfulAttack,
+                    Cooldown::seconds(3.0),
+                ),
+                AbilitySlot::new(Ability::heal(), AbilityType::Heal, Cooldown::seconds(5.0)),
+                AbilitySlot::new(
+                    Ability::quick_strike(),
+                    AbilityType::BasicAttack,
+                    Cooldown::seconds(0.2),
+                ),
             ],
         }
     }
 
-    /// Creates a default monster ability set.
+    /// Creates a default real-time monster ability set.
     pub fn monster_default() -> Self {
         Self {
             abilities: vec![
-                AbilitySlot::new(Ability::basic_attack(), 1.0),
-                AbilitySlot::new(Ability::quick_strike(), 0.5),
+                AbilitySlot::new(
+                    Ability::basic_attack(),
+                    AbilityType::BasicAttack,
+                    Cooldown::seconds(1.0),
+                ),
+                AbilitySlot::new(
+                    Ability::quick_strike(),
+                    AbilityType::BasicAttack,
+                    Cooldown::seconds(0.5),
+                ),
             ],
         }
     }
 
-    /// Ticks all ability cooldowns.
-    pub fn tick_all(&mut self, delta: f32) {
+    /// Creates the default turn-based monster ability set used by
+    /// [`choose_monster_action`]: basic attack, powerful attack and heal.
+    pub fn monster_turn_based() -> Self {
+        Self {
+            abilities: vec![
+                AbilitySlot::basic_attack_turns(),
+                AbilitySlot::powerful_attack_turns(),
+                AbilitySlot::heal_turns(),
+            ],
+        }
+    }
+
+    /// Ticks all real-time ability cooldowns.
+    pub fn tick_all(&mut self, delta: Millis) {
         for slot in &mut self.abilities {
             slot.tick(delta);
         }
     }
 
+    /// Ticks all turn-based ability cooldowns by one turn.
+    pub fn tick_turn_all(&mut self) {
+        for slot in &mut self.abilities {
+            slot.tick_turn();
+        }
+    }
+
     /// Gets a ready ability by index, if available.
     pub fn get_ready_ability(&mut self, index: usize) -> Option<&mut AbilitySlot> {
         self.abilities.get_mut(index).filter(|slot| slot.is_ready())
     }
-}
 
-/// The result of a combat action.
-///
-/// A `CombatEvent` records what happened when one character attacked another.
-/// It contains the names of both participants, the damage dealt, the defender's
-/// remaining HP after the attack, and which ability was used.
-///
-/// # F# Equivalent
-///
-/// ```fsharp
-/// type CombatEvent =
-///     { AttackerName: string
-///       DefenderName: string
-///       Damage: int
-///       DefenderHpAfter: int
-///       AbilityUsed: string }
-/// ```
-///
-/// # Design Notes
-///
-/// - Events are immutable records of what happened
-/// - The defender's HP must be manually updated based on `defender_hp_after`
-/// - Events can be logged, displayed in UI, or used for AI decision-making
-/// - Damage can be negative for healing abilities
-///
-/// # Examples
-///
-/// ```
-/// use bevy_wasm_fsharp_ref_logic::*;
-///
-/// let player = Character::new_player("Hero");
-/// let monster = Character::new_monster("Slime");
-/// let ability = Ability::basic_attack();
-///
-/// let event = compute_attack(&player, &monster, &ability);
-///
-/// println!("{} used {} on {} for {} damage! ({} HP remaining)",
-///          event.attacker_name,
-///          event.ability_used,
-///          event.defender_name,
-///          event.damage,
-///          event.defender_hp_after);
-/// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CombatEvent {
-    /// Name of the character who performed the attack.
-    pub attacker_name: String,
-
-    /// Name of the character who received the attack.
-    pub defender_name: String,
-
-    /// Amount of damage dealt (after defense calculation).
-    /// Negative damage means healing.
-    pub damage: i32,
-
-    /// Defender's remaining HP after taking damage.
-    /// If this is <= 0, the defender is defeated.
-    pub defender_hp_after: i32,
-
-    /// Name of the ability that was used.
-    pub ability_used: String,
+    /// Triggers the cooldown of the first ability with the given name.
+    ///
+    /// Returns `false` if no ability in the set has that name.
+    pub fn use_ability(&mut self, name: &str) -> bool {
+        match self
+            .abilities
+            .iter_mut()
+            .find(|slot| slot.ability.name == name)
+        {
+            Some(slot) => {
+                slot.use_ability();
+                true
+            }
+            None => false,
+        }
+    }
 }
 
-/// Computes the result of an attack between two characters.
+/// Computes the result of an ability used by one character on another.
 ///
 /// This is the core combat resolution function. It takes an attacker, defender, and ability,
-/// then calculates damage and produces a [`CombatEvent`] describing the outcome.
+/// then resolves the ability's damage and healing effects in order and produces a
+/// [`CombatEvent`] describing the outcome.
 ///
 /// # Combat Formula
 ///
 /// ```text
-/// raw_damage = attacker.stats.attack + ability.power
-/// actual_damage = max(1, raw_damage - defender.stats.defense)  // For attacks
-/// actual_damage = ability.power  // For healing (negative power)
-/// new_hp = defender.hp - actual_damage
+/// For each Damage { power } effect:
+///     raw_damage = attacker.stats.attack + power
+///     base_damage = max(1, raw_damage - defender.stats.defense)  // see DamageFormula
+///     multiplier = defender.stats.resistances.multiplier(ability.element)
+///     damage = max(1, base_damage * multiplier / 100)  // 0 if immune
+///     dealt = dealt + damage
+/// For each Heal { amount } effect:
+///     dealt = dealt - amount
+/// defender_hp_after = clamp(defender.hp - dealt, 0, defender.stats.hp)
 /// ```
 ///
 /// **Note**:
-/// - Damage is always at least 1 for attacks, even if defense exceeds attack power.
-/// - Healing abilities (negative power) bypass defense and restore HP directly.
+/// - Damage is always at least 1 for attacks, even if defense exceeds attack power,
+///   unless the defender is immune to the ability's element.
+/// - Healing bypasses defense and resistances.
+/// - Each effect's result is recorded in [`CombatEvent::outcomes`]; `damage` is the
+///   net amount dealt, so it is negative for heals.
+/// - HP is clamped between 0 and max HP; what was lost to clamping is reported as
+///   `overkill` or `overheal` (see [`event`]).
+/// - [`Shield`](AbilityEffect::Shield) and [`ApplyStatus`](AbilityEffect::ApplyStatus)
+///   effects need the defender's [`StatusEffects`] and are resolved afterwards by
+///   [`apply_ability_status`].
+/// - The element's [`Effectiveness`] is reported on the event.
 ///
 /// # F# Equivalent
 ///
 /// ```fsharp
 /// let computeAttack (attacker: Character) (defender: Character) (ability: Ability) : CombatEvent =
-///     let raw = attacker.Stats.Attack + ability.Power
-///     let dmg =
-///         if ability.Power < 0 then
-///             ability.Power  // Healing (negative)
-///         else
-///             max 1 (raw - defender.Stats.Defense)
-///     let hpAfter = defender.Hp - dmg
-///     { AttackerName = attacker.Name
+///     let multiplier =
+///         defender.Stats.Resistances
+///         |> Map.tryFind ability.Element
+///         |> Option.defaultValue 100
+///         |> max 0
+///     let resolve (hp, dealt, outcomes) effect =
+///         match effect with
+///         | Damage power ->
+///             let baseDmg = max 1 (attacker.Stats.Attack + power - defender.Stats.Defense)
+///             let dmg = if multiplier = 0 then 0 else max 1 (baseDmg * multiplier / 100)
+///             hp - dmg, dealt + dmg, outcomes @ [ Damaged dmg ]
+///         | AbilityEffect.Heal amount ->
+///             let healed = max 0 (min amount (defender.Stats.Hp - hp))
+///             hp + healed, dealt - amount, outcomes @ [ Healed healed ]
+///         | AbilityEffect.Shield _
+///         | ApplyStatus _ -> hp, dealt, outcomes
+///     let _, dealt, outcomes = ability.Effects |> List.fold resolve (defender.Hp, 0, [])
+///     { Version = combatEventVersion
+///       AttackerName = attacker.Name
 ///       DefenderName = defender.Name
-///       Damage = dmg
-///       DefenderHpAfter = hpAfter
-///       AbilityUsed = ability.Name }
+///       Damage = dealt
+///       DefenderHpAfter = defender.Hp
+///       EffectiveAmount = 0
+///       Overkill = 0
+///       Overheal = 0
+///       KillingBlow = false
+///       AbilityUsed = ability.Name
+///       Critical = false
+///       Missed = false
+///       Effectiveness = if dealsDamage ability then effectivenessOf multiplier else Normal
+///       StatusEvents = []
+///       Outcomes = outcomes }
+///     |> settle defender.Hp defender.Stats.Hp
 /// ```
 ///
 /// # Arguments
 ///
-/// * `attacker` - The character performing the attack
-/// * `defender` - The character receiving the attack
+/// * `attacker` - The character using the ability
+/// * `defender` - The character the ability is used on (the attacker itself for
+///   self-targeted abilities)
 /// * `ability` - The ability being used
 ///
 /// # Returns
 ///
 /// A [`CombatEvent`] containing:
 /// - Attacker and defender names
-/// - Damage dealt
-/// - Defender's HP after the attack
+/// - Net damage dealt
+/// - Defender's HP after the ability, with overkill and overheal
+/// - The outcome of each damage or healing effect
 ///
 /// # Examples
 ///
@@ -649,6 +710,7 @@ pub struct CombatEvent {
 ///
 /// // Player (attack=10) + BasicAttack (power=5) - Monster (defense=1) = 14 damage
 /// assert_eq!(event.damage, 14);
+/// assert_eq!(event.outcomes, vec![EffectOutcome::Damaged { amount: 14 }]);
 /// assert_eq!(event.attacker_name, "Hero");
 /// assert_eq!(event.defender_name, "Slime");
 /// assert_eq!(event.defender_hp_after, 20 - 14); // Monster starts with 20 HP
@@ -663,14 +725,14 @@ pub struct CombatEvent {
 /// let weak = Character {
 ///     name: "Weak Fighter".to_string(),
 ///     hp: 10,
-///     stats: Stats { hp: 10, attack: 1, defense: 0 },
+///     stats: Stats { hp: 10, attack: 1, defense: 0, ..Default::default() },
 /// };
 ///
 /// // Create a highly defensive target
 /// let tank = Character {
 ///     name: "Tank".to_string(),
 ///     hp: 50,
-///     stats: Stats { hp: 50, attack: 5, defense: 20 },
+///     stats: Stats { hp: 50, attack: 5, defense: 20, ..Default::default() },
 /// };
 ///
 /// let ability = Ability::basic_attack();
@@ -682,6 +744,21 @@ pub struct CombatEvent {
 /// assert_eq!(event.defender_hp_after, 49);
 /// ```
 ///
+/// ## Healing Example
+///
+/// ```
+/// use bevy_wasm_fsharp_ref_logic::*;
+///
+/// let mut monster = Character::new_monster("Slime");
+/// monster.hp = 5;
+///
+/// let event = compute_attack(&monster, &monster, &Ability::heal());
+///
+/// assert_eq!(event.outcomes, vec![EffectOutcome::Healed { amount: 8 }]);
+/// assert_eq!(event.damage, -8);
+/// assert_eq!(event.defender_hp_after, 13);
+/// ```
+///
 /// ## Defeating an Enemy
 ///
 /// ```
@@ -709,160 +786,205 @@ pub struct CombatEvent {
 /// - The defender's HP is **not modified** - the caller must apply the new HP
 /// - Events can be logged, animated, or used for AI without affecting game state
 /// - The minimum damage rule prevents invulnerable scenarios
+/// - Random stats (`crit_chance`, `evade_chance`, `damage_variance`) are ignored;
+///   this is the zero-variance special case of [`compute_attack_with_rng`]
+/// - This is [`compute_attack_with_formula`] with the default [`FlatDefenseFormula`]
 pub fn compute_attack(
     attacker: &Character,
     defender: &Character,
     ability: &Ability,
 ) -> CombatEvent {
-    // Handle healing abilities (negative power) differently
-    let dmg = if ability.power < 0 {
-        // Healing: apply directly without defense calculation
-        ability.power
-    } else {
-        // Normal attack: calculate with defense
-        let raw = attacker.stats.attack + ability.power;
-        (raw - defender.stats.defense).max(1)
-    };
+    compute_attack_with_formula(attacker, defender, ability, &FlatDefenseFormula)
+}
 
-    // Calculate new HP (healing reduces damage, thus increases HP)
-    let hp_after = defender.hp - dmg;
+/// [`compute_attack`] with each hit's base damage computed by `formula` instead of
+/// `attacker.attack + power - defender.defense`.
+///
+/// Resistances and the minimum of 1 damage per hit still apply.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_wasm_fsharp_ref_logic::*;
+///
+/// let player = Character::new_player("Hero");
+/// let monster = Character::new_monster("Slime");
+///
+/// // (10 + 5) * 10 / (10 + 1)
+/// let event = compute_attack_with_formula(
+///     &player,
+///     &monster,
+///     &Ability::basic_attack(),
+///     &PercentArmorFormula::default(),
+/// );
+/// assert_eq!(event.damage, 13);
+/// ```
+pub fn compute_attack_with_formula(
+    attacker: &Character,
+    defender: &Character,
+    ability: &Ability,
+    formula: &dyn DamageFormula,
+) -> CombatEvent {
+    resolve_hp_effects(attacker, defender, ability, formula, |dmg| (dmg, false))
+}
 
-    CombatEvent {
+/// Resolves the damage and healing effects of `ability` in order.
+///
+/// `roll` turns the damage of each hit (after resistances) into the damage dealt
+/// and whether it was critical. It is not called for hits the defender is immune to.
+fn resolve_hp_effects(
+    attacker: &Character,
+    defender: &Character,
+    ability: &Ability,
+    formula: &dyn DamageFormula,
+    mut roll: impl FnMut(i32) -> (i32, bool),
+) -> CombatEvent {
+    let multiplier = defender.stats.resistances.multiplier(ability.element);
+    let mut event = CombatEvent {
+        version: COMBAT_EVENT_VERSION,
         attacker_name: attacker.name.clone(),
         defender_name: defender.name.clone(),
-        damage: dmg,
-        defender_hp_after: hp_after,
+        damage: 0,
+        defender_hp_after: defender.hp,
+        effective_amount: 0,
+        overkill: 0,
+        overheal: 0,
+        killing_blow: false,
         ability_used: ability.name.clone(),
+        critical: false,
+        missed: false,
+        effectiveness: Effectiveness::Normal,
+        status_events: Vec::new(),
+        outcomes: Vec::new(),
+        xp_grants: Vec::new(),
+    };
+
+    // HP as the effects land, for the per-heal outcomes
+    let mut hp = defender.hp;
+    for effect in &ability.effects {
+        match *effect {
+            AbilityEffect::Damage { power } => {
+                // Calculate with defense, then apply the element multiplier
+                let base = formula.base_damage(attacker, defender, power).max(1);
+                let dmg = if multiplier == 0 {
+                    0
+                } else {
+                    let (dmg, critical) = roll((base * multiplier / NEUTRAL_MULTIPLIER).max(1));
+                    event.critical |= critical;
+                    dmg.max(1)
+                };
+                hp -= dmg;
+                event.damage += dmg;
+                event.effectiveness = Effectiveness::from_multiplier(multiplier);
+                event.outcomes.push(EffectOutcome::Damaged { amount: dmg });
+            }
+            AbilityEffect::Heal { amount } => {
+                // Healing bypasses defense; what doesn't fit under max HP is overheal
+                let healed = amount.min(defender.stats.hp - hp).max(0);
+                hp += healed;
+                event.damage -= amount;
+                event
+                    .outcomes
+                    .push(EffectOutcome::Healed { amount: healed });
+            }
+            // Resolved against the defender's status effects by apply_ability_status
+            AbilityEffect::Shield { .. } | AbilityEffect::ApplyStatus(_) => {}
+        }
     }
+
+    event.settle(defender.hp, defender.stats.hp);
+    event
 }
 
-/// Ability type enumeration for AI decision making.
+/// Critical hits deal this percentage of normal damage.
+pub const CRIT_DAMAGE_PERCENT: i32 = 150;
+
+/// Computes the result of an ability, rolling for evasion, variance and critical hits.
 ///
-/// Classifies abilities into categories to help the AI choose appropriate actions.
+/// This extends [`compute_attack`] with the random stats on [`Stats`]. All rolls are
+/// drawn from the supplied [`CombatRng`], so the same seed always produces the same
+/// outcome on every platform.
 ///
-/// # F# Equivalent
+/// # Resolution Order
 ///
-/// ```fsharp
-/// type AbilityType =
-///     | BasicAttack
-///     | PowerfulAttack
-///     | Heal
+/// ```text
+/// 1. Abilities aimed at allies or the caster never miss, vary or crit
+/// 2. Evasion:  defender.evade_chance % -> missed, 0 damage, one Evaded outcome
+/// 3. For each Damage effect:
+///    Base:     compute_attack damage, including the element multiplier
+///              (immune defenders take 0 and nothing further is rolled)
+///    Variance: base * (100 +/- attacker.damage_variance) / 100
+///    Critical: attacker.crit_chance % -> damage * CRIT_DAMAGE_PERCENT / 100
+///    Damage is always at least 1 for hits
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum AbilityType {
-    BasicAttack,
-    PowerfulAttack,
-    Heal,
-}
-
-/// Extended ability with type and cooldown support for AI system.
 ///
-/// Wraps an [`Ability`] with metadata needed for intelligent decision making.
+/// A roll is only drawn when the relevant stat is non-zero. With all three random
+/// stats at 0 this function returns exactly what [`compute_attack`] returns and
+/// leaves the generator untouched.
 ///
-/// # F# Equivalent
+/// # Examples
 ///
-/// ```fsharp
-/// type AbilityWithMeta =
-///     { Ability: Ability
-///       AbilityType: AbilityType
-///       Cooldown: int
-///       CurrentCooldown: int }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AbilityWithMeta {
-    /// The underlying ability.
-    pub ability: Ability,
-
-    /// Type classification for AI decision making.
-    pub ability_type: AbilityType,
-
-    /// Maximum cooldown turns.
-    pub cooldown: i32,
-
-    /// Current cooldown remaining (0 = ready to use).
-    pub current_cooldown: i32,
+/// use bevy_wasm_fsharp_ref_logic::*;
+///
+/// let mut player = Character::new_player("Hero");
+/// player.stats.crit_chance = 25;
+/// player.stats.damage_variance = 20;
+/// let monster = Character::new_monster("Slime");
+/// let ability = Ability::basic_attack();
+///
+/// let first = compute_attack_with_rng(&player, &monster, &ability, &mut SeededRng::new(7));
+/// let again = compute_attack_with_rng(&player, &monster, &ability, &mut SeededRng::new(7));
+///
+/// // Same seed, same outcome
+/// assert_eq!(first.damage, again.damage);
+/// assert_eq!(first.critical, again.critical);
+/// ```
+pub fn compute_attack_with_rng(
+    attacker: &Character,
+    defender: &Character,
+    ability: &Ability,
+    rng: &mut impl CombatRng,
+) -> CombatEvent {
+    compute_attack_with_formula_and_rng(attacker, defender, ability, &FlatDefenseFormula, rng)
 }
 
-impl AbilityWithMeta {
-    /// Creates a new ability with metadata.
-    pub fn new(
-        ability: Ability,
-        ability_type: AbilityType,
-        cooldown: i32,
-        current_cooldown: i32,
-    ) -> Self {
-        Self {
-            ability,
-            ability_type,
-            cooldown,
-            current_cooldown,
-        }
-    }
-
-    /// Creates a basic attack with metadata (no cooldown).
-    ///
-    /// # F# Equivalent
-    ///
-    /// ```fsharp
-    /// let basicAttackWithMeta =
-    ///     { Ability = basicAttack
-    ///       AbilityType = BasicAttack
-    ///       Cooldown = 0
-    ///       CurrentCooldown = 0 }
-    /// ```
-    pub fn basic_attack_meta() -> Self {
-        Self::new(Ability::basic_attack(), AbilityType::BasicAttack, 0, 0)
-    }
-
-    /// Creates a powerful attack with metadata (3-turn cooldown).
-    ///
-    /// # F# Equivalent
-    ///
-    /// ```fsharp
-    /// let powerfulAttackWithMeta =
-    ///     { Ability = powerfulAttack
-    ///       AbilityType = PowerfulAttack
-    ///       Cooldown = 3
-    ///       CurrentCooldown = 0 }
-    /// ```
-    pub fn powerful_attack_meta() -> Self {
-        Self::new(
-            Ability::powerful_attack(),
-            AbilityType::PowerfulAttack,
-            3,
-            0,
-        )
-    }
-
-    /// Creates a heal ability with metadata (4-turn cooldown).
-    ///
-    /// # F# Equivalent
-    ///
-    /// ```fsharp
-    /// let healAbilityWithMeta =
-    ///     { Ability = healAbility
-    ///       AbilityType = Heal
-    ///       Cooldown = 4
-    ///       CurrentCooldown = 0 }
-    /// ```
-    pub fn heal_meta() -> Self {
-        Self::new(Ability::heal(), AbilityType::Heal, 4, 0)
+/// [`compute_attack_with_rng`] with each hit's base damage computed by `formula`
+/// (see [`compute_attack_with_formula`]).
+pub fn compute_attack_with_formula_and_rng(
+    attacker: &Character,
+    defender: &Character,
+    ability: &Ability,
+    formula: &dyn DamageFormula,
+    rng: &mut impl CombatRng,
+) -> CombatEvent {
+    // Support abilities are not attacks, so nothing to roll
+    if !ability.target.is_offensive() {
+        return compute_attack_with_formula(attacker, defender, ability, formula);
     }
 
-    /// Checks if the ability is ready to use (not on cooldown).
-    pub fn is_ready(&self) -> bool {
-        self.current_cooldown == 0
+    if rng.chance(defender.stats.evade_chance) {
+        let mut event = compute_attack_with_formula(attacker, defender, ability, formula);
+        event.damage = 0;
+        event.missed = true;
+        event.outcomes = vec![EffectOutcome::Evaded];
+        event.settle(defender.hp, defender.stats.hp);
+        return event;
     }
 
-    /// Decrements the cooldown by 1 turn (minimum 0).
-    pub fn tick_cooldown(&mut self) {
-        self.current_cooldown = (self.current_cooldown - 1).max(0);
-    }
+    resolve_hp_effects(attacker, defender, ability, formula, |mut dmg| {
+        if attacker.stats.damage_variance > 0 {
+            let variance = attacker.stats.damage_variance;
+            let percent = rng.range_inclusive(100 - variance, 100 + variance);
+            dmg = dmg * percent / 100;
+        }
 
-    /// Activates the ability, setting it on cooldown.
-    pub fn activate(&mut self) {
-        self.current_cooldown = self.cooldown;
-    }
+        let critical = rng.chance(attacker.stats.crit_chance);
+        if critical {
+            dmg = dmg * CRIT_DAMAGE_PERCENT / 100;
+        }
+        (dmg, critical)
+    })
 }
 
 /// AI decision-making function for monster combat behavior.
@@ -877,9 +999,9 @@ impl AbilityWithMeta {
 /// # F# Equivalent
 ///
 /// ```fsharp
-/// let chooseMonsterAction (monster: Character) (_player: Character) (availableAbilities: AbilityWithMeta list) : Ability =
+/// let chooseMonsterAction (monster: Character) (_player: Character) (availableAbilities: AbilitySlot list) : Ability =
 ///     let hpPercent = float monster.Hp / float monster.Stats.Hp
-///     let usableAbilities = availableAbilities |> List.filter (fun a -> a.CurrentCooldown = 0)
+///     let usableAbilities = availableAbilities |> List.filter (fun a -> isReady a.Cooldown)
 ///
 ///     if hpPercent < 0.3 then
 ///         // Try heal, fall back to basic
@@ -907,31 +1029,27 @@ impl AbilityWithMeta {
 /// let monster = Character {
 ///     name: "Goblin".to_string(),
 ///     hp: 5,
-///     stats: Stats { hp: 20, attack: 6, defense: 1 },
+///     stats: Stats { hp: 20, attack: 6, defense: 1, ..Default::default() },
 /// };
 /// let player = Character::new_player("Hero");
-/// let abilities = vec![
-///     AbilityWithMeta::basic_attack_meta(),
-///     AbilityWithMeta::powerful_attack_meta(),
-///     AbilityWithMeta::heal_meta(),
-/// ];
+/// let abilities = AbilitySet::monster_turn_based();
 ///
-/// let chosen = choose_monster_action(&monster, &player, &abilities);
+/// let chosen = choose_monster_action(&monster, &player, &abilities.abilities);
 /// // Monster HP is 5/20 = 25% (< 30%), so AI will choose heal if available
 /// assert_eq!(chosen.name, "Heal");
 /// ```
 pub fn choose_monster_action(
     monster: &Character,
     _player: &Character,
-    available_abilities: &[AbilityWithMeta],
+    available_abilities: &[AbilitySlot],
 ) -> Ability {
     // Calculate monster's HP percentage
     let hp_percent = monster.hp as f32 / monster.stats.hp as f32;
 
     // Filter abilities that are not on cooldown
-    let usable: Vec<&AbilityWithMeta> = available_abilities
+    let usable: Vec<&AbilitySlot> = available_abilities
         .iter()
-        .filter(|a| a.current_cooldown == 0)
+        .filter(|a| a.is_ready())
         .collect();
 
     // Defensive strategy: HP < 30%, try to heal
@@ -1074,7 +1192,7 @@ mod tests {
         let ability = Ability::basic_attack();
 
         assert_eq!(ability.name, "Basic Attack");
-        assert_eq!(ability.power, 5);
+        assert_eq!(ability.power(), 5);
     }
 
     #[test]
@@ -1083,7 +1201,7 @@ mod tests {
         let ability2 = ability1.clone();
 
         assert_eq!(ability1.name, ability2.name);
-        assert_eq!(ability1.power, ability2.power);
+        assert_eq!(ability1.power(), ability2.power());
     }
 
     // ==================== compute_attack Tests ====================
@@ -1128,6 +1246,7 @@ mod tests {
                 hp: 10,
                 attack: 0,
                 defense: 0,
+                ..Default::default()
             },
         };
 
@@ -1139,6 +1258,7 @@ mod tests {
                 hp: 100,
                 attack: 5,
                 defense: 50,
+                ..Default::default()
             },
         };
 
@@ -1160,6 +1280,7 @@ mod tests {
                 hp: 15,
                 attack: 5,
                 defense: 0,
+                ..Default::default()
             },
         };
         let ability = Ability::basic_attack();
@@ -1175,20 +1296,18 @@ mod tests {
     fn test_compute_attack_high_power_ability() {
         let attacker = Character::new_player("Hero");
         let defender = Character::new_monster("Slime");
-        let powerful_ability = Ability {
-            name: "Mega Attack".into(),
-            power: 20,
-        };
+        let powerful_ability = Ability::damage("Mega Attack", 20);
 
         let event = compute_attack(&attacker, &defender, &powerful_ability);
 
         // Expected: 10 (attack) + 20 (ability) - 1 (defense) = 29 damage
         assert_eq!(event.damage, 29);
-        assert_eq!(event.defender_hp_after, -9); // 20 - 29 (negative HP allowed)
+        assert_eq!(event.defender_hp_after, 0); // 20 - 29, clamped at 0
+        assert_eq!(event.overkill, 9);
     }
 
     #[test]
-    fn test_compute_attack_negative_hp_after() {
+    fn test_compute_attack_overkill_is_clamped() {
         let attacker = Character::new_player("Hero");
         let defender = Character {
             name: "Wounded".into(),
@@ -1197,15 +1316,19 @@ mod tests {
                 hp: 20,
                 attack: 3,
                 defense: 1,
+                ..Default::default()
             },
         };
         let ability = Ability::basic_attack();
 
         let event = compute_attack(&attacker, &defender, &ability);
 
-        // Damage should exceed defender's current HP
+        // Damage exceeds the defender's current HP: HP stops at 0, the rest is overkill
         assert_eq!(event.damage, 14);
-        assert_eq!(event.defender_hp_after, -9); // 5 - 14
+        assert_eq!(event.defender_hp_after, 0);
+        assert_eq!(event.effective_amount, 5);
+        assert_eq!(event.overkill, 9); // 5 - 14
+        assert!(event.killing_blow);
     }
 
     #[test]
@@ -1218,6 +1341,7 @@ mod tests {
                 hp: 20,
                 attack: 3,
                 defense: 1,
+                ..Default::default()
             },
         };
         let ability = Ability::basic_attack();
@@ -1252,6 +1376,7 @@ mod tests {
                 hp: i32::MAX,
                 attack: i32::MAX,
                 defense: 0,
+                ..Default::default()
             },
         };
 
@@ -1262,10 +1387,7 @@ mod tests {
 
     #[test]
     fn test_ability_with_zero_power() {
-        let zero_power = Ability {
-            name: "Weak".into(),
-            power: 0,
-        };
+        let zero_power = Ability::damage("Weak", 0);
 
         let attacker = Character::new_player("Hero");
         let defender = Character::new_monster("Slime");
@@ -1278,20 +1400,65 @@ mod tests {
 
     #[test]
     fn test_ability_with_negative_power() {
-        let negative_power = Ability {
-            name: "Heal".into(),
-            power: -5,
-        };
+        let negative_power = Ability::damage("Drain", -5);
 
         let attacker = Character::new_player("Hero");
         let defender = Character::new_monster("Slime");
 
         let event = compute_attack(&attacker, &defender, &negative_power);
 
-        // Negative power is treated as healing (bypasses defense)
-        // Damage is -5 (which means +5 HP when applied)
+        // Negative power no longer heals: 10 - 5 - 1 = 4 damage
+        assert_eq!(event.damage, 4);
+        assert_eq!(event.outcomes, vec![EffectOutcome::Damaged { amount: 4 }]);
+    }
+
+    #[test]
+    fn test_heal_effect_bypasses_defense_and_caps_at_max_hp() {
+        let mut hero = Character::new_player("Hero");
+        hero.hp = 10;
+
+        let event = compute_attack(&hero, &hero, &Ability::healing("Mend", 5));
+
+        // Defense plays no part: the full 5 HP is restored
         assert_eq!(event.damage, -5);
-        assert_eq!(event.defender_hp_after, 25); // 20 - (-5) = 25
+        assert_eq!(event.defender_hp_after, 15);
+
+        hero.hp = 28;
+        let event = compute_attack(&hero, &hero, &Ability::healing("Mend", 5));
+
+        // Only 2 HP fit under the max of 30
+        assert_eq!(event.defender_hp_after, 30);
+        assert_eq!(event.outcomes, vec![EffectOutcome::Healed { amount: 2 }]);
+    }
+
+    #[test]
+    fn test_effects_resolve_in_order() {
+        let mut hero = Character::new_player("Hero");
+        hero.hp = 30;
+        let drain = Ability {
+            name: "Blood Pact".into(),
+            target: Targeting::Caster,
+            effects: vec![
+                AbilityEffect::Damage { power: 0 },
+                AbilityEffect::Heal { amount: 20 },
+            ],
+            ..Default::default()
+        };
+
+        let event = compute_attack(&hero, &hero, &drain);
+
+        // 10 attack - 2 defense = 8 damage, then healed back up to the max
+        assert_eq!(
+            event.outcomes,
+            vec![
+                EffectOutcome::Damaged { amount: 8 },
+                EffectOutcome::Healed { amount: 8 },
+            ]
+        );
+        assert_eq!(event.damage, -12); // 8 - 20
+        assert_eq!(event.defender_hp_after, 30);
+        assert_eq!(event.effective_amount, 0);
+        assert_eq!(event.overheal, 12);
     }
 
     #[test]
@@ -1303,6 +1470,7 @@ mod tests {
                 hp: 20,
                 attack: 5,
                 defense: 1,
+                ..Default::default()
             },
         };
 
@@ -1311,8 +1479,10 @@ mod tests {
 
         let event = compute_attack(&attacker, &dead_char, &ability);
 
-        // Attacking a dead character should still work
-        assert_eq!(event.defender_hp_after, -14); // 0 - 14
+        // Attacking a dead character should still work, but is not a killing blow
+        assert_eq!(event.defender_hp_after, 0);
+        assert_eq!(event.overkill, 14);
+        assert!(!event.killing_blow);
     }
 
     #[test]
@@ -1324,6 +1494,7 @@ mod tests {
                 hp: 20,
                 attack: 5,
                 defense: 1,
+                ..Default::default()
             },
         };
 
@@ -1332,8 +1503,10 @@ mod tests {
 
         let event = compute_attack(&attacker, &overkill, &ability);
 
-        // Should handle negative HP without panic
-        assert_eq!(event.defender_hp_after, -24); // -10 - 14
+        // Should handle negative HP without panic, and never lower it further
+        assert_eq!(event.defender_hp_after, -10);
+        assert_eq!(event.effective_amount, 0);
+        assert_eq!(event.overkill, 14);
     }
 
     // ==================== Serialization Tests ====================
@@ -1344,6 +1517,7 @@ mod tests {
             hp: 30,
             attack: 10,
             defense: 2,
+            ..Default::default()
         };
 
         // Test that Stats can be serialized
@@ -1377,7 +1551,7 @@ mod tests {
         let deserialized: Ability = serde_json::from_str(&json).expect("Should deserialize");
 
         assert_eq!(deserialized.name, ability.name);
-        assert_eq!(deserialized.power, ability.power);
+        assert_eq!(deserialized.power(), ability.power());
     }
 
     #[test]
@@ -1416,7 +1590,7 @@ mod tests {
         let cloned = original.clone();
 
         assert_eq!(original.name, cloned.name);
-        assert_eq!(original.power, cloned.power);
+        assert_eq!(original.power(), cloned.power());
     }
 
     #[test]
@@ -1427,6 +1601,12 @@ mod tests {
             damage: 10,
             defender_hp_after: 5,
             ability_used: "Test Ability".into(),
+            critical: false,
+            missed: false,
+            effectiveness: Effectiveness::Normal,
+            status_events: Vec::new(),
+            outcomes: vec![EffectOutcome::Damaged { amount: 10 }],
+            ..Default::default()
         };
 
         let cloned = event.clone();
@@ -1443,13 +1623,7 @@ mod tests {
     #[test]
     fn test_damage_always_positive() {
         // Test with various random-ish combinations
-        let test_cases = vec![
-            (0, 0, 0),
-            (1, 1, 1),
-            (10, 5, 2),
-            (100, 50, 25),
-            (5, 20, 10),
-        ];
+        let test_cases = vec![(0, 0, 0), (1, 1, 1), (10, 5, 2), (100, 50, 25), (5, 20, 10)];
 
         for (attack, power, defense) in test_cases {
             let attacker = Character {
@@ -1459,6 +1633,7 @@ mod tests {
                     hp: 100,
                     attack,
                     defense: 0,
+                    ..Default::default()
                 },
             };
 
@@ -1469,13 +1644,11 @@ mod tests {
                     hp: 100,
                     attack: 0,
                     defense,
+                    ..Default::default()
                 },
             };
 
-            let ability = Ability {
-                name: "Test".into(),
-                power,
-            };
+            let ability = Ability::damage("Test", power);
 
             let event = compute_attack(&attacker, &defender, &ability);
 
@@ -1512,6 +1685,7 @@ mod tests {
                 hp: 50,
                 attack: 15,
                 defense: 0,
+                ..Default::default()
             },
         };
 
@@ -1522,23 +1696,253 @@ mod tests {
                 hp: 50,
                 attack: 0,
                 defense: 3,
+                ..Default::default()
             },
         };
 
-        let ability = Ability {
-            name: "Test".into(),
-            power: 7,
-        };
+        let ability = Ability::damage("Test", 7);
 
         let event = compute_attack(&attacker, &defender, &ability);
 
         // Manually calculate expected damage
-        let expected_raw = attacker.stats.attack + ability.power; // 15 + 7 = 22
+        let expected_raw = attacker.stats.attack + ability.power(); // 15 + 7 = 22
         let expected_damage = (expected_raw - defender.stats.defense).max(1); // (22 - 3).max(1) = 19
 
         assert_eq!(event.damage, expected_damage);
     }
 
+    // ==================== compute_attack_with_rng Tests ====================
+
+    #[test]
+    fn test_compute_attack_with_rng_zero_variance_matches_compute_attack() {
+        let attacker = Character::new_player("Hero");
+        let defender = Character::new_monster("Slime");
+        let ability = Ability::basic_attack();
+        let mut rng = SeededRng::new(42);
+        let rng_before = rng.clone();
+
+        let expected = compute_attack(&attacker, &defender, &ability);
+        let event = compute_attack_with_rng(&attacker, &defender, &ability, &mut rng);
+
+        assert_eq!(event.damage, expected.damage);
+        assert_eq!(event.defender_hp_after, expected.defender_hp_after);
+        assert!(!event.critical);
+        assert!(!event.missed);
+        // No random stats means no rolls were drawn
+        assert_eq!(rng, rng_before);
+    }
+
+    #[test]
+    fn test_compute_attack_with_rng_same_seed_same_outcome() {
+        let mut attacker = Character::new_player("Hero");
+        attacker.stats.crit_chance = 30;
+        attacker.stats.damage_variance = 25;
+        let mut defender = Character::new_monster("Slime");
+        defender.stats.evade_chance = 20;
+        let ability = Ability::basic_attack();
+
+        let mut rng_a = SeededRng::new(2024);
+        let mut rng_b = SeededRng::new(2024);
+
+        for _ in 0..50 {
+            let a = compute_attack_with_rng(&attacker, &defender, &ability, &mut rng_a);
+            let b = compute_attack_with_rng(&attacker, &defender, &ability, &mut rng_b);
+
+            assert_eq!(a.damage, b.damage);
+            assert_eq!(a.critical, b.critical);
+            assert_eq!(a.missed, b.missed);
+        }
+    }
+
+    #[test]
+    fn test_compute_attack_with_rng_guaranteed_crit() {
+        let mut attacker = Character::new_player("Hero");
+        attacker.stats.crit_chance = 100;
+        let defender = Character::new_monster("Slime");
+        let ability = Ability::basic_attack();
+
+        let event = compute_attack_with_rng(&attacker, &defender, &ability, &mut SeededRng::new(1));
+
+        // 14 base damage * 150% = 21
+        assert!(event.critical);
+        assert_eq!(event.damage, 21);
+        assert_eq!(event.defender_hp_after, 0);
+        assert_eq!(event.overkill, 1);
+    }
+
+    #[test]
+    fn test_compute_attack_with_rng_guaranteed_evade() {
+        let attacker = Character::new_player("Hero");
+        let mut defender = Character::new_monster("Slime");
+        defender.stats.evade_chance = 100;
+        let ability = Ability::basic_attack();
+
+        let event = compute_attack_with_rng(&attacker, &defender, &ability, &mut SeededRng::new(1));
+
+        assert!(event.missed);
+        assert!(!event.critical);
+        assert_eq!(event.damage, 0);
+        assert_eq!(event.defender_hp_after, defender.hp);
+    }
+
+    #[test]
+    fn test_compute_attack_with_rng_variance_stays_in_range() {
+        let mut attacker = Character::new_player("Hero");
+        attacker.stats.damage_variance = 50;
+        let defender = Character::new_monster("Slime");
+        let ability = Ability::basic_attack();
+        let mut rng = SeededRng::new(77);
+
+        let damages: Vec<i32> = (0..200)
+            .map(|_| compute_attack_with_rng(&attacker, &defender, &ability, &mut rng).damage)
+            .collect();
+
+        // 14 base damage, +/-50% => 7..=21
+        assert!(damages.iter().all(|d| (7..=21).contains(d)));
+        assert!(damages.iter().any(|&d| d != 14));
+    }
+
+    #[test]
+    fn test_compute_attack_with_rng_heal_ignores_rolls() {
+        let attacker = Character::new_player("Hero");
+        let mut defender = Character::new_monster("Slime");
+        defender.stats.evade_chance = 100;
+        defender.hp = 5;
+
+        let event = compute_attack_with_rng(
+            &attacker,
+            &defender,
+            &Ability::heal(),
+            &mut SeededRng::new(3),
+        );
+
+        assert!(!event.missed);
+        assert_eq!(event.damage, -8);
+    }
+
+    #[test]
+    fn test_random_stats_default_when_missing_from_json() {
+        let json = r#"{"hp":30,"attack":10,"defense":2}"#;
+        let stats: Stats = serde_json::from_str(json).expect("Should deserialize");
+
+        assert_eq!(stats.crit_chance, 0);
+        assert_eq!(stats.evade_chance, 0);
+        assert_eq!(stats.damage_variance, 0);
+    }
+
+    // ==================== Elemental Damage Tests ====================
+
+    fn fire_bolt() -> Ability {
+        Ability {
+            element: Element::Fire,
+            ..Ability::damage("Fire Bolt", 5)
+        }
+    }
+
+    #[test]
+    fn test_neutral_element_matches_base_damage() {
+        let attacker = Character::new_player("Hero");
+        let defender = Character::new_monster("Slime");
+
+        let event = compute_attack(&attacker, &defender, &fire_bolt());
+
+        assert_eq!(event.damage, 14);
+        assert_eq!(event.effectiveness, Effectiveness::Normal);
+    }
+
+    #[test]
+    fn test_weakness_multiplies_damage() {
+        let attacker = Character::new_player("Hero");
+        let mut defender = Character::new_monster("Ice Slime");
+        defender.stats.resistances = Resistances::default().with(Element::Fire, 150);
+
+        let event = compute_attack(&attacker, &defender, &fire_bolt());
+
+        // (10 + 5 - 1) * 150 / 100 = 21
+        assert_eq!(event.damage, 21);
+        assert_eq!(event.defender_hp_after, 0);
+        assert_eq!(event.effectiveness, Effectiveness::SuperEffective);
+    }
+
+    #[test]
+    fn test_resistance_keeps_minimum_damage() {
+        let attacker = Character::new_player("Hero");
+        let mut defender = Character::new_monster("Fire Elemental");
+        defender.stats.defense = 20;
+        defender.stats.resistances = Resistances::default().with(Element::Fire, 25);
+
+        let event = compute_attack(&attacker, &defender, &fire_bolt());
+
+        assert_eq!(event.damage, 1);
+        assert_eq!(event.effectiveness, Effectiveness::Resisted);
+    }
+
+    #[test]
+    fn test_immunity_deals_no_damage() {
+        let attacker = Character::new_player("Hero");
+        let mut defender = Character::new_monster("Fire Elemental");
+        defender.stats.resistances = Resistances::default().with(Element::Fire, 0);
+
+        let event = compute_attack(&attacker, &defender, &fire_bolt());
+
+        assert_eq!(event.damage, 0);
+        assert_eq!(event.defender_hp_after, defender.hp);
+        assert_eq!(event.effectiveness, Effectiveness::Immune);
+    }
+
+    #[test]
+    fn test_resistance_only_applies_to_matching_element() {
+        let attacker = Character::new_player("Hero");
+        let mut defender = Character::new_monster("Fire Elemental");
+        defender.stats.resistances = Resistances::default().with(Element::Fire, 0);
+
+        let event = compute_attack(&attacker, &defender, &Ability::basic_attack());
+
+        assert_eq!(event.damage, 14);
+        assert_eq!(event.effectiveness, Effectiveness::Normal);
+    }
+
+    #[test]
+    fn test_healing_ignores_resistances() {
+        let caster = Character::new_monster("Shaman");
+        let mut target = Character::new_monster("Ghoul");
+        target.stats.resistances = Resistances::default().with(Element::Physical, 0);
+        target.hp = 5;
+
+        let event = compute_attack(&caster, &target, &Ability::heal());
+
+        assert_eq!(event.damage, -8);
+        assert_eq!(event.effectiveness, Effectiveness::Normal);
+    }
+
+    #[test]
+    fn test_immunity_is_not_upgraded_by_crit() {
+        let mut attacker = Character::new_player("Hero");
+        attacker.stats.crit_chance = 100;
+        attacker.stats.damage_variance = 50;
+        let mut defender = Character::new_monster("Fire Elemental");
+        defender.stats.resistances = Resistances::default().with(Element::Fire, 0);
+
+        let event =
+            compute_attack_with_rng(&attacker, &defender, &fire_bolt(), &mut SeededRng::new(1));
+
+        assert_eq!(event.damage, 0);
+        assert!(!event.critical);
+        assert_eq!(event.effectiveness, Effectiveness::Immune);
+    }
+
+    #[test]
+    fn test_element_defaults_when_missing_from_json() {
+        let ability: Ability =
+            serde_json::from_str(r#"{"name":"Slash","effects":[{"Damage":{"power":4}}]}"#)
+                .expect("Should deserialize");
+        let stats: Stats = serde_json::from_str(r#"{"hp":30,"attack":10,"defense":2}"#)
+            .expect("Should deserialize");
+
+        assert_eq!(ability.element, Element::Physical);
+        assert!(stats.resistances.0.is_empty());
+    }
+
     // ==================== AI Decision Tests ====================
 
     #[test]
@@ -1551,13 +1955,14 @@ mod tests {
                 hp: 20,
                 attack: 6,
                 defense: 1,
+                ..Default::default()
             },
         };
         let player = Character::new_player("Hero");
         let abilities = vec![
-            AbilityWithMeta::basic_attack_meta(),
-            AbilityWithMeta::powerful_attack_meta(),
-            AbilityWithMeta::heal_meta(),
+            AbilitySlot::basic_attack_turns(),
+            AbilitySlot::powerful_attack_turns(),
+            AbilitySlot::heal_turns(),
         ];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
@@ -1571,9 +1976,9 @@ mod tests {
         let monster = Character::new_monster("Slime");
         let player = Character::new_player("Hero");
         let abilities = vec![
-            AbilityWithMeta::basic_attack_meta(),
-            AbilityWithMeta::powerful_attack_meta(),
-            AbilityWithMeta::heal_meta(),
+            AbilitySlot::basic_attack_turns(),
+            AbilitySlot::powerful_attack_turns(),
+            AbilitySlot::heal_turns(),
         ];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
@@ -1591,13 +1996,14 @@ mod tests {
                 hp: 20,
                 attack: 6,
                 defense: 1,
+                ..Default::default()
             },
         };
         let player = Character::new_player("Hero");
         let abilities = vec![
-            AbilityWithMeta::basic_attack_meta(),
-            AbilityWithMeta::powerful_attack_meta(),
-            AbilityWithMeta::heal_meta(),
+            AbilitySlot::basic_attack_turns(),
+            AbilitySlot::powerful_attack_turns(),
+            AbilitySlot::heal_turns(),
         ];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
@@ -1615,14 +2021,15 @@ mod tests {
                 hp: 20,
                 attack: 6,
                 defense: 1,
+                ..Default::default()
             },
         };
         let player = Character::new_player("Hero");
-        let mut heal_ability = AbilityWithMeta::heal_meta();
-        heal_ability.current_cooldown = 2; // On cooldown
+        let mut heal_ability = AbilitySlot::heal_turns();
+        heal_ability.use_ability(); // On cooldown
         let abilities = vec![
-            AbilityWithMeta::basic_attack_meta(),
-            AbilityWithMeta::powerful_attack_meta(),
+            AbilitySlot::basic_attack_turns(),
+            AbilitySlot::powerful_attack_turns(),
             heal_ability,
         ];
 
@@ -1636,12 +2043,12 @@ mod tests {
         // Monster at 100% HP with powerful on cooldown should fall back to basic
         let monster = Character::new_monster("Slime");
         let player = Character::new_player("Hero");
-        let mut powerful_ability = AbilityWithMeta::powerful_attack_meta();
-        powerful_ability.current_cooldown = 1; // On cooldown
+        let mut powerful_ability = AbilitySlot::powerful_attack_turns();
+        powerful_ability.use_ability(); // On cooldown
         let abilities = vec![
-            AbilityWithMeta::basic_attack_meta(),
+            AbilitySlot::basic_attack_turns(),
             powerful_ability,
-            AbilityWithMeta::heal_meta(),
+            AbilitySlot::heal_turns(),
         ];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
@@ -1654,12 +2061,12 @@ mod tests {
         // Even with no abilities, should return basic attack fallback
         let monster = Character::new_monster("Slime");
         let player = Character::new_player("Hero");
-        let abilities: Vec<AbilityWithMeta> = vec![];
+        let abilities: Vec<AbilitySlot> = vec![];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
 
         assert_eq!(chosen.name, "Basic Attack");
-        assert_eq!(chosen.power, 5);
+        assert_eq!(chosen.power(), 5);
     }
 
     #[test]
@@ -1667,12 +2074,12 @@ mod tests {
         // All abilities on cooldown should use emergency fallback
         let monster = Character::new_monster("Slime");
         let player = Character::new_player("Hero");
-        let mut basic = AbilityWithMeta::basic_attack_meta();
-        basic.current_cooldown = 1;
-        let mut powerful = AbilityWithMeta::powerful_attack_meta();
-        powerful.current_cooldown = 2;
-        let mut heal = AbilityWithMeta::heal_meta();
-        heal.current_cooldown = 3;
+        let mut basic = AbilitySlot::basic_attack_turns();
+        basic.use_ability();
+        let mut powerful = AbilitySlot::powerful_attack_turns();
+        powerful.use_ability();
+        let mut heal = AbilitySlot::heal_turns();
+        heal.use_ability();
         let abilities = vec![basic, powerful, heal];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
@@ -1690,13 +2097,14 @@ mod tests {
                 hp: 20,
                 attack: 6,
                 defense: 1,
+                ..Default::default()
             },
         };
         let player = Character::new_player("Hero");
         let abilities = vec![
-            AbilityWithMeta::basic_attack_meta(),
-            AbilityWithMeta::powerful_attack_meta(),
-            AbilityWithMeta::heal_meta(),
+            AbilitySlot::basic_attack_turns(),
+            AbilitySlot::powerful_attack_turns(),
+            AbilitySlot::heal_turns(),
         ];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
@@ -1715,13 +2123,14 @@ mod tests {
                 hp: 20,
                 attack: 6,
                 defense: 1,
+                ..Default::default()
             },
         };
         let player = Character::new_player("Hero");
         let abilities = vec![
-            AbilityWithMeta::basic_attack_meta(),
-            AbilityWithMeta::powerful_attack_meta(),
-            AbilityWithMeta::heal_meta(),
+            AbilitySlot::basic_attack_turns(),
+            AbilitySlot::powerful_attack_turns(),
+            AbilitySlot::heal_turns(),
         ];
 
         let chosen = choose_monster_action(&monster, &player, &abilities);
@@ -1731,31 +2140,82 @@ mod tests {
     }
 
     #[test]
-    fn test_ability_with_meta_is_ready() {
-        let mut ability = AbilityWithMeta::powerful_attack_meta();
+    fn test_turn_based_slot_is_ready() {
+        let mut ability = AbilitySlot::powerful_attack_turns();
 
         assert!(ability.is_ready());
 
-        ability.activate();
+        ability.use_ability();
         assert!(!ability.is_ready());
-        assert_eq!(ability.current_cooldown, 3);
-
-        ability.tick_cooldown();
-        assert_eq!(ability.current_cooldown, 2);
+        assert_eq!(
+            ability.cooldown,
+            Cooldown::Turns {
+                max: 3,
+                remaining: 3
+            }
+        );
+
+        ability.tick_turn();
+        assert_eq!(
+            ability.cooldown,
+            Cooldown::Turns {
+                max: 3,
+                remaining: 2
+            }
+        );
         assert!(!ability.is_ready());
 
-        ability.tick_cooldown();
-        ability.tick_cooldown();
-        assert_eq!(ability.current_cooldown, 0);
+        ability.tick_turn();
+        ability.tick_turn();
         assert!(ability.is_ready());
     }
 
     #[test]
-    fn test_ability_with_meta_tick_does_not_go_negative() {
-        let mut ability = AbilityWithMeta::basic_attack_meta();
+    fn test_turn_based_slot_tick_does_not_go_negative() {
+        let mut ability = AbilitySlot::basic_attack_turns();
+
+        ability.tick_turn();
+        assert_eq!(
+            ability.cooldown,
+            Cooldown::Turns {
+                max: 0,
+                remaining: 0
+            }
+        ); // Should not go negative
+    }
+
+    #[test]
+    fn test_choose_monster_action_works_with_real_time_cooldowns() {
+        let monster = Character::new_monster("Goblin"); // 100% HP
+        let player = Character::new_player("Hero");
+        let mut abilities = vec![
+            AbilitySlot::new(
+                Ability::basic_attack(),
+                AbilityType::BasicAttack,
+                Cooldown::seconds(1.0),
+            ),
+            AbilitySlot::new(
+                Ability::powerful_attack(),
+                AbilityType::PowerfulAttack,
+                Cooldown::seconds(3.0),
+            ),
+        ];
 
-        assert_eq!(ability.current_cooldown, 0);
-        ability.tick_cooldown();
-        assert_eq!(ability.current_cooldown, 0); // Should not go negative
+        assert_eq!(
+            choose_monster_action(&monster, &player, &abilities).name,
+            "Powerful Attack"
+        );
+
+        abilities[1].use_ability();
+        assert_eq!(
+            choose_monster_action(&monster, &player, &abilities).name,
+            "Basic Attack"
+        );
+
+        abilities[1].tick(Millis::from_secs(3));
+        assert_eq!(
+            choose_monster_action(&monster, &player, &abilities).name,
+            "Powerful Attack"
+        );
     }
 }
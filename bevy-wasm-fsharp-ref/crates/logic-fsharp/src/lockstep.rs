@@ -0,0 +1,735 @@
+//! Lockstep duels between two peers.
+//!
+//! In a lockstep duel both peers simulate the whole [`Encounter`] and exchange
+//! only their inputs. For every turn each peer sends one [`TurnInput`]: the
+//! peer whose combatant acts sends its [`Action`], the other an empty input, and
+//! both attach the [`checksum`] of the state the turn starts from. Once a peer
+//! holds both inputs for a turn it compares the checksums and plays the action
+//! through [`reduce_with_rng`], drawing from a [`SeededRng`] both peers started
+//! from the same seed. Once the duel is over each peer sends one more empty
+//! input carrying the checksum of the final state, so the last turn is checked
+//! too. The rules are deterministic, so the peers stay in step; a checksum
+//! mismatch means they did not, and stops the duel with
+//! [`LockstepError::Desync`].
+//!
+//! Inputs travel over any [`Transport`]. With the `std` feature,
+//! [`ChannelTransport`] connects two sessions in one process and
+//! [`TcpTransport`] two processes, one JSON input per line.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type TurnInput =
+//!     { Turn: uint32
+//!       Action: Action option
+//!       Checksum: uint64 }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! # // `ChannelTransport` needs `std`
+//! # #[cfg(feature = "std")]
+//! # {
+//! let duel = Encounter::new(vec![
+//!     Combatant::new(Team::Allies, Character::new_player("Hero"), AbilitySet::monster_turn_based()),
+//!     Combatant::new(Team::Enemies, Character::new_monster("Slime"), AbilitySet::monster_turn_based()),
+//! ]);
+//! let (near, far) = ChannelTransport::pair();
+//! let mut hero = Lockstep::new(duel.clone(), 7, Team::Allies, near);
+//! let mut slime = Lockstep::new(duel, 7, Team::Enemies, far);
+//!
+//! // The hero is faster and acts first; the slime's peer only acknowledges
+//! hero.submit(Action::UseAbility {
+//!     ability: "Basic Attack".to_string(),
+//!     target: CombatantId(1),
+//! })
+//! .unwrap();
+//! slime.poll().unwrap();
+//! let events = hero.poll().unwrap();
+//! slime.poll().unwrap();
+//!
+//! assert_eq!(events[0].attacker_name, "Hero");
+//! assert_eq!(hero.turn(), 1);
+//! assert_eq!(slime.turn(), 1);
+//! assert_eq!(hero.checksum(), slime.checksum());
+//! # }
+//! ```
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{BufRead, BufReader, Write};
+#[cfg(feature = "std")]
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::{
+    reduce_with_rng, Action, CombatEvent, Encounter, EncounterError, EncounterOutcome, SeededRng,
+    Team,
+};
+
+/// One peer's input for one turn of a [`Lockstep`] duel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnInput {
+    /// The turn this input is for, counting from 0.
+    pub turn: u32,
+
+    /// The action of the combatant whose turn it is, sent only by the peer
+    /// that controls it. Empty in the closing input sent once the duel is over.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+
+    /// [`checksum`] of the sender's state before the turn, or of the final
+    /// state in the closing input.
+    pub checksum: u64,
+}
+
+/// Errors that stop a [`Lockstep`] duel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LockstepError {
+    /// The transport failed to send or receive.
+    Io { message: String },
+
+    /// The peer sent data that is not a [`TurnInput`].
+    Parse { message: String },
+
+    /// The peer has gone away.
+    Disconnected,
+
+    /// An action was submitted while no local combatant was waiting to act.
+    NotYourTurn,
+
+    /// An action was refused by the rules.
+    Rejected { turn: u32, error: EncounterError },
+
+    /// The peer sent an input for a turn other than the current one.
+    OutOfOrder { expected: u32, got: u32 },
+
+    /// The peer sent an action when it was not its turn, or none when it was.
+    OutOfTurn { turn: u32 },
+
+    /// The peers' states differed at the start of `turn`, or at the end of the
+    /// duel when `turn` is the number of turns played.
+    Desync { turn: u32, local: u64, remote: u64 },
+}
+
+impl fmt::Display for LockstepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockstepError::Io { message } => write!(f, "transport error: {}", message),
+            LockstepError::Parse { message } => write!(f, "invalid input: {}", message),
+            LockstepError::Disconnected => write!(f, "the peer disconnected"),
+            LockstepError::NotYourTurn => write!(f, "it is not a local combatant's turn"),
+            LockstepError::Rejected { turn, error } => {
+                write!(f, "turn #{} was rejected: {}", turn, error)
+            }
+            LockstepError::OutOfOrder { expected, got } => write!(
+                f,
+                "expected an input for turn #{}, got turn #{}",
+                expected, got
+            ),
+            LockstepError::OutOfTurn { turn } => {
+                write!(f, "the peer acted out of turn on turn #{}", turn)
+            }
+            LockstepError::Desync {
+                turn,
+                local,
+                remote,
+            } => write!(
+                f,
+                "desync at turn #{}: local state {:016x}, peer state {:016x}",
+                turn, local, remote
+            ),
+        }
+    }
+}
+
+impl core::error::Error for LockstepError {}
+
+/// Carries [`TurnInput`]s between the two peers of a [`Lockstep`] duel.
+///
+/// Inputs must arrive in the order they were sent.
+pub trait Transport {
+    /// Sends `input` to the peer.
+    fn send(&mut self, input: &TurnInput) -> Result<(), LockstepError>;
+
+    /// The next input from the peer, or `None` if none has arrived yet. Never
+    /// blocks.
+    fn try_recv(&mut self) -> Result<Option<TurnInput>, LockstepError>;
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&mut self, input: &TurnInput) -> Result<(), LockstepError> {
+        (**self).send(input)
+    }
+
+    fn try_recv(&mut self) -> Result<Option<TurnInput>, LockstepError> {
+        (**self).try_recv()
+    }
+}
+
+/// A 64-bit FNV-1a hash of `state` and `rng`, the same on every platform.
+///
+/// Both are hashed through their JSON form, which has no floats and orders its
+/// maps, so equal states always give equal checksums.
+pub fn checksum(state: &Encounter, rng: &SeededRng) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let bytes = serde_json::to_vec(&(state, rng)).expect("encounters always serialize");
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// One peer's side of a lockstep duel.
+///
+/// The local peer controls the combatants on its [`Team`]. Call
+/// [`submit`](Self::submit) when one of them is waiting to act, and
+/// [`poll`](Self::poll) regularly, such as once a frame, to exchange inputs and
+/// play every turn both peers have sent.
+#[derive(Debug)]
+pub struct Lockstep<T> {
+    state: Encounter,
+    rng: SeededRng,
+    local: Team,
+    turn: u32,
+    local_input: Option<TurnInput>,
+    remote_input: Option<TurnInput>,
+    confirmed: bool,
+    transport: T,
+}
+
+impl<T: Transport> Lockstep<T> {
+    /// Starts a duel from `state`. Both peers must pass the same state and
+    /// `seed`, and opposite teams.
+    pub fn new(state: Encounter, seed: u64, local: Team, transport: T) -> Self {
+        Self {
+            state,
+            rng: SeededRng::new(seed),
+            local,
+            turn: 0,
+            local_input: None,
+            remote_input: None,
+            confirmed: false,
+            transport,
+        }
+    }
+
+    /// The state after every turn played so far.
+    pub fn state(&self) -> &Encounter {
+        &self.state
+    }
+
+    /// The number of turns played.
+    pub fn turn(&self) -> u32 {
+        self.turn
+    }
+
+    /// The team the local peer controls.
+    pub fn local_team(&self) -> Team {
+        self.local
+    }
+
+    /// [`checksum`] of the current state and generator.
+    pub fn checksum(&self) -> u64 {
+        checksum(&self.state, &self.rng)
+    }
+
+    /// Whether the duel has been won or lost.
+    pub fn is_over(&self) -> bool {
+        self.state.outcome() != EncounterOutcome::Ongoing
+    }
+
+    /// Whether the duel is over and the peer has confirmed it ended in the same
+    /// state. Nothing is left to exchange after that.
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed
+    }
+
+    /// Whether a local combatant is acting and has not submitted its action.
+    pub fn awaiting_local_action(&self) -> bool {
+        self.local_input.is_none() && self.expects_local_action()
+    }
+
+    /// Sends the acting local combatant's `action` to the peer. The turn is
+    /// played by a later [`poll`](Self::poll), once the peer has answered.
+    ///
+    /// Actions the rules refuse are not sent.
+    pub fn submit(&mut self, action: Action) -> Result<(), LockstepError> {
+        if !self.awaiting_local_action() {
+            return Err(LockstepError::NotYourTurn);
+        }
+        reduce_with_rng(&self.state, &action, &mut self.rng.clone()).map_err(|error| {
+            LockstepError::Rejected {
+                turn: self.turn,
+                error,
+            }
+        })?;
+        self.send_input(Some(action))
+    }
+
+    /// Exchanges inputs with the peer and plays every turn both have sent.
+    /// Once the duel is over, checks the final state with the peer.
+    ///
+    /// Returns the events of the turns played, in order.
+    pub fn poll(&mut self) -> Result<Vec<CombatEvent>, LockstepError> {
+        let mut events = Vec::new();
+        loop {
+            if self.confirmed {
+                return Ok(events);
+            }
+            // The peer that is not acting acknowledges the turn straight away,
+            // and both close the duel once it is over
+            if self.local_input.is_none() && !self.expects_local_action() {
+                self.send_input(None)?;
+            }
+            if self.local_input.is_some() && self.remote_input.is_some() {
+                events.extend(self.advance()?);
+                continue;
+            }
+            match self.transport.try_recv()? {
+                Some(input) => self.receive(input)?,
+                None => return Ok(events),
+            }
+        }
+    }
+
+    /// Whether this turn's action comes from the local peer, rather than the
+    /// remote one or nobody once the duel is over.
+    fn expects_local_action(&self) -> bool {
+        !self.is_over() && self.local_acts()
+    }
+
+    /// Whether this turn's action comes from the remote peer.
+    fn expects_remote_action(&self) -> bool {
+        !self.is_over() && !self.local_acts()
+    }
+
+    fn local_acts(&self) -> bool {
+        self.state
+            .current()
+            .and_then(|id| self.state.combatant(id))
+            .is_some_and(|combatant| combatant.team == self.local)
+    }
+
+    fn send_input(&mut self, action: Option<Action>) -> Result<(), LockstepError> {
+        let input = TurnInput {
+            turn: self.turn,
+            action,
+            checksum: self.checksum(),
+        };
+        self.transport.send(&input)?;
+        self.local_input = Some(input);
+        Ok(())
+    }
+
+    fn receive(&mut self, input: TurnInput) -> Result<(), LockstepError> {
+        if input.turn != self.turn || self.remote_input.is_some() {
+            return Err(LockstepError::OutOfOrder {
+                expected: self.turn,
+                got: input.turn,
+            });
+        }
+        if input.action.is_some() != self.expects_remote_action() {
+            return Err(LockstepError::OutOfTurn { turn: self.turn });
+        }
+        self.remote_input = Some(input);
+        Ok(())
+    }
+
+    /// Plays the current turn from both peers' inputs, or confirms the final
+    /// state once the duel is over.
+    fn advance(&mut self) -> Result<Vec<CombatEvent>, LockstepError> {
+        let (Some(local), Some(remote)) = (self.local_input.take(), self.remote_input.take())
+        else {
+            return Ok(Vec::new());
+        };
+        if local.checksum != remote.checksum {
+            return Err(LockstepError::Desync {
+                turn: self.turn,
+                local: local.checksum,
+                remote: remote.checksum,
+            });
+        }
+        if self.is_over() {
+            self.confirmed = true;
+            return Ok(Vec::new());
+        }
+        // `receive` checked that exactly one of the inputs has an action
+        let Some(action) = local.action.or(remote.action) else {
+            return Err(LockstepError::OutOfTurn { turn: self.turn });
+        };
+
+        let (next, events) =
+            reduce_with_rng(&self.state, &action, &mut self.rng).map_err(|error| {
+                LockstepError::Rejected {
+                    turn: self.turn,
+                    error,
+                }
+            })?;
+        self.state = next;
+        self.turn += 1;
+        Ok(events)
+    }
+}
+
+/// An in-memory [`Transport`] between two sessions in the same process.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ChannelTransport {
+    outgoing: Sender<TurnInput>,
+    incoming: Receiver<TurnInput>,
+}
+
+#[cfg(feature = "std")]
+impl ChannelTransport {
+    /// Two transports connected to each other.
+    pub fn pair() -> (Self, Self) {
+        let (near_tx, far_rx) = mpsc::channel();
+        let (far_tx, near_rx) = mpsc::channel();
+        (
+            Self {
+                outgoing: near_tx,
+                incoming: near_rx,
+            },
+            Self {
+                outgoing: far_tx,
+                incoming: far_rx,
+            },
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Transport for ChannelTransport {
+    fn send(&mut self, input: &TurnInput) -> Result<(), LockstepError> {
+        self.outgoing
+            .send(input.clone())
+            .map_err(|_| LockstepError::Disconnected)
+    }
+
+    fn try_recv(&mut self) -> Result<Option<TurnInput>, LockstepError> {
+        match self.incoming.try_recv() {
+            Ok(input) => Ok(Some(input)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(LockstepError::Disconnected),
+        }
+    }
+}
+
+/// A [`Transport`] over a TCP connection, sending one JSON [`TurnInput`] per
+/// line.
+///
+/// A background thread reads the connection, so
+/// [`try_recv`](Transport::try_recv) never blocks.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct TcpTransport {
+    stream: TcpStream,
+    incoming: Receiver<Result<TurnInput, LockstepError>>,
+}
+
+#[cfg(feature = "std")]
+impl TcpTransport {
+    /// Connects to a peer waiting in [`accept`](Self::accept).
+    pub fn connect(addr: impl ToSocketAddrs) -> Result<Self, LockstepError> {
+        Self::new(TcpStream::connect(addr).map_err(io_error)?)
+    }
+
+    /// Waits for a peer to [`connect`](Self::connect) to `listener`.
+    pub fn accept(listener: &TcpListener) -> Result<Self, LockstepError> {
+        let (stream, _) = listener.accept().map_err(io_error)?;
+        Self::new(stream)
+    }
+
+    /// Uses an already connected `stream`.
+    pub fn new(stream: TcpStream) -> Result<Self, LockstepError> {
+        stream.set_nodelay(true).map_err(io_error)?;
+        let reader = BufReader::new(stream.try_clone().map_err(io_error)?);
+        let (tx, incoming) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                let input = line.map_err(io_error).and_then(|line| {
+                    serde_json::from_str(&line).map_err(|e| LockstepError::Parse {
+                        message: e.to_string(),
+                    })
+                });
+                let failed = input.is_err();
+                if tx.send(input).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Ok(Self { stream, incoming })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Transport for TcpTransport {
+    fn send(&mut self, input: &TurnInput) -> Result<(), LockstepError> {
+        let line = serde_json::to_string(input).expect("inputs always serialize");
+        writeln!(self.stream, "{}", line).map_err(io_error)
+    }
+
+    fn try_recv(&mut self) -> Result<Option<TurnInput>, LockstepError> {
+        match self.incoming.try_recv() {
+            Ok(input) => input.map(Some),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(LockstepError::Disconnected),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn io_error(error: std::io::Error) -> LockstepError {
+    LockstepError::Io {
+        message: error.to_string(),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{AbilitySet, Character, Combatant, CombatantId, Item};
+
+    fn duel() -> Encounter {
+        let mut hero = Character::new_player("Hero");
+        hero.stats.crit_chance = 30;
+        hero.stats.damage_variance = 20;
+        let mut rival = Character::new_player("Rival");
+        rival.stats.speed -= 1;
+        rival.stats.evade_chance = 20;
+        Encounter::new(vec![
+            Combatant::new(Team::Allies, hero, AbilitySet::monster_turn_based()),
+            Combatant::new(Team::Enemies, rival, AbilitySet::monster_turn_based()),
+        ])
+    }
+
+    /// The acting combatant attacks its opponent, or passes while stunned.
+    fn attack(state: &Encounter) -> Action {
+        let actor = state.current().unwrap();
+        Action::UseAbility {
+            ability: "Basic Attack".to_string(),
+            target: CombatantId(1 - actor.0),
+        }
+    }
+
+    /// Plays both sessions to the end, final state confirmed, and returns every
+    /// event the first saw.
+    fn play_out<T: Transport>(a: &mut Lockstep<T>, b: &mut Lockstep<T>) -> Vec<CombatEvent> {
+        let mut events = Vec::new();
+        // Give TCP peers up to five seconds to deliver each other's inputs
+        for _ in 0..5000 {
+            for session in [&mut *a, &mut *b] {
+                if session.awaiting_local_action() {
+                    let action = attack(session.state());
+                    session.submit(action).unwrap();
+                }
+            }
+            events.extend(a.poll().unwrap());
+            b.poll().unwrap();
+            if a.is_confirmed() && b.is_confirmed() {
+                return events;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("the duel did not finish");
+    }
+
+    fn channel_duel(seed_a: u64, seed_b: u64) -> [Lockstep<ChannelTransport>; 2] {
+        let (near, far) = ChannelTransport::pair();
+        [
+            Lockstep::new(duel(), seed_a, Team::Allies, near),
+            Lockstep::new(duel(), seed_b, Team::Enemies, far),
+        ]
+    }
+
+    #[test]
+    fn test_peers_stay_in_step() {
+        let [mut a, mut b] = channel_duel(7, 7);
+        let events = play_out(&mut a, &mut b);
+
+        assert!(a.turn() > 2);
+        assert_eq!(a.turn(), b.turn());
+        assert_eq!(a.checksum(), b.checksum());
+        assert_eq!(a.state().outcome(), b.state().outcome());
+        assert!(events.iter().any(|e| e.attacker_name == "Rival"));
+    }
+
+    #[test]
+    fn test_matches_local_simulation() {
+        let [mut a, mut b] = channel_duel(11, 11);
+        play_out(&mut a, &mut b);
+
+        let mut state = duel();
+        let mut rng = SeededRng::new(11);
+        while state.outcome() == EncounterOutcome::Ongoing {
+            state = reduce_with_rng(&state, &attack(&state), &mut rng)
+                .unwrap()
+                .0;
+        }
+        assert_eq!(checksum(&state, &rng), a.checksum());
+    }
+
+    #[test]
+    fn test_different_seeds_desync() {
+        let [mut a, mut b] = channel_duel(1, 2);
+        a.submit(attack(a.state())).unwrap();
+
+        // Both peers notice as soon as they hold each other's first input
+        for session in [&mut b, &mut a] {
+            let err = session.poll().unwrap_err();
+            assert!(matches!(err, LockstepError::Desync { turn: 0, .. }));
+            assert_eq!(session.turn(), 0);
+        }
+    }
+
+    #[test]
+    fn test_last_turn_desync() {
+        // Count the turns the duel lasts
+        let mut state = duel();
+        let mut rng = SeededRng::new(7);
+        let mut turns = 0;
+        while state.outcome() == EncounterOutcome::Ongoing {
+            state = reduce_with_rng(&state, &attack(&state), &mut rng)
+                .unwrap()
+                .0;
+            turns += 1;
+        }
+
+        let [mut a, mut b] = channel_duel(7, 7);
+        while a.turn() + 1 < turns || b.turn() + 1 < turns {
+            for session in [&mut a, &mut b] {
+                if session.awaiting_local_action() {
+                    session.submit(attack(session.state())).unwrap();
+                }
+            }
+            a.poll().unwrap();
+            b.poll().unwrap();
+        }
+
+        // The last actor sends its input, then its state drifts in a way the
+        // final attack does not touch, so only the final states differ
+        let actor = if a.awaiting_local_action() {
+            &mut a
+        } else {
+            &mut b
+        };
+        actor.submit(attack(actor.state())).unwrap();
+        actor.state.combatants[0].inventory.add(Item::potion());
+
+        // Both peers play the last turn and notice when they close the duel
+        let mut errors = [None, None];
+        for _ in 0..3 {
+            for (session, error) in [&mut a, &mut b].into_iter().zip(&mut errors) {
+                if error.is_none() {
+                    *error = session.poll().err();
+                }
+            }
+        }
+        for (session, error) in [&a, &b].into_iter().zip(errors) {
+            assert!(matches!(error, Some(LockstepError::Desync { turn, .. }) if turn == turns));
+            assert!(session.is_over());
+            assert!(!session.is_confirmed());
+        }
+    }
+
+    #[test]
+    fn test_submit_checks_turn_and_rules() {
+        let [mut a, mut b] = channel_duel(7, 7);
+
+        // The hero acts first, so the rival cannot
+        assert!(!b.awaiting_local_action());
+        assert_eq!(
+            b.submit(attack(a.state())).unwrap_err(),
+            LockstepError::NotYourTurn
+        );
+
+        let err = a.submit(Action::UseAbility {
+            ability: "Meteor".to_string(),
+            target: CombatantId(1),
+        });
+        assert!(matches!(err, Err(LockstepError::Rejected { turn: 0, .. })));
+        assert!(a.awaiting_local_action());
+
+        a.submit(attack(a.state())).unwrap();
+        assert_eq!(
+            a.submit(attack(a.state())).unwrap_err(),
+            LockstepError::NotYourTurn
+        );
+    }
+
+    #[test]
+    fn test_peer_acting_out_of_turn_is_an_error() {
+        let (near, mut far) = ChannelTransport::pair();
+        let mut a = Lockstep::new(duel(), 7, Team::Allies, near);
+        let checksum = a.checksum();
+
+        far.send(&TurnInput {
+            turn: 0,
+            action: Some(Action::Pass),
+            checksum,
+        })
+        .unwrap();
+        assert_eq!(a.poll(), Err(LockstepError::OutOfTurn { turn: 0 }));
+
+        let (near, mut far) = ChannelTransport::pair();
+        let mut a = Lockstep::new(duel(), 7, Team::Allies, near);
+        far.send(&TurnInput {
+            turn: 3,
+            action: None,
+            checksum,
+        })
+        .unwrap();
+        assert_eq!(
+            a.poll(),
+            Err(LockstepError::OutOfOrder {
+                expected: 0,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_dropped_peer_disconnects() {
+        let [mut a, b] = channel_duel(7, 7);
+        drop(b);
+
+        assert_eq!(a.poll(), Err(LockstepError::Disconnected));
+    }
+
+    #[test]
+    fn test_tcp_loopback_duel() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let guest = std::thread::spawn(move || TcpTransport::connect(addr).unwrap());
+        let host = TcpTransport::accept(&listener).unwrap();
+
+        let mut a = Lockstep::new(duel(), 5, Team::Allies, host);
+        let mut b = Lockstep::new(duel(), 5, Team::Enemies, guest.join().unwrap());
+        play_out(&mut a, &mut b);
+
+        assert!(a.is_over() && a.is_confirmed());
+        assert_eq!(a.checksum(), b.checksum());
+    }
+
+    #[test]
+    fn test_turn_input_json() {
+        let input = TurnInput {
+            turn: 2,
+            action: None,
+            checksum: 42,
+        };
+        let json = serde_json::to_string(&input).unwrap();
+
+        assert_eq!(json, r#"{"turn":2,"checksum":42}"#);
+        assert_eq!(serde_json::from_str::<TurnInput>(&json).unwrap(), input);
+    }
+}
@@ -0,0 +1,588 @@
+//! Weighted, seeded loot tables.
+//!
+//! A [`LootTable`] lists [`LootEntry`]s. Each roll of the table:
+//!
+//! 1. Drops every [`guaranteed`](LootEntry::guaranteed) entry
+//! 2. Makes [`rolls`](LootTable::rolls) weighted picks among the other entries,
+//!    with replacement. An entry's chance is its weight over the total weight;
+//!    entries without a weight use their [`Rarity::weight`]
+//!
+//! Entries drop [`LootDrop::Nothing`], a random count of an [`Item`], or
+//! everything a roll on another table yields ([`LootDrop::Table`]), so shared
+//! tables like gems can be nested into many monsters' tables. Items keep the
+//! rarity of the entry that dropped them, and identical drops are merged.
+//!
+//! All randomness comes from a [`CombatRng`], so a roll is deterministic for a
+//! given seed. Tables are validated when a [`LootCatalog`] is built: references
+//! must resolve and nesting may not loop, so rolls cannot fail on catalog data.
+//!
+//! The built-in tables ([`LootCatalog::builtin`]) are embedded from
+//! `data/loot.ron`.
+//!
+//! # Data Format
+//!
+//! ```ron
+//! (
+//!     tables: [
+//!         (
+//!             name: "Slime",
+//!             rolls: 1,
+//!             entries: [
+//!                 (drop: Item(item: (...), min: 1, max: 2), guaranteed: true),
+//!                 (drop: Nothing, weight: Some(100)),
+//!                 (drop: Table(table: "Gems"), rarity: Epic),
+//!             ],
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! # // The built-in loot tables need `std`
+//! # #[cfg(feature = "std")]
+//! # {
+//! let catalog = LootCatalog::builtin();
+//!
+//! let first = catalog.roll("Goblin", &mut SeededRng::new(7)).unwrap();
+//! let again = catalog.roll("Goblin", &mut SeededRng::new(7)).unwrap();
+//!
+//! // Same seed, same loot; goblins always drop a potion
+//! assert_eq!(first, again);
+//! assert!(first.iter().any(|loot| loot.item.name == "Potion"));
+//! # }
+//! ```
+
+use alloc::collections::BTreeSet;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::{CombatRng, Item, Loot, LootDrop, LootEntry, LootTable, Rarity};
+
+/// Source of the built-in loot tables.
+#[cfg(feature = "std")]
+const BUILTIN_LOOT: &str = include_str!("../data/loot.ron");
+
+impl Rarity {
+    /// Every rarity, from most to least common.
+    pub const ALL: [Rarity; 5] = [
+        Rarity::Common,
+        Rarity::Uncommon,
+        Rarity::Rare,
+        Rarity::Epic,
+        Rarity::Legendary,
+    ];
+
+    /// Weight of entries of this rarity that do not set their own.
+    pub fn weight(self) -> u32 {
+        match self {
+            Rarity::Common => 100,
+            Rarity::Uncommon => 40,
+            Rarity::Rare => 15,
+            Rarity::Epic => 5,
+            Rarity::Legendary => 1,
+        }
+    }
+}
+
+impl LootEntry {
+    /// An entry dropping `min..=max` copies of `item`.
+    pub fn item(item: Item, min: u32, max: u32, rarity: Rarity) -> Self {
+        Self {
+            drop: LootDrop::Item { item, min, max },
+            rarity,
+            ..Default::default()
+        }
+    }
+
+    /// This entry's chance relative to the table's other weighted entries.
+    pub fn weight(&self) -> u32 {
+        self.weight.unwrap_or_else(|| self.rarity.weight())
+    }
+
+    /// Makes the entry drop on every roll.
+    pub fn always_drops(mut self) -> Self {
+        self.guaranteed = true;
+        self
+    }
+}
+
+impl LootTable {
+    /// Total weight of the entries weighted picks choose from.
+    pub fn total_weight(&self) -> u64 {
+        self.weighted().map(|entry| u64::from(entry.weight())).sum()
+    }
+
+    fn weighted(&self) -> impl Iterator<Item = &LootEntry> {
+        self.entries.iter().filter(|entry| !entry.guaranteed)
+    }
+
+    /// Picks one weighted entry.
+    fn pick(&self, rng: &mut impl CombatRng) -> Option<&LootEntry> {
+        let total = u32::try_from(self.total_weight()).unwrap_or(u32::MAX);
+        let mut roll = rng.roll_below(total);
+        for entry in self.weighted() {
+            if roll < entry.weight() {
+                return Some(entry);
+            }
+            roll -= entry.weight();
+        }
+        None
+    }
+}
+
+/// Errors produced while loading a [`LootCatalog`] or rolling on it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LootError {
+    /// The loot file could not be read.
+    Io { path: String, message: String },
+
+    /// The file extension is not `.json` or `.ron`.
+    UnsupportedFormat { path: String },
+
+    /// The data could not be deserialized.
+    Parse {
+        format: &'static str,
+        message: String,
+    },
+
+    /// A table has an empty name.
+    EmptyName { index: usize },
+
+    /// Two tables share a name.
+    DuplicateName { name: String },
+
+    /// A table failed validation.
+    InvalidTable { name: String, reason: String },
+
+    /// A table nests itself, directly or through other tables.
+    Cycle { name: String },
+
+    /// A roll or a nested entry named a table that is not in the catalog.
+    UnknownTable { name: String },
+}
+
+impl fmt::Display for LootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LootError::Io { path, message } => {
+                write!(f, "failed to read loot tables {}: {}", path, message)
+            }
+            LootError::UnsupportedFormat { path } => write!(
+                f,
+                "unsupported loot table format {} (expected .json or .ron)",
+                path
+            ),
+            LootError::Parse { format, message } => {
+                write!(f, "invalid {} loot tables: {}", format, message)
+            }
+            LootError::EmptyName { index } => write!(f, "loot table #{} has an empty name", index),
+            LootError::DuplicateName { name } => {
+                write!(f, "loot table \"{}\" is defined more than once", name)
+            }
+            LootError::InvalidTable { name, reason } => {
+                write!(f, "loot table \"{}\" is invalid: {}", name, reason)
+            }
+            LootError::Cycle { name } => write!(f, "loot table \"{}\" nests itself", name),
+            LootError::UnknownTable { name } => {
+                write!(f, "loot table \"{}\" is not in the catalog", name)
+            }
+        }
+    }
+}
+
+impl core::error::Error for LootError {}
+
+/// A validated collection of loot tables.
+///
+/// Construct with [`from_json`](Self::from_json), [`from_ron`](Self::from_ron),
+/// [`from_file`](Self::from_file) or [`new`](Self::new); all of them validate every
+/// table, so a catalog value is always well-formed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct LootCatalog {
+    tables: Vec<LootTable>,
+}
+
+/// On-disk shape of a loot file.
+#[derive(Deserialize)]
+struct LootFile {
+    tables: Vec<LootTable>,
+}
+
+impl LootCatalog {
+    /// Validates the tables and builds a catalog.
+    pub fn new(tables: Vec<LootTable>) -> Result<Self, LootError> {
+        let mut names = BTreeSet::new();
+
+        for (index, table) in tables.iter().enumerate() {
+            if table.name.trim().is_empty() {
+                return Err(LootError::EmptyName { index });
+            }
+            if !names.insert(table.name.as_str()) {
+                return Err(LootError::DuplicateName {
+                    name: table.name.clone(),
+                });
+            }
+            validate_table(table)?;
+        }
+
+        let catalog = Self { tables };
+        for table in &catalog.tables {
+            catalog.check_nesting(table, &mut Vec::new())?;
+        }
+        Ok(catalog)
+    }
+
+    /// Parses and validates JSON loot tables.
+    pub fn from_json(source: &str) -> Result<Self, LootError> {
+        let file: LootFile = serde_json::from_str(source).map_err(|e| LootError::Parse {
+            format: "JSON",
+            message: e.to_string(),
+        })?;
+        Self::new(file.tables)
+    }
+
+    /// Parses and validates RON loot tables.
+    #[cfg(feature = "std")]
+    pub fn from_ron(source: &str) -> Result<Self, LootError> {
+        let file: LootFile = ron::from_str(source).map_err(|e| LootError::Parse {
+            format: "RON",
+            message: e.to_string(),
+        })?;
+        Self::new(file.tables)
+    }
+
+    /// Loads a loot file, choosing the format from its `.json` or `.ron` extension.
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, LootError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        let source = std::fs::read_to_string(path).map_err(|e| LootError::Io {
+            path: display.clone(),
+            message: e.to_string(),
+        })?;
+
+        match extension {
+            Some("json") => Self::from_json(&source),
+            Some("ron") => Self::from_ron(&source),
+            _ => Err(LootError::UnsupportedFormat { path: display }),
+        }
+    }
+
+    /// The tables embedded in the crate from `data/loot.ron`.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: the embedded data is checked by the crate's tests.
+    #[cfg(feature = "std")]
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_LOOT).expect("built-in loot tables are valid")
+    }
+
+    /// All tables, in file order.
+    pub fn tables(&self) -> &[LootTable] {
+        &self.tables
+    }
+
+    /// Looks up a table by name.
+    pub fn get(&self, name: &str) -> Option<&LootTable> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+
+    /// Looks up a table by name, failing with [`LootError::UnknownTable`].
+    pub fn require(&self, name: &str) -> Result<&LootTable, LootError> {
+        self.get(name).ok_or_else(|| LootError::UnknownTable {
+            name: name.to_string(),
+        })
+    }
+
+    /// Rolls the named table once. Drops of the same item and rarity are merged,
+    /// in the order they first dropped.
+    pub fn roll(&self, table: &str, rng: &mut impl CombatRng) -> Result<Vec<Loot>, LootError> {
+        let table = self.require(table)?;
+        let mut loot = Vec::new();
+        self.roll_into(table, rng, &mut loot);
+        Ok(loot)
+    }
+
+    fn roll_into(&self, table: &LootTable, rng: &mut impl CombatRng, loot: &mut Vec<Loot>) {
+        for entry in table.entries.iter().filter(|entry| entry.guaranteed) {
+            self.drop_into(entry, rng, loot);
+        }
+        for _ in 0..table.rolls {
+            if let Some(entry) = table.pick(rng) {
+                self.drop_into(entry, rng, loot);
+            }
+        }
+    }
+
+    fn drop_into(&self, entry: &LootEntry, rng: &mut impl CombatRng, loot: &mut Vec<Loot>) {
+        match &entry.drop {
+            LootDrop::Nothing => {}
+            LootDrop::Item { item, min, max } => {
+                let count = if max > min {
+                    min + rng.roll_below((max - min).saturating_add(1))
+                } else {
+                    *min
+                };
+                if count == 0 {
+                    return;
+                }
+                match loot
+                    .iter_mut()
+                    .find(|drop| drop.item == *item && drop.rarity == entry.rarity)
+                {
+                    Some(drop) => drop.count += count,
+                    None => loot.push(Loot {
+                        item: item.clone(),
+                        count,
+                        rarity: entry.rarity,
+                    }),
+                }
+            }
+            LootDrop::Table { table } => {
+                // References were resolved when the catalog was built
+                if let Some(table) = self.get(table) {
+                    self.roll_into(table, rng, loot);
+                }
+            }
+        }
+    }
+
+    /// Fails if `table` reaches an unknown table or itself through nested entries.
+    fn check_nesting<'a>(
+        &'a self,
+        table: &'a LootTable,
+        path: &mut Vec<&'a str>,
+    ) -> Result<(), LootError> {
+        if path.contains(&table.name.as_str()) {
+            return Err(LootError::Cycle {
+                name: table.name.clone(),
+            });
+        }
+        path.push(&table.name);
+        for entry in &table.entries {
+            if let LootDrop::Table { table: nested } = &entry.drop {
+                self.check_nesting(self.require(nested)?, path)?;
+            }
+        }
+        path.pop();
+        Ok(())
+    }
+}
+
+/// Checks a table's own entries; nesting is checked by [`LootCatalog::new`].
+fn validate_table(table: &LootTable) -> Result<(), LootError> {
+    let invalid = |reason: String| LootError::InvalidTable {
+        name: table.name.clone(),
+        reason,
+    };
+
+    for entry in &table.entries {
+        if let LootDrop::Item { item, min, max } = &entry.drop {
+            if min > max {
+                return Err(invalid(format!(
+                    "\"{}\" drops between {} and {} copies",
+                    item.name, min, max
+                )));
+            }
+            if *max == 0 {
+                return Err(invalid(format!("\"{}\" never drops", item.name)));
+            }
+        }
+    }
+
+    if table.rolls > 0 && table.total_weight() == 0 {
+        return Err(invalid(format!(
+            "{} weighted rolls but no weighted entries",
+            table.rolls
+        )));
+    }
+    if table.total_weight() > u64::from(u32::MAX) {
+        return Err(invalid("total weight does not fit in a u32".to_string()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SeededRng;
+
+    fn gem(name: &str) -> Item {
+        Item::equipment(name, crate::EquipmentSlot::Accessory, Vec::new())
+    }
+
+    fn table(name: &str, rolls: u32, entries: Vec<LootEntry>) -> LootTable {
+        LootTable {
+            name: name.to_string(),
+            rolls,
+            entries,
+        }
+    }
+
+    fn nested(name: &str) -> LootEntry {
+        LootEntry {
+            drop: LootDrop::Table {
+                table: name.to_string(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_builtin_tables_are_valid() {
+        let catalog = LootCatalog::builtin();
+
+        for name in ["Slime", "Bat", "Goblin", "Gems"] {
+            assert!(catalog.get(name).is_some(), "{}", name);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_rolls_are_deterministic_per_seed() {
+        let catalog = LootCatalog::builtin();
+        let rolls = |seed| {
+            let mut rng = SeededRng::new(seed);
+            (0..20)
+                .map(|_| catalog.roll("Goblin", &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(rolls(42), rolls(42));
+        assert_ne!(rolls(42), rolls(43));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_guaranteed_entries_always_drop() {
+        let catalog = LootCatalog::builtin();
+        let mut rng = SeededRng::new(1);
+
+        for _ in 0..50 {
+            let loot = catalog.roll("Slime", &mut rng).unwrap();
+            let jelly = loot.iter().find(|l| l.item.name == "Slime Jelly").unwrap();
+            assert!((1..=2).contains(&jelly.count));
+        }
+    }
+
+    #[test]
+    fn test_weights_set_pick_frequency() {
+        let catalog = LootCatalog::new(vec![table(
+            "Chest",
+            1,
+            vec![
+                LootEntry::item(gem("Common Gem"), 1, 1, Rarity::Common),
+                LootEntry::item(gem("Rare Gem"), 1, 1, Rarity::Rare),
+                LootEntry {
+                    weight: Some(0),
+                    ..LootEntry::item(gem("Never"), 1, 1, Rarity::Common)
+                },
+            ],
+        )])
+        .unwrap();
+        let mut rng = SeededRng::new(3);
+
+        let mut common = 0;
+        for _ in 0..1150 {
+            let loot = catalog.roll("Chest", &mut rng).unwrap();
+            assert_eq!(loot.len(), 1);
+            assert_ne!(loot[0].item.name, "Never");
+            if loot[0].rarity == Rarity::Common {
+                common += 1;
+            }
+        }
+
+        // Expected 1000 of 1150 (100 vs 15)
+        assert!((950..1050).contains(&common), "{}", common);
+    }
+
+    #[test]
+    fn test_nested_tables_merge_their_drops() {
+        let catalog = LootCatalog::new(vec![
+            table(
+                "Gems",
+                0,
+                vec![LootEntry::item(gem("Ruby"), 1, 1, Rarity::Rare).always_drops()],
+            ),
+            table("Hoard", 3, vec![nested("Gems")]),
+        ])
+        .unwrap();
+
+        let loot = catalog.roll("Hoard", &mut SeededRng::new(0)).unwrap();
+
+        assert_eq!(loot.len(), 1);
+        assert_eq!(loot[0].count, 3);
+        assert_eq!(loot[0].rarity, Rarity::Rare);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_invalid_tables_are_rejected() {
+        assert_eq!(
+            LootCatalog::new(vec![
+                table("A", 1, vec![nested("B")]),
+                table("B", 1, vec![nested("A")])
+            ]),
+            Err(LootError::Cycle {
+                name: "A".to_string()
+            })
+        );
+        assert_eq!(
+            LootCatalog::new(vec![table("A", 1, vec![nested("Missing")])]),
+            Err(LootError::UnknownTable {
+                name: "Missing".to_string()
+            })
+        );
+        assert!(matches!(
+            LootCatalog::new(vec![table(
+                "A",
+                1,
+                vec![LootEntry::item(gem("Ruby"), 1, 1, Rarity::Rare).always_drops()]
+            )]),
+            Err(LootError::InvalidTable { .. })
+        ));
+        assert!(matches!(
+            LootCatalog::new(vec![table(
+                "A",
+                1,
+                vec![LootEntry::item(gem("Ruby"), 3, 2, Rarity::Rare)]
+            )]),
+            Err(LootError::InvalidTable { .. })
+        ));
+        assert_eq!(
+            LootCatalog::builtin().roll("Dragon", &mut SeededRng::new(0)),
+            Err(LootError::UnknownTable {
+                name: "Dragon".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_tables() {
+        let catalog = LootCatalog::from_json(
+            r#"{"tables": [{"name": "Crate", "rolls": 1, "entries": [
+                {"drop": "Nothing", "weight": 1}
+            ]}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.roll("Crate", &mut SeededRng::new(0)).unwrap(),
+            Vec::new()
+        );
+    }
+}
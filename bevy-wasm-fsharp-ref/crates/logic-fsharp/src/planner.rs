@@ -0,0 +1,519 @@
+//! Lookahead search over the pure combat rules.
+//!
+//! [`Planner`] scores each ability a character could use by simulating future
+//! turns of a one-on-one fight with [`compute_attack`] and the [`Cooldown`] types.
+//! Each simulated turn mirrors the game loop:
+//!
+//! 1. The side about to act ticks its turn-based cooldowns
+//! 2. It uses a ready ability (or [`Ability::basic_attack`] when nothing is ready)
+//! 3. Heals restore the caster's HP up to its maximum; attacks damage the other side
+//!
+//! The actor's turns are max nodes. Opponent turns are scored according to the
+//! [`OpponentModel`]: the average over the opponent's ready abilities (expectimax),
+//! or the worst case for the actor (minimax). Leaves are scored from the actor's
+//! point of view as the difference in HP per mille, with wins and losses dominating
+//! every other score and sooner wins ranked higher.
+//!
+//! The search deepens one ply at a time up to [`Planner::depth`] and stops early
+//! once [`Planner::max_nodes`] have been visited, returning the deepest complete
+//! result. Scores use integer math only, so plans are identical on every platform.
+//!
+//! Damage is the deterministic [`compute_attack`] result; crits, evasion and status
+//! effects are not simulated.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let state = DuelState {
+//!     actor: Character::new_monster("Goblin"),
+//!     actor_abilities: AbilitySet::monster_turn_based().abilities,
+//!     opponent: Character::new_player("Hero"),
+//!     opponent_abilities: vec![AbilitySlot::basic_attack_turns()],
+//! };
+//!
+//! let plan = Planner::new(4).plan(&state);
+//!
+//! assert_eq!(plan.depth, 4);
+//! assert_eq!(plan.candidates.len(), 3);
+//! assert!(plan.candidates.iter().all(|c| c.value <= plan.expected_value));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::{compute_attack, Ability, AbilitySlot, AiPolicy, Character};
+
+/// Score of a won fight, before the bonus for winning sooner.
+pub const WIN_SCORE: i32 = 1_000_000;
+
+/// How the planner expects the opponent to choose its abilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OpponentModel {
+    /// Each ready ability is equally likely (expectimax).
+    #[default]
+    Uniform,
+
+    /// The opponent always makes the move that is worst for the actor (minimax).
+    Adversarial,
+}
+
+/// A one-on-one fight as seen by the character about to act.
+///
+/// `actor_abilities` should already be ticked for the actor's current turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuelState {
+    pub actor: Character,
+    pub actor_abilities: Vec<AbilitySlot>,
+    pub opponent: Character,
+    pub opponent_abilities: Vec<AbilitySlot>,
+}
+
+/// The score of one candidate ability.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateScore {
+    /// Ability name.
+    pub name: String,
+
+    /// Expected score after using the ability.
+    pub value: i32,
+}
+
+/// The result of a search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plan {
+    /// The best ability to use this turn.
+    pub ability: Ability,
+
+    /// Expected score of [`ability`](Plan::ability).
+    pub expected_value: i32,
+
+    /// Depth in plies of the deepest completed search.
+    pub depth: u32,
+
+    /// Number of positions visited across all depths.
+    pub nodes: u64,
+
+    /// Every candidate ability with its score, in ability order.
+    pub candidates: Vec<CandidateScore>,
+}
+
+/// Depth-limited expectimax/minimax search over a [`DuelState`].
+///
+/// Also usable as an [`AiPolicy`]. Since a policy only sees the actor's abilities,
+/// the opponent is assumed to have `opponent_abilities`, or only a basic attack
+/// when that is empty.
+#[derive(Debug, Clone)]
+pub struct Planner {
+    /// Maximum search depth in plies (one ply is one character's turn).
+    pub depth: u32,
+
+    /// Stop deepening once this many positions have been visited.
+    pub max_nodes: u64,
+
+    /// How opponent turns are scored.
+    pub opponent_model: OpponentModel,
+
+    /// Abilities assumed for the opponent when used as an [`AiPolicy`].
+    pub opponent_abilities: Vec<AbilitySlot>,
+}
+
+impl Planner {
+    /// The search used by [`AiPersonality::Tactician`](crate::AiPersonality::Tactician).
+    pub const HARD: Planner = Planner {
+        depth: 6,
+        max_nodes: 50_000,
+        opponent_model: OpponentModel::Uniform,
+        opponent_abilities: Vec::new(),
+    };
+
+    /// A planner searching `depth` plies with the default node budget.
+    pub const fn new(depth: u32) -> Self {
+        Planner {
+            depth,
+            max_nodes: 50_000,
+            opponent_model: OpponentModel::Uniform,
+            opponent_abilities: Vec::new(),
+        }
+    }
+
+    /// Sets the node budget.
+    pub fn with_max_nodes(mut self, max_nodes: u64) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Sets how opponent turns are scored.
+    pub fn with_opponent_model(mut self, opponent_model: OpponentModel) -> Self {
+        self.opponent_model = opponent_model;
+        self
+    }
+
+    /// Sets the abilities assumed for the opponent when used as an [`AiPolicy`].
+    pub fn with_opponent_abilities(mut self, abilities: Vec<AbilitySlot>) -> Self {
+        self.opponent_abilities = abilities;
+        self
+    }
+
+    /// Searches `state` and returns the best ability for the actor.
+    ///
+    /// Depth 1 is always completed, even if it exceeds the node budget.
+    pub fn plan(&self, state: &DuelState) -> Plan {
+        let mut search = Search {
+            model: self.opponent_model,
+            nodes: 0,
+            max_nodes: self.max_nodes,
+        };
+
+        let root = Node::from_state(state);
+        let mut best = None;
+
+        for depth in 1..=self.depth.max(1) {
+            // The first iteration always runs to completion
+            let budget = if depth == 1 { u64::MAX } else { self.max_nodes };
+            search.max_nodes = budget;
+
+            match search.score_candidates(&root, depth) {
+                Some(candidates) => best = Some((depth, candidates)),
+                None => break,
+            }
+        }
+
+        let (depth, candidates) = best.expect("depth 1 always completes");
+        let (ability, expected_value) = candidates
+            .iter()
+            .fold(None, |best: Option<&(Ability, i32)>, c| match best {
+                Some(b) if b.1 >= c.1 => Some(b),
+                _ => Some(c),
+            })
+            .cloned()
+            .expect("there is always at least one candidate");
+
+        Plan {
+            ability,
+            expected_value,
+            depth,
+            nodes: search.nodes,
+            candidates: candidates
+                .into_iter()
+                .map(|(ability, value)| CandidateScore {
+                    name: ability.name,
+                    value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for Planner {
+    fn default() -> Self {
+        Planner::HARD
+    }
+}
+
+impl AiPolicy for Planner {
+    fn name(&self) -> &str {
+        "Tactician"
+    }
+
+    fn choose(
+        &self,
+        actor: &Character,
+        opponent: &Character,
+        abilities: &[AbilitySlot],
+    ) -> Ability {
+        let opponent_abilities = if self.opponent_abilities.is_empty() {
+            vec![AbilitySlot::basic_attack_turns()]
+        } else {
+            self.opponent_abilities.clone()
+        };
+
+        self.plan(&DuelState {
+            actor: actor.clone(),
+            actor_abilities: abilities.to_vec(),
+            opponent: opponent.clone(),
+            opponent_abilities,
+        })
+        .ability
+    }
+}
+
+/// One side of a simulated fight.
+#[derive(Clone)]
+struct Side {
+    hp: i32,
+    character: Character,
+    abilities: Vec<AbilitySlot>,
+}
+
+/// A simulated position. `sides[0]` is always the planning actor.
+#[derive(Clone)]
+struct Node {
+    sides: [Side; 2],
+}
+
+impl Node {
+    fn from_state(state: &DuelState) -> Self {
+        let side = |character: &Character, abilities: &[AbilitySlot]| Side {
+            hp: character.hp,
+            character: character.clone(),
+            abilities: abilities.to_vec(),
+        };
+        Node {
+            sides: [
+                side(&state.actor, &state.actor_abilities),
+                side(&state.opponent, &state.opponent_abilities),
+            ],
+        }
+    }
+
+    /// Indices of the ready abilities of `side`, or `None` if it must fall back to
+    /// a basic attack.
+    fn choices(&self, side: usize) -> Vec<Option<usize>> {
+        let ready: Vec<_> = self.sides[side]
+            .abilities
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_ready())
+            .map(|(i, _)| Some(i))
+            .collect();
+
+        if ready.is_empty() {
+            vec![None]
+        } else {
+            ready
+        }
+    }
+
+    fn ability(&self, side: usize, choice: Option<usize>) -> Ability {
+        choice
+            .map(|i| self.sides[side].abilities[i].ability.clone())
+            .unwrap_or_else(Ability::basic_attack)
+    }
+
+    /// `side` uses `choice`, then the other side's turn begins.
+    fn apply(&self, side: usize, choice: Option<usize>) -> Node {
+        let mut next = self.clone();
+        let other = 1 - side;
+        let ability = self.ability(side, choice);
+
+        if let Some(i) = choice {
+            next.sides[side].abilities[i].use_ability();
+        }
+
+        let caster = next.sides[side].current();
+        if !ability.target.is_offensive() {
+            let event = compute_attack(&caster, &caster, &ability);
+            next.sides[side].hp = event.defender_hp_after;
+        } else {
+            let target = next.sides[other].current();
+            let event = compute_attack(&caster, &target, &ability);
+            next.sides[other].hp = event.defender_hp_after;
+        }
+
+        for slot in &mut next.sides[other].abilities {
+            slot.tick_turn();
+        }
+
+        next
+    }
+
+    /// Leaf score from the actor's point of view. `depth` is the remaining depth,
+    /// so earlier wins score higher.
+    fn score(&self, depth: u32) -> Option<i32> {
+        let bonus = depth as i32;
+        if self.sides[1].hp <= 0 {
+            Some(WIN_SCORE + bonus)
+        } else if self.sides[0].hp <= 0 {
+            Some(-WIN_SCORE - bonus)
+        } else if depth == 0 {
+            Some(self.sides[0].hp_per_mille() - self.sides[1].hp_per_mille())
+        } else {
+            None
+        }
+    }
+}
+
+impl Side {
+    fn current(&self) -> Character {
+        Character {
+            hp: self.hp,
+            ..self.character.clone()
+        }
+    }
+
+    fn hp_per_mille(&self) -> i32 {
+        if self.character.stats.hp <= 0 {
+            0
+        } else {
+            self.hp * 1000 / self.character.stats.hp
+        }
+    }
+}
+
+/// Search bookkeeping shared across one [`Planner::plan`] call.
+struct Search {
+    model: OpponentModel,
+    nodes: u64,
+    max_nodes: u64,
+}
+
+impl Search {
+    /// Scores every actor choice at the root. `None` if the node budget ran out.
+    fn score_candidates(&mut self, root: &Node, depth: u32) -> Option<Vec<(Ability, i32)>> {
+        root.choices(0)
+            .into_iter()
+            .map(|choice| {
+                let value = self.value(&root.apply(0, choice), 1, depth - 1)?;
+                Some((root.ability(0, choice), value))
+            })
+            .collect()
+    }
+
+    /// Value of `node` with `side` to move and `depth` plies left.
+    fn value(&mut self, node: &Node, side: usize, depth: u32) -> Option<i32> {
+        self.nodes += 1;
+        if self.nodes > self.max_nodes {
+            return None;
+        }
+
+        if let Some(score) = node.score(depth) {
+            return Some(score);
+        }
+
+        let mut values = Vec::new();
+        for choice in node.choices(side) {
+            values.push(self.value(&node.apply(side, choice), 1 - side, depth - 1)?);
+        }
+
+        match (side, self.model) {
+            (0, _) => values.iter().copied().max(),
+            (_, OpponentModel::Adversarial) => values.iter().copied().min(),
+            (_, OpponentModel::Uniform) => {
+                let total: i64 = values.iter().map(|&v| v as i64).sum();
+                Some((total / values.len() as i64) as i32)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AbilitySet, Cooldown};
+
+    fn on_cooldown(mut slot: AbilitySlot, remaining: i32) -> AbilitySlot {
+        if let Cooldown::Turns { max, .. } = slot.cooldown {
+            slot.cooldown = Cooldown::Turns { max, remaining };
+        }
+        slot
+    }
+
+    fn duel(actor_hp: i32, opponent_hp: i32) -> DuelState {
+        let mut actor = Character::new_monster("Goblin");
+        actor.hp = actor_hp;
+        let mut opponent = Character::new_player("Hero");
+        opponent.hp = opponent_hp;
+
+        DuelState {
+            actor,
+            actor_abilities: AbilitySet::monster_turn_based().abilities,
+            opponent,
+            opponent_abilities: vec![AbilitySlot::basic_attack_turns()],
+        }
+    }
+
+    #[test]
+    fn test_takes_the_win() {
+        // Basic attack deals 9, so either attack wins on the spot
+        let plan = Planner::new(4).plan(&duel(20, 9));
+
+        assert!(plan.expected_value > WIN_SCORE);
+        assert_ne!(plan.ability.name, "Heal");
+    }
+
+    #[test]
+    fn test_heals_out_of_lethal_range() {
+        // The hero hits for 14; only healing survives the next turn
+        let plan = Planner::new(2).plan(&duel(14, 30));
+
+        assert_eq!(plan.ability.name, "Heal");
+        assert!(plan.expected_value > -WIN_SCORE);
+    }
+
+    #[test]
+    fn test_depth_one_is_greedy() {
+        let plan = Planner::new(1).plan(&duel(20, 30));
+
+        assert_eq!(plan.ability.name, "Powerful Attack");
+        assert_eq!(plan.depth, 1);
+    }
+
+    #[test]
+    fn test_only_ready_abilities_are_candidates() {
+        let mut state = duel(20, 30);
+        state.actor_abilities[1] = on_cooldown(state.actor_abilities[1].clone(), 2);
+
+        let plan = Planner::new(3).plan(&state);
+
+        assert_eq!(plan.candidates.len(), 2);
+        assert!(plan.candidates.iter().all(|c| c.name != "Powerful Attack"));
+    }
+
+    #[test]
+    fn test_falls_back_to_basic_attack() {
+        let mut state = duel(20, 30);
+        state.actor_abilities = state
+            .actor_abilities
+            .into_iter()
+            .map(|slot| on_cooldown(slot, 3))
+            .collect();
+
+        let plan = Planner::new(2).plan(&state);
+
+        assert_eq!(plan.ability.name, "Basic Attack");
+        assert_eq!(plan.candidates.len(), 1);
+    }
+
+    #[test]
+    fn test_node_budget_limits_depth() {
+        let plan = Planner::new(12).with_max_nodes(200).plan(&duel(20, 30));
+
+        assert!(plan.depth >= 1);
+        assert!(plan.depth < 12);
+    }
+
+    #[test]
+    fn test_adversarial_is_never_more_optimistic() {
+        let mut state = duel(20, 30);
+        state.opponent_abilities = AbilitySet::monster_turn_based().abilities;
+
+        let uniform = Planner::new(4).plan(&state);
+        let adversarial = Planner::new(4)
+            .with_opponent_model(OpponentModel::Adversarial)
+            .plan(&state);
+
+        assert!(adversarial.expected_value <= uniform.expected_value);
+    }
+
+    #[test]
+    fn test_plans_are_deterministic() {
+        let planner = Planner::HARD;
+        let first = planner.plan(&duel(12, 25));
+        let second = planner.plan(&duel(12, 25));
+
+        assert_eq!(first.ability.name, second.ability.name);
+        assert_eq!(first.candidates, second.candidates);
+        assert_eq!(first.nodes, second.nodes);
+    }
+
+    #[test]
+    fn test_policy_uses_opponent_abilities() {
+        let state = duel(14, 30);
+
+        let ability = Planner::new(2).choose(&state.actor, &state.opponent, &state.actor_abilities);
+
+        assert_eq!(ability.name, "Heal");
+    }
+}
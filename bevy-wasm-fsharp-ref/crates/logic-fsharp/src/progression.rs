@@ -0,0 +1,821 @@
+//! Character progression: XP, levels, stat growth and ability unlocks.
+//!
+//! A [`CharacterClass`] defines a character's level 1 [`Stats`], the
+//! [`StatGrowth`] it gains per level, the [`LevelCurve`] its levels follow and the
+//! abilities it learns along the way. A [`Progression`] tracks one character's
+//! class, level and XP, and is carried by its [`Combatant`].
+//!
+//! XP is awarded by [`reduce`](crate::reduce): when a combatant is defeated,
+//! every living opponent with a progression earns its [`xp_reward`]. Level-ups
+//! grow the character's stats right away (current HP grows with max HP), and each
+//! [`XpGrant`] is recorded in the turn's last [`CombatEvent`](crate::CombatEvent).
+//! Unlocked abilities join the character's loadout when its next
+//! [`AbilitySet`] is built with [`CharacterClass::ability_set`].
+//!
+//! The built-in classes ([`ClassCatalog::builtin`]) are embedded from
+//! `data/classes.ron`.
+//!
+//! # Data Format
+//!
+//! ```ron
+//! (
+//!     classes: [
+//!         (
+//!             name: "Warrior",
+//!             stats: (hp: 30, attack: 10, defense: 2, speed: 5),
+//!             growth: (hp: 5, attack: 2, defense: 1),
+//!             curve: Quadratic(scale: 20),
+//!             unlocks: [
+//!                 (level: 1, ability: "Basic Attack"),
+//!                 (level: 2, ability: "Powerful Attack"),
+//!             ],
+//!             xp_reward: 25,
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! Curves are `Linear(step: N)`, `Quadratic(scale: N)` or
+//! `Table(totals: [...])`; see [`LevelCurve`]. Levels are capped at
+//! [`MAX_LEVEL`], or at the end of a table.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! let xpForLevel (curve: LevelCurve) (level: uint32) : uint32 =
+//!     let steps = (max 1u (min level (maxLevelOf curve))) - 1u
+//!     match curve with
+//!     | Linear step -> step * steps
+//!     | Quadratic scale -> scale * steps * steps
+//!     | Table totals -> if steps = 0u then 0u else totals.[int steps - 1]
+//!
+//! let xpReward (combatant: Combatant) : uint32 =
+//!     match combatant.Progression with
+//!     | Some p -> p.Class.XpReward * p.Level
+//!     | None -> uint32 (max 0 combatant.Character.Stats.Hp)
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! # // The built-in catalogs need `std`
+//! # #[cfg(feature = "std")]
+//! # {
+//! let classes = ClassCatalog::builtin();
+//! let abilities = AbilityCatalog::builtin();
+//! let warrior = classes.require("Warrior").unwrap();
+//!
+//! let hero = warrior
+//!     .combatant(Team::Allies, "Hero", 1, &abilities, CooldownMode::Turns)
+//!     .unwrap();
+//! let mut slime = Combatant::new(
+//!     Team::Enemies,
+//!     Character::new_monster("Slime"),
+//!     AbilitySet::monster_turn_based(),
+//! );
+//! slime.character.hp = 5;
+//!
+//! let encounter = Encounter::new(vec![hero, slime]);
+//! let action = Action::UseAbility {
+//!     ability: "Basic Attack".to_string(),
+//!     target: CombatantId(1),
+//! };
+//! let (next, events) = reduce(&encounter, &action).unwrap();
+//!
+//! // A slime without a class is worth its 20 max HP in XP: enough for level 2
+//! let grant = &events[0].xp_grants[0];
+//! assert_eq!(grant.amount, 20);
+//! assert_eq!(grant.level_after, 2);
+//! assert_eq!(grant.unlocked, vec!["Powerful Attack".to_string()]);
+//! assert_eq!(next.combatants[0].character.stats.attack, 12);
+//! # }
+//! ```
+
+use alloc::collections::BTreeSet;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::{
+    AbilityCatalog, AbilitySet, CatalogError, Character, CharacterClass, Combatant, CombatantId,
+    CooldownMode, Encounter, LevelCurve, Progression, StatGrowth, Stats, Team, XpGrant,
+};
+
+/// Source of the built-in classes.
+#[cfg(feature = "std")]
+const BUILTIN_CLASSES: &str = include_str!("../data/classes.ron");
+
+/// The highest level any curve reaches.
+pub const MAX_LEVEL: u32 = 99;
+
+impl LevelCurve {
+    /// Total XP needed to reach `level`. Level 1 needs none; levels past
+    /// [`max_level`](Self::max_level) need as much as the max level.
+    pub fn xp_for_level(&self, level: u32) -> u32 {
+        let steps = level.clamp(1, self.max_level()) - 1;
+        match self {
+            LevelCurve::Linear { step } => step.saturating_mul(steps),
+            LevelCurve::Quadratic { scale } => scale.saturating_mul(steps.saturating_mul(steps)),
+            LevelCurve::Table { totals } => match steps.checked_sub(1) {
+                Some(index) => totals[index as usize],
+                None => 0,
+            },
+        }
+    }
+
+    /// The highest level of the curve: [`MAX_LEVEL`], or the end of a table.
+    pub fn max_level(&self) -> u32 {
+        match self {
+            LevelCurve::Table { totals } => (totals.len() as u32 + 1).min(MAX_LEVEL),
+            LevelCurve::Linear { .. } | LevelCurve::Quadratic { .. } => MAX_LEVEL,
+        }
+    }
+
+    /// The level reached with `xp` total XP.
+    pub fn level_for_xp(&self, xp: u32) -> u32 {
+        let mut level = 1;
+        while level < self.max_level() && self.xp_for_level(level + 1) <= xp {
+            level += 1;
+        }
+        level
+    }
+}
+
+impl StatGrowth {
+    /// Adds `levels` levels of growth to `stats`.
+    pub fn apply(&self, stats: &mut Stats, levels: u32) {
+        let levels = i32::try_from(levels).unwrap_or(i32::MAX);
+        stats.hp = stats.hp.saturating_add(self.hp.saturating_mul(levels));
+        stats.attack = stats
+            .attack
+            .saturating_add(self.attack.saturating_mul(levels));
+        stats.defense = stats
+            .defense
+            .saturating_add(self.defense.saturating_mul(levels));
+        stats.speed = stats
+            .speed
+            .saturating_add(self.speed.saturating_mul(levels));
+    }
+}
+
+impl CharacterClass {
+    /// Stats of a member of the class at `level`.
+    pub fn stats_at(&self, level: u32) -> Stats {
+        let mut stats = self.stats.clone();
+        self.growth.apply(&mut stats, level.max(1) - 1);
+        stats
+    }
+
+    /// A member of the class at `level`, at full HP.
+    pub fn character(&self, name: &str, level: u32) -> Character {
+        let stats = self.stats_at(level);
+        Character {
+            name: name.to_string(),
+            hp: stats.hp,
+            stats,
+        }
+    }
+
+    /// Names of the abilities learned by `level`, in unlock order.
+    pub fn unlocked_at(&self, level: u32) -> impl Iterator<Item = &str> {
+        self.unlocks
+            .iter()
+            .filter(move |unlock| unlock.level <= level)
+            .map(|unlock| unlock.ability.as_str())
+    }
+
+    /// The loadout of a member of the class at `level`, with cooldowns counting
+    /// in `mode`.
+    pub fn ability_set(
+        &self,
+        level: u32,
+        catalog: &AbilityCatalog,
+        mode: CooldownMode,
+    ) -> Result<AbilitySet, CatalogError> {
+        let names: Vec<&str> = self.unlocked_at(level).collect();
+        catalog.ability_set(&names, mode)
+    }
+
+    /// XP for defeating a member of the class at `level`.
+    pub fn xp_reward(&self, level: u32) -> u32 {
+        self.xp_reward.saturating_mul(level.max(1))
+    }
+
+    /// A combatant of the class at `level`, with its stats, loadout and
+    /// [`Progression`].
+    pub fn combatant(
+        &self,
+        team: Team,
+        name: &str,
+        level: u32,
+        catalog: &AbilityCatalog,
+        mode: CooldownMode,
+    ) -> Result<Combatant, CatalogError> {
+        let progression = Progression::new(self.clone(), level);
+        let character = self.character(name, progression.level);
+        let abilities = self.ability_set(progression.level, catalog, mode)?;
+        Ok(Combatant::new(team, character, abilities).with_progression(progression))
+    }
+
+    /// Checks the class for values progression cannot handle.
+    pub fn validate(&self) -> Result<(), ClassError> {
+        let invalid = |reason: String| ClassError::InvalidClass {
+            name: self.name.clone(),
+            reason,
+        };
+
+        if self.stats.hp <= 0 {
+            return Err(invalid(format!(
+                "stats.hp must be positive (got {})",
+                self.stats.hp
+            )));
+        }
+
+        let growth = &self.growth;
+        if [growth.hp, growth.attack, growth.defense, growth.speed]
+            .iter()
+            .any(|&value| value < 0)
+        {
+            return Err(invalid(format!(
+                "growth must not be negative (got {:?})",
+                growth
+            )));
+        }
+
+        match &self.curve {
+            LevelCurve::Linear { step: 0 } => {
+                return Err(invalid("Linear curve step must be positive".to_string()));
+            }
+            LevelCurve::Quadratic { scale: 0 } => {
+                return Err(invalid(
+                    "Quadratic curve scale must be positive".to_string(),
+                ));
+            }
+            LevelCurve::Table { totals } => {
+                let increasing = totals.first().is_some_and(|&first| first > 0)
+                    && totals.windows(2).all(|pair| pair[0] < pair[1]);
+                if !increasing {
+                    return Err(invalid(format!(
+                        "Table curve totals must be positive and increasing (got {:?})",
+                        totals
+                    )));
+                }
+            }
+            LevelCurve::Linear { .. } | LevelCurve::Quadratic { .. } => {}
+        }
+
+        let max_level = self.curve.max_level();
+        for unlock in &self.unlocks {
+            if unlock.level < 1 || unlock.level > max_level {
+                return Err(invalid(format!(
+                    "\"{}\" unlocks at level {}, outside 1..={}",
+                    unlock.ability, unlock.level, max_level
+                )));
+            }
+        }
+        if self
+            .unlocks
+            .windows(2)
+            .any(|pair| pair[0].level > pair[1].level)
+        {
+            return Err(invalid("unlocks must be ordered by level".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Progression {
+    /// `level` of `class`, with exactly the XP that level needs. The level is
+    /// clamped to the class's curve.
+    pub fn new(class: CharacterClass, level: u32) -> Self {
+        let level = level.clamp(1, class.curve.max_level());
+        Self {
+            xp: class.curve.xp_for_level(level),
+            class,
+            level,
+        }
+    }
+
+    /// XP still needed for the next level, or `None` at the max level.
+    pub fn xp_to_next_level(&self) -> Option<u32> {
+        (self.level < self.class.curve.max_level()).then(|| {
+            self.class
+                .curve
+                .xp_for_level(self.level + 1)
+                .saturating_sub(self.xp)
+        })
+    }
+
+    /// Adds `amount` XP. For every level gained, `character`'s stats grow by the
+    /// class's growth; a living character's current HP grows with its max HP.
+    pub fn gain_xp(&mut self, character: &mut Character, amount: u32) -> XpGrant {
+        self.xp = self.xp.saturating_add(amount);
+        let level = self.class.curve.level_for_xp(self.xp).max(self.level);
+        let levels_gained = level - self.level;
+        let unlocked = self
+            .class
+            .unlocks
+            .iter()
+            .filter(|unlock| unlock.level > self.level && unlock.level <= level)
+            .map(|unlock| unlock.ability.clone())
+            .collect();
+
+        let max_hp_before = character.stats.hp;
+        self.class.growth.apply(&mut character.stats, levels_gained);
+        if character.hp > 0 {
+            character.hp += character.stats.hp - max_hp_before;
+        }
+        self.level = level;
+
+        XpGrant {
+            recipient: character.name.clone(),
+            amount,
+            level_after: level,
+            levels_gained,
+            unlocked,
+        }
+    }
+}
+
+/// XP for defeating `combatant`: its class's reward at its level, or its max HP
+/// if it has no class.
+pub fn xp_reward(combatant: &Combatant) -> u32 {
+    match &combatant.progression {
+        Some(progression) => progression.class.xp_reward(progression.level),
+        None => combatant.character.stats.hp.max(0) as u32,
+    }
+}
+
+/// Grants the XP for defeating `defeated` to each of its living opponents that
+/// has a progression.
+pub(crate) fn award_xp(state: &mut Encounter, defeated: CombatantId) -> Vec<XpGrant> {
+    let Some(loser) = state.combatant(defeated) else {
+        return Vec::new();
+    };
+    let reward = xp_reward(loser);
+    let team = loser.team.opponent();
+    if reward == 0 {
+        return Vec::new();
+    }
+
+    state
+        .combatants
+        .iter_mut()
+        .filter(|c| c.team == team && c.is_alive())
+        .filter_map(|c| {
+            let progression = c.progression.as_mut()?;
+            Some(progression.gain_xp(&mut c.character, reward))
+        })
+        .collect()
+}
+
+/// Errors produced while loading or querying a [`ClassCatalog`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassError {
+    /// The class file could not be read.
+    Io { path: String, message: String },
+
+    /// The file extension is not `.json` or `.ron`.
+    UnsupportedFormat { path: String },
+
+    /// The data could not be deserialized.
+    Parse {
+        format: &'static str,
+        message: String,
+    },
+
+    /// A class has an empty name.
+    EmptyName { index: usize },
+
+    /// Two classes share a name.
+    DuplicateName { name: String },
+
+    /// A class failed validation.
+    InvalidClass { name: String, reason: String },
+
+    /// A lookup named a class that is not in the catalog.
+    UnknownClass { name: String },
+
+    /// A class's unlocked abilities are not all in the ability catalog.
+    Ability(CatalogError),
+}
+
+impl fmt::Display for ClassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClassError::Io { path, message } => {
+                write!(f, "failed to read class catalog {}: {}", path, message)
+            }
+            ClassError::UnsupportedFormat { path } => write!(
+                f,
+                "unsupported class catalog format {} (expected .json or .ron)",
+                path
+            ),
+            ClassError::Parse { format, message } => {
+                write!(f, "invalid {} class catalog: {}", format, message)
+            }
+            ClassError::EmptyName { index } => write!(f, "class #{} has an empty name", index),
+            ClassError::DuplicateName { name } => {
+                write!(f, "class \"{}\" is defined more than once", name)
+            }
+            ClassError::InvalidClass { name, reason } => {
+                write!(f, "class \"{}\" is invalid: {}", name, reason)
+            }
+            ClassError::UnknownClass { name } => {
+                write!(f, "class \"{}\" is not in the catalog", name)
+            }
+            ClassError::Ability(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ClassError {}
+
+/// A validated collection of character classes.
+///
+/// Construct with [`from_json`](Self::from_json), [`from_ron`](Self::from_ron),
+/// [`from_file`](Self::from_file) or [`new`](Self::new); all of them validate every
+/// class, so a catalog value is always well-formed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct ClassCatalog {
+    classes: Vec<CharacterClass>,
+}
+
+/// On-disk shape of a class file.
+#[derive(Deserialize)]
+struct ClassFile {
+    classes: Vec<CharacterClass>,
+}
+
+impl ClassCatalog {
+    /// Validates the classes and builds a catalog.
+    pub fn new(classes: Vec<CharacterClass>) -> Result<Self, ClassError> {
+        let mut names = BTreeSet::new();
+
+        for (index, class) in classes.iter().enumerate() {
+            if class.name.trim().is_empty() {
+                return Err(ClassError::EmptyName { index });
+            }
+            if !names.insert(class.name.as_str()) {
+                return Err(ClassError::DuplicateName {
+                    name: class.name.clone(),
+                });
+            }
+            class.validate()?;
+        }
+
+        Ok(Self { classes })
+    }
+
+    /// Parses and validates a JSON class catalog.
+    pub fn from_json(source: &str) -> Result<Self, ClassError> {
+        let file: ClassFile = serde_json::from_str(source).map_err(|e| ClassError::Parse {
+            format: "JSON",
+            message: e.to_string(),
+        })?;
+        Self::new(file.classes)
+    }
+
+    /// Parses and validates a RON class catalog.
+    #[cfg(feature = "std")]
+    pub fn from_ron(source: &str) -> Result<Self, ClassError> {
+        let file: ClassFile = ron::from_str(source).map_err(|e| ClassError::Parse {
+            format: "RON",
+            message: e.to_string(),
+        })?;
+        Self::new(file.classes)
+    }
+
+    /// Loads a class file, choosing the format from its `.json` or `.ron` extension.
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ClassError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        let source = std::fs::read_to_string(path).map_err(|e| ClassError::Io {
+            path: display.clone(),
+            message: e.to_string(),
+        })?;
+
+        match extension {
+            Some("json") => Self::from_json(&source),
+            Some("ron") => Self::from_ron(&source),
+            _ => Err(ClassError::UnsupportedFormat { path: display }),
+        }
+    }
+
+    /// The classes embedded in the crate from `data/classes.ron`.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: the embedded data is checked by the crate's tests.
+    #[cfg(feature = "std")]
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_CLASSES).expect("built-in class catalog is valid")
+    }
+
+    /// All classes, in file order.
+    pub fn classes(&self) -> &[CharacterClass] {
+        &self.classes
+    }
+
+    /// Looks up a class by name.
+    pub fn get(&self, name: &str) -> Option<&CharacterClass> {
+        self.classes.iter().find(|class| class.name == name)
+    }
+
+    /// Looks up a class by name, failing with [`ClassError::UnknownClass`].
+    pub fn require(&self, name: &str) -> Result<&CharacterClass, ClassError> {
+        self.get(name).ok_or_else(|| ClassError::UnknownClass {
+            name: name.to_string(),
+        })
+    }
+
+    /// Builds a member of the class `class` at `level`: its character, at full
+    /// HP, and the loadout it has unlocked.
+    pub fn spawn(
+        &self,
+        class: &str,
+        name: &str,
+        level: u32,
+        abilities: &AbilityCatalog,
+        mode: CooldownMode,
+    ) -> Result<(Character, AbilitySet), ClassError> {
+        let class = self.require(class)?;
+        let level = level.clamp(1, class.curve.max_level());
+        let loadout = class
+            .ability_set(level, abilities, mode)
+            .map_err(ClassError::Ability)?;
+        Ok((class.character(name, level), loadout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reduce, Action};
+    #[cfg(feature = "std")]
+    use crate::{AbilityUnlock, StatusEffect};
+
+    #[cfg(feature = "std")]
+    fn warrior() -> CharacterClass {
+        ClassCatalog::builtin().require("Warrior").unwrap().clone()
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_builtin_classes_unlock_catalog_abilities() {
+        let abilities = AbilityCatalog::builtin();
+
+        for class in ClassCatalog::builtin().classes() {
+            let max_level = class.curve.max_level();
+            assert!(
+                class
+                    .ability_set(max_level, &abilities, CooldownMode::Turns)
+                    .is_ok(),
+                "{}",
+                class.name
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_level_one_classes_match_constructors() {
+        let classes = ClassCatalog::builtin();
+
+        assert_eq!(
+            classes.require("Warrior").unwrap().character("Hero", 1),
+            Character::new_player("Hero")
+        );
+        assert_eq!(
+            classes.require("Slime").unwrap().character("Slime", 1),
+            Character::new_monster("Slime")
+        );
+    }
+
+    #[test]
+    fn test_level_curves() {
+        let linear = LevelCurve::Linear { step: 30 };
+        let quadratic = LevelCurve::Quadratic { scale: 20 };
+        let table = LevelCurve::Table {
+            totals: vec![15, 45, 90],
+        };
+
+        assert_eq!(linear.xp_for_level(1), 0);
+        assert_eq!(linear.xp_for_level(4), 90);
+        assert_eq!(quadratic.xp_for_level(4), 180);
+        assert_eq!(table.xp_for_level(3), 45);
+        assert_eq!(table.max_level(), 4);
+        assert_eq!(table.xp_for_level(10), 90);
+
+        assert_eq!(quadratic.level_for_xp(79), 2);
+        assert_eq!(quadratic.level_for_xp(80), 3);
+        assert_eq!(table.level_for_xp(u32::MAX), 4);
+        assert_eq!(linear.level_for_xp(u32::MAX), MAX_LEVEL);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_stats_grow_with_level() {
+        let stats = warrior().stats_at(3);
+
+        assert_eq!(stats.hp, 40);
+        assert_eq!(stats.attack, 14);
+        assert_eq!(stats.defense, 4);
+        assert_eq!(stats.speed, 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gaining_several_levels_at_once() {
+        let mut progression = Progression::new(warrior(), 1);
+        let mut hero = warrior().character("Hero", 1);
+        hero.hp = 10;
+
+        let grant = progression.gain_xp(&mut hero, 85);
+
+        assert_eq!(grant.level_after, 3);
+        assert_eq!(grant.levels_gained, 2);
+        assert_eq!(grant.unlocked, vec!["Powerful Attack", "Heal"]);
+        assert_eq!(hero.stats.hp, 40);
+        assert_eq!(hero.hp, 20); // Wounds carry over
+        assert_eq!(progression.xp_to_next_level(), Some(95)); // 180 - 85
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_max_level_stops_leveling() {
+        let mage = ClassCatalog::builtin().require("Mage").unwrap().clone();
+        let mut progression = Progression::new(mage.clone(), 6);
+        let mut character = mage.character("Merlin", 6);
+
+        let grant = progression.gain_xp(&mut character, 1000);
+
+        assert_eq!(grant.levels_gained, 0);
+        assert_eq!(progression.level, 6);
+        assert_eq!(progression.xp_to_next_level(), None);
+        assert_eq!(Progression::new(mage, 50).level, 6);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_defeat_rewards_every_living_opponent() {
+        let abilities = AbilityCatalog::builtin();
+        let classes = ClassCatalog::builtin();
+        let combatant = |class: &str, team, name: &str| {
+            classes
+                .require(class)
+                .unwrap()
+                .combatant(team, name, 1, &abilities, CooldownMode::Turns)
+                .unwrap()
+        };
+        let mut slime = combatant("Slime", Team::Enemies, "Slime");
+        slime.progression = Some(Progression::new(
+            classes.require("Slime").unwrap().clone(),
+            2,
+        ));
+        slime.character.hp = 1;
+        let mut fallen = combatant("Mage", Team::Allies, "Fallen");
+        fallen.character.hp = 0;
+
+        let state = Encounter::new(vec![
+            combatant("Ranger", Team::Allies, "Robin"),
+            combatant("Warrior", Team::Allies, "Hero"),
+            fallen,
+            slime,
+        ]);
+        let (next, events) = reduce(
+            &state,
+            &Action::UseAbility {
+                ability: "Basic Attack".to_string(),
+                target: CombatantId(3),
+            },
+        )
+        .unwrap();
+
+        // A level 2 slime is worth 2 * 20 XP, to both living allies
+        let grants = &events[0].xp_grants;
+        assert_eq!(grants.len(), 2);
+        assert!(grants.iter().all(|grant| grant.amount == 40));
+        assert_eq!(grants[0].recipient, "Robin");
+        assert_eq!(next.combatants[0].progression.as_ref().unwrap().xp, 40);
+        assert_eq!(next.combatants[1].progression.as_ref().unwrap().level, 2);
+        assert_eq!(next.combatants[2].progression.as_ref().unwrap().xp, 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_status_kills_award_xp() {
+        let abilities = AbilityCatalog::builtin();
+        let hero = warrior()
+            .combatant(Team::Allies, "Hero", 1, &abilities, CooldownMode::Turns)
+            .unwrap();
+        let mut slime = Combatant::new(
+            Team::Enemies,
+            Character::new_monster("Slime"),
+            AbilitySet::monster_turn_based(),
+        );
+        slime.character.hp = 1;
+        slime.effects.apply("Slime", &StatusEffect::poison(3, 2));
+        let mut state = Encounter::new(vec![hero, slime]);
+        state.end_turn();
+
+        let (next, events) = reduce(&state, &Action::Pass).unwrap();
+
+        assert!(!next.combatants[1].is_alive());
+        assert_eq!(events[0].xp_grants[0].amount, 20);
+    }
+
+    #[test]
+    fn test_classless_combatants_earn_nothing() {
+        let state = Encounter::new(vec![
+            Combatant::new(
+                Team::Allies,
+                Character::new_player("Hero"),
+                AbilitySet::monster_turn_based(),
+            ),
+            Combatant::new(
+                Team::Enemies,
+                Character {
+                    hp: 1,
+                    ..Character::new_monster("Slime")
+                },
+                AbilitySet::monster_turn_based(),
+            ),
+        ]);
+
+        let (_, events) = reduce(
+            &state,
+            &Action::UseAbility {
+                ability: "Basic Attack".to_string(),
+                target: CombatantId(1),
+            },
+        )
+        .unwrap();
+
+        assert!(events[0].killing_blow);
+        assert!(events[0].xp_grants.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_invalid_classes_are_rejected() {
+        let mut class = warrior();
+        class.curve = LevelCurve::Table {
+            totals: vec![50, 40],
+        };
+        assert!(matches!(
+            ClassCatalog::new(vec![class]),
+            Err(ClassError::InvalidClass { .. })
+        ));
+
+        let mut class = warrior();
+        class.unlocks.push(AbilityUnlock {
+            level: 1,
+            ability: "Late Basic".to_string(),
+        });
+        assert!(class.validate().is_err());
+
+        let mut class = warrior();
+        class.growth.hp = -1;
+        assert!(class.validate().is_err());
+
+        assert_eq!(
+            ClassCatalog::new(vec![warrior(), warrior()]),
+            Err(ClassError::DuplicateName {
+                name: "Warrior".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_catalog() {
+        let catalog = ClassCatalog::from_json(
+            r#"{"classes": [
+                {"name": "Squire", "stats": {"hp": 10, "attack": 3, "defense": 1},
+                 "growth": {"hp": 2, "attack": 1, "defense": 0},
+                 "curve": {"Linear": {"step": 10}}, "xp_reward": 5}
+            ]}"#,
+        )
+        .unwrap();
+
+        let squire = catalog.require("Squire").unwrap();
+        assert_eq!(squire.stats_at(4).hp, 16);
+        assert!(squire.unlocks.is_empty());
+        assert_eq!(
+            catalog.require("Knight"),
+            Err(ClassError::UnknownClass {
+                name: "Knight".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,557 @@
+//! The encounter turn loop as a pure reducer.
+//!
+//! [`reduce`] takes an [`Encounter`] and the [`Action`] chosen by the combatant whose
+//! turn it is, and returns the next encounter state together with the
+//! [`CombatEvent`]s describing what happened. It owns every rule of a turn:
+//!
+//! 1. The action is validated: the encounter must be ongoing, the ability must be
+//!    one of the actor's and ready (or the item one of its consumables), stunned
+//!    actors may only [`Action::Pass`], and the target must be valid for the
+//!    ability's [`Targeting`](crate::Targeting) rule (see [`Encounter::check_target`])
+//! 2. The ability's effects are resolved against the target, or every member of the
+//!    target's team for area abilities: damage and healing with
+//!    [`compute_attack_with_formula`] and the encounter's
+//!    [`formula`](Encounter::formula), shields and status effects with
+//!    [`apply_ability_status`]. Both sides fight with their
+//!    [effective stats](Combatant::effective_stats), equipment included
+//! 3. The ability's cooldown starts, or the item is used up
+//! 4. The actor's turn-based status effects tick; their events are appended to the
+//!    turn's last event
+//! 5. Living opponents of everyone defeated this turn earn XP if they have a
+//!    [`Progression`](crate::Progression); the [`XpGrant`]s are appended to the turn's
+//!    last event (see [`crate::progression`])
+//! 6. The turn passes to the next living combatant, whose cooldowns tick
+//!
+//! Victory and defeat are read from the returned state with [`Encounter::outcome`],
+//! and the next actor with [`Encounter::current`].
+//!
+//! The input state is never modified, and both states and actions serialize, so a
+//! fight can be replayed from its starting state and action list.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type Action =
+//!     | UseAbility of ability: string * target: CombatantId
+//!     | Pass
+//!
+//! let reduce (state: Encounter) (action: Action) : Result<Encounter * CombatEvent list, EncounterError> =
+//!     ...
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let encounter = Encounter::new(vec![
+//!     Combatant::new(Team::Allies, Character::new_player("Hero"), AbilitySet::monster_turn_based()),
+//!     Combatant::new(Team::Enemies, Character::new_monster("Slime"), AbilitySet::monster_turn_based()),
+//! ]);
+//!
+//! let action = Action::UseAbility {
+//!     ability: "Powerful Attack".to_string(),
+//!     target: CombatantId(1),
+//! };
+//! let (next, events) = reduce(&encounter, &action).unwrap();
+//!
+//! assert_eq!(events[0].damage, 21);
+//! assert_eq!(next.combatants[1].character.hp, 0);
+//! assert!(events[0].killing_blow);
+//! assert_eq!(next.outcome(), EncounterOutcome::Victory);
+//!
+//! // The input state is untouched
+//! assert_eq!(encounter.combatants[1].character.hp, 20);
+//! ```
+
+use crate::prelude::*;
+use crate::progression::award_xp;
+use crate::{
+    apply_ability_status, compute_attack_with_formula, compute_attack_with_formula_and_rng,
+    Ability, Action, AiPolicy, Character, CombatEvent, CombatRng, Combatant, CombatantId,
+    Effectiveness, Encounter, EncounterError, EncounterOutcome, XpGrant, COMBAT_EVENT_VERSION,
+};
+
+/// Ability name recorded in the event of a passed turn.
+pub const PASS: &str = "Pass";
+
+/// Applies `action` for the current combatant and advances to the next turn.
+///
+/// Returns the new state and the turn's events: one event per combatant the
+/// ability or item affected, or one event naming [`PASS`] for a passed turn. Status effect
+/// changes during the turn are listed in the events' `status_events`.
+pub fn reduce(
+    state: &Encounter,
+    action: &Action,
+) -> Result<(Encounter, Vec<CombatEvent>), EncounterError> {
+    let formula = state.formula;
+    reduce_with(state, action, |attacker, defender, ability| {
+        compute_attack_with_formula(attacker, defender, ability, &formula)
+    })
+}
+
+/// [`reduce`] with crits, evasion and damage variance rolled from `rng` (see
+/// [`compute_attack_with_rng`](crate::compute_attack_with_rng)).
+///
+/// The same state, action and generator state always produce the same result.
+pub fn reduce_with_rng(
+    state: &Encounter,
+    action: &Action,
+    rng: &mut impl CombatRng,
+) -> Result<(Encounter, Vec<CombatEvent>), EncounterError> {
+    let formula = state.formula;
+    reduce_with(state, action, |attacker, defender, ability| {
+        compute_attack_with_formula_and_rng(attacker, defender, ability, &formula, rng)
+    })
+}
+
+fn reduce_with(
+    state: &Encounter,
+    action: &Action,
+    attack: impl FnMut(&Character, &Character, &Ability) -> CombatEvent,
+) -> Result<(Encounter, Vec<CombatEvent>), EncounterError> {
+    if state.outcome() != EncounterOutcome::Ongoing {
+        return Err(EncounterError::EncounterOver);
+    }
+    let actor = state.current().ok_or(EncounterError::EncounterOver)?;
+    let mut next = state.clone();
+    let alive_before: Vec<bool> = state.combatants.iter().map(Combatant::is_alive).collect();
+
+    let mut events = match action {
+        Action::UseAbility { ability, target } => {
+            use_ability(&mut next, actor, ability, *target, attack)?
+        }
+        Action::UseItem { item, target } => use_item(&mut next, actor, item, *target, attack)?,
+        Action::Pass => vec![pass_event(&next, actor)],
+    };
+
+    // Turn-based status effects tick at the end of their owner's turn, healing up
+    // to the effective max HP
+    let combatant = &mut next.combatants[actor.0];
+    let mut character = combatant.effective_character();
+    let status_events = combatant.effects.tick_turn(&mut character);
+    combatant.character.hp = character.hp;
+
+    // Everyone defeated this turn, by the action or by a status tick, is worth XP
+    let defeated: Vec<CombatantId> = next
+        .combatants
+        .iter()
+        .zip(&alive_before)
+        .filter(|(combatant, &was_alive)| was_alive && !combatant.is_alive())
+        .map(|(combatant, _)| combatant.id)
+        .collect();
+    let xp_grants: Vec<XpGrant> = defeated
+        .into_iter()
+        .flat_map(|id| award_xp(&mut next, id))
+        .collect();
+
+    if let Some(event) = events.last_mut() {
+        event.status_events.extend(status_events);
+        event.xp_grants.extend(xp_grants);
+    }
+
+    next.end_turn();
+    if let Some(id) = next.current() {
+        next.combatants[id.0].abilities.tick_turn_all();
+    }
+
+    Ok((next, events))
+}
+
+fn use_ability(
+    state: &mut Encounter,
+    actor: CombatantId,
+    name: &str,
+    target: CombatantId,
+    attack: impl FnMut(&Character, &Character, &Ability) -> CombatEvent,
+) -> Result<Vec<CombatEvent>, EncounterError> {
+    let combatant = &state.combatants[actor.0];
+    if combatant.effects.is_stunned() {
+        return Err(EncounterError::Stunned { id: actor });
+    }
+
+    let slot = combatant
+        .abilities
+        .abilities
+        .iter()
+        .find(|slot| slot.ability.name == name)
+        .ok_or_else(|| EncounterError::UnknownAbility {
+            name: name.to_string(),
+        })?;
+    if !slot.is_ready() {
+        return Err(EncounterError::AbilityNotReady {
+            name: name.to_string(),
+        });
+    }
+    let ability = slot.ability.clone();
+    state.check_target(actor, ability.target, target)?;
+    state.combatants[actor.0].abilities.use_ability(name);
+
+    Ok(resolve(state, actor, &ability, target, attack))
+}
+
+fn use_item(
+    state: &mut Encounter,
+    actor: CombatantId,
+    name: &str,
+    target: CombatantId,
+    attack: impl FnMut(&Character, &Character, &Ability) -> CombatEvent,
+) -> Result<Vec<CombatEvent>, EncounterError> {
+    let combatant = &mut state.combatants[actor.0];
+    if combatant.effects.is_stunned() {
+        return Err(EncounterError::Stunned { id: actor });
+    }
+
+    // `state` is a copy that is discarded on error, so the item is only lost
+    // if the target is valid
+    let ability = combatant
+        .inventory
+        .take_consumable(name)
+        .map_err(EncounterError::Inventory)?;
+    state.check_target(actor, ability.target, target)?;
+
+    Ok(resolve(state, actor, &ability, target, attack))
+}
+
+/// Resolves `ability` against `target` and everyone else it affects.
+fn resolve(
+    state: &mut Encounter,
+    actor: CombatantId,
+    ability: &Ability,
+    target: CombatantId,
+    mut attack: impl FnMut(&Character, &Character, &Ability) -> CombatEvent,
+) -> Vec<CombatEvent> {
+    let attacker = state.combatants[actor.0].effective_character();
+
+    let mut events = Vec::new();
+    for id in state.affected(ability.target, target) {
+        let defender = &mut state.combatants[id.0];
+        let character = defender.effective_character();
+        let mut event = attack(&attacker, &character, ability);
+        apply_ability_status(&mut event, ability, &character, &mut defender.effects);
+        defender.character.hp = event.defender_hp_after;
+        events.push(event);
+    }
+
+    events
+}
+
+fn pass_event(state: &Encounter, actor: CombatantId) -> CombatEvent {
+    let character = &state.combatants[actor.0].character;
+    CombatEvent {
+        version: COMBAT_EVENT_VERSION,
+        attacker_name: character.name.clone(),
+        defender_name: character.name.clone(),
+        damage: 0,
+        defender_hp_after: character.hp,
+        effective_amount: 0,
+        overkill: 0,
+        overheal: 0,
+        killing_blow: false,
+        ability_used: PASS.to_string(),
+        critical: false,
+        missed: false,
+        effectiveness: Effectiveness::Normal,
+        status_events: Vec::new(),
+        xp_grants: Vec::new(),
+        outcomes: Vec::new(),
+    }
+}
+
+/// The action an AI-controlled combatant takes on its turn.
+///
+/// Stunned combatants pass. Otherwise `policy` chooses an ability against the
+/// weakest opponent (see [`Encounter::weakest_target`]); abilities that are not
+/// aimed at opponents target the actor itself. Returns `None` when the encounter
+/// is over.
+pub fn choose_ai_action(state: &Encounter, policy: &dyn AiPolicy) -> Option<Action> {
+    let actor = state.current()?;
+    let combatant = state.combatant(actor)?;
+    if combatant.effects.is_stunned() {
+        return Some(Action::Pass);
+    }
+
+    let opponent = state.weakest_target(actor)?;
+    let ability = policy.choose(
+        &combatant.character,
+        &state.combatant(opponent)?.character,
+        &combatant.abilities.abilities,
+    );
+
+    Some(Action::UseAbility {
+        target: if ability.target.is_offensive() {
+            opponent
+        } else {
+            actor
+        },
+        ability: ability.name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AbilityEffect, AbilitySet, AiPersonality, Combatant, Cooldown, DamageModel, EffectOutcome,
+        SeededRng, StatusEffect, StatusEvent, Targeting, Team,
+    };
+
+    fn duel() -> Encounter {
+        Encounter::new(vec![
+            Combatant::new(
+                Team::Allies,
+                Character::new_player("Hero"),
+                AbilitySet::monster_turn_based(),
+            ),
+            Combatant::new(
+                Team::Enemies,
+                Character::new_monster("Slime"),
+                AbilitySet::monster_turn_based(),
+            ),
+        ])
+    }
+
+    fn use_on(ability: &str, target: usize) -> Action {
+        Action::UseAbility {
+            ability: ability.to_string(),
+            target: CombatantId(target),
+        }
+    }
+
+    #[test]
+    fn test_attack_applies_damage_and_passes_turn() {
+        let (next, events) = reduce(&duel(), &use_on("Basic Attack", 1)).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].damage, 14);
+        assert_eq!(next.combatants[1].character.hp, 6);
+        assert_eq!(next.current(), Some(CombatantId(1)));
+    }
+
+    #[test]
+    fn test_encounter_formula_is_used_with_and_without_rng() {
+        let state = duel().with_formula(DamageModel::AttackRatio);
+
+        let (_, events) = reduce(&state, &use_on("Basic Attack", 1)).unwrap();
+        let (_, rolled) =
+            reduce_with_rng(&state, &use_on("Basic Attack", 1), &mut SeededRng::new(3)).unwrap();
+
+        // 15 * 15 / (15 + 1), the default stats have no random rolls
+        assert_eq!(events[0].damage, 14);
+        assert_eq!(rolled, events);
+
+        let mut golem = state.clone();
+        golem.combatants[1].character.stats.defense = 15;
+        let (_, events) = reduce(&golem, &use_on("Basic Attack", 1)).unwrap();
+        assert_eq!(events[0].damage, 7); // 225 / 30, where flat defense would deal 1
+    }
+
+    #[test]
+    fn test_heal_is_capped_at_max_hp() {
+        let mut state = duel();
+        state.combatants[0].character.hp = 25;
+
+        let (next, events) = reduce(&state, &use_on("Heal", 0)).unwrap();
+
+        assert_eq!(next.combatants[0].character.hp, 30);
+        assert_eq!(events[0].defender_hp_after, 30);
+        assert_eq!(
+            events[0].outcomes,
+            vec![EffectOutcome::Healed { amount: 5 }]
+        );
+    }
+
+    #[test]
+    fn test_area_ability_hits_every_living_opponent() {
+        let mut state = Encounter::new(vec![
+            Combatant::new(
+                Team::Allies,
+                Character::new_player("Hero"),
+                AbilitySet::monster_turn_based(),
+            ),
+            Combatant::new(
+                Team::Enemies,
+                Character::new_monster("Slime"),
+                AbilitySet::monster_turn_based(),
+            ),
+            Combatant::new(
+                Team::Enemies,
+                Character::new_monster("Bat"),
+                AbilitySet::monster_turn_based(),
+            ),
+        ]);
+        state.combatants[0].abilities.abilities[0].ability.target = Targeting::AllEnemies;
+
+        let (next, events) = reduce(&state, &use_on("Basic Attack", 2)).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].defender_name, "Slime");
+        assert_eq!(events[1].defender_name, "Bat");
+        assert_eq!(next.combatants[1].character.hp, 6);
+        assert_eq!(next.combatants[2].character.hp, 6);
+    }
+
+    #[test]
+    fn test_cooldowns_start_and_recover_between_turns() {
+        let mut state = duel();
+        state.combatants[1].character.hp = 50;
+
+        let (next, _) = reduce(&state, &use_on("Powerful Attack", 1)).unwrap();
+        let (next, _) = reduce(&next, &use_on("Basic Attack", 0)).unwrap();
+
+        // Back to the hero, one turn into a three turn cooldown
+        assert_eq!(
+            reduce(&next, &use_on("Powerful Attack", 1)).unwrap_err(),
+            EncounterError::AbilityNotReady {
+                name: "Powerful Attack".to_string()
+            }
+        );
+        assert_eq!(
+            next.combatants[0].abilities.abilities[1].cooldown,
+            Cooldown::Turns {
+                max: 3,
+                remaining: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_actions_are_rejected() {
+        let state = duel();
+
+        assert_eq!(
+            reduce(&state, &use_on("Meteor", 1)).unwrap_err(),
+            EncounterError::UnknownAbility {
+                name: "Meteor".to_string()
+            }
+        );
+        assert_eq!(
+            reduce(&state, &use_on("Basic Attack", 0)).unwrap_err(),
+            EncounterError::WrongTeam {
+                id: CombatantId(0),
+                expected: Team::Enemies
+            }
+        );
+        assert_eq!(
+            reduce(&state, &use_on("Heal", 1)).unwrap_err(),
+            EncounterError::WrongTeam {
+                id: CombatantId(1),
+                expected: Team::Allies
+            }
+        );
+    }
+
+    #[test]
+    fn test_stunned_actor_must_pass() {
+        let mut state = duel();
+        state.combatants[0]
+            .effects
+            .apply("Hero", &StatusEffect::stun(1));
+
+        assert_eq!(
+            reduce(&state, &use_on("Basic Attack", 1)).unwrap_err(),
+            EncounterError::Stunned { id: CombatantId(0) }
+        );
+
+        let (next, events) = reduce(&state, &Action::Pass).unwrap();
+        assert_eq!(events[0].ability_used, PASS);
+        assert!(events[0]
+            .status_events
+            .iter()
+            .any(|e| matches!(e, StatusEvent::Expired { .. })));
+        assert_eq!(next.current(), Some(CombatantId(1)));
+    }
+
+    #[test]
+    fn test_status_effects_are_applied_and_ticked() {
+        let mut state = duel();
+        state.combatants[0].abilities.abilities[0]
+            .ability
+            .effects
+            .push(AbilityEffect::ApplyStatus(StatusEffect::poison(3, 2)));
+
+        let (next, events) = reduce(&state, &use_on("Basic Attack", 1)).unwrap();
+        assert!(matches!(
+            events[0].status_events[..],
+            [StatusEvent::Applied { .. }]
+        ));
+        assert_eq!(next.combatants[1].character.hp, 6);
+
+        // The poison ticks at the end of the slime's turn
+        let (next, events) = reduce(&next, &use_on("Basic Attack", 0)).unwrap();
+        assert!(matches!(
+            events[0].status_events[..],
+            [StatusEvent::Ticked {
+                amount: 3,
+                hp_after: 3,
+                ..
+            }]
+        ));
+        assert_eq!(next.combatants[1].character.hp, 3);
+    }
+
+    #[test]
+    fn test_finished_encounter_rejects_actions() {
+        let mut state = duel();
+        state.combatants[1].character.hp = 1;
+
+        let (next, _) = reduce(&state, &use_on("Basic Attack", 1)).unwrap();
+
+        assert_eq!(next.outcome(), EncounterOutcome::Victory);
+        assert_eq!(
+            reduce(&next, &Action::Pass).unwrap_err(),
+            EncounterError::EncounterOver
+        );
+    }
+
+    #[test]
+    fn test_ai_action() {
+        let mut state = duel();
+        state.combatants[0].character.hp = 5;
+
+        assert_eq!(
+            choose_ai_action(&state, &AiPersonality::Threshold),
+            Some(use_on("Heal", 0))
+        );
+        assert_eq!(
+            choose_ai_action(&state, &AiPersonality::Aggressive),
+            Some(use_on("Powerful Attack", 1))
+        );
+    }
+
+    #[test]
+    fn test_replaying_actions_is_deterministic() {
+        let actions = [
+            use_on("Powerful Attack", 1),
+            use_on("Basic Attack", 0),
+            use_on("Basic Attack", 1),
+        ];
+
+        let run = || {
+            let mut state = duel();
+            state.combatants[1].character.hp = 50;
+            for action in &actions {
+                state = reduce(&state, action).map(|(next, _)| next).unwrap();
+            }
+            serde_json::to_string(&state).unwrap()
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_rng_rolls_are_seeded() {
+        let mut state = duel();
+        state.combatants[0].character.stats.crit_chance = 50;
+        state.combatants[0].character.stats.damage_variance = 20;
+        let action = use_on("Basic Attack", 1);
+
+        let roll = |seed| {
+            let (_, events) = reduce_with_rng(&state, &action, &mut SeededRng::new(seed)).unwrap();
+            (events[0].damage, events[0].critical)
+        };
+
+        assert_eq!(roll(3), roll(3));
+        assert!((0..20).any(|seed| roll(seed) != roll(0)));
+    }
+}
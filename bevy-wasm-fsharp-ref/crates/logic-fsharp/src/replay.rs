@@ -0,0 +1,408 @@
+//! Recording fights and checking them against the current rules.
+//!
+//! A [`Replay`] holds everything needed to run a fight again: the starting
+//! [`Encounter`], and for every turn the [`Action`] taken, the [`SeededRng`]
+//! state the turn was rolled with (if any) and the [`CombatEvent`]s it produced.
+//! Replays serialize to JSON, so they can be attached to bug reports or checked
+//! into a test suite.
+//!
+//! [`Replay::play`] and [`Replay::play_with_rng`] are drop-in replacements for
+//! [`reduce`] and [`reduce_with_rng`] that record each turn as it is played.
+//! [`Replay::verify`] runs the recorded actions through the reducer again and
+//! fails with [`ReplayError::Mismatch`] at the first turn whose events differ, so
+//! a rule change that alters a recorded fight shows up as a failing replay.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type ReplayTurn =
+//!     { Action: Action
+//!       Rng: SeededRng option
+//!       Events: CombatEvent list }
+//!
+//! type Replay =
+//!     { Version: uint32
+//!       Initial: Encounter
+//!       Turns: ReplayTurn list }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let encounter = Encounter::new(vec![
+//!     Combatant::new(Team::Allies, Character::new_player("Hero"), AbilitySet::monster_turn_based()),
+//!     Combatant::new(Team::Enemies, Character::new_monster("Slime"), AbilitySet::monster_turn_based()),
+//! ]);
+//! let mut replay = Replay::new(encounter.clone());
+//! let mut rng = SeededRng::new(42);
+//!
+//! let attack = Action::UseAbility {
+//!     ability: "Basic Attack".to_string(),
+//!     target: CombatantId(1),
+//! };
+//! let (state, _) = replay.play_with_rng(&encounter, &attack, &mut rng).unwrap();
+//! let (state, _) = replay.play(&state, &Action::Pass).unwrap();
+//!
+//! // Round-trips through JSON and re-simulates to the same state
+//! let loaded = Replay::from_json(&replay.to_json()).unwrap();
+//! let replayed = loaded.verify().unwrap();
+//! assert_eq!(replayed.combatants[1].character.hp, state.combatants[1].character.hp);
+//! ```
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::{reduce, reduce_with_rng, Action, CombatEvent, Encounter, EncounterError, SeededRng};
+
+/// The version of the [`Replay`] format written by this crate.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// One recorded turn of a [`Replay`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayTurn {
+    /// The action the current combatant took.
+    pub action: Action,
+
+    /// Generator state before the turn, or `None` for a turn played with
+    /// [`reduce`] and no randomness.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rng: Option<SeededRng>,
+
+    /// The events the turn produced.
+    pub events: Vec<CombatEvent>,
+}
+
+/// Errors produced while loading or verifying a [`Replay`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    /// The replay file could not be read.
+    Io { path: String, message: String },
+
+    /// The data could not be deserialized.
+    Parse { message: String },
+
+    /// The replay was written by a newer version of the crate.
+    UnsupportedVersion { version: u32 },
+
+    /// A recorded action is no longer accepted by the reducer.
+    Rejected { turn: usize, error: EncounterError },
+
+    /// A turn produced different events than were recorded. `event` is the
+    /// index of the first differing event; a missing event is `None`.
+    Mismatch {
+        turn: usize,
+        event: usize,
+        expected: Option<Box<CombatEvent>>,
+        actual: Option<Box<CombatEvent>>,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io { path, message } => {
+                write!(f, "failed to read replay {}: {}", path, message)
+            }
+            ReplayError::Parse { message } => write!(f, "invalid replay: {}", message),
+            ReplayError::UnsupportedVersion { version } => write!(
+                f,
+                "replay version {} is newer than supported version {}",
+                version, REPLAY_VERSION
+            ),
+            ReplayError::Rejected { turn, error } => {
+                write!(f, "turn #{} was rejected: {}", turn, error)
+            }
+            ReplayError::Mismatch {
+                turn,
+                event,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "turn #{} diverged at event #{}: expected {}, got {}",
+                turn,
+                event,
+                summary(expected.as_deref()),
+                summary(actual.as_deref())
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ReplayError {}
+
+/// "Hero used Basic Attack on Slime: 14 damage, 6 HP left"
+fn summary(event: Option<&CombatEvent>) -> String {
+    match event {
+        Some(event) => format!(
+            "{} used {} on {}: {} damage, {} HP left",
+            event.attacker_name,
+            event.ability_used,
+            event.defender_name,
+            event.damage,
+            event.defender_hp_after
+        ),
+        None => "no event".to_string(),
+    }
+}
+
+/// A recorded fight: its starting state and every turn played since.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct Replay {
+    /// Format version, [`REPLAY_VERSION`] for replays recorded by this crate.
+    pub version: u32,
+
+    /// The encounter before the first turn.
+    pub initial: Encounter,
+
+    /// Every turn played, in order.
+    pub turns: Vec<ReplayTurn>,
+}
+
+impl Replay {
+    /// Starts an empty recording of a fight from `initial`.
+    pub fn new(initial: Encounter) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            initial,
+            turns: Vec::new(),
+        }
+    }
+
+    /// Plays `action` with [`reduce`] and records the turn.
+    ///
+    /// `state` must be the state after the last recorded turn, or
+    /// [`initial`](Self::initial) for the first. Rejected actions are not
+    /// recorded.
+    pub fn play(
+        &mut self,
+        state: &Encounter,
+        action: &Action,
+    ) -> Result<(Encounter, Vec<CombatEvent>), EncounterError> {
+        let (next, events) = reduce(state, action)?;
+        self.turns.push(ReplayTurn {
+            action: action.clone(),
+            rng: None,
+            events: events.clone(),
+        });
+        Ok((next, events))
+    }
+
+    /// Plays `action` with [`reduce_with_rng`] and records the turn, including
+    /// the generator state it started from.
+    pub fn play_with_rng(
+        &mut self,
+        state: &Encounter,
+        action: &Action,
+        rng: &mut SeededRng,
+    ) -> Result<(Encounter, Vec<CombatEvent>), EncounterError> {
+        let before = rng.clone();
+        let (next, events) = reduce_with_rng(state, action, rng)?;
+        self.turns.push(ReplayTurn {
+            action: action.clone(),
+            rng: Some(before),
+            events: events.clone(),
+        });
+        Ok((next, events))
+    }
+
+    /// Re-simulates every recorded turn and checks that it produces the recorded
+    /// events. Returns the final state.
+    pub fn verify(&self) -> Result<Encounter, ReplayError> {
+        if self.version > REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion {
+                version: self.version,
+            });
+        }
+
+        let mut state = self.initial.clone();
+        for (index, turn) in self.turns.iter().enumerate() {
+            let result = match &turn.rng {
+                Some(rng) => reduce_with_rng(&state, &turn.action, &mut rng.clone()),
+                None => reduce(&state, &turn.action),
+            };
+            let (next, events) =
+                result.map_err(|error| ReplayError::Rejected { turn: index, error })?;
+
+            let count = events.len().max(turn.events.len());
+            if let Some(event) = (0..count).find(|&i| events.get(i) != turn.events.get(i)) {
+                return Err(ReplayError::Mismatch {
+                    turn: index,
+                    event,
+                    expected: turn.events.get(event).cloned().map(Box::new),
+                    actual: events.get(event).cloned().map(Box::new),
+                });
+            }
+            state = next;
+        }
+        Ok(state)
+    }
+
+    /// Serializes the replay as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("replays always serialize")
+    }
+
+    /// Reads a JSON replay.
+    pub fn from_json(json: &str) -> Result<Self, ReplayError> {
+        serde_json::from_str(json).map_err(|e| ReplayError::Parse {
+            message: e.to_string(),
+        })
+    }
+
+    /// Reads a JSON replay file.
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| ReplayError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        Self::from_json(&source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AbilitySet, Character, Combatant, CombatantId, DamageModel, Team};
+
+    fn duel() -> Encounter {
+        let mut hero = Character::new_player("Hero");
+        hero.stats.crit_chance = 50;
+        let mut slime = Character::new_monster("Slime");
+        slime.stats.hp = 60;
+        slime.hp = 60;
+        Encounter::new(vec![
+            Combatant::new(Team::Allies, hero, AbilitySet::monster_turn_based()),
+            Combatant::new(Team::Enemies, slime, AbilitySet::monster_turn_based()),
+        ])
+    }
+
+    fn attack(target: usize) -> Action {
+        Action::UseAbility {
+            ability: "Basic Attack".to_string(),
+            target: CombatantId(target),
+        }
+    }
+
+    /// Hero and Slime trade basic attacks, with rolls from seed 7.
+    fn recorded() -> Replay {
+        let mut state = duel();
+        let mut replay = Replay::new(state.clone());
+        let mut rng = SeededRng::new(7);
+        for target in [1, 0, 1] {
+            state = replay
+                .play_with_rng(&state, &attack(target), &mut rng)
+                .unwrap()
+                .0;
+        }
+        replay
+    }
+
+    #[test]
+    fn test_recorded_fight_verifies() {
+        let replay = recorded();
+        let state = replay.verify().unwrap();
+
+        assert_eq!(replay.turns.len(), 3);
+        assert!(replay.turns.iter().all(|turn| turn.rng.is_some()));
+        assert_eq!(state.round, 2);
+        assert_eq!(
+            state.combatants[1].character.hp,
+            replay.turns[2].events[0].defender_hp_after
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let replay = recorded();
+        let loaded = Replay::from_json(&replay.to_json()).unwrap();
+
+        assert_eq!(loaded.version, REPLAY_VERSION);
+        assert_eq!(loaded.turns, replay.turns);
+        assert!(loaded.verify().is_ok());
+    }
+
+    #[test]
+    fn test_rule_change_is_a_mismatch() {
+        let mut replay = recorded();
+        replay.initial = replay.initial.with_formula(DamageModel::PercentArmor);
+
+        match replay.verify() {
+            Err(ReplayError::Mismatch {
+                turn: 0,
+                event: 0,
+                expected: Some(expected),
+                actual: Some(actual),
+            }) => assert_ne!(expected.damage, actual.damage),
+            other => panic!("expected a mismatch on the first turn, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stronger_starting_stats_are_a_mismatch() {
+        let mut replay = recorded();
+        replay.initial.combatants[1].character.stats.defense = 5;
+
+        let err = replay.verify().unwrap_err();
+        assert!(err.to_string().starts_with("turn #0 diverged at event #0"));
+    }
+
+    #[test]
+    fn test_invalid_action_is_rejected() {
+        let mut replay = recorded();
+        replay.turns[1].action = attack(1);
+
+        assert_eq!(
+            replay.verify().unwrap_err(),
+            ReplayError::Rejected {
+                turn: 1,
+                error: EncounterError::WrongTeam {
+                    id: CombatantId(1),
+                    expected: Team::Allies,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_event_is_a_mismatch() {
+        let mut replay = recorded();
+        replay.turns[0].events.push(CombatEvent::default());
+
+        assert!(matches!(
+            replay.verify(),
+            Err(ReplayError::Mismatch {
+                turn: 0,
+                event: 1,
+                expected: Some(_),
+                actual: None,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_newer_replays_are_refused() {
+        let mut replay = recorded();
+        replay.version = REPLAY_VERSION + 1;
+
+        assert_eq!(
+            replay.verify().unwrap_err(),
+            ReplayError::UnsupportedVersion {
+                version: REPLAY_VERSION + 1
+            }
+        );
+        assert!(matches!(
+            Replay::from_json("{}"),
+            Err(ReplayError::Parse { .. })
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+//! Deterministic random number generation for combat rolls.
+//!
+//! Combat randomness (critical hits, evasion, damage variance) is drawn from a
+//! [`CombatRng`] that the caller passes in explicitly. Nothing in the logic crate
+//! reaches for a global or OS-seeded generator, so the same seed always produces
+//! the same fight.
+//!
+//! [`SeededRng`] is the default implementation. It is a SplitMix64 generator that
+//! uses only wrapping 64-bit integer arithmetic, which makes its output identical
+//! on native targets and `wasm32`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::{CombatRng, SeededRng};
+//!
+//! let mut a = SeededRng::new(42);
+//! let mut b = SeededRng::new(42);
+//!
+//! assert_eq!(a.next_u64(), b.next_u64());
+//! assert_eq!(a.roll_below(100), b.roll_below(100));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Source of randomness for combat resolution.
+///
+/// Only [`next_u64`](CombatRng::next_u64) must be implemented; the remaining
+/// methods derive bounded values from it with integer math so every
+/// implementation stays platform-independent.
+pub trait CombatRng {
+    /// Returns the next 64 random bits.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a value uniformly distributed in `0..bound`.
+    ///
+    /// Returns 0 when `bound` is 0.
+    fn roll_below(&mut self, bound: u32) -> u32 {
+        // Multiply-shift maps the top 32 bits onto the range without a modulo.
+        (((self.next_u64() >> 32) * bound as u64) >> 32) as u32
+    }
+
+    /// Rolls a percentage check. Returns `true` with probability `percent / 100`.
+    ///
+    /// No value is drawn when `percent <= 0`, so zero-chance stats leave the
+    /// generator untouched.
+    fn chance(&mut self, percent: i32) -> bool {
+        percent > 0 && (self.roll_below(100) as i32) < percent
+    }
+
+    /// Returns a value uniformly distributed in `min..=max`.
+    ///
+    /// Returns `min` without drawing when `max <= min`.
+    fn range_inclusive(&mut self, min: i32, max: i32) -> i32 {
+        if max <= min {
+            return min;
+        }
+        // The span is up to 2^32, one more than `roll_below` takes, so roll with
+        // the same multiply-shift in 64 bits. The sum stays within `min..=max`.
+        let span = (max as i64 - min as i64 + 1) as u64;
+        let roll = ((self.next_u64() >> 32) * span) >> 32;
+        (min as i64 + roll as i64) as i32
+    }
+}
+
+/// Seedable SplitMix64 generator.
+///
+/// The full generator state is a single `u64`, so it can be serialized alongside
+/// combat state and resumed later.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_wasm_fsharp_ref_logic::{CombatRng, SeededRng};
+///
+/// let mut rng = SeededRng::new(7);
+/// let roll = rng.range_inclusive(1, 6);
+/// assert!((1..=6).contains(&roll));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeededRng {
+    /// Current generator state.
+    pub state: u64,
+}
+
+impl SeededRng {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl CombatRng for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SeededRng::new(1234);
+        let mut b = SeededRng::new(1234);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_different_seeds_diverge() {
+        let mut a = SeededRng::new(1);
+        let mut b = SeededRng::new(2);
+
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn test_known_sequence_is_stable() {
+        // Reference values pin the algorithm so native and wasm32 builds
+        // (and future refactors) can't silently change outcomes.
+        let mut rng = SeededRng::new(0);
+
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+        assert_eq!(rng.next_u64(), 0x06C4_5D18_8009_454F);
+    }
+
+    #[test]
+    fn test_roll_below_stays_in_bounds() {
+        let mut rng = SeededRng::new(99);
+
+        for _ in 0..1000 {
+            assert!(rng.roll_below(100) < 100);
+        }
+        assert_eq!(rng.roll_below(0), 0);
+    }
+
+    #[test]
+    fn test_range_inclusive_covers_both_ends() {
+        let mut rng = SeededRng::new(5);
+        let rolls: Vec<i32> = (0..1000).map(|_| rng.range_inclusive(-2, 2)).collect();
+
+        assert!(rolls.iter().all(|r| (-2..=2).contains(r)));
+        assert!(rolls.contains(&-2));
+        assert!(rolls.contains(&2));
+    }
+
+    #[test]
+    fn test_range_inclusive_extreme_bounds() {
+        let mut rng = SeededRng::new(5);
+
+        for _ in 0..1000 {
+            assert!(rng.range_inclusive(-2, i32::MAX) >= -2);
+            assert!(rng.range_inclusive(i32::MAX - 1, i32::MAX) >= i32::MAX - 1);
+            assert!(rng.range_inclusive(i32::MIN, i32::MIN + 1) <= i32::MIN + 1);
+        }
+
+        // The full range doesn't collapse to `min`
+        let rolls: Vec<i32> = (0..100)
+            .map(|_| rng.range_inclusive(i32::MIN, i32::MAX))
+            .collect();
+        assert!(rolls.iter().any(|&r| r > 0));
+        assert!(rolls.iter().any(|&r| r < 0));
+    }
+
+    #[test]
+    fn test_zero_chance_does_not_consume_state() {
+        let mut rng = SeededRng::new(10);
+        let before = rng.clone();
+
+        assert!(!rng.chance(0));
+        assert_eq!(rng, before);
+    }
+
+    #[test]
+    fn test_full_chance_always_succeeds() {
+        let mut rng = SeededRng::new(10);
+
+        assert!((0..100).all(|_| rng.chance(100)));
+    }
+}
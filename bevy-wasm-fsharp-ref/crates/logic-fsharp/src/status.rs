@@ -0,0 +1,685 @@
+//! Status effects: poison, stun, shield and regen.
+//!
+//! A [`StatusEffect`] is a definition carried by an [`Ability`]. When the ability
+//! lands, the effect is applied to the defender's [`StatusEffects`] component, which
+//! lives next to [`Character`] on the same entity rather than inside it.
+//!
+//! Effects last either a number of turns or a number of seconds, matching the two
+//! [`CooldownMode`](crate::CooldownMode)s:
+//!
+//! - Turn-based effects are ticked by [`StatusEffects::tick_turn`], alongside
+//!   [`Cooldown::tick_turn`](crate::Cooldown::tick_turn)
+//! - Real-time effects are ticked by [`StatusEffects::tick`], alongside
+//!   [`Cooldown::tick`](crate::Cooldown::tick); damage and healing fire once
+//!   per full second
+//!
+//! Every change is reported as a [`StatusEvent`] so it can be logged or animated.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type StatusKind =
+//!     | Poison
+//!     | Stun
+//!     | Shield
+//!     | Regen
+//!
+//! type EffectDuration =
+//!     | Turns of int
+//!     | Seconds of Millis
+//!
+//! type StackingRule =
+//!     | Refresh
+//!     | Stack of maxStacks: int
+//!     | Ignore
+//!
+//! type StatusEffect =
+//!     { Kind: StatusKind
+//!       Magnitude: int
+//!       Duration: EffectDuration
+//!       Stacking: StackingRule }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let mut slime = Character::new_monster("Slime");
+//! let mut effects = StatusEffects::default();
+//!
+//! effects.apply(&slime.name, &StatusEffect::poison(3, 2));
+//!
+//! // Two turns of poison, then it wears off
+//! effects.tick_turn(&mut slime);
+//! let events = effects.tick_turn(&mut slime);
+//!
+//! assert_eq!(slime.hp, 14);
+//! assert!(events.iter().any(|e| matches!(e, StatusEvent::Expired { .. })));
+//! assert!(effects.effects.is_empty());
+//! ```
+
+use crate::prelude::*;
+use crate::{
+    Ability, ActiveEffect, Character, CombatEvent, EffectDuration, EffectOutcome, Millis,
+    StackingRule, StatusEffect, StatusEffects, StatusEvent, StatusKind,
+};
+
+impl StatusEffect {
+    /// Poison dealing `damage` per turn for `turns` turns. Stacks up to 3 times.
+    pub fn poison(damage: i32, turns: i32) -> Self {
+        Self {
+            kind: StatusKind::Poison,
+            magnitude: damage,
+            duration: EffectDuration::Turns(turns),
+            stacking: StackingRule::Stack { max_stacks: 3 },
+        }
+    }
+
+    /// Stun lasting `turns` turns.
+    pub fn stun(turns: i32) -> Self {
+        Self {
+            kind: StatusKind::Stun,
+            magnitude: 0,
+            duration: EffectDuration::Turns(turns),
+            stacking: StackingRule::Refresh,
+        }
+    }
+
+    /// Shield absorbing up to `points` damage for `turns` turns.
+    pub fn shield(points: i32, turns: i32) -> Self {
+        Self {
+            kind: StatusKind::Shield,
+            magnitude: points,
+            duration: EffectDuration::Turns(turns),
+            stacking: StackingRule::Refresh,
+        }
+    }
+
+    /// Regeneration restoring `heal` HP per turn for `turns` turns.
+    pub fn regen(heal: i32, turns: i32) -> Self {
+        Self {
+            kind: StatusKind::Regen,
+            magnitude: heal,
+            duration: EffectDuration::Turns(turns),
+            stacking: StackingRule::Refresh,
+        }
+    }
+}
+
+impl ActiveEffect {
+    fn new(effect: &StatusEffect) -> Self {
+        Self {
+            effect: effect.clone(),
+            remaining: effect.duration,
+            stacks: 1,
+            shield_remaining: effect.magnitude,
+            tick_progress: Millis::ZERO,
+        }
+    }
+
+    /// The per-tick amount, scaled by stack count.
+    pub fn potency(&self) -> i32 {
+        self.effect.magnitude * self.stacks as i32
+    }
+}
+
+impl StatusEffects {
+    /// Returns the active effect of the given kind, if any.
+    pub fn get(&self, kind: StatusKind) -> Option<&ActiveEffect> {
+        self.effects.iter().find(|e| e.effect.kind == kind)
+    }
+
+    /// Returns `true` if the character is stunned and should skip its turn.
+    pub fn is_stunned(&self) -> bool {
+        self.get(StatusKind::Stun).is_some()
+    }
+
+    /// Applies an effect, honouring its [`StackingRule`].
+    ///
+    /// Returns `None` if the effect was ignored because of [`StackingRule::Ignore`].
+    pub fn apply(&mut self, target: &str, effect: &StatusEffect) -> Option<StatusEvent> {
+        let stacks = match self
+            .effects
+            .iter_mut()
+            .find(|e| e.effect.kind == effect.kind)
+        {
+            None => {
+                self.effects.push(ActiveEffect::new(effect));
+                1
+            }
+            Some(existing) => match effect.stacking {
+                StackingRule::Ignore => return None,
+                StackingRule::Refresh => {
+                    let stacks = existing.stacks;
+                    *existing = ActiveEffect::new(effect);
+                    existing.stacks = stacks;
+                    existing.shield_remaining = existing.potency();
+                    stacks
+                }
+                StackingRule::Stack { max_stacks } => {
+                    let stacks = (existing.stacks + 1).min(max_stacks.max(1));
+                    *existing = ActiveEffect::new(effect);
+                    existing.stacks = stacks;
+                    existing.shield_remaining = existing.potency();
+                    stacks
+                }
+            },
+        };
+
+        Some(StatusEvent::Applied {
+            target: target.to_string(),
+            kind: effect.kind,
+            stacks,
+        })
+    }
+
+    /// Absorbs incoming damage with an active shield.
+    ///
+    /// Returns the damage left over after the shield, plus the events produced
+    /// (an [`StatusEvent::Absorbed`] and, if the shield broke, an
+    /// [`StatusEvent::Expired`]).
+    pub fn absorb(&mut self, target: &str, damage: i32) -> (i32, Vec<StatusEvent>) {
+        let mut events = Vec::new();
+        let Some(index) = self
+            .effects
+            .iter()
+            .position(|e| e.effect.kind == StatusKind::Shield)
+        else {
+            return (damage, events);
+        };
+
+        let shield = &mut self.effects[index];
+        let absorbed = damage.min(shield.shield_remaining).max(0);
+        shield.shield_remaining -= absorbed;
+
+        if absorbed > 0 {
+            events.push(StatusEvent::Absorbed {
+                target: target.to_string(),
+                amount: absorbed,
+            });
+        }
+        if shield.shield_remaining <= 0 {
+            self.effects.remove(index);
+            events.push(StatusEvent::Expired {
+                target: target.to_string(),
+                kind: StatusKind::Shield,
+            });
+        }
+
+        (damage - absorbed, events)
+    }
+
+    /// Advances turn-based effects by one turn.
+    ///
+    /// Call once at the end of the owning character's turn, after checking
+    /// [`is_stunned`](Self::is_stunned). Poison and regen fire once, durations
+    /// count down and expired effects are removed. Real-time effects are left
+    /// untouched.
+    pub fn tick_turn(&mut self, character: &mut Character) -> Vec<StatusEvent> {
+        let mut events = Vec::new();
+
+        for active in &mut self.effects {
+            let EffectDuration::Turns(turns) = active.remaining else {
+                continue;
+            };
+            fire_tick(active, character, &mut events);
+            active.remaining = EffectDuration::Turns(turns - 1);
+        }
+
+        self.remove_expired(&character.name, &mut events);
+        events
+    }
+
+    /// Advances real-time effects by `delta`.
+    ///
+    /// Poison and regen fire once for every full second elapsed. Turn-based
+    /// effects are left untouched.
+    pub fn tick(&mut self, delta: Millis, character: &mut Character) -> Vec<StatusEvent> {
+        let mut events = Vec::new();
+
+        for active in &mut self.effects {
+            let EffectDuration::Seconds(seconds) = active.remaining else {
+                continue;
+            };
+            // Never tick past the end of the effect
+            let elapsed = delta.min(seconds);
+            active.tick_progress = active.tick_progress.saturating_add(elapsed);
+            while active.tick_progress >= Millis::SECOND {
+                active.tick_progress = active.tick_progress.saturating_sub(Millis::SECOND);
+                fire_tick(active, character, &mut events);
+            }
+            active.remaining = EffectDuration::Seconds(seconds.saturating_sub(delta));
+        }
+
+        self.remove_expired(&character.name, &mut events);
+        events
+    }
+
+    fn remove_expired(&mut self, target: &str, events: &mut Vec<StatusEvent>) {
+        self.effects.retain(|active| {
+            let expired = match active.remaining {
+                EffectDuration::Turns(turns) => turns <= 0,
+                EffectDuration::Seconds(seconds) => seconds.is_zero(),
+            };
+            if expired {
+                events.push(StatusEvent::Expired {
+                    target: target.to_string(),
+                    kind: active.effect.kind,
+                });
+            }
+            !expired
+        });
+    }
+}
+
+/// Applies one tick of poison or regen to the character.
+fn fire_tick(active: &ActiveEffect, character: &mut Character, events: &mut Vec<StatusEvent>) {
+    let amount = match active.effect.kind {
+        // Poison never takes HP below 0
+        StatusKind::Poison => active.potency().min(character.hp.max(0)),
+        StatusKind::Regen => {
+            let missing = (character.stats.hp - character.hp).max(0);
+            -active.potency().min(missing)
+        }
+        StatusKind::Stun | StatusKind::Shield => return,
+    };
+
+    character.hp -= amount;
+    events.push(StatusEvent::Ticked {
+        target: character.name.clone(),
+        kind: active.effect.kind,
+        amount,
+        hp_after: character.hp,
+    });
+}
+
+/// Resolves the status side of an ability after its [`CombatEvent`] was computed.
+///
+/// - A defender's shield absorbs each hit, lowering its
+///   [`EffectOutcome::Damaged`] amount and `event.damage`. The event's HP and
+///   overkill are then settled again against `defender`, the character as it was
+///   before the ability
+/// - Unless the attack missed, the ability's [`AbilityEffect::Shield`] and
+///   [`AbilityEffect::ApplyStatus`] effects are applied to the defender, each
+///   adding an [`EffectOutcome::StatusApplied`] or [`EffectOutcome::StatusIgnored`]
+///
+/// All resulting [`StatusEvent`]s are appended to `event.status_events`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_wasm_fsharp_ref_logic::*;
+///
+/// let player = Character::new_player("Hero");
+/// let monster = Character::new_monster("Slime");
+/// let mut monster_effects = StatusEffects::default();
+///
+/// let venom = Ability {
+///     name: "Venom Strike".to_string(),
+///     effects: vec![
+///         AbilityEffect::Damage { power: 2 },
+///         AbilityEffect::ApplyStatus(StatusEffect::poison(2, 3)),
+///     ],
+///     ..Default::default()
+/// };
+///
+/// let mut event = compute_attack(&player, &monster, &venom);
+/// apply_ability_status(&mut event, &venom, &monster, &mut monster_effects);
+///
+/// assert!(monster_effects.get(StatusKind::Poison).is_some());
+/// assert_eq!(event.status_events.len(), 1);
+/// assert_eq!(
+///     event.outcomes[1],
+///     EffectOutcome::StatusApplied { kind: StatusKind::Poison, stacks: 1 }
+/// );
+/// ```
+pub fn apply_ability_status(
+    event: &mut CombatEvent,
+    ability: &Ability,
+    defender: &Character,
+    defender_effects: &mut StatusEffects,
+) {
+    if event.missed {
+        return;
+    }
+
+    for outcome in &mut event.outcomes {
+        let EffectOutcome::Damaged { amount } = outcome else {
+            continue;
+        };
+        if *amount > 0 {
+            let (remaining, events) = defender_effects.absorb(&event.defender_name, *amount);
+            event.damage -= *amount - remaining;
+            *amount = remaining;
+            event.status_events.extend(events);
+        }
+    }
+    event.settle(defender.hp, defender.stats.hp);
+
+    for effect in ability.status_effects() {
+        match defender_effects.apply(&event.defender_name, &effect) {
+            Some(applied) => {
+                if let StatusEvent::Applied { kind, stacks, .. } = applied {
+                    event
+                        .outcomes
+                        .push(EffectOutcome::StatusApplied { kind, stacks });
+                }
+                event.status_events.push(applied);
+            }
+            None => event
+                .outcomes
+                .push(EffectOutcome::StatusIgnored { kind: effect.kind }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_attack, AbilityEffect, Targeting};
+
+    fn wounded(name: &str, hp: i32) -> Character {
+        let mut character = Character::new_player(name);
+        character.hp = hp;
+        character
+    }
+
+    #[test]
+    fn test_poison_ticks_each_turn_then_expires() {
+        let mut target = Character::new_monster("Slime");
+        let mut effects = StatusEffects::default();
+        effects.apply(&target.name, &StatusEffect::poison(4, 2));
+
+        let first = effects.tick_turn(&mut target);
+        assert_eq!(target.hp, 16);
+        assert_eq!(first.len(), 1);
+
+        let second = effects.tick_turn(&mut target);
+        assert_eq!(target.hp, 12);
+        assert!(second.contains(&StatusEvent::Expired {
+            target: "Slime".into(),
+            kind: StatusKind::Poison,
+        }));
+        assert!(effects.effects.is_empty());
+    }
+
+    #[test]
+    fn test_poison_does_not_take_hp_below_zero() {
+        let mut target = Character::new_monster("Slime");
+        target.hp = 3;
+        let mut effects = StatusEffects::default();
+        effects.apply(&target.name, &StatusEffect::poison(5, 2));
+
+        let events = effects.tick_turn(&mut target);
+
+        assert_eq!(target.hp, 0);
+        assert_eq!(
+            events[0],
+            StatusEvent::Ticked {
+                target: "Slime".into(),
+                kind: StatusKind::Poison,
+                amount: 3,
+                hp_after: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_poison_stacks_up_to_max() {
+        let mut target = Character::new_monster("Slime");
+        let mut effects = StatusEffects::default();
+        let poison = StatusEffect::poison(1, 3);
+
+        for _ in 0..5 {
+            effects.apply(&target.name, &poison);
+        }
+
+        assert_eq!(effects.get(StatusKind::Poison).unwrap().stacks, 3);
+        effects.tick_turn(&mut target);
+        assert_eq!(target.hp, 17);
+    }
+
+    #[test]
+    fn test_refresh_resets_duration() {
+        let mut target = Character::new_monster("Slime");
+        let mut effects = StatusEffects::default();
+        effects.apply(&target.name, &StatusEffect::stun(2));
+        effects.tick_turn(&mut target);
+
+        effects.apply(&target.name, &StatusEffect::stun(2));
+
+        assert_eq!(
+            effects.get(StatusKind::Stun).unwrap().remaining,
+            EffectDuration::Turns(2)
+        );
+        assert_eq!(effects.get(StatusKind::Stun).unwrap().stacks, 1);
+    }
+
+    #[test]
+    fn test_ignore_keeps_existing_effect() {
+        let mut effects = StatusEffects::default();
+        let mut first = StatusEffect::regen(2, 5);
+        first.stacking = StackingRule::Ignore;
+        let mut second = StatusEffect::regen(9, 1);
+        second.stacking = StackingRule::Ignore;
+
+        assert!(effects.apply("Hero", &first).is_some());
+        assert!(effects.apply("Hero", &second).is_none());
+        assert_eq!(effects.get(StatusKind::Regen).unwrap().effect.magnitude, 2);
+    }
+
+    #[test]
+    fn test_stun_lasts_one_turn() {
+        let mut target = Character::new_monster("Slime");
+        let mut effects = StatusEffects::default();
+        effects.apply(&target.name, &StatusEffect::stun(1));
+
+        assert!(effects.is_stunned());
+        effects.tick_turn(&mut target);
+        assert!(!effects.is_stunned());
+        assert_eq!(target.hp, 20);
+    }
+
+    #[test]
+    fn test_regen_caps_at_max_hp() {
+        let mut target = wounded("Hero", 27);
+        let mut effects = StatusEffects::default();
+        effects.apply(&target.name, &StatusEffect::regen(5, 3));
+
+        let events = effects.tick_turn(&mut target);
+
+        assert_eq!(target.hp, 30);
+        assert_eq!(
+            events[0],
+            StatusEvent::Ticked {
+                target: "Hero".into(),
+                kind: StatusKind::Regen,
+                amount: -3,
+                hp_after: 30,
+            }
+        );
+    }
+
+    #[test]
+    fn test_shield_absorbs_then_breaks() {
+        let mut effects = StatusEffects::default();
+        effects.apply("Hero", &StatusEffect::shield(10, 3));
+
+        let (left, events) = effects.absorb("Hero", 6);
+        assert_eq!(left, 0);
+        assert_eq!(events.len(), 1);
+
+        let (left, events) = effects.absorb("Hero", 6);
+        assert_eq!(left, 2);
+        assert_eq!(events.len(), 2);
+        assert!(effects.get(StatusKind::Shield).is_none());
+    }
+
+    #[test]
+    fn test_real_time_effects_tick_per_second() {
+        let mut target = Character::new_monster("Slime");
+        let mut effects = StatusEffects::default();
+        let burn = StatusEffect {
+            kind: StatusKind::Poison,
+            magnitude: 2,
+            duration: EffectDuration::Seconds(Millis(3000)),
+            stacking: StackingRule::Refresh,
+        };
+        effects.apply(&target.name, &burn);
+
+        effects.tick(Millis(500), &mut target);
+        assert_eq!(target.hp, 20);
+        effects.tick(Millis(500), &mut target);
+        assert_eq!(target.hp, 18);
+
+        let events = effects.tick(Millis::from_secs(5), &mut target);
+        assert_eq!(target.hp, 14);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, StatusEvent::Expired { .. })));
+        assert!(effects.effects.is_empty());
+    }
+
+    #[test]
+    fn test_turn_and_real_time_ticks_are_independent() {
+        let mut target = Character::new_monster("Slime");
+        let mut effects = StatusEffects::default();
+        effects.apply(&target.name, &StatusEffect::poison(1, 2));
+
+        // Real-time ticking leaves turn-based effects alone
+        effects.tick(Millis::from_secs(10), &mut target);
+        assert_eq!(target.hp, 20);
+        assert_eq!(effects.effects.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_ability_status_applies_effects_and_shield() {
+        let attacker = Character::new_player("Hero");
+        let defender = Character::new_monster("Slime");
+        let mut defender_effects = StatusEffects::default();
+        defender_effects.apply(&defender.name, &StatusEffect::shield(5, 2));
+
+        let ability = Ability {
+            name: "Venom Strike".into(),
+            effects: vec![
+                AbilityEffect::Damage { power: 5 },
+                AbilityEffect::ApplyStatus(StatusEffect::poison(2, 2)),
+            ],
+            ..Default::default()
+        };
+
+        let mut event = compute_attack(&attacker, &defender, &ability);
+        apply_ability_status(&mut event, &ability, &defender, &mut defender_effects);
+
+        // 14 damage, 5 absorbed by the shield
+        assert_eq!(event.damage, 9);
+        assert_eq!(event.defender_hp_after, 11);
+        assert_eq!(event.effective_amount, 9);
+        assert_eq!(
+            event.outcomes,
+            vec![
+                EffectOutcome::Damaged { amount: 9 },
+                EffectOutcome::StatusApplied {
+                    kind: StatusKind::Poison,
+                    stacks: 1
+                },
+            ]
+        );
+        assert!(defender_effects.get(StatusKind::Poison).is_some());
+        assert!(defender_effects.get(StatusKind::Shield).is_none());
+        assert_eq!(event.status_events.len(), 3);
+    }
+
+    #[test]
+    fn test_apply_ability_status_skips_missed_attacks() {
+        let attacker = Character::new_player("Hero");
+        let defender = Character::new_monster("Slime");
+        let mut defender_effects = StatusEffects::default();
+        let ability = Ability {
+            name: "Stunning Blow".into(),
+            effects: vec![
+                AbilityEffect::Damage { power: 1 },
+                AbilityEffect::ApplyStatus(StatusEffect::stun(1)),
+            ],
+            ..Default::default()
+        };
+
+        let mut event = compute_attack(&attacker, &defender, &ability);
+        event.missed = true;
+        apply_ability_status(&mut event, &ability, &defender, &mut defender_effects);
+
+        assert!(!defender_effects.is_stunned());
+        assert!(event.status_events.is_empty());
+    }
+
+    #[test]
+    fn test_shield_ability_raises_and_refreshes_shield() {
+        let cleric = Character::new_player("Cleric");
+        let mut effects = StatusEffects::default();
+        let ward = Ability {
+            name: "Ward".into(),
+            target: Targeting::Caster,
+            effects: vec![AbilityEffect::Shield {
+                points: 6,
+                duration: EffectDuration::Turns(2),
+            }],
+            ..Default::default()
+        };
+
+        let mut event = compute_attack(&cleric, &cleric, &ward);
+        apply_ability_status(&mut event, &ward, &cleric, &mut effects);
+        assert_eq!(event.damage, 0);
+        assert_eq!(effects.get(StatusKind::Shield).unwrap().shield_remaining, 6);
+
+        // A hit wears the shield down, and casting again restores it
+        effects.absorb(&cleric.name, 4);
+        let mut event = compute_attack(&cleric, &cleric, &ward);
+        apply_ability_status(&mut event, &ward, &cleric, &mut effects);
+        assert_eq!(effects.get(StatusKind::Shield).unwrap().shield_remaining, 6);
+        assert_eq!(
+            event.outcomes,
+            vec![EffectOutcome::StatusApplied {
+                kind: StatusKind::Shield,
+                stacks: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn test_ignored_status_is_reported() {
+        let attacker = Character::new_player("Hero");
+        let defender = Character::new_monster("Slime");
+        let mut defender_effects = StatusEffects::default();
+        let mut stun = StatusEffect::stun(2);
+        stun.stacking = StackingRule::Ignore;
+        defender_effects.apply(&defender.name, &stun);
+        let ability = Ability {
+            name: "Daze".into(),
+            effects: vec![AbilityEffect::ApplyStatus(stun)],
+            ..Default::default()
+        };
+
+        let mut event = compute_attack(&attacker, &defender, &ability);
+        apply_ability_status(&mut event, &ability, &defender, &mut defender_effects);
+
+        assert_eq!(
+            event.outcomes,
+            vec![EffectOutcome::StatusIgnored {
+                kind: StatusKind::Stun
+            }]
+        );
+        assert!(event.status_events.is_empty());
+    }
+
+    #[test]
+    fn test_status_effects_serialization() {
+        let mut effects = StatusEffects::default();
+        effects.apply("Hero", &StatusEffect::poison(2, 3));
+
+        let json = serde_json::to_string(&effects).expect("Should serialize");
+        let deserialized: StatusEffects = serde_json::from_str(&json).expect("Should deserialize");
+
+        assert_eq!(deserialized, effects);
+    }
+}
@@ -0,0 +1,158 @@
+//! Fixed-point time for real-time combat.
+//!
+//! Real-time [`Cooldown`](crate::Cooldown)s and [`EffectDuration`](crate::EffectDuration)s
+//! count down in whole [`Millis`] instead of `f32` seconds. Integer arithmetic
+//! gives the same answer on every platform, and for every way of slicing the same
+//! span of time into ticks, so native and wasm builds fed the same ticks end in
+//! identical states and their checksums can be compared.
+//!
+//! Convert at the edges: [`Millis::from_secs_f32`] for designer-facing data such
+//! as catalog cooldowns, and `Millis::from(duration)` for a game clock. A game
+//! loop that ticks by a fixed step, such as Bevy's `FixedUpdate`, is fully
+//! deterministic; one that ticks by frame time is deterministic for a given
+//! sequence of frames.
+//!
+//! # F# Equivalent
+//!
+//! ```fsharp
+//! type Millis = Millis of uint32
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy_wasm_fsharp_ref_logic::*;
+//!
+//! let mut fast = Cooldown::seconds(1.5);
+//! let mut slow = Cooldown::seconds(1.5);
+//! fast.trigger();
+//! slow.trigger();
+//!
+//! // 75 ticks of 20 ms and 3 ticks of 500 ms land on exactly the same state
+//! for _ in 0..75 {
+//!     fast.tick(Millis(20));
+//! }
+//! for _ in 0..3 {
+//!     slow.tick(Millis(500));
+//! }
+//! assert_eq!(fast, slow);
+//! assert!(fast.is_ready());
+//! ```
+
+use core::fmt;
+use core::time::Duration;
+
+use crate::Millis;
+
+impl Millis {
+    /// No time at all.
+    pub const ZERO: Millis = Millis(0);
+
+    /// One second.
+    pub const SECOND: Millis = Millis(1000);
+
+    /// `secs` whole seconds, saturating at `u32::MAX` milliseconds.
+    pub const fn from_secs(secs: u32) -> Self {
+        Millis(secs.saturating_mul(1000))
+    }
+
+    /// `secs` seconds rounded to the nearest millisecond. Negative and NaN
+    /// values are zero.
+    pub fn from_secs_f32(secs: f32) -> Self {
+        // Float-to-int casts saturate, and NaN casts to 0
+        Millis((secs * 1000.0 + 0.5) as u32)
+    }
+
+    /// This duration in seconds, for display.
+    pub fn as_secs_f32(self) -> f32 {
+        self.0 as f32 / 1000.0
+    }
+
+    /// Whether no time is left.
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// `self + rhs`, saturating at `u32::MAX` milliseconds.
+    pub const fn saturating_add(self, rhs: Millis) -> Self {
+        Millis(self.0.saturating_add(rhs.0))
+    }
+
+    /// `self - rhs`, stopping at zero.
+    pub const fn saturating_sub(self, rhs: Millis) -> Self {
+        Millis(self.0.saturating_sub(rhs.0))
+    }
+}
+
+/// Whole milliseconds of `duration`, rounding down and saturating at
+/// `u32::MAX`.
+impl From<Duration> for Millis {
+    fn from(duration: Duration) -> Self {
+        Millis(u32::try_from(duration.as_millis()).unwrap_or(u32::MAX))
+    }
+}
+
+impl From<Millis> for Duration {
+    fn from(millis: Millis) -> Self {
+        Duration::from_millis(millis.0.into())
+    }
+}
+
+/// "1.500s"
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:03}s", self.0 / 1000, self.0 % 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_from_secs_f32_rounds_to_nearest() {
+        assert_eq!(Millis::from_secs_f32(1.5), Millis(1500));
+        assert_eq!(Millis::from_secs_f32(0.2), Millis(200));
+        assert_eq!(Millis::from_secs_f32(0.0004), Millis(0));
+        assert_eq!(Millis::from_secs_f32(0.0006), Millis(1));
+    }
+
+    #[test]
+    fn test_from_secs_f32_clamps_invalid_values() {
+        assert_eq!(Millis::from_secs_f32(-2.0), Millis::ZERO);
+        assert_eq!(Millis::from_secs_f32(f32::NAN), Millis::ZERO);
+        assert_eq!(Millis::from_secs_f32(f32::INFINITY), Millis(u32::MAX));
+    }
+
+    #[test]
+    fn test_arithmetic_saturates() {
+        assert_eq!(Millis(300).saturating_sub(Millis(500)), Millis::ZERO);
+        assert_eq!(Millis(u32::MAX).saturating_add(Millis(1)), Millis(u32::MAX));
+        assert_eq!(Millis::from_secs(u32::MAX), Millis(u32::MAX));
+        assert_eq!(Millis::from_secs(3), Millis(3000));
+    }
+
+    #[test]
+    fn test_duration_round_trip() {
+        assert_eq!(Millis::from(Duration::from_micros(16_999)), Millis(16));
+        assert_eq!(Duration::from(Millis(20)), Duration::from_millis(20));
+        assert_eq!(
+            Millis::from(Duration::from_secs(u64::MAX)),
+            Millis(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Millis(1500).to_string(), "1.500s");
+        assert_eq!(Millis(20).to_string(), "0.020s");
+        assert_eq!(Millis::ZERO.as_secs_f32(), 0.0);
+    }
+
+    #[test]
+    fn test_serializes_as_integer() {
+        assert_eq!(serde_json::to_string(&Millis(250)).unwrap(), "250");
+        assert_eq!(serde_json::from_str::<Millis>("250").unwrap(), Millis(250));
+    }
+}
@@ -2,77 +2,107 @@
 
 use bevy_wasm_fsharp_ref_logic::*;
 
+fn remaining_seconds(slot: &AbilitySlot) -> f32 {
+    match slot.cooldown {
+        Cooldown::Seconds { remaining, .. } => remaining.as_secs_f32(),
+        Cooldown::Turns { .. } => panic!("expected a real-time cooldown"),
+    }
+}
+
 // ==================== AbilitySlot Tests ====================
 
 #[test]
 fn test_ability_slot_creation() {
-    let slot = AbilitySlot::new(Ability::basic_attack(), 2.0);
+    let slot = AbilitySlot::new(
+        Ability::basic_attack(),
+        AbilityType::BasicAttack,
+        Cooldown::seconds(2.0),
+    );
 
     assert_eq!(slot.ability.name, "Basic Attack");
-    assert_eq!(slot.cooldown_max, 2.0);
-    assert_eq!(slot.cooldown_current, 0.0);
+    assert_eq!(slot.cooldown, Cooldown::seconds(2.0));
     assert!(slot.is_ready());
 }
 
 #[test]
 fn test_ability_slot_use_triggers_cooldown() {
-    let mut slot = AbilitySlot::new(Ability::powerful_attack(), 3.0);
+    let mut slot = AbilitySlot::new(
+        Ability::powerful_attack(),
+        AbilityType::PowerfulAttack,
+        Cooldown::seconds(3.0),
+    );
 
     assert!(slot.is_ready());
 
     slot.use_ability();
 
     assert!(!slot.is_ready());
-    assert_eq!(slot.cooldown_current, 3.0);
+    assert_eq!(remaining_seconds(&slot), 3.0);
 }
 
 #[test]
 fn test_ability_slot_tick_decreases_cooldown() {
-    let mut slot = AbilitySlot::new(Ability::basic_attack(), 2.0);
+    let mut slot = AbilitySlot::new(
+        Ability::basic_attack(),
+        AbilityType::BasicAttack,
+        Cooldown::seconds(2.0),
+    );
     slot.use_ability();
 
-    assert_eq!(slot.cooldown_current, 2.0);
+    assert_eq!(remaining_seconds(&slot), 2.0);
 
-    slot.tick(0.5);
-    assert_eq!(slot.cooldown_current, 1.5);
+    slot.tick(Millis(500));
+    assert_eq!(remaining_seconds(&slot), 1.5);
 
-    slot.tick(1.0);
-    assert_eq!(slot.cooldown_current, 0.5);
+    slot.tick(Millis(1000));
+    assert_eq!(remaining_seconds(&slot), 0.5);
 
-    slot.tick(0.5);
-    assert_eq!(slot.cooldown_current, 0.0);
+    slot.tick(Millis(500));
+    assert_eq!(remaining_seconds(&slot), 0.0);
     assert!(slot.is_ready());
 }
 
 #[test]
 fn test_ability_slot_tick_does_not_go_negative() {
-    let mut slot = AbilitySlot::new(Ability::quick_strike(), 0.2);
+    let mut slot = AbilitySlot::new(
+        Ability::quick_strike(),
+        AbilityType::BasicAttack,
+        Cooldown::seconds(0.2),
+    );
 
-    assert_eq!(slot.cooldown_current, 0.0);
+    assert_eq!(remaining_seconds(&slot), 0.0);
 
-    slot.tick(1.0);
-    assert_eq!(slot.cooldown_current, 0.0); // Should not go negative
+    slot.tick(Millis(1000));
+    assert_eq!(remaining_seconds(&slot), 0.0); // Should not go negative
 }
 
 #[test]
 fn test_ability_slot_cooldown_progress() {
-    let mut slot = AbilitySlot::new(Ability::powerful_attack(), 4.0);
+    let mut slot = AbilitySlot::new(
+        Ability::powerful_attack(),
+        AbilityType::PowerfulAttack,
+        Cooldown::seconds(4.0),
+    );
 
     assert_eq!(slot.cooldown_progress(), 0.0); // Ready
 
     slot.use_ability();
     assert_eq!(slot.cooldown_progress(), 1.0); // Just used
 
-    slot.tick(2.0);
+    slot.tick(Millis(2000));
     assert_eq!(slot.cooldown_progress(), 0.5); // Halfway
 
-    slot.tick(2.0);
+    slot.tick(Millis(2000));
     assert_eq!(slot.cooldown_progress(), 0.0); // Ready again
 }
 
 #[test]
 fn test_ability_slot_zero_cooldown() {
-    let mut slot = AbilitySlot::new(Ability::basic_attack(), 0.0);
+    let mut slot = AbilitySlot::new(
+        Ability::basic_attack(),
+        AbilityType::BasicAttack,
+        Cooldown::seconds(0.0),
+    );
 
     assert!(slot.is_ready());
 
@@ -115,7 +145,7 @@ fn test_ability_set_tick_all() {
     }
 
     // Tick all cooldowns
-    ability_set.tick_all(0.3);
+    ability_set.tick_all(Millis(300));
 
     // Check quick strike (0.2s cooldown) is ready
     assert!(ability_set.abilities[3].is_ready());
@@ -152,6 +182,23 @@ fn test_ability_set_get_ready_ability_out_of_bounds() {
     assert!(ability_set.get_ready_ability(10).is_none()); // Out of bounds
 }
 
+#[test]
+fn test_ability_set_turn_based_cooldowns() {
+    let mut ability_set = AbilitySet::monster_turn_based();
+
+    assert!(ability_set.use_ability("Powerful Attack"));
+    assert!(!ability_set.use_ability("Meteor")); // Not in the set
+
+    // Real-time ticks do not advance turn cooldowns
+    ability_set.tick_all(Millis(10000));
+    assert!(ability_set.get_ready_ability(1).is_none());
+
+    for _ in 0..3 {
+        ability_set.tick_turn_all();
+    }
+    assert!(ability_set.get_ready_ability(1).is_some());
+}
+
 // ==================== Healing Ability Tests ====================
 
 #[test]
@@ -171,13 +218,14 @@ fn test_heal_ability_restores_hp() {
 #[test]
 fn test_heal_bypasses_defense() {
     let attacker = Character::new_player("Hero");
-    let mut defender = Character {
+    let defender = Character {
         name: "Tank".to_string(),
         hp: 20,
         stats: Stats {
             hp: 50,
             attack: 5,
             defense: 100, // Very high defense
+            ..Default::default()
         },
     };
 
@@ -190,16 +238,19 @@ fn test_heal_bypasses_defense() {
 }
 
 #[test]
-fn test_heal_can_exceed_original_hp() {
-    // Note: In the app, HP should be capped at stats.hp
-    // But the logic function itself doesn't enforce this
+fn test_heal_is_capped_at_max_hp() {
+    // The logic crate caps healing, so callers can copy defender_hp_after as-is
     let attacker = Character::new_player("Hero");
     let defender = Character::new_player("Full HP Hero");
 
     let heal_ability = Ability::heal();
     let event = compute_attack(&attacker, &defender, &heal_ability);
 
-    assert_eq!(event.defender_hp_after, 38); // 30 + 8 = 38 (over max)
+    assert_eq!(event.damage, -8);
+    assert_eq!(event.defender_hp_after, 30); // Already at the max of 30
+    assert_eq!(event.effective_amount, 0);
+    assert_eq!(event.overheal, 8);
+    assert_eq!(event.outcomes, vec![EffectOutcome::Healed { amount: 0 }]);
 }
 
 // ==================== Diverse Ability Tests ====================
@@ -209,7 +260,7 @@ fn test_basic_attack_ability() {
     let ability = Ability::basic_attack();
 
     assert_eq!(ability.name, "Basic Attack");
-    assert_eq!(ability.power, 5);
+    assert_eq!(ability.power(), 5);
 }
 
 #[test]
@@ -217,7 +268,7 @@ fn test_powerful_attack_ability() {
     let ability = Ability::powerful_attack();
 
     assert_eq!(ability.name, "Powerful Attack");
-    assert_eq!(ability.power, 12);
+    assert_eq!(ability.power(), 12);
 }
 
 #[test]
@@ -225,7 +276,8 @@ fn test_heal_ability() {
     let ability = Ability::heal();
 
     assert_eq!(ability.name, "Heal");
-    assert_eq!(ability.power, -8);
+    assert_eq!(ability.effects, vec![AbilityEffect::Heal { amount: 8 }]);
+    assert_eq!(ability.target, Targeting::Ally);
 }
 
 #[test]
@@ -233,7 +285,7 @@ fn test_quick_strike_ability() {
     let ability = Ability::quick_strike();
 
     assert_eq!(ability.name, "Quick Strike");
-    assert_eq!(ability.power, 3);
+    assert_eq!(ability.power(), 3);
 }
 
 #[test]
@@ -288,7 +340,7 @@ fn test_full_combat_sequence_with_cooldowns() {
     }
 
     // Tick forward by 0.6 seconds
-    ability_set.tick_all(0.6);
+    ability_set.tick_all(Millis(600));
 
     // Basic attack should be ready again (0.5s cooldown)
     assert!(ability_set.get_ready_ability(0).is_some());
@@ -297,7 +349,7 @@ fn test_full_combat_sequence_with_cooldowns() {
     assert!(ability_set.get_ready_ability(1).is_none());
 
     // Tick forward by 2.5 more seconds
-    ability_set.tick_all(2.5);
+    ability_set.tick_all(Millis(2500));
 
     // Powerful attack should be ready now (total 3.1s elapsed)
     assert!(ability_set.get_ready_ability(1).is_some());
@@ -329,10 +381,10 @@ fn test_spam_protection() {
     assert!(ability_set.get_ready_ability(3).is_none());
 
     // Tick by 0.1s (not enough)
-    ability_set.tick_all(0.1);
+    ability_set.tick_all(Millis(100));
     assert!(ability_set.get_ready_ability(3).is_none());
 
     // Tick by another 0.1s (total 0.2s, should be ready)
-    ability_set.tick_all(0.1);
+    ability_set.tick_all(Millis(100));
     assert!(ability_set.get_ready_ability(3).is_some());
 }
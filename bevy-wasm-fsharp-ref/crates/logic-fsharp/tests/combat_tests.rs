@@ -1,7 +1,7 @@
-/// Tests for combat logic to verify F# ↔ Rust type alignment
-/// These tests ensure the Rust implementation matches the F# specification
+//! Tests for combat logic to verify F# ↔ Rust type alignment
+//! These tests ensure the Rust implementation matches the F# specification
 
-use bevy_wasm_fsharp_ref_logic::{Ability, Character, Stats, compute_attack};
+use bevy_wasm_fsharp_ref_logic::{Ability, Character, Stats, compute_attack, COMBAT_EVENT_VERSION};
 
 #[test]
 fn test_basic_attack_damage_calculation() {
@@ -13,6 +13,7 @@ fn test_basic_attack_damage_calculation() {
             hp: 30,
             attack: 10,
             defense: 2,
+            ..Default::default()
         },
     };
 
@@ -23,13 +24,11 @@ fn test_basic_attack_damage_calculation() {
             hp: 20,
             attack: 6,
             defense: 1,
+            ..Default::default()
         },
     };
 
-    let basic_attack = Ability {
-        name: "Basic Attack".into(),
-        power: 5,
-    };
+    let basic_attack = Ability::damage("Basic Attack", 5);
 
     // Execute: Player attacks monster
     let event = compute_attack(&player, &monster, &basic_attack);
@@ -51,6 +50,7 @@ fn test_minimum_damage_one() {
             hp: 10,
             attack: 1,
             defense: 0,
+            ..Default::default()
         },
     };
 
@@ -61,13 +61,11 @@ fn test_minimum_damage_one() {
             hp: 50,
             attack: 5,
             defense: 20,
+            ..Default::default()
         },
     };
 
-    let weak_ability = Ability {
-        name: "Poke".into(),
-        power: 1,
-    };
+    let weak_ability = Ability::damage("Poke", 1);
 
     // Execute: Weak attack against strong defense
     let event = compute_attack(&weak_attacker, &strong_defender, &weak_ability);
@@ -100,7 +98,7 @@ fn test_character_constructors() {
 fn test_ability_basic_attack() {
     let ability = Ability::basic_attack();
     assert_eq!(ability.name, "Basic Attack");
-    assert_eq!(ability.power, 5);
+    assert_eq!(ability.power(), 5);
 }
 
 #[test]
@@ -115,7 +113,9 @@ fn test_combat_event_fields() {
     assert!(!event.attacker_name.is_empty());
     assert!(!event.defender_name.is_empty());
     assert!(event.damage > 0);
-    // defender_hp_after can be negative (character death)
+    assert_eq!(event.version, COMBAT_EVENT_VERSION);
+    // defender_hp_after is clamped at 0 (character death)
+    assert!(event.defender_hp_after >= 0);
 }
 
 #[test]
@@ -128,6 +128,7 @@ fn test_overkill_damage() {
             hp: 5,
             attack: 1,
             defense: 0,
+            ..Default::default()
         },
     };
     let ability = Ability::basic_attack();
@@ -136,8 +137,10 @@ fn test_overkill_damage() {
 
     // Damage exceeds defender's HP
     assert!(event.damage >= weak_defender.hp);
-    // HP can go negative
-    assert!(event.defender_hp_after <= 0);
+    // HP stops at 0 and the rest is reported as overkill
+    assert_eq!(event.defender_hp_after, 0);
+    assert_eq!(event.overkill, event.damage - weak_defender.hp);
+    assert!(event.killing_blow);
 }
 
 #[test]
@@ -177,6 +180,7 @@ fn test_fsharp_alignment_example() {
             hp: 30,
             attack: 10,
             defense: 2,
+            ..Default::default()
         },
     };
 
@@ -187,13 +191,11 @@ fn test_fsharp_alignment_example() {
             hp: 20,
             attack: 6,
             defense: 1,
+            ..Default::default()
         },
     };
 
-    let basic_attack = Ability {
-        name: "Basic Attack".into(),
-        power: 5,
-    };
+    let basic_attack = Ability::damage("Basic Attack", 5);
 
     let result = compute_attack(&player, &monster, &basic_attack);
 
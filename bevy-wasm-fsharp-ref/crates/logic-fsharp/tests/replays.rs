@@ -0,0 +1,105 @@
+//! Recorded fights that must keep playing out the same way.
+//!
+//! Every `.json` file in `tests/replays` is a [`Replay`] re-simulated against the
+//! current rules. A failure means a rule change altered a recorded fight: if the
+//! change is intended, re-record the fixtures with
+//!
+//! ```text
+//! cargo test -p bevy-wasm-fsharp-ref-logic --test replays -- --ignored
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use bevy_wasm_fsharp_ref_logic::*;
+
+fn replay_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/replays")
+}
+
+/// The demo party against a bestiary encounter, every side driven by its AI
+/// personality.
+fn record(encounter: &str, seed: Option<u64>) -> Replay {
+    let abilities = AbilityCatalog::builtin();
+    let classes = ClassCatalog::builtin();
+    let bestiary = Bestiary::builtin();
+
+    let mut combatants = Vec::new();
+    let mut personalities = Vec::new();
+    for (name, class) in [("Hero", "Warrior"), ("Ranger", "Ranger")] {
+        let class = classes.require(class).unwrap();
+        combatants.push(
+            class
+                .combatant(Team::Allies, name, 1, &abilities, CooldownMode::Turns)
+                .unwrap(),
+        );
+        personalities.push(class.personality);
+    }
+    for (monster, template) in bestiary
+        .spawn_encounter(encounter, &abilities, CooldownMode::Turns)
+        .unwrap()
+    {
+        combatants.push(monster);
+        personalities.push(template.personality);
+    }
+
+    let mut state = Encounter::new(combatants);
+    let mut replay = Replay::new(state.clone());
+    let mut rng = seed.map(SeededRng::new);
+    while state.outcome() == EncounterOutcome::Ongoing {
+        let actor = state.current().unwrap();
+        let action = choose_ai_action(&state, &personalities[actor.0]).unwrap();
+        state = match &mut rng {
+            Some(rng) => replay.play_with_rng(&state, &action, rng),
+            None => replay.play(&state, &action),
+        }
+        .unwrap()
+        .0;
+    }
+    replay
+}
+
+fn fixtures() -> Vec<(&'static str, Replay)> {
+    vec![
+        ("cave_ambush", record("cave", Some(2024))),
+        ("slime_pit", record("slime_pit", None)),
+    ]
+}
+
+#[test]
+fn test_recorded_replays_still_verify() {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(replay_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    files.sort();
+    assert!(
+        !files.is_empty(),
+        "no replays in {}",
+        replay_dir().display()
+    );
+
+    for path in files {
+        let replay = Replay::from_file(&path).unwrap();
+        if let Err(err) = replay.verify() {
+            panic!("{}: {}", path.display(), err);
+        }
+    }
+}
+
+#[test]
+fn test_recorded_fights_finish() {
+    for (name, replay) in fixtures() {
+        let state = replay.verify().unwrap();
+        assert_ne!(state.outcome(), EncounterOutcome::Ongoing, "{}", name);
+    }
+}
+
+#[test]
+#[ignore = "re-records the replay fixtures"]
+fn record_replays() {
+    for (name, replay) in fixtures() {
+        let path = replay_dir().join(format!("{}.json", name));
+        std::fs::write(&path, replay.to_json() + "\n").unwrap();
+    }
+}
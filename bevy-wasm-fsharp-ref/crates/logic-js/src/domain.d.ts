@@ -0,0 +1,536 @@
+// @generated by fsharp-codegen from fsharp/Domain.fs. Do not edit by hand.
+
+/** The damage type of an ability. */
+export type Element =
+    | "Physical"
+    | "Fire"
+    | "Ice"
+    | "Lightning"
+    | "Poison"
+    | "Holy";
+
+/** How well an attack's element matched the defender's resistances. */
+export type Effectiveness =
+    | "Normal"
+    | "SuperEffective"
+    | "Resisted"
+    | "Immune";
+
+/**
+ * The built-in damage formulas.
+ *
+ * Dispatches to the matching formula, so an encounter or a data file can name
+ * the formula it uses wherever a `DamageFormula` is expected.
+ */
+export type DamageModel =
+    | "FlatDefense"
+    | "PercentArmor"
+    | "AttackRatio";
+
+/**
+ * A character's damage multipliers per element, in percent.
+ *
+ * Elements without an entry take neutral (100%) damage. Serializes as a plain
+ * map, e.g. `{"Fire": 200, "Ice": 50}`.
+ */
+export type Resistances = Partial<Record<Element, number>>;
+
+/**
+ * Character combat statistics.
+ *
+ * Stats define a character's combat capabilities: maximum health, offensive
+ * power and defensive resilience. These values are used in damage calculations
+ * via `compute_attack`.
+ *
+ * The three random stats default to 0, which keeps combat fully deterministic.
+ * Resistances default to empty, meaning every element deals neutral damage.
+ */
+export interface Stats {
+    /** Maximum hit points. When current HP reaches 0, the character is defeated. */
+    hp: number;
+    /** Attack power. Added to ability power when calculating damage. */
+    attack: number;
+    /** Damage reduction. Subtracted from incoming damage (minimum 1 damage always applies). */
+    defense: number;
+    /**
+     * Critical hit chance in percent (0-100). Critical hits deal
+     * `CRIT_DAMAGE_PERCENT` of normal damage.
+     */
+    crit_chance?: number;
+    /** Evasion chance in percent (0-100). An evaded attack deals no damage. */
+    evade_chance?: number;
+    /**
+     * Damage variance in percent. Outgoing damage is scaled by a random
+     * factor in `[100 - variance, 100 + variance]` percent.
+     */
+    damage_variance?: number;
+    /**
+     * Incoming damage multipliers per element, in percent. Elements without
+     * an entry take 100% damage.
+     */
+    resistances?: Resistances;
+    /** Initiative. Higher speed acts earlier in an encounter round. */
+    speed?: number;
+}
+
+/**
+ * A game character (player or monster).
+ *
+ * Characters are the core entities in the combat system. Each character has a
+ * name, current health and base statistics. Current HP can differ from max HP
+ * (`stats.hp`) after taking damage; a character is defeated when `hp <= 0`.
+ */
+export interface Character {
+    /** Display name of the character. */
+    name: string;
+    /** Current hit points. When this reaches 0 or below, the character is defeated. */
+    hp: number;
+    /** Base combat statistics (max HP, attack, defense). */
+    stats: Stats;
+}
+
+/**
+ * A real-time duration in whole milliseconds.
+ *
+ * Real-time cooldowns and status effects count down in integer milliseconds
+ * rather than floating-point seconds, so every platform ticks them identically.
+ */
+export type Millis = number;
+
+/** The kind of a status effect. */
+export type StatusKind =
+    | "Poison"
+    | "Stun"
+    | "Shield"
+    | "Regen";
+
+/** How long a status effect lasts. */
+export type EffectDuration =
+    | { Turns: number }
+    | { Seconds: Millis };
+
+/** What happens when an effect is applied to a character that already has it. */
+export type StackingRule =
+    | "Refresh"
+    | { Stack: { max_stacks: number } }
+    | "Ignore";
+
+/** A status effect definition, as carried by an `Ability`. */
+export interface StatusEffect {
+    /** What the effect does. */
+    kind: StatusKind;
+    /** Damage or healing per tick, or shield points. Unused by `Stun`. */
+    magnitude: number;
+    /** How long the effect lasts. */
+    duration: EffectDuration;
+    /** How re-application interacts with an existing effect of the same kind. */
+    stacking?: StackingRule;
+}
+
+/** A status effect currently affecting a character. */
+export interface ActiveEffect {
+    /** The definition this effect was applied from. */
+    effect: StatusEffect;
+    /** Time left before the effect expires. */
+    remaining: EffectDuration;
+    /** Number of stacks (at least 1). */
+    stacks: number;
+    /** Shield points left; only meaningful for `Shield`. */
+    shield_remaining: number;
+    /** Time accumulated toward the next per-second tick. */
+    tick_progress: Millis;
+}
+
+/** Something that happened to a character's status effects. */
+export type StatusEvent =
+    | { Applied: { target: string; kind: StatusKind; stacks: number } }
+    | { Ticked: { target: string; kind: StatusKind; amount: number; hp_after: number } }
+    | { Absorbed: { target: string; amount: number } }
+    | { Expired: { target: string; kind: StatusKind } };
+
+/**
+ * The status effects currently affecting one character.
+ *
+ * This is a companion component: spawn it on the same entity as the
+ * `Character` it belongs to.
+ */
+export interface StatusEffects {
+    /** Active effects, at most one entry per `StatusKind`. */
+    effects: ActiveEffect[];
+}
+
+/** Who an ability can be aimed at. */
+export type Targeting =
+    | "Enemy"
+    | "AllEnemies"
+    | "Caster"
+    | "Ally"
+    | "AllAllies";
+
+/** One thing an ability does to each of its targets. */
+export type AbilityEffect =
+    | { Damage: { power: number } }
+    | { Heal: { amount: number } }
+    | { Shield: { points: number; duration: EffectDuration } }
+    | { ApplyStatus: StatusEffect };
+
+/**
+ * A combat ability or attack.
+ *
+ * Abilities represent actions that characters can perform in combat. Each
+ * ability has a target rule and a list of effects, resolved in order against
+ * every target; `element` is checked against the target's resistances.
+ */
+export interface Ability {
+    /** Display name of the ability. */
+    name: string;
+    /** Damage type, checked against the defender's resistances. */
+    element?: Element;
+    /** Who the ability can be aimed at. */
+    target?: Targeting;
+    /** What the ability does to each target. */
+    effects: AbilityEffect[];
+}
+
+/**
+ * Ability type enumeration for AI decision making.
+ *
+ * Classifies abilities into categories to help the AI choose appropriate actions.
+ */
+export type AbilityType =
+    | "BasicAttack"
+    | "PowerfulAttack"
+    | "Heal";
+
+/** The clock a cooldown counts against. */
+export type CooldownMode =
+    | "Turns"
+    | "Seconds";
+
+/** A cooldown timer in turns or seconds. */
+export type Cooldown =
+    | { Turns: { max: number; remaining: number } }
+    | { Seconds: { max: Millis; remaining: Millis } };
+
+/**
+ * An ability slot with cooldown tracking.
+ *
+ * Pairs an `Ability` with its `AbilityType` classification and a `Cooldown`.
+ * The same slot type is used for real-time and turn-based combat; only the
+ * cooldown's mode differs.
+ */
+export interface AbilitySlot {
+    /** The ability contained in this slot. */
+    ability: Ability;
+    /** Type classification for AI decision making. */
+    ability_type: AbilityType;
+    /** Cooldown in turns or seconds. */
+    cooldown: Cooldown;
+}
+
+/**
+ * A set of abilities for a character.
+ *
+ * Contains multiple ability slots that can be used in combat.
+ */
+export interface AbilitySet {
+    /** List of ability slots. */
+    abilities: AbilitySlot[];
+}
+
+/**
+ * The built-in AI personalities.
+ *
+ * Dispatches to the matching policy, so it can be stored in data files or
+ * attached to a monster entity and used wherever an `AiPolicy` is expected.
+ */
+export type AiPersonality =
+    | "Threshold"
+    | "Aggressive"
+    | "Defensive"
+    | "Balanced"
+    | "Smart"
+    | "Tactician";
+
+/** What one effect of an ability did to its target. */
+export type EffectOutcome =
+    | { Damaged: { amount: number } }
+    | { Healed: { amount: number } }
+    | { StatusApplied: { kind: StatusKind; stacks: number } }
+    | { StatusIgnored: { kind: StatusKind } }
+    | "Evaded";
+
+/** Total XP needed to reach each level. */
+export type LevelCurve =
+    | { Linear: { step: number } }
+    | { Quadratic: { scale: number } }
+    | { Table: { totals: number[] } };
+
+/** Stats gained with every level above 1. */
+export interface StatGrowth {
+    /** Max HP per level. */
+    hp: number;
+    /** Attack per level. */
+    attack: number;
+    /** Defense per level. */
+    defense: number;
+    /** Speed per level. */
+    speed?: number;
+}
+
+/** An ability a class learns on reaching `Level`. */
+export interface AbilityUnlock {
+    level: number;
+    /** Ability catalog name. */
+    ability: string;
+}
+
+/** A character class: level 1 stats, growth, level curve and ability unlocks. */
+export interface CharacterClass {
+    /** Display name. Must be unique within a class catalog. */
+    name: string;
+    /** Stats at level 1. */
+    stats: Stats;
+    /** Stats gained per level. */
+    growth: StatGrowth;
+    /** XP needed for each level. */
+    curve: LevelCurve;
+    /** Abilities learned by level, in the order they are learned. */
+    unlocks?: AbilityUnlock[];
+    /** XP for defeating a level 1 member of the class. Scales with level. */
+    xp_reward: number;
+    /** How members of the class fight when the AI controls them. */
+    personality?: AiPersonality;
+}
+
+/** A character's class, level and experience. */
+export interface Progression {
+    class: CharacterClass;
+    /** Current level, starting at 1. */
+    level: number;
+    /** Total XP earned. */
+    xp: number;
+}
+
+/** XP one character earned from a combat action. */
+export interface XpGrant {
+    /** Name of the character who earned the XP. */
+    recipient: string;
+    /** XP earned. */
+    amount: number;
+    /** Level after the XP was added. */
+    level_after: number;
+    /** Levels gained. */
+    levels_gained?: number;
+    /** Abilities unlocked by the levels gained. */
+    unlocked?: string[];
+}
+
+/**
+ * The result of a combat action.
+ *
+ * A `CombatEvent` records what happened when one character used an ability on
+ * another: the names of both participants, the net damage dealt, the defender's
+ * remaining HP afterwards, which ability was used and what each of its effects
+ * did. Events are immutable records; the defender's HP must be updated from
+ * `defender_hp_after`, which is always clamped between 0 and max HP.
+ *
+ * `Damage = EffectiveAmount + Overkill - Overheal` always holds.
+ */
+export interface CombatEvent {
+    /**
+     * Version of the serialized event format. Logs written before events
+     * were versioned have no version and read as 0.
+     */
+    version?: number;
+    /** Name of the character who performed the attack. */
+    attacker_name: string;
+    /** Name of the character who received the attack. */
+    defender_name: string;
+    /**
+     * Net damage dealt: damage minus healing, before HP is clamped.
+     * Negative damage means healing.
+     */
+    damage: number;
+    /**
+     * Defender's remaining HP after the ability, between 0 and max HP.
+     * If this is 0, the defender is defeated.
+     */
+    defender_hp_after: number;
+    /** HP the defender actually lost; negative for HP actually restored. */
+    effective_amount?: number;
+    /** Damage dealt past 0 HP. */
+    overkill?: number;
+    /** Healing past max HP. */
+    overheal?: number;
+    /** Whether this event took the defender from above 0 HP to 0. */
+    killing_blow?: boolean;
+    /** Name of the ability that was used. */
+    ability_used: string;
+    /** Whether the attack was a critical hit. */
+    critical?: boolean;
+    /** Whether the defender evaded the attack (no damage dealt). */
+    missed?: boolean;
+    /** How the ability's element matched the defender's resistances. */
+    effectiveness?: Effectiveness;
+    /** Status effects applied, absorbed or expired as part of this action. */
+    status_events?: StatusEvent[];
+    /** What each of the ability's effects did, in resolution order. */
+    outcomes?: EffectOutcome[];
+    /** XP earned for the characters defeated this turn. */
+    xp_grants?: XpGrant[];
+}
+
+/** A stat that equipment can modify. */
+export type StatKind =
+    | "MaxHp"
+    | "Attack"
+    | "Defense"
+    | "Speed"
+    | "CritChance"
+    | "EvadeChance";
+
+/** A change an equipped item makes to one of its wearer's stats. */
+export type StatModifier =
+    | { Flat: { stat: StatKind; amount: number } }
+    | { Percent: { stat: StatKind; percent: number } };
+
+/** Where an item is worn. A combatant wears at most one item per slot. */
+export type EquipmentSlot =
+    | "Weapon"
+    | "Armor"
+    | "Accessory";
+
+/** What an item does. */
+export type ItemKind =
+    | { Equipment: { slot: EquipmentSlot; modifiers: StatModifier[] } }
+    | { Consumable: { ability: Ability } };
+
+/** Something a character can carry. */
+export interface Item {
+    /** Display name, unique within an inventory's lookups. */
+    name: string;
+    kind: ItemKind;
+}
+
+/**
+ * A character's items: a bag of carried items and the equipment being worn.
+ *
+ * Equipped items modify the wearer's effective stats; its base `Stats` never change.
+ */
+export interface Inventory {
+    /** Carried items, consumables included, in pickup order. */
+    items?: Item[];
+    /** Worn equipment by slot. */
+    equipped?: Partial<Record<EquipmentSlot, Item>>;
+}
+
+/**
+ * How rare a drop is. Loot entries without a weight use their rarity's default
+ * weight.
+ */
+export type Rarity =
+    | "Common"
+    | "Uncommon"
+    | "Rare"
+    | "Epic"
+    | "Legendary";
+
+/** What a loot entry yields when it is picked. */
+export type LootDrop =
+    | "Nothing"
+    | { Item: { item: Item; min: number; max: number } }
+    | { Table: { table: string } };
+
+/** One possible outcome of a loot table. */
+export interface LootEntry {
+    drop: LootDrop;
+    /**
+     * Relative chance among the table's weighted entries. Defaults to the
+     * weight of `Rarity`.
+     */
+    weight?: number | null;
+    /** Dropped on every roll of the table, on top of its weighted picks. */
+    guaranteed?: boolean;
+    rarity?: Rarity;
+}
+
+/** Weighted loot, e.g. what a monster drops when defeated. */
+export interface LootTable {
+    name: string;
+    /** Number of weighted picks per roll. Picks are made with replacement. */
+    rolls: number;
+    entries: LootEntry[];
+}
+
+/** Items rolled from a loot table. */
+export interface Loot {
+    item: Item;
+    count: number;
+    /** The rarity of the entry that dropped the item. */
+    rarity: Rarity;
+}
+
+/** A kind of monster in the bestiary. */
+export interface MonsterTemplate {
+    /** Template ID, e.g. `"goblin"`. Must be unique within a bestiary. */
+    id: string;
+    /** Display name of spawned monsters. */
+    name: string;
+    stats: Stats;
+    /** Ability catalog names, in slot order. */
+    abilities: string[];
+    personality: AiPersonality;
+    /** Loot table rolled when the monster is defeated. */
+    loot?: string | null;
+}
+
+/** A group of monsters from the bestiary that is fought together. */
+export interface EncounterTemplate {
+    /** Template ID. Must be unique within a bestiary. */
+    id: string;
+    /** Display name of the encounter. */
+    name: string;
+    /** Monster template IDs, one per spawned monster. */
+    monsters: string[];
+}
+
+/** The side a combatant fights for. */
+export type Team =
+    | "Allies"
+    | "Enemies";
+
+/** Identifies a combatant within an `Encounter`. */
+export type CombatantId = number;
+
+/** A character taking part in an encounter, with its abilities and status effects. */
+export interface Combatant {
+    /** Assigned by `Encounter::new`. */
+    id: CombatantId;
+    team: Team;
+    character: Character;
+    abilities: AbilitySet;
+    effects?: StatusEffects;
+    /**
+     * Class and level, for combatants that earn XP. Defeating a combatant
+     * without one is worth its max HP in XP.
+     */
+    progression?: Progression | null;
+    /**
+     * Carried and worn items. Equipment modifies the stats the combatant
+     * fights with.
+     */
+    inventory?: Inventory;
+}
+
+/** How an encounter has ended, from the allies' point of view. */
+export type EncounterOutcome =
+    | "Ongoing"
+    | "Victory"
+    | "Defeat";
+
+/** What the current combatant does with its turn. */
+export type Action =
+    | { UseAbility: { ability: string; target: CombatantId } }
+    | { UseItem: { item: string; target: CombatantId } }
+    | "Pass";
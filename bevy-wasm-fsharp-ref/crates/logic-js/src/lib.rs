@@ -0,0 +1,313 @@
+//! # Logic JS
+//!
+//! JavaScript bindings for the combat rules in `bevy-wasm-fsharp-ref-logic`, so
+//! web tooling such as an ability calculator runs the exact Rust rules the game
+//! uses, without loading Bevy.
+//!
+//! Domain values cross the boundary as plain objects shaped like their JSON:
+//! a `Character` is `{ name, hp, stats: { hp, attack, ... } }` and a unit union
+//! case such as `Element::Fire` is the string `"Fire"`. Their TypeScript
+//! declarations in `domain.d.ts` are generated from `fsharp/Domain.fs` by
+//! `fsharp-codegen`, together with the Rust types, so the two never drift.
+//! Functions take values and return new ones; nothing is mutated in place.
+//!
+//! ## Building
+//!
+//! ```text
+//! just build-logic-js
+//! ```
+//!
+//! writes `logic_js.js`, `logic_js_bg.wasm` and `logic_js.d.ts` to `web/pkg`.
+//!
+//! ## Usage
+//!
+//! ```js
+//! import init, { newPlayer, newMonster, ability, computeAttack } from "./pkg/logic_js.js";
+//!
+//! await init();
+//! const hero = newPlayer("Hero");
+//! const slime = newMonster("Slime");
+//! const event = computeAttack(hero, slime, ability("Fire Bolt"), "PercentArmor", 7);
+//! console.log(`${event.damage} damage, ${event.defender_hp_after} HP left`);
+//! ```
+
+use std::sync::OnceLock;
+
+use bevy_wasm_fsharp_ref_logic as logic;
+use logic::{
+    Ability, AbilityCatalog, AbilitySet, AbilitySlot, Character, ClassCatalog, CombatEvent,
+    CooldownMode, DamageModel, Millis, SeededRng,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// The domain types, generated from `fsharp/Domain.fs`.
+#[wasm_bindgen(typescript_custom_section)]
+const DOMAIN_TYPES: &str = include_str!("domain.d.ts");
+
+/// Types used only by these bindings.
+#[wasm_bindgen(typescript_custom_section)]
+const BINDING_TYPES: &str = "
+/** A class member built by `spawnClass`. */
+export interface Spawned {
+    character: Character;
+    abilities: AbilitySet;
+}
+";
+
+fn ability_catalog() -> &'static AbilityCatalog {
+    static CATALOG: OnceLock<AbilityCatalog> = OnceLock::new();
+    CATALOG.get_or_init(AbilityCatalog::builtin)
+}
+
+fn class_catalog() -> &'static ClassCatalog {
+    static CATALOG: OnceLock<ClassCatalog> = OnceLock::new();
+    CATALOG.get_or_init(ClassCatalog::builtin)
+}
+
+/// Converts a JavaScript value to a domain type, failing on a wrong shape.
+fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T, JsError> {
+    Ok(serde_wasm_bindgen::from_value(value)?)
+}
+
+/// Converts a domain type to a plain JavaScript object shaped like its JSON.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+/// A player character with the default stats.
+#[wasm_bindgen(js_name = newPlayer, unchecked_return_type = "Character")]
+pub fn new_player(name: &str) -> Result<JsValue, JsError> {
+    to_js(&Character::new_player(name))
+}
+
+/// A monster with the default stats.
+#[wasm_bindgen(js_name = newMonster, unchecked_return_type = "Character")]
+pub fn new_monster(name: &str) -> Result<JsValue, JsError> {
+    to_js(&Character::new_monster(name))
+}
+
+/// A member of a built-in class at `level`, with the abilities it has unlocked.
+///
+/// Throws if there is no class with that name.
+#[wasm_bindgen(js_name = spawnClass, unchecked_return_type = "Spawned")]
+pub fn spawn_class(
+    class: &str,
+    name: &str,
+    level: u32,
+    #[wasm_bindgen(unchecked_param_type = "CooldownMode")] mode: JsValue,
+) -> Result<JsValue, JsError> {
+    let mode: CooldownMode = from_js(mode)?;
+    let (character, abilities) =
+        class_catalog().spawn(class, name, level, ability_catalog(), mode)?;
+    to_js(&Spawned {
+        character,
+        abilities,
+    })
+}
+
+#[derive(Serialize)]
+struct Spawned {
+    character: Character,
+    abilities: AbilitySet,
+}
+
+/// The names of the built-in abilities, in catalog order.
+#[wasm_bindgen(js_name = abilityNames)]
+pub fn ability_names() -> Vec<String> {
+    ability_catalog()
+        .abilities()
+        .iter()
+        .map(|def| def.name.clone())
+        .collect()
+}
+
+/// The built-in ability with the given name.
+///
+/// Throws if there is no such ability.
+#[wasm_bindgen(unchecked_return_type = "Ability")]
+pub fn ability(name: &str) -> Result<JsValue, JsError> {
+    to_js(&ability_catalog().ability(name)?)
+}
+
+/// An ability set of built-in abilities, in order, with cooldowns counting in
+/// `mode`.
+///
+/// Throws if one of the names is not in the catalog.
+#[wasm_bindgen(js_name = abilitySet, unchecked_return_type = "AbilitySet")]
+pub fn ability_set(
+    names: Vec<String>,
+    #[wasm_bindgen(unchecked_param_type = "CooldownMode")] mode: JsValue,
+) -> Result<JsValue, JsError> {
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    to_js(&ability_catalog().ability_set(&names, from_js(mode)?)?)
+}
+
+/// Resolves `ability` used by `attacker` on `defender`, as the game does.
+///
+/// `model` picks the damage formula (`"FlatDefense"` when left out). With a
+/// `seed`, critical hits, evasion and damage variance are rolled; without one
+/// the attack has no randomness.
+#[wasm_bindgen(js_name = computeAttack, unchecked_return_type = "CombatEvent")]
+pub fn compute_attack(
+    #[wasm_bindgen(unchecked_param_type = "Character")] attacker: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Character")] defender: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Ability")] ability: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "DamageModel | undefined")] model: JsValue,
+    seed: Option<u32>,
+) -> Result<JsValue, JsError> {
+    let attacker: Character = from_js(attacker)?;
+    let defender: Character = from_js(defender)?;
+    let ability: Ability = from_js(ability)?;
+    let model: Option<DamageModel> = from_js(model)?;
+    to_js(&attack(&attacker, &defender, &ability, model, seed))
+}
+
+fn attack(
+    attacker: &Character,
+    defender: &Character,
+    ability: &Ability,
+    model: Option<DamageModel>,
+    seed: Option<u32>,
+) -> CombatEvent {
+    let formula = model.unwrap_or_default().formula();
+    match seed {
+        Some(seed) => logic::compute_attack_with_formula_and_rng(
+            attacker,
+            defender,
+            ability,
+            formula,
+            &mut SeededRng::new(seed.into()),
+        ),
+        None => logic::compute_attack_with_formula(attacker, defender, ability, formula),
+    }
+}
+
+/// The ability the monster AI picks from its ready `abilities` this turn.
+#[wasm_bindgen(js_name = chooseMonsterAction, unchecked_return_type = "Ability")]
+pub fn choose_monster_action(
+    #[wasm_bindgen(unchecked_param_type = "Character")] monster: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Character")] player: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "AbilitySlot[]")] abilities: JsValue,
+) -> Result<JsValue, JsError> {
+    let monster: Character = from_js(monster)?;
+    let player: Character = from_js(player)?;
+    let abilities: Vec<AbilitySlot> = from_js(abilities)?;
+    to_js(&logic::choose_monster_action(&monster, &player, &abilities))
+}
+
+/// `abilities` after `millis` milliseconds of real-time cooldowns.
+#[wasm_bindgen(js_name = tickCooldowns, unchecked_return_type = "AbilitySet")]
+pub fn tick_cooldowns(
+    #[wasm_bindgen(unchecked_param_type = "AbilitySet")] abilities: JsValue,
+    millis: u32,
+) -> Result<JsValue, JsError> {
+    let mut abilities: AbilitySet = from_js(abilities)?;
+    abilities.tick_all(Millis(millis));
+    to_js(&abilities)
+}
+
+/// `abilities` after one turn of turn-based cooldowns.
+#[wasm_bindgen(js_name = tickCooldownTurns, unchecked_return_type = "AbilitySet")]
+pub fn tick_cooldown_turns(
+    #[wasm_bindgen(unchecked_param_type = "AbilitySet")] abilities: JsValue,
+) -> Result<JsValue, JsError> {
+    let mut abilities: AbilitySet = from_js(abilities)?;
+    abilities.tick_turn_all();
+    to_js(&abilities)
+}
+
+/// `abilities` with the cooldown of the named ability started.
+///
+/// Throws if the set has no ability with that name.
+#[wasm_bindgen(js_name = useAbility, unchecked_return_type = "AbilitySet")]
+pub fn use_ability(
+    #[wasm_bindgen(unchecked_param_type = "AbilitySet")] abilities: JsValue,
+    name: &str,
+) -> Result<JsValue, JsError> {
+    let mut abilities: AbilitySet = from_js(abilities)?;
+    if !abilities.use_ability(name) {
+        return Err(JsError::new(&format!("no ability named `{}`", name)));
+    }
+    to_js(&abilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// This file, whose `BINDING_TYPES` declare the types not in `domain.d.ts`.
+    const SOURCE: &str = include_str!("lib.rs");
+
+    /// Type names in the `unchecked_*_type` annotations of this file.
+    fn annotated_types() -> Vec<&'static str> {
+        let mut types = Vec::new();
+        for marker in ["unchecked_param_type = \"", "unchecked_return_type = \""] {
+            for (start, _) in SOURCE.match_indices(marker) {
+                let rest = &SOURCE[start + marker.len()..];
+                let annotation = &rest[..rest.find('"').unwrap()];
+                types.extend(
+                    annotation
+                        .split(|c: char| !c.is_alphanumeric())
+                        .filter(|name| !name.is_empty() && *name != "undefined"),
+                );
+            }
+        }
+        types
+    }
+
+    #[test]
+    fn test_annotated_types_are_declared() {
+        let types = annotated_types();
+        assert!(types.contains(&"CombatEvent"));
+
+        for name in types {
+            let declared = [include_str!("domain.d.ts"), SOURCE].iter().any(|ts| {
+                ts.contains(&format!("export interface {} ", name))
+                    || ts.contains(&format!("export type {} ", name))
+            });
+            assert!(declared, "`{}` has no TypeScript declaration", name);
+        }
+    }
+
+    #[test]
+    fn test_attack_without_seed_matches_compute_attack() {
+        let hero = Character::new_player("Hero");
+        let slime = Character::new_monster("Slime");
+        let ability = Ability::basic_attack();
+
+        assert_eq!(
+            attack(&hero, &slime, &ability, None, None),
+            logic::compute_attack(&hero, &slime, &ability)
+        );
+    }
+
+    #[test]
+    fn test_attack_uses_model_and_seed() {
+        let mut hero = Character::new_player("Hero");
+        hero.stats.damage_variance = 50;
+        let slime = Character::new_monster("Slime");
+        let ability = Ability::basic_attack();
+        let model = Some(DamageModel::PercentArmor);
+
+        let event = attack(&hero, &slime, &ability, model, Some(7));
+        assert_eq!(event, attack(&hero, &slime, &ability, model, Some(7)));
+        assert_eq!(
+            event,
+            logic::compute_attack_with_formula_and_rng(
+                &hero,
+                &slime,
+                &ability,
+                DamageModel::PercentArmor.formula(),
+                &mut SeededRng::new(7),
+            )
+        );
+    }
+
+    #[test]
+    fn test_builtin_catalogs_load() {
+        assert!(ability_names().iter().any(|name| name == "Basic Attack"));
+        assert!(class_catalog().get("Warrior").is_some());
+    }
+}
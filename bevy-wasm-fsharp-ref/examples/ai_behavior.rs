@@ -5,9 +5,10 @@
 //!
 //! ## What You'll Learn
 //!
-//! - How to implement AI decision trees
-//! - Different AI personalities (aggressive, defensive, balanced)
-//! - State-based ability selection
+//! - How to use the `AiPolicy` trait from the logic crate
+//! - Different AI personalities (aggressive, defensive, balanced, smart)
+//! - State-based ability selection that respects cooldowns
+//! - Lookahead search with the `Planner`
 //! - How to test AI strategies against each other
 //!
 //! ## Run This Example
@@ -19,187 +20,137 @@
 //! ## Key Concepts
 //!
 //! AI behavior is implemented as **pure functions** that:
-//! - Take game state as input (character HP, stats, etc.)
+//! - Take game state as input (both characters and the available abilities)
 //! - Return an ability choice as output
 //! - Have no side effects (can be tested easily)
 //!
 //! This functional approach aligns perfectly with F# → Rust transpilation.
 
-use bevy_wasm_fsharp_ref_logic::{Ability, Character, compute_attack};
+use bevy_wasm_fsharp_ref_logic::{
+    compute_attack, AbilityCatalog, AbilitySet, AbilityType, AiPersonality, AiPolicy, Character,
+    CooldownMode, DuelState, OpponentModel, Planner, Stats,
+};
 
-/// AI Strategy trait - defines how an AI makes decisions
-trait AiStrategy {
-    fn choose_ability(&self, ai_character: &Character, opponent: &Character) -> Ability;
-    fn name(&self) -> &str;
-}
-
-/// Aggressive AI - always uses the strongest attack
-struct AggressiveAi;
-
-impl AiStrategy for AggressiveAi {
-    fn choose_ability(&self, _ai_character: &Character, _opponent: &Character) -> Ability {
-        // Always use the most powerful attack
-        Ability {
-            name: "Aggressive Strike".to_string(),
-            power: 15,
-        }
-    }
+/// Abilities every fighter brings, by catalog name
+const LOADOUT: [&str; 3] = ["Basic Attack", "Powerful Attack", "Heal"];
 
-    fn name(&self) -> &str {
-        "Aggressive"
-    }
+/// A character with its abilities and the policy that picks between them
+struct Fighter<'a> {
+    character: Character,
+    abilities: AbilitySet,
+    policy: &'a dyn AiPolicy,
 }
 
-/// Defensive AI - uses weaker attacks but focuses on survival
-struct DefensiveAi;
-
-impl AiStrategy for DefensiveAi {
-    fn choose_ability(&self, ai_character: &Character, _opponent: &Character) -> Ability {
-        // Use healing when HP is low (conceptual - would restore HP in real implementation)
-        if ai_character.hp < ai_character.stats.hp / 2 {
-            Ability {
-                name: "Heal (Conceptual)".to_string(),
-                power: 10, // In a real implementation, this would restore HP
-            }
-        } else {
-            // Use basic attack when HP is healthy
-            Ability::basic_attack()
+impl<'a> Fighter<'a> {
+    fn new(catalog: &AbilityCatalog, character: Character, policy: &'a dyn AiPolicy) -> Self {
+        let abilities = catalog
+            .ability_set(&LOADOUT, CooldownMode::Turns)
+            .expect("loadout is in the builtin catalog");
+        Self {
+            character,
+            abilities,
+            policy,
         }
     }
-
-    fn name(&self) -> &str {
-        "Defensive"
-    }
 }
 
-/// Balanced AI - adapts strategy based on opponent's HP
-struct BalancedAi;
-
-impl AiStrategy for BalancedAi {
-    fn choose_ability(&self, _ai_character: &Character, opponent: &Character) -> Ability {
-        let hp_percentage = (opponent.hp * 100) / opponent.stats.hp;
-
-        if hp_percentage > 70 {
-            // Opponent is healthy - use strong attack to pressure
-            Ability {
-                name: "Power Attack".to_string(),
-                power: 12,
-            }
-        } else if hp_percentage > 30 {
-            // Opponent is wounded - use balanced attack
-            Ability::basic_attack()
-        } else {
-            // Opponent is critical - finish with ultimate
-            Ability {
-                name: "Finishing Blow".to_string(),
-                power: 20,
-            }
-        }
-    }
-
-    fn name(&self) -> &str {
-        "Balanced"
+/// A sturdier monster than the default goblin
+fn ogre() -> Character {
+    Character {
+        name: "Ogre".to_string(),
+        hp: 45,
+        stats: Stats {
+            hp: 45,
+            attack: 8,
+            defense: 3,
+            ..Default::default()
+        },
     }
 }
 
-/// Smart AI - considers both HP values and defense
-struct SmartAi;
-
-impl AiStrategy for SmartAi {
-    fn choose_ability(&self, ai_character: &Character, opponent: &Character) -> Ability {
-        let ai_hp_percent = (ai_character.hp * 100) / ai_character.stats.hp;
-        let opponent_hp_percent = (opponent.hp * 100) / opponent.stats.hp;
-
-        // If we're low HP and opponent is low HP, go aggressive
-        if ai_hp_percent < 30 && opponent_hp_percent < 40 {
-            return Ability {
-                name: "Desperate Strike".to_string(),
-                power: 25,
-            };
-        }
-
-        // If opponent has high defense, use armor-breaking attack
-        if opponent.stats.defense > 5 {
-            return Ability {
-                name: "Armor Break".to_string(),
-                power: 18,
-            };
-        }
-
-        // If we're healthy and opponent is healthy, build up
-        if ai_hp_percent > 70 && opponent_hp_percent > 70 {
-            return Ability {
-                name: "Charge Attack".to_string(),
-                power: 8,
-            };
+/// Runs one fighter's turn. Returns true if the opponent was defeated.
+fn take_turn(actor: &mut Fighter, opponent: &mut Fighter, verbose: bool) -> bool {
+    actor.abilities.tick_turn_all();
+
+    let ability = actor.policy.choose(
+        &actor.character,
+        &opponent.character,
+        &actor.abilities.abilities,
+    );
+    let is_heal = actor
+        .abilities
+        .abilities
+        .iter()
+        .any(|slot| slot.ability.name == ability.name && slot.ability_type == AbilityType::Heal);
+    actor.abilities.use_ability(&ability.name);
+
+    if is_heal {
+        // Heals target the caster; compute_attack caps them at max HP
+        let event = compute_attack(&actor.character, &actor.character, &ability);
+        actor.character.hp = event.defender_hp_after;
+        if verbose {
+            eprintln!(
+                "  {} uses {} → heals to {} HP",
+                actor.character.name, ability.name, actor.character.hp
+            );
         }
-
-        // Default: basic attack
-        Ability::basic_attack()
+        return false;
     }
 
-    fn name(&self) -> &str {
-        "Smart"
+    let event = compute_attack(&actor.character, &opponent.character, &ability);
+    opponent.character.hp = event.defender_hp_after;
+    if verbose {
+        eprintln!(
+            "  {} uses {} → {} damage (Opponent HP: {})",
+            actor.character.name, ability.name, event.damage, opponent.character.hp
+        );
     }
+
+    opponent.character.hp <= 0
 }
 
 /// Run a combat simulation between two AI-controlled characters
-fn simulate_combat(
-    mut ai1_char: Character,
-    ai1_strategy: &dyn AiStrategy,
-    mut ai2_char: Character,
-    ai2_strategy: &dyn AiStrategy,
-    verbose: bool,
-) -> String {
+fn simulate_combat(mut first: Fighter, mut second: Fighter, verbose: bool) -> String {
     let mut turn = 0;
     const MAX_TURNS: i32 = 20; // Prevent infinite loops
 
     if verbose {
-        eprintln!("\n=== Combat: {} ({}) vs {} ({}) ===",
-            ai1_char.name, ai1_strategy.name(),
-            ai2_char.name, ai2_strategy.name());
-        eprintln!("Starting HP: {} vs {}\n", ai1_char.hp, ai2_char.hp);
+        eprintln!(
+            "\n=== Combat: {} ({}) vs {} ({}) ===",
+            first.character.name,
+            first.policy.name(),
+            second.character.name,
+            second.policy.name()
+        );
+        eprintln!(
+            "Starting HP: {} vs {}\n",
+            first.character.hp, second.character.hp
+        );
     }
 
-    while ai1_char.hp > 0 && ai2_char.hp > 0 && turn < MAX_TURNS {
+    while turn < MAX_TURNS {
         turn += 1;
 
         if verbose {
             eprintln!("Turn {}:", turn);
         }
 
-        // AI 1's turn
-        let ability1 = ai1_strategy.choose_ability(&ai1_char, &ai2_char);
-        let event1 = compute_attack(&ai1_char, &ai2_char, &ability1);
-        ai2_char.hp = event1.defender_hp_after;
-
-        if verbose {
-            eprintln!("  {} uses {} → {} damage (Opponent HP: {})",
-                ai1_char.name, ability1.name, event1.damage, ai2_char.hp);
+        if take_turn(&mut first, &mut second, verbose) {
+            if verbose {
+                eprintln!("\n✓ {} wins in {} turns!\n", first.character.name, turn);
+            }
+            return first.character.name;
         }
 
-        if ai2_char.hp <= 0 {
+        if take_turn(&mut second, &mut first, verbose) {
             if verbose {
-                eprintln!("\n✓ {} wins in {} turns!\n", ai1_char.name, turn);
+                eprintln!("\n✓ {} wins in {} turns!\n", second.character.name, turn);
             }
-            return ai1_char.name.clone();
+            return second.character.name;
         }
 
-        // AI 2's turn
-        let ability2 = ai2_strategy.choose_ability(&ai2_char, &ai1_char);
-        let event2 = compute_attack(&ai2_char, &ai1_char, &ability2);
-        ai1_char.hp = event2.defender_hp_after;
-
         if verbose {
-            eprintln!("  {} uses {} → {} damage (Opponent HP: {})\n",
-                ai2_char.name, ability2.name, event2.damage, ai1_char.hp);
-        }
-
-        if ai1_char.hp <= 0 {
-            if verbose {
-                eprintln!("✓ {} wins in {} turns!\n", ai2_char.name, turn);
-            }
-            return ai2_char.name.clone();
+            eprintln!();
         }
     }
 
@@ -208,10 +159,10 @@ fn simulate_combat(
     }
 
     // If we hit max turns, winner is whoever has more HP
-    if ai1_char.hp > ai2_char.hp {
-        ai1_char.name.clone()
+    if first.character.hp > second.character.hp {
+        first.character.name
     } else {
-        ai2_char.name.clone()
+        second.character.name
     }
 }
 
@@ -226,11 +177,13 @@ fn main() {
     // ====================
 
     eprintln!("=== 1. AI Strategies ===\n");
-    eprintln!("Available AI strategies:");
-    eprintln!("  • Aggressive: Always uses strongest attacks");
-    eprintln!("  • Defensive: Focuses on survival, heals when low HP");
+    eprintln!("Built-in AI personalities (bevy_wasm_fsharp_ref_logic::AiPersonality):");
+    eprintln!("  • Threshold: Heals below 30% HP, powerful attack below 70%");
+    eprintln!("  • Aggressive: Always uses the strongest ready attack");
+    eprintln!("  • Defensive: Focuses on survival, heals below half HP");
     eprintln!("  • Balanced: Adapts based on opponent's HP percentage");
-    eprintln!("  • Smart: Considers multiple factors (HP, defense, state)");
+    eprintln!("  • Smart: Takes kills, heals when the next hit could be fatal");
+    eprintln!("\nEvery policy only picks abilities whose cooldowns are ready.");
 
     // ====================
     // Section 2: Single Combat Demo
@@ -238,84 +191,107 @@ fn main() {
 
     eprintln!("\n=== 2. Detailed Combat Demo ===");
 
-    let fighter1 = Character::new_player("Warrior");
-    let fighter2 = Character::new_monster("Goblin");
-
-    let aggressive = AggressiveAi;
-    let balanced = BalancedAi;
+    let catalog = AbilityCatalog::builtin();
+    let warrior = Fighter::new(
+        &catalog,
+        Character::new_player("Warrior"),
+        &AiPersonality::Aggressive,
+    );
+    let brute = Fighter::new(&catalog, ogre(), &AiPersonality::Smart);
 
-    simulate_combat(fighter1, &aggressive, fighter2, &balanced, true);
+    simulate_combat(warrior, brute, true);
 
     // ====================
     // Section 3: AI Tournament
     // ====================
 
     eprintln!("\n=== 3. AI Strategy Tournament ===\n");
-    eprintln!("Testing each AI strategy against all others (best of 5):\n");
+    eprintln!("Testing each AI personality against all others (each side goes first once):\n");
 
-    let strategies: Vec<(&str, Box<dyn AiStrategy>)> = vec![
-        ("Aggressive", Box::new(AggressiveAi)),
-        ("Defensive", Box::new(DefensiveAi)),
-        ("Balanced", Box::new(BalancedAi)),
-        ("Smart", Box::new(SmartAi)),
-    ];
+    let personalities = AiPersonality::ALL;
 
-    // Track wins for each strategy
-    let mut wins = vec![0; strategies.len()];
+    // Track wins for each personality
+    let mut wins = vec![0; personalities.len()];
 
-    for i in 0..strategies.len() {
-        for j in (i + 1)..strategies.len() {
-            let strategy1 = &strategies[i];
-            let strategy2 = &strategies[j];
+    for i in 0..personalities.len() {
+        for j in (i + 1)..personalities.len() {
+            let (p1, p2) = (&personalities[i], &personalities[j]);
 
-            eprint!("{} vs {}: ", strategy1.0, strategy2.0);
+            eprint!("{} vs {}: ", p1.name(), p2.name());
 
-            let mut strategy1_wins = 0;
-            let mut strategy2_wins = 0;
+            let mut p1_wins = 0;
+            let mut p2_wins = 0;
 
-            // Best of 5
-            for _ in 0..5 {
-                let char1 = Character::new_player("Fighter1");
-                let char2 = Character::new_player("Fighter2");
+            for p1_first in [true, false] {
+                let f1 = Fighter::new(&catalog, Character::new_player("Fighter1"), p1);
+                let f2 = Fighter::new(&catalog, Character::new_player("Fighter2"), p2);
 
-                let winner = simulate_combat(
-                    char1.clone(),
-                    strategy1.1.as_ref(),
-                    char2.clone(),
-                    strategy2.1.as_ref(),
-                    false,
-                );
+                let winner = if p1_first {
+                    simulate_combat(f1, f2, false)
+                } else {
+                    simulate_combat(f2, f1, false)
+                };
 
                 if winner == "Fighter1" {
-                    strategy1_wins += 1;
+                    p1_wins += 1;
                 } else {
-                    strategy2_wins += 1;
+                    p2_wins += 1;
                 }
             }
 
-            if strategy1_wins > strategy2_wins {
-                eprintln!("✓ {} wins {}-{}", strategy1.0, strategy1_wins, strategy2_wins);
+            if p1_wins > p2_wins {
+                eprintln!("✓ {} wins {}-{}", p1.name(), p1_wins, p2_wins);
                 wins[i] += 1;
-            } else {
-                eprintln!("✓ {} wins {}-{}", strategy2.0, strategy2_wins, strategy1_wins);
+            } else if p2_wins > p1_wins {
+                eprintln!("✓ {} wins {}-{}", p2.name(), p2_wins, p1_wins);
                 wins[j] += 1;
+            } else {
+                eprintln!("= Draw {}-{}", p1_wins, p2_wins);
             }
         }
     }
 
     eprintln!("\n=== Tournament Results ===\n");
-    let mut results: Vec<_> = strategies.iter().zip(wins.iter()).collect();
+    let mut results: Vec<_> = personalities.iter().zip(wins.iter()).collect();
     results.sort_by(|a, b| b.1.cmp(a.1));
 
-    for (rank, (strategy, win_count)) in results.iter().enumerate() {
-        eprintln!("{}. {} - {} wins", rank + 1, strategy.0, win_count);
+    for (rank, (personality, win_count)) in results.iter().enumerate() {
+        eprintln!("{}. {} - {} wins", rank + 1, personality.name(), win_count);
+    }
+
+    // ====================
+    // Section 4: Lookahead Planner
+    // ====================
+
+    eprintln!("\n=== 4. Lookahead Planner ===\n");
+    eprintln!("The Tactician personality searches future turns instead of using thresholds.");
+    eprintln!("Scoring every opening move against a worst-case opponent shows which");
+    eprintln!("abilities dominate:\n");
+
+    let loadout = |character| Fighter::new(&catalog, character, &AiPersonality::Tactician);
+    let (warrior, brute) = (loadout(Character::new_player("Warrior")), loadout(ogre()));
+    let plan = Planner::new(8)
+        .with_opponent_model(OpponentModel::Adversarial)
+        .plan(&DuelState {
+            actor: brute.character,
+            actor_abilities: brute.abilities.abilities,
+            opponent: warrior.character,
+            opponent_abilities: warrior.abilities.abilities,
+        });
+
+    for candidate in &plan.candidates {
+        eprintln!("  {:<16} {:>8}", candidate.name, candidate.value);
     }
+    eprintln!(
+        "\nBest: {} (depth {}, {} positions searched)",
+        plan.ability.name, plan.depth, plan.nodes
+    );
 
     // ====================
-    // Section 4: Implementing AI in F#
+    // Section 5: Implementing AI in F#
     // ====================
 
-    eprintln!("\n=== 4. F# Implementation Pattern ===\n");
+    eprintln!("\n=== 5. F# Implementation Pattern ===\n");
     eprintln!("To implement AI in F# (then transpile to Rust):\n");
     eprintln!("```fsharp");
     eprintln!("type AiStrategy = Character -> Character -> Ability");
@@ -334,6 +310,7 @@ fn main() {
     eprintln!("This F# code transpiles to Rust functions that can be called from Bevy systems!\n");
 
     eprintln!("📚 Next Steps:");
+    eprintln!("  - Implement your own AiPolicy and pass it to simulate_combat");
     eprintln!("  - Implement AI functions in fsharp/GameLogic.fs");
     eprintln!("  - Add status effects and buffs");
     eprintln!("  - Create more complex decision trees");
@@ -7,8 +7,9 @@
 //!
 //! - How to create custom abilities with different power levels
 //! - How abilities affect damage calculations
-//! - Multiple ability types (damage, heal, buff concepts)
+//! - Multiple ability effects (damage, heal, shield, status)
 //! - How to extend the type system for game-specific abilities
+//! - How to load abilities from a data-driven catalog
 //!
 //! ## Run This Example
 //!
@@ -20,14 +21,21 @@
 //!
 //! The Ability struct is simple but powerful:
 //! - `name`: Display name for UI/logging
-//! - `power`: Added to attacker's base attack stat
+//! - `target`: Who the ability can be aimed at (enemy, ally, caster, whole teams)
+//! - `effects`: What it does, resolved in order; a `Damage` effect's power is
+//!   added to the attacker's base attack stat
+//!
+//! Abilities can also be defined as data and loaded through an `AbilityCatalog`,
+//! which adds cooldowns, targeting and status effects and validates every entry.
 //!
 //! Future enhancements could add:
-//! - MP cost, cooldowns, status effects
-//! - Multi-target abilities
+//! - MP cost
 //! - Conditional effects based on HP/state
 
-use bevy_wasm_fsharp_ref_logic::{Ability, Character, Stats, compute_attack};
+use bevy_wasm_fsharp_ref_logic::{
+    Ability, AbilityCatalog, AbilityEffect, Character, CooldownMode, EffectOutcome, Stats,
+    Targeting, compute_attack,
+};
 
 fn main() {
     println!("╔════════════════════════════════════════╗");
@@ -42,29 +50,20 @@ fn main() {
     println!("=== 1. Defining Custom Abilities ===\n");
 
     // Weak attack - low power
-    let weak_strike = Ability {
-        name: "Weak Strike".to_string(),
-        power: 2,
-    };
-    println!("✓ Created: {} (Power: {})", weak_strike.name, weak_strike.power);
+    let weak_strike = Ability::damage("Weak Strike", 2);
+    println!("✓ Created: {} (Power: {})", weak_strike.name, weak_strike.power());
 
     // Standard attack (use the built-in helper)
     let basic_attack = Ability::basic_attack();
-    println!("✓ Created: {} (Power: {})", basic_attack.name, basic_attack.power);
+    println!("✓ Created: {} (Power: {})", basic_attack.name, basic_attack.power());
 
     // Powerful attack - high power, could have MP cost in the future
-    let heavy_slash = Ability {
-        name: "Heavy Slash".to_string(),
-        power: 12,
-    };
-    println!("✓ Created: {} (Power: {})", heavy_slash.name, heavy_slash.power);
+    let heavy_slash = Ability::damage("Heavy Slash", 12);
+    println!("✓ Created: {} (Power: {})", heavy_slash.name, heavy_slash.power());
 
     // Ultimate attack - very high power
-    let ultimate_attack = Ability {
-        name: "Ultimate Strike".to_string(),
-        power: 25,
-    };
-    println!("✓ Created: {} (Power: {})\n", ultimate_attack.name, ultimate_attack.power);
+    let ultimate_attack = Ability::damage("Ultimate Strike", 25);
+    println!("✓ Created: {} (Power: {})\n", ultimate_attack.name, ultimate_attack.power());
 
     // ====================
     // Section 2: Demonstrate Damage Scaling
@@ -94,35 +93,61 @@ fn main() {
             ability.name,
             event.damage,
             player.stats.attack,
-            ability.power,
+            ability.power(),
             monster.stats.defense,
             event.damage
         );
     }
 
     // ====================
-    // Section 3: Healing Ability Concept
+    // Section 3: Healing and Mixed Effects
     // ====================
 
-    println!("\n=== 3. Healing Ability Concept ===\n");
-    println!("Note: The current logic crate doesn't have healing functions,");
-    println!("but we can demonstrate how it could work in F# → Rust.\n");
+    println!("\n=== 3. Healing and Mixed Effects ===\n");
 
-    // This is what a healing ability might look like
-    let heal_spell = Ability {
-        name: "Heal".to_string(),
-        power: 15, // Would restore 15 HP instead of dealing damage
+    // A heal is its own effect, aimed at an ally rather than an enemy
+    let heal_spell = Ability::healing("Greater Heal", 15);
+    let mut wounded = Character::new_player("Wounded Hero");
+    wounded.hp = 20;
+
+    // Healing bypasses defense and is capped at max HP by the logic crate
+    let event = compute_attack(&wounded, &wounded, &heal_spell);
+    println!(
+        "▶ {} on {}: HP {} → {} (max {})",
+        heal_spell.name, wounded.name, wounded.hp, event.defender_hp_after, wounded.stats.hp
+    );
+    for outcome in &event.outcomes {
+        println!("    {:?}", outcome);
+    }
+
+    // Abilities can combine effects; they are resolved in order
+    let life_tap = Ability {
+        name: "Life Tap".to_string(),
+        target: Targeting::Caster,
+        effects: vec![
+            AbilityEffect::Damage { power: 0 },
+            AbilityEffect::Heal { amount: 12 },
+        ],
+        ..Default::default()
     };
+    let event = compute_attack(&wounded, &wounded, &life_tap);
+    let healed: i32 = event
+        .outcomes
+        .iter()
+        .map(|outcome| match outcome {
+            EffectOutcome::Healed { amount } => *amount,
+            _ => 0,
+        })
+        .sum();
+    println!(
+        "▶ {} hurts, then heals {} HP: {} → {}",
+        life_tap.name, healed, wounded.hp, event.defender_hp_after
+    );
 
-    println!("Conceptual Healing Ability:");
-    println!("  Name: {}", heal_spell.name);
-    println!("  Restore: {} HP", heal_spell.power);
-    println!("\nIn F#, you would define:");
-    println!("  type AbilityType = Damage | Heal | Buff");
-    println!("  type Ability = {{ Name: string; Power: int; Type: AbilityType }}");
-    println!("\nThen in Rust (via fsrs):");
-    println!("  enum AbilityType {{ Damage, Heal, Buff }}");
-    println!("  struct Ability {{ name: String, power: i32, ability_type: AbilityType }}");
+    println!("\nIn F#, the same effects are a discriminated union:");
+    println!("  type AbilityEffect = Damage of power: int | Heal of amount: int | ...");
+    println!("\nAnd in Rust:");
+    println!("  enum AbilityEffect {{ Damage {{ power: i32 }}, Heal {{ amount: i32 }}, ... }}");
 
     // ====================
     // Section 4: Advanced Ability Concepts
@@ -138,6 +163,7 @@ fn main() {
             hp: 100,
             attack: 15,
             defense: 10, // High defense!
+            ..Default::default()
         },
     };
 
@@ -149,15 +175,9 @@ fn main() {
 
     // Show how different abilities perform against high defense
     let test_abilities = vec![
-        Ability {
-            name: "Weak Poke".to_string(),
-            power: 1,
-        },
+        Ability::damage("Weak Poke", 1),
         Ability::basic_attack(),
-        Ability {
-            name: "Armor Break".to_string(),
-            power: 20,
-        },
+        Ability::damage("Armor Break", 20),
     ];
 
     for ability in &test_abilities {
@@ -167,7 +187,7 @@ fn main() {
             ability.name,
             event.damage,
             player.stats.attack,
-            ability.power,
+            ability.power(),
             boss.stats.defense,
             event.damage
         );
@@ -184,17 +204,19 @@ fn main() {
     let mut player = Character::new_player("Warrior");
     let mut enemy = Character::new_monster("Goblin");
 
-    let player_abilities = vec![
-        Ability::basic_attack(),
-        Ability {
-            name: "Power Strike".to_string(),
-            power: 10,
-        },
-    ];
+    // Build the player's loadout from the built-in catalog
+    let catalog = AbilityCatalog::builtin();
+    let player_abilities: Vec<Ability> = catalog
+        .ability_set(&["Basic Attack", "Powerful Attack"], CooldownMode::Turns)
+        .expect("Both abilities are in the built-in catalog")
+        .abilities
+        .into_iter()
+        .map(|slot| slot.ability)
+        .collect();
 
     println!("Player has access to multiple abilities:");
     for (i, ability) in player_abilities.iter().enumerate() {
-        println!("  {}. {} (Power: {})", i + 1, ability.name, ability.power);
+        println!("  {}. {} (Power: {})", i + 1, ability.name, ability.power());
     }
 
     println!("\nSimulating strategic ability usage:\n");
@@ -204,9 +226,9 @@ fn main() {
         turn += 1;
         println!("Turn {}:", turn);
 
-        // Simple AI: Use power strike if enemy HP > 10, otherwise basic attack
+        // Simple AI: Use powerful attack if enemy HP > 10, otherwise basic attack
         let chosen_ability = if enemy.hp > 10 {
-            &player_abilities[1] // Power Strike
+            &player_abilities[1] // Powerful Attack
         } else {
             &player_abilities[0] // Basic Attack
         };
@@ -234,6 +256,50 @@ fn main() {
         );
     }
 
+    // ====================
+    // Section 6: Data-Driven Abilities
+    // ====================
+
+    println!("\n=== 6. Loading Abilities from a Catalog ===\n");
+
+    println!("Built-in catalog (data/abilities.ron):");
+    for def in catalog.abilities() {
+        println!(
+            "  - {} ({:?}, {:?}, {:?}, cooldown {} turns / {}s, {} effect(s))",
+            def.name,
+            def.targeting,
+            def.ability_type,
+            def.element,
+            def.cooldown.turns,
+            def.cooldown.seconds,
+            def.effects.len()
+        );
+    }
+
+    // Designers can ship their own catalog as JSON or RON
+    let custom = AbilityCatalog::from_json(
+        r#"{"abilities": [
+            {"name": "Meteor", "type": "PowerfulAttack", "element": "Fire",
+             "cooldown": {"turns": 5, "seconds": 8.0}, "targeting": "AllEnemies",
+             "effects": [{"Damage": {"power": 20}}]}
+        ]}"#,
+    )
+    .expect("Meteor is a valid ability");
+    let meteor = custom.ability("Meteor").expect("Meteor was just defined");
+    let event = compute_attack(&player, &boss, &meteor);
+    println!(
+        "\n▶ Custom {} vs {}: {} damage",
+        meteor.name, boss.name, event.damage
+    );
+
+    // Invalid definitions are rejected with a descriptive error
+    let invalid = AbilityCatalog::from_json(
+        r#"{"abilities": [{"name": "Bad Heal", "type": "Heal", "effects": [{"Damage": {"power": -5}}]}]}"#,
+    );
+    if let Err(err) = invalid {
+        println!("▶ Rejected: {}", err);
+    }
+
     println!("\n📚 Next Steps:");
     println!("  - Implement these ability types in F# (fsharp/GameLogic.fs)");
     println!("  - Transpile to Rust via fsrs");
@@ -67,6 +67,7 @@ fn main() {
         hp: 30,
         attack: 10,
         defense: 2,
+        ..Default::default()
     };
 
     println!("Created Stats instance:");
@@ -83,7 +84,7 @@ fn main() {
     println!("F# Function:");
     println!("```fsharp");
     println!("let computeAttack (attacker: Character) (defender: Character) (ability: Ability) : CombatEvent =");
-    println!("    let raw = attacker.Stats.Attack + ability.Power");
+    println!("    let raw = attacker.Stats.Attack + abilityPower ability");
     println!("    let dmg = max 1 (raw - defender.Stats.Defense)");
     println!("    let hpAfter = defender.Hp - dmg");
     println!("    {{ AttackerName = attacker.Name");
@@ -99,7 +100,7 @@ fn main() {
     println!("    defender: &Character,");
     println!("    ability: &Ability,");
     println!(") -> CombatEvent {{");
-    println!("    let raw = attacker.stats.attack + ability.power;");
+    println!("    let raw = attacker.stats.attack + ability.power();");
     println!("    let dmg = (raw - defender.stats.defense).max(1);");
     println!("    let hp_after = defender.hp - dmg;");
     println!("    CombatEvent {{");
@@ -188,6 +189,7 @@ fn main() {
             hp: 50,
             attack: 15,
             defense: 5,
+            ..Default::default()
         },
     };
 
@@ -198,18 +200,16 @@ fn main() {
             hp: 100,
             attack: 20,
             defense: 8,
+            ..Default::default()
         },
     };
 
-    let power_ability = Ability {
-        name: "Power Strike".to_string(),
-        power: 10,
-    };
+    let power_ability = Ability::damage("Power Strike", 10);
 
     let event = compute_attack(&warrior, &dragon, &power_ability);
 
     println!("  Warrior (ATK: {}) + Power Strike (Power: {}) - Dragon (DEF: {})",
-        warrior.stats.attack, power_ability.power, dragon.stats.defense);
+        warrior.stats.attack, power_ability.power(), dragon.stats.defense);
     println!("  = {} damage", event.damage);
     println!("  Dragon HP: {} → {}\n", dragon.hp, event.defender_hp_after);
 
@@ -226,7 +226,7 @@ fn main() {
     println!("let criticalChance = 0.15  // 15% crit chance");
     println!();
     println!("let computeAttackWithCrit (attacker: Character) (defender: Character) (ability: Ability) (rng: float) : CombatEvent =");
-    println!("    let raw = attacker.Stats.Attack + ability.Power");
+    println!("    let raw = attacker.Stats.Attack + abilityPower ability");
     println!("    let isCrit = rng < criticalChance");
     println!("    let multiplier = if isCrit then 2.0 else 1.0");
     println!("    let dmg = max 1 (int (float (raw - defender.Stats.Defense) * multiplier))");
@@ -20,24 +20,20 @@
 //!
 //! - **Testing**: Run thousands of combat scenarios
 //! - **Benchmarking**: Measure performance without rendering overhead
-//! - **AI Training**: Generate training data for machine learning
+//! - **AI Training**: Generate training data for machine learning (for a
+//!   reset/step environment, see `CombatEnv` and the `rl_training` example)
 //! - **Server**: Run game logic on a server without graphics
 //! - **CI/CD**: Automated testing in headless environments
 
 use bevy::app::{App, AppExit, Startup, Update};
 use bevy::ecs::event::EventWriter;
-use bevy::ecs::system::{Commands, Query, ResMut, Resource};
+use bevy::ecs::system::{Commands, Res, ResMut, Resource};
 use bevy::log::{Level, LogPlugin};
-use bevy::prelude::{Component, MinimalPlugins};
-use bevy_wasm_fsharp_ref_logic::{Ability, Character, compute_attack};
-
-/// Marker component for player entities
-#[derive(Component)]
-struct Player;
-
-/// Marker component for monster entities
-#[derive(Component)]
-struct Monster;
+use bevy::prelude::MinimalPlugins;
+use bevy_wasm_fsharp_ref_logic::{
+    reduce, AbilityCatalog, Action, Character, Combatant, CooldownMode, Encounter,
+    EncounterOutcome, Team,
+};
 
 /// Resource tracking simulation state
 #[derive(Resource)]
@@ -65,24 +61,31 @@ impl Default for SimulationState {
     }
 }
 
-/// System to set up a single combat scenario
-fn setup_combat(mut commands: Commands) {
-    // Spawn player
-    commands.spawn((Player, Character::new_player("Hero")));
+/// A fresh Hero vs Slime encounter where both sides only have a basic attack
+fn new_encounter(catalog: &AbilityCatalog) -> Encounter {
+    let abilities = catalog
+        .ability_set(&["Basic Attack"], CooldownMode::Turns)
+        .expect("Basic Attack is in the built-in catalog");
 
-    // Spawn monster
-    commands.spawn((Monster, Character::new_monster("Slime")));
+    Encounter::new(vec![
+        Combatant::new(Team::Allies, Character::new_player("Hero"), abilities.clone()),
+        Combatant::new(Team::Enemies, Character::new_monster("Slime"), abilities),
+    ])
+}
+
+/// System to set up a single combat scenario
+fn setup_combat(mut commands: Commands, catalog: Res<AbilityCatalog>) {
+    commands.insert_resource(new_encounter(&catalog));
 }
 
 /// System that runs the combat simulation (headless)
+///
+/// All combat rules live in the logic crate's `reduce`: each frame plays one full
+/// round by feeding it the current combatant's action.
 fn run_combat(
-    mut commands: Commands,
+    catalog: Res<AbilityCatalog>,
     mut state: ResMut<SimulationState>,
-    mut players: Query<&mut Character, bevy::ecs::query::With<Player>>,
-    mut monsters: Query<
-        &mut Character,
-        (bevy::ecs::query::With<Monster>, bevy::ecs::query::Without<Player>),
-    >,
+    mut encounter: ResMut<Encounter>,
     mut exit: EventWriter<AppExit>,
 ) {
     // Check if we're done with all simulations
@@ -93,14 +96,6 @@ fn run_combat(
         return;
     }
 
-    // Get player and monster
-    let Ok(mut player) = players.get_single_mut() else {
-        return;
-    };
-    let Ok(mut monster) = monsters.get_single_mut() else {
-        return;
-    };
-
     // Run combat turn
     state.turn += 1;
 
@@ -110,61 +105,57 @@ fn run_combat(
             "Simulation {} reached turn limit",
             state.completed_simulations + 1
         );
-        reset_combat(&mut commands, &mut state, &mut player, &mut monster);
+        reset_combat(&catalog, &mut state, &mut encounter);
         return;
     }
 
-    let ability = Ability::basic_attack();
-
-    // Player attacks
-    let event = compute_attack(&player, &monster, &ability);
-    monster.hp = event.defender_hp_after;
-
-    if monster.hp <= 0 {
-        // Player wins
-        state.player_wins += 1;
-        state.total_turns += state.turn;
-        println!(
-            "Simulation {}/{}: Player wins in {} turns",
-            state.completed_simulations + 1,
-            state.total_simulations,
-            state.turn
-        );
-        reset_combat(&mut commands, &mut state, &mut player, &mut monster);
-        return;
+    // Every living combatant attacks its weakest opponent
+    let round = encounter.round;
+    while encounter.round == round {
+        let Some(actor) = encounter.current() else {
+            break;
+        };
+        let action = match encounter.weakest_target(actor) {
+            Some(target) => Action::UseAbility {
+                ability: "Basic Attack".to_string(),
+                target,
+            },
+            None => Action::Pass,
+        };
+        let (next, _events) =
+            reduce(&encounter, &action).expect("basic attacks are always valid");
+        *encounter = next;
     }
 
-    // Monster attacks
-    let event = compute_attack(&monster, &player, &ability);
-    player.hp = event.defender_hp_after;
-
-    if player.hp <= 0 {
-        // Monster wins
-        state.monster_wins += 1;
-        state.total_turns += state.turn;
-        println!(
-            "Simulation {}/{}: Monster wins in {} turns",
-            state.completed_simulations + 1,
-            state.total_simulations,
-            state.turn
-        );
-        reset_combat(&mut commands, &mut state, &mut player, &mut monster);
-    }
+    let winner = match encounter.outcome() {
+        EncounterOutcome::Ongoing => return,
+        EncounterOutcome::Victory => {
+            state.player_wins += 1;
+            "Player"
+        }
+        EncounterOutcome::Defeat => {
+            state.monster_wins += 1;
+            "Monster"
+        }
+    };
+    state.total_turns += state.turn;
+    println!(
+        "Simulation {}/{}: {} wins in {} turns",
+        state.completed_simulations + 1,
+        state.total_simulations,
+        winner,
+        state.turn
+    );
+    reset_combat(&catalog, &mut state, &mut encounter);
 }
 
 /// Helper function to reset combat state for next simulation
-fn reset_combat(
-    _commands: &mut Commands,
-    state: &mut SimulationState,
-    player: &mut Character,
-    monster: &mut Character,
-) {
+fn reset_combat(catalog: &AbilityCatalog, state: &mut SimulationState, encounter: &mut Encounter) {
     state.completed_simulations += 1;
     state.turn = 0;
 
     // Reset characters to full HP
-    *player = Character::new_player("Hero");
-    *monster = Character::new_monster("Slime");
+    *encounter = new_encounter(catalog);
 }
 
 /// Print final statistics
@@ -210,6 +201,7 @@ fn main() {
         })
         // Add our simulation state
         .init_resource::<SimulationState>()
+        .insert_resource(AbilityCatalog::builtin())
         // Setup system runs once
         .add_systems(Startup, setup_combat)
         // Update system runs every frame
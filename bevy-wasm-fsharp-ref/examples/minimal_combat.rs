@@ -48,7 +48,7 @@ fn main() {
     let mut monster = Character::new_monster("Slime");
 
     // Step 2: Create an ability
-    // The basic attack has 5 power and is available to all characters
+    // The basic attack deals damage with 5 power and is available to all characters
     let basic_attack = Ability::basic_attack();
 
     // Display starting state
@@ -62,7 +62,7 @@ fn main() {
         monster.name, monster.hp, monster.stats.hp, monster.stats.attack, monster.stats.defense
     );
 
-    println!("Combat Formula: damage = max(1, attacker.attack + ability.power - defender.defense)\n");
+    println!("Combat Formula: damage = max(1, attacker.attack + power - defender.defense)\n");
 
     // Step 3: Combat loop
     // Continue until one combatant reaches 0 HP or below
@@ -1,30 +1,600 @@
 module Domain
 
+open System
+
+// ---- Code generation ----
+//
+// crates/logic-fsharp/src/domain.rs is generated from the types in this file by
+// `cargo run -p fsharp-tools --bin fsharp-codegen`. These attributes carry the
+// Rust details the types themselves cannot express; they do nothing in F#.
+
+/// The generated Rust type derives `bevy::prelude::Component`, so it can be
+/// attached to entities.
+type ComponentAttribute() =
+    inherit Attribute()
+
+/// The field may be missing from serialized data, in which case it takes its
+/// type's default value (`#[serde(default)]` in Rust).
+type SerdeDefaultAttribute() =
+    inherit Attribute()
+
+// ---- Types ----
+
+/// The damage type of an ability.
+type Element =
+    /// Untyped weapon damage. The default for every ability.
+    | Physical
+    | Fire
+    | Ice
+    | Lightning
+    | Poison
+    | Holy
+
+/// How well an attack's element matched the defender's resistances.
+type Effectiveness =
+    /// Neutral damage.
+    | Normal
+    /// The defender is weak to the element.
+    | SuperEffective
+    /// The defender resists the element.
+    | Resisted
+    /// The defender is immune to the element and took no damage.
+    | Immune
+
+/// The built-in damage formulas.
+///
+/// Dispatches to the matching formula, so an encounter or a data file can name
+/// the formula it uses wherever a `DamageFormula` is expected.
+type DamageModel =
+    /// `FlatDefenseFormula`: `max(1, attack + power - defense)`, the default
+    | FlatDefense
+    /// `PercentArmorFormula`: each point of defense blocks a shrinking share of damage
+    | PercentArmor
+    /// `AttackRatioFormula`: `attack^2 / (attack + defense)`
+    | AttackRatio
+
+/// A character's damage multipliers per element, in percent.
+///
+/// Elements without an entry take neutral (100%) damage. Serializes as a plain
+/// map, e.g. `{"Fire": 200, "Ice": 50}`.
+type Resistances = Resistances of Map<Element, int>
+
+/// Character combat statistics.
+///
+/// Stats define a character's combat capabilities: maximum health, offensive
+/// power and defensive resilience. These values are used in damage calculations
+/// via `compute_attack`.
+///
+/// The three random stats default to 0, which keeps combat fully deterministic.
+/// Resistances default to empty, meaning every element deals neutral damage.
 type Stats =
-    { Hp: int
+    { /// Maximum hit points. When current HP reaches 0, the character is defeated.
+      Hp: int
+      /// Attack power. Added to ability power when calculating damage.
       Attack: int
-      Defense: int }
+      /// Damage reduction. Subtracted from incoming damage (minimum 1 damage always applies).
+      Defense: int
+      /// Critical hit chance in percent (0-100). Critical hits deal
+      /// `CRIT_DAMAGE_PERCENT` of normal damage.
+      [<SerdeDefault>]
+      CritChance: int
+      /// Evasion chance in percent (0-100). An evaded attack deals no damage.
+      [<SerdeDefault>]
+      EvadeChance: int
+      /// Damage variance in percent. Outgoing damage is scaled by a random
+      /// factor in `[100 - variance, 100 + variance]` percent.
+      [<SerdeDefault>]
+      DamageVariance: int
+      /// Incoming damage multipliers per element, in percent. Elements without
+      /// an entry take 100% damage.
+      [<SerdeDefault>]
+      Resistances: Resistances
+      /// Initiative. Higher speed acts earlier in an encounter round.
+      [<SerdeDefault>]
+      Speed: int }
 
+/// A game character (player or monster).
+///
+/// Characters are the core entities in the combat system. Each character has a
+/// name, current health and base statistics. Current HP can differ from max HP
+/// (`stats.hp`) after taking damage; a character is defeated when `hp <= 0`.
+[<Component>]
 type Character =
-    { Name: string
+    { /// Display name of the character.
+      Name: string
+      /// Current hit points. When this reaches 0 or below, the character is defeated.
       Hp: int
+      /// Base combat statistics (max HP, attack, defense).
       Stats: Stats }
 
+/// A real-time duration in whole milliseconds.
+///
+/// Real-time cooldowns and status effects count down in integer milliseconds
+/// rather than floating-point seconds, so every platform ticks them identically.
+type Millis = Millis of uint32
+
+/// The kind of a status effect.
+type StatusKind =
+    /// Deals `magnitude` damage per tick, ignoring defense and shields.
+    | Poison
+    /// The affected character loses its turns while the effect lasts.
+    | Stun
+    /// Absorbs up to `magnitude` incoming attack damage.
+    | Shield
+    /// Restores `magnitude` HP per tick, up to max HP.
+    | Regen
+
+/// How long a status effect lasts.
+type EffectDuration =
+    /// Lasts this many turns. Ticked by `StatusEffects::tick_turn`.
+    | Turns of int
+    /// Lasts this long in real time. Ticked by `StatusEffects::tick`.
+    | Seconds of Millis
+
+/// What happens when an effect is applied to a character that already has it.
+type StackingRule =
+    /// Reset the duration; magnitude stays the same.
+    | Refresh
+    /// Add a stack (up to `max_stacks`) and reset the duration. Ticks and
+    /// shields scale with the stack count.
+    | Stack of maxStacks: uint32
+    /// Keep the existing effect and discard the new one.
+    | Ignore
+
+/// A status effect definition, as carried by an `Ability`.
+type StatusEffect =
+    { /// What the effect does.
+      Kind: StatusKind
+      /// Damage or healing per tick, or shield points. Unused by `Stun`.
+      Magnitude: int
+      /// How long the effect lasts.
+      Duration: EffectDuration
+      /// How re-application interacts with an existing effect of the same kind.
+      [<SerdeDefault>]
+      Stacking: StackingRule }
+
+/// A status effect currently affecting a character.
+type ActiveEffect =
+    { /// The definition this effect was applied from.
+      Effect: StatusEffect
+      /// Time left before the effect expires.
+      Remaining: EffectDuration
+      /// Number of stacks (at least 1).
+      Stacks: uint32
+      /// Shield points left; only meaningful for `Shield`.
+      ShieldRemaining: int
+      /// Time accumulated toward the next per-second tick.
+      TickProgress: Millis }
+
+/// Something that happened to a character's status effects.
+type StatusEvent =
+    /// An effect was applied or re-applied. `stacks` is the resulting count.
+    | Applied of target: string * kind: StatusKind * stacks: uint32
+    /// A damage or healing effect ticked. `amount` is positive for damage and
+    /// negative for healing, matching `CombatEvent::damage`.
+    | Ticked of target: string * kind: StatusKind * amount: int * hpAfter: int
+    /// A shield absorbed incoming damage.
+    | Absorbed of target: string * amount: int
+    /// An effect ran out (or a shield was depleted) and was removed.
+    | Expired of target: string * kind: StatusKind
+
+/// The status effects currently affecting one character.
+///
+/// This is a companion component: spawn it on the same entity as the
+/// `Character` it belongs to.
+[<Component>]
+type StatusEffects =
+    { /// Active effects, at most one entry per `StatusKind`.
+      Effects: ActiveEffect list }
+
+/// Who an ability can be aimed at.
+type Targeting =
+    /// A single opponent.
+    | Enemy
+    /// Every opponent.
+    | AllEnemies
+    /// The character using the ability.
+    | Caster
+    /// A single ally, possibly the caster.
+    | Ally
+    /// Every ally, including the caster.
+    | AllAllies
+
+/// One thing an ability does to each of its targets.
+type AbilityEffect =
+    /// Deals damage of the ability's element: the caster's attack plus `power`,
+    /// minus the target's defense.
+    | Damage of power: int
+    /// Restores `amount` HP, up to the target's max HP.
+    | Heal of amount: int
+    /// Raises a shield that absorbs up to `points` attack damage.
+    | Shield of points: int * duration: EffectDuration
+    /// Applies a status effect.
+    | ApplyStatus of StatusEffect
+
+/// A combat ability or attack.
+///
+/// Abilities represent actions that characters can perform in combat. Each
+/// ability has a target rule and a list of effects, resolved in order against
+/// every target; `element` is checked against the target's resistances.
 type Ability =
-    { Name: string
-      Power: int }
+    { /// Display name of the ability.
+      Name: string
+      /// Damage type, checked against the defender's resistances.
+      [<SerdeDefault>]
+      Element: Element
+      /// Who the ability can be aimed at.
+      [<SerdeDefault>]
+      Target: Targeting
+      /// What the ability does to each target.
+      Effects: AbilityEffect list }
+
+/// Ability type enumeration for AI decision making.
+///
+/// Classifies abilities into categories to help the AI choose appropriate actions.
+type AbilityType =
+    | BasicAttack
+    | PowerfulAttack
+    | Heal
+
+/// The clock a cooldown counts against.
+type CooldownMode =
+    /// Whole turns, for turn-based combat.
+    | Turns
+    /// Seconds, for real-time combat.
+    | Seconds
 
+/// A cooldown timer in turns or seconds.
+type Cooldown =
+    /// Counts down one per `tick_turn`.
+    | Turns of max: int * remaining: int
+    /// Counts down by `delta` per `tick`.
+    | Seconds of max: Millis * remaining: Millis
+
+/// An ability slot with cooldown tracking.
+///
+/// Pairs an `Ability` with its `AbilityType` classification and a `Cooldown`.
+/// The same slot type is used for real-time and turn-based combat; only the
+/// cooldown's mode differs.
+[<Component>]
 type AbilitySlot =
-    { Ability: Ability
-      CooldownMax: float
-      CooldownCurrent: float }
+    { /// The ability contained in this slot.
+      Ability: Ability
+      /// Type classification for AI decision making.
+      AbilityType: AbilityType
+      /// Cooldown in turns or seconds.
+      Cooldown: Cooldown }
 
+/// A set of abilities for a character.
+///
+/// Contains multiple ability slots that can be used in combat.
+[<Component>]
 type AbilitySet =
-    { Abilities: AbilitySlot list }
+    { /// List of ability slots.
+      Abilities: AbilitySlot list }
+
+/// The built-in AI personalities.
+///
+/// Dispatches to the matching policy, so it can be stored in data files or
+/// attached to a monster entity and used wherever an `AiPolicy` is expected.
+[<Component>]
+type AiPersonality =
+    /// `ThresholdAi`
+    | Threshold
+    /// `AggressiveAi`
+    | Aggressive
+    /// `DefensiveAi`
+    | Defensive
+    /// `BalancedAi`
+    | Balanced
+    /// `SmartAi`
+    | Smart
+    /// `Planner::HARD`, the hard difficulty
+    | Tactician
+
+/// What one effect of an ability did to its target.
+type EffectOutcome =
+    /// A `Damage` effect dealt `amount` damage, after defense, resistances, shields
+    /// and critical hits. Damage past 0 HP counts towards the event's `Overkill`.
+    | Damaged of amount: int
+    /// A `Heal` effect restored `amount` HP. Healing beyond max HP is lost.
+    | Healed of amount: int
+    /// A `Shield` or `ApplyStatus` effect took hold; `stacks` is the resulting count.
+    | StatusApplied of kind: StatusKind * stacks: uint32
+    /// A `Shield` or `ApplyStatus` effect was discarded because of `StackingRule.Ignore`.
+    | StatusIgnored of kind: StatusKind
+    /// The target evaded the ability, so none of its effects applied.
+    | Evaded
 
+/// Total XP needed to reach each level.
+type LevelCurve =
+    /// Every level costs the same: level `n` needs `step * (n - 1)` XP in total.
+    | Linear of step: uint32
+    /// Levels get steeper: level `n` needs `scale * (n - 1)^2` XP in total.
+    | Quadratic of scale: uint32
+    /// `totals[i]` is the total XP needed for level `i + 2`. The last entry
+    /// reaches the maximum level.
+    | Table of totals: uint32 list
+
+/// Stats gained with every level above 1.
+type StatGrowth =
+    { /// Max HP per level.
+      Hp: int
+      /// Attack per level.
+      Attack: int
+      /// Defense per level.
+      Defense: int
+      /// Speed per level.
+      [<SerdeDefault>]
+      Speed: int }
+
+/// An ability a class learns on reaching `Level`.
+type AbilityUnlock =
+    { Level: uint32
+      /// Ability catalog name.
+      Ability: string }
+
+/// A character class: level 1 stats, growth, level curve and ability unlocks.
+type CharacterClass =
+    { /// Display name. Must be unique within a class catalog.
+      Name: string
+      /// Stats at level 1.
+      Stats: Stats
+      /// Stats gained per level.
+      Growth: StatGrowth
+      /// XP needed for each level.
+      Curve: LevelCurve
+      /// Abilities learned by level, in the order they are learned.
+      [<SerdeDefault>]
+      Unlocks: AbilityUnlock list
+      /// XP for defeating a level 1 member of the class. Scales with level.
+      XpReward: uint32
+      /// How members of the class fight when the AI controls them.
+      [<SerdeDefault>]
+      Personality: AiPersonality }
+
+/// A character's class, level and experience.
+[<Component>]
+type Progression =
+    { Class: CharacterClass
+      /// Current level, starting at 1.
+      Level: uint32
+      /// Total XP earned.
+      Xp: uint32 }
+
+/// XP one character earned from a combat action.
+type XpGrant =
+    { /// Name of the character who earned the XP.
+      Recipient: string
+      /// XP earned.
+      Amount: uint32
+      /// Level after the XP was added.
+      LevelAfter: uint32
+      /// Levels gained.
+      [<SerdeDefault>]
+      LevelsGained: uint32
+      /// Abilities unlocked by the levels gained.
+      [<SerdeDefault>]
+      Unlocked: string list }
+
+/// The result of a combat action.
+///
+/// A `CombatEvent` records what happened when one character used an ability on
+/// another: the names of both participants, the net damage dealt, the defender's
+/// remaining HP afterwards, which ability was used and what each of its effects
+/// did. Events are immutable records; the defender's HP must be updated from
+/// `defender_hp_after`, which is always clamped between 0 and max HP.
+///
+/// `Damage = EffectiveAmount + Overkill - Overheal` always holds.
 type CombatEvent =
-    { AttackerName: string
+    { /// Version of the serialized event format. Logs written before events
+      /// were versioned have no version and read as 0.
+      [<SerdeDefault>]
+      Version: uint32
+      /// Name of the character who performed the attack.
+      AttackerName: string
+      /// Name of the character who received the attack.
       DefenderName: string
+      /// Net damage dealt: damage minus healing, before HP is clamped.
+      /// Negative damage means healing.
       Damage: int
+      /// Defender's remaining HP after the ability, between 0 and max HP.
+      /// If this is 0, the defender is defeated.
       DefenderHpAfter: int
-      AbilityUsed: string }
+      /// HP the defender actually lost; negative for HP actually restored.
+      [<SerdeDefault>]
+      EffectiveAmount: int
+      /// Damage dealt past 0 HP.
+      [<SerdeDefault>]
+      Overkill: int
+      /// Healing past max HP.
+      [<SerdeDefault>]
+      Overheal: int
+      /// Whether this event took the defender from above 0 HP to 0.
+      [<SerdeDefault>]
+      KillingBlow: bool
+      /// Name of the ability that was used.
+      AbilityUsed: string
+      /// Whether the attack was a critical hit.
+      [<SerdeDefault>]
+      Critical: bool
+      /// Whether the defender evaded the attack (no damage dealt).
+      [<SerdeDefault>]
+      Missed: bool
+      /// How the ability's element matched the defender's resistances.
+      [<SerdeDefault>]
+      Effectiveness: Effectiveness
+      /// Status effects applied, absorbed or expired as part of this action.
+      [<SerdeDefault>]
+      StatusEvents: StatusEvent list
+      /// What each of the ability's effects did, in resolution order.
+      [<SerdeDefault>]
+      Outcomes: EffectOutcome list
+      /// XP earned for the characters defeated this turn.
+      [<SerdeDefault>]
+      XpGrants: XpGrant list }
+
+/// A stat that equipment can modify.
+type StatKind =
+    /// `Stats.Hp`, the maximum hit points.
+    | MaxHp
+    | Attack
+    | Defense
+    | Speed
+    | CritChance
+    | EvadeChance
+
+/// A change an equipped item makes to one of its wearer's stats.
+type StatModifier =
+    /// Adds `amount` to the stat.
+    | Flat of stat: StatKind * amount: int
+    /// Scales the stat by `percent` percent (`20` is +20%), after every `Flat`
+    /// modifier. Percentages on the same stat add up.
+    | Percent of stat: StatKind * percent: int
+
+/// Where an item is worn. A combatant wears at most one item per slot.
+type EquipmentSlot =
+    | Weapon
+    | Armor
+    | Accessory
+
+/// What an item does.
+type ItemKind =
+    /// Worn in `slot`, modifying the wearer's stats while equipped.
+    | Equipment of slot: EquipmentSlot * modifiers: StatModifier list
+    /// Used up to resolve `ability`'s effects, e.g. a potion's `Heal`.
+    | Consumable of ability: Ability
+
+/// Something a character can carry.
+type Item =
+    { /// Display name, unique within an inventory's lookups.
+      Name: string
+      Kind: ItemKind }
+
+/// A character's items: a bag of carried items and the equipment being worn.
+///
+/// Equipped items modify the wearer's effective stats; its base `Stats` never change.
+[<Component>]
+type Inventory =
+    { /// Carried items, consumables included, in pickup order.
+      [<SerdeDefault>]
+      Items: Item list
+      /// Worn equipment by slot.
+      [<SerdeDefault>]
+      Equipped: Map<EquipmentSlot, Item> }
+
+/// How rare a drop is. Loot entries without a weight use their rarity's default
+/// weight.
+type Rarity =
+    | Common
+    | Uncommon
+    | Rare
+    | Epic
+    | Legendary
+
+/// What a loot entry yields when it is picked.
+type LootDrop =
+    /// No drop. Gives a table a chance of dropping nothing.
+    | Nothing
+    /// Between `min` and `max` copies of `item`.
+    | Item of item: Item * min: uint32 * max: uint32
+    /// Everything a roll on the named table yields.
+    | Table of table: string
+
+/// One possible outcome of a loot table.
+type LootEntry =
+    { Drop: LootDrop
+      /// Relative chance among the table's weighted entries. Defaults to the
+      /// weight of `Rarity`.
+      [<SerdeDefault>]
+      Weight: uint32 option
+      /// Dropped on every roll of the table, on top of its weighted picks.
+      [<SerdeDefault>]
+      Guaranteed: bool
+      [<SerdeDefault>]
+      Rarity: Rarity }
+
+/// Weighted loot, e.g. what a monster drops when defeated.
+type LootTable =
+    { Name: string
+      /// Number of weighted picks per roll. Picks are made with replacement.
+      Rolls: uint32
+      Entries: LootEntry list }
+
+/// Items rolled from a loot table.
+type Loot =
+    { Item: Item
+      Count: uint32
+      /// The rarity of the entry that dropped the item.
+      Rarity: Rarity }
+
+/// A kind of monster in the bestiary.
+type MonsterTemplate =
+    { /// Template ID, e.g. `"goblin"`. Must be unique within a bestiary.
+      Id: string
+      /// Display name of spawned monsters.
+      Name: string
+      Stats: Stats
+      /// Ability catalog names, in slot order.
+      Abilities: string list
+      Personality: AiPersonality
+      /// Loot table rolled when the monster is defeated.
+      [<SerdeDefault>]
+      Loot: string option }
+
+/// A group of monsters from the bestiary that is fought together.
+type EncounterTemplate =
+    { /// Template ID. Must be unique within a bestiary.
+      Id: string
+      /// Display name of the encounter.
+      Name: string
+      /// Monster template IDs, one per spawned monster.
+      Monsters: string list }
+
+/// The side a combatant fights for.
+[<Component>]
+type Team =
+    /// The player's party.
+    | Allies
+    /// The monsters.
+    | Enemies
+
+/// Identifies a combatant within an `Encounter`.
+[<Component>]
+type CombatantId = CombatantId of unativeint
+
+/// A character taking part in an encounter, with its abilities and status effects.
+type Combatant =
+    { /// Assigned by `Encounter::new`.
+      Id: CombatantId
+      Team: Team
+      Character: Character
+      Abilities: AbilitySet
+      [<SerdeDefault>]
+      Effects: StatusEffects
+      /// Class and level, for combatants that earn XP. Defeating a combatant
+      /// without one is worth its max HP in XP.
+      [<SerdeDefault>]
+      Progression: Progression option
+      /// Carried and worn items. Equipment modifies the stats the combatant
+      /// fights with.
+      [<SerdeDefault>]
+      Inventory: Inventory }
+
+/// How an encounter has ended, from the allies' point of view.
+type EncounterOutcome =
+    /// Both teams still have living combatants.
+    | Ongoing
+    /// Every enemy has been defeated.
+    | Victory
+    /// Every ally has been defeated.
+    | Defeat
+
+/// What the current combatant does with its turn.
+type Action =
+    /// Use one of the actor's ready abilities on `target`.
+    | UseAbility of ability: string * target: CombatantId
+    /// Use up one of the actor's consumable items on `target`.
+    | UseItem of item: string * target: CombatantId
+    /// Do nothing this turn. The only action available to a stunned combatant.
+    | Pass
@@ -2,63 +2,137 @@ module GameLogic
 
 open Domain
 
-/// Ability type enumeration for AI decision making
-type AbilityType =
-    | BasicAttack
-    | PowerfulAttack
-    | Heal
-
-/// Extended ability with type and cooldown support
-type AbilityWithMeta =
-    { Ability: Ability
-      AbilityType: AbilityType
-      Cooldown: int
-      CurrentCooldown: int }
-
 /// Basic attack ability with power of 5.
 /// Corresponds to Rust: Ability::basic_attack()
 let basicAttack =
     { Name = "Basic Attack"
-      Power = 5 }
+      Element = Physical
+      Target = Enemy
+      Effects = [ Damage(5) ] }
 
 /// Powerful attack ability with higher damage but longer cooldown.
 /// Corresponds to Rust: Ability::powerful_attack()
 let powerfulAttack =
     { Name = "Powerful Attack"
-      Power = 12 }
+      Element = Physical
+      Target = Enemy
+      Effects = [ Damage(12) ] }
 
-/// Heal ability that restores HP instead of dealing damage.
+/// Heal ability that restores 8 HP to an ally.
 /// Corresponds to Rust: Ability::heal()
 let healAbility =
     { Name = "Heal"
-      Power = 10 }
+      Element = Physical
+      Target = Ally
+      Effects = [ AbilityEffect.Heal(8) ] }
+
+/// The total power of an ability's damage effects.
+/// Corresponds to Rust: Ability::power()
+let abilityPower (ability: Ability) : int =
+    ability.Effects
+    |> List.sumBy (function
+        | Damage power -> power
+        | _ -> 0)
+
+/// Whether an ability has any damage effect.
+let dealsDamage (ability: Ability) : bool =
+    ability.Effects
+    |> List.exists (function
+        | Damage _ -> true
+        | _ -> false)
 
-/// Creates a basic attack with metadata
-let basicAttackWithMeta =
+/// Whether a cooldown has finished.
+/// Corresponds to Rust: Cooldown::is_ready()
+let isReady (cooldown: Cooldown) : bool =
+    match cooldown with
+    | Cooldown.Turns(_, remaining) -> remaining <= 0
+    | Cooldown.Seconds(_, Millis remaining) -> remaining = 0u
+
+/// Basic attack slot with no turn cooldown.
+/// Corresponds to Rust: AbilitySlot::basic_attack_turns()
+let basicAttackSlot =
     { Ability = basicAttack
       AbilityType = BasicAttack
-      Cooldown = 0
-      CurrentCooldown = 0 }
+      Cooldown = Cooldown.Turns(0, 0) }
 
-/// Creates a powerful attack with metadata (3-turn cooldown)
-let powerfulAttackWithMeta =
+/// Powerful attack slot (3-turn cooldown).
+/// Corresponds to Rust: AbilitySlot::powerful_attack_turns()
+let powerfulAttackSlot =
     { Ability = powerfulAttack
       AbilityType = PowerfulAttack
-      Cooldown = 3
-      CurrentCooldown = 0 }
+      Cooldown = Cooldown.Turns(3, 0) }
 
-/// Creates a heal ability with metadata (4-turn cooldown)
-let healAbilityWithMeta =
+/// Heal slot (4-turn cooldown).
+/// Corresponds to Rust: AbilitySlot::heal_turns()
+let healSlot =
     { Ability = healAbility
       AbilityType = Heal
-      Cooldown = 4
-      CurrentCooldown = 0 }
+      Cooldown = Cooldown.Turns(4, 0) }
+
+/// Classifies an element damage multiplier (in percent).
+/// Corresponds to Rust: Effectiveness::from_multiplier()
+let effectivenessOf (multiplier: int) : Effectiveness =
+    if multiplier <= 0 then Immune
+    elif multiplier < 100 then Resisted
+    elif multiplier = 100 then Normal
+    else SuperEffective
+
+/// The version of the CombatEvent format written by this module.
+///
+/// Corresponds to Rust: COMBAT_EVENT_VERSION
+let combatEventVersion = 1u
+
+/// Clamps an event's DefenderHpAfter between 0 and max HP and fills in its
+/// accounting fields, so that Damage = EffectiveAmount + Overkill - Overheal.
+///
+/// A defender who was already outside that range is not pushed further out.
+///
+/// Corresponds to Rust: `CombatEvent::settle` (crate-private)
+let settle (hpBefore: int) (maxHp: int) (event: CombatEvent) : CombatEvent =
+    let floor = min hpBefore 0
+    let ceiling = max maxHp hpBefore
+    let rawAfter = hpBefore - event.Damage
+    let hpAfter = rawAfter |> max floor |> min ceiling
+    { event with
+        DefenderHpAfter = hpAfter
+        EffectiveAmount = hpBefore - hpAfter
+        Overkill = max 0 (floor - rawAfter)
+        Overheal = max 0 (rawAfter - ceiling)
+        KillingBlow = hpBefore > 0 && hpAfter <= 0 }
+
+/// The defense that halves damage under the PercentArmor model.
+///
+/// Corresponds to Rust: PercentArmorFormula::DEFAULT_ARMOR_SCALE
+let armorScale = 10
+
+/// Base damage of one hit with `power`, before resistances, variance and crits.
+///
+/// Corresponds to Rust: DamageModel::base_damage()
+let baseDamage (model: DamageModel) (attacker: Character) (defender: Character) (power: int) : int =
+    let raw = attacker.Stats.Attack + power
+    let defense = max 0 defender.Stats.Defense
+    let dmg =
+        match model with
+        | FlatDefense -> raw - defender.Stats.Defense
+        | PercentArmor -> raw * armorScale / (armorScale + defense)
+        | AttackRatio -> if raw <= 0 then 0 else raw * raw / (raw + defense)
+    max 1 dmg
 
 /// Computes the result of an attack between two characters using a specified ability.
 ///
-/// Damage calculation: damage = max(1, attacker.attack + ability.power - defender.defense)
+/// The ability's effects are resolved in order.
+///
+/// Damage effects: damage = max(1, attacker.attack + power - defender.defense)
 /// - Ensures minimum 1 damage even if defender's defense is high
-/// - Returns a CombatEvent containing the combat result
+/// - The result is scaled by the defender's resistance to the ability's element
+///   (0 damage if immune)
+///
+/// Heal effects bypass defense and never raise HP above the defender's max HP.
+/// The event's HP is clamped between 0 and max HP, with the rest reported as
+/// overkill or overheal (see `settle`).
+/// Shields and status effects are applied afterwards by the status module.
+///
+/// Returns a CombatEvent containing the combat result and one outcome per effect
 ///
 /// Type Mapping (F# → Rust):
 /// - F# `int` → Rust `i32`
@@ -88,13 +162,130 @@ let healAbilityWithMeta =
 /// // result.DefenderHpAfter will be 20 - 14 = 6
 /// ```
 let computeAttack (attacker: Character) (defender: Character) (ability: Ability) : CombatEvent =
-    let raw = attacker.Stats.Attack + ability.Power
-    let dmg = max 1 (raw - defender.Stats.Defense)
-    let hpAfter = defender.Hp - dmg
-    { AttackerName = attacker.Name
+    let multiplier =
+        defender.Stats.Resistances
+        |> Map.tryFind ability.Element
+        |> Option.defaultValue 100
+        |> max 0
+    let resolve (hp, dealt, outcomes) effect =
+        match effect with
+        | Damage power ->
+            let baseDmg = baseDamage FlatDefense attacker defender power
+            let dmg = if multiplier = 0 then 0 else max 1 (baseDmg * multiplier / 100)
+            hp - dmg, dealt + dmg, outcomes @ [ Damaged dmg ]
+        | AbilityEffect.Heal amount ->
+            let healed = max 0 (min amount (defender.Stats.Hp - hp))
+            hp + healed, dealt - amount, outcomes @ [ Healed healed ]
+        | AbilityEffect.Shield _
+        | ApplyStatus _ -> hp, dealt, outcomes
+    let _, dealt, outcomes = ability.Effects |> List.fold resolve (defender.Hp, 0, [])
+    { Version = combatEventVersion
+      AttackerName = attacker.Name
       DefenderName = defender.Name
-      Damage = dmg
-      DefenderHpAfter = hpAfter }
+      Damage = dealt
+      DefenderHpAfter = defender.Hp
+      EffectiveAmount = 0
+      Overkill = 0
+      Overheal = 0
+      KillingBlow = false
+      AbilityUsed = ability.Name
+      Critical = false
+      Missed = false
+      Effectiveness = if dealsDamage ability then effectivenessOf multiplier else Normal
+      StatusEvents = []
+      Outcomes = outcomes
+      XpGrants = [] }
+    |> settle defender.Hp defender.Stats.Hp
+
+/// The highest level any curve reaches.
+///
+/// Corresponds to Rust: MAX_LEVEL
+let maxLevel = 99u
+
+/// The highest level of a curve: maxLevel, or the end of a table.
+///
+/// Corresponds to Rust: `LevelCurve::max_level()`
+let maxLevelOf (curve: LevelCurve) : uint32 =
+    match curve with
+    | Table totals -> min maxLevel (uint32 (List.length totals) + 1u)
+    | Linear _
+    | Quadratic _ -> maxLevel
+
+/// Total XP needed to reach a level. Level 1 needs none.
+///
+/// Corresponds to Rust: `LevelCurve::xp_for_level()`
+let xpForLevel (curve: LevelCurve) (level: uint32) : uint32 =
+    let steps = (max 1u (min level (maxLevelOf curve))) - 1u
+    match curve with
+    | Linear step -> step * steps
+    | Quadratic scale -> scale * steps * steps
+    | Table totals -> if steps = 0u then 0u else totals.[int steps - 1]
+
+/// The level reached with a total amount of XP.
+///
+/// Corresponds to Rust: `LevelCurve::level_for_xp()`
+let levelForXp (curve: LevelCurve) (xp: uint32) : uint32 =
+    let rec climb level =
+        if level < maxLevelOf curve && xpForLevel curve (level + 1u) <= xp then climb (level + 1u)
+        else level
+    climb 1u
+
+/// XP for defeating a combatant: its class's reward times its level, or its
+/// max HP if it has no class.
+///
+/// Corresponds to Rust: `xp_reward()`
+let xpReward (combatant: Combatant) : uint32 =
+    match combatant.Progression with
+    | Some p -> p.Class.XpReward * p.Level
+    | None -> uint32 (max 0 combatant.Character.Stats.Hp)
+
+/// Stats as modified by equipment: flat amounts first, then each stat's summed
+/// percentage bonus. Max HP stays at least 1; crit and evade chances stay in 0-100.
+///
+/// Corresponds to Rust: `apply_modifiers()`
+let applyModifiers (stats: Stats) (modifiers: StatModifier list) : Stats =
+    let flat stat =
+        modifiers |> List.sumBy (function Flat (s, amount) when s = stat -> amount | _ -> 0)
+    let percent stat =
+        modifiers |> List.sumBy (function Percent (s, bonus) when s = stat -> bonus | _ -> 0)
+    let effective stat value =
+        int (int64 (value + flat stat) * int64 (max 0 (100 + percent stat)) / 100L)
+    { stats with
+        Hp = max 1 (effective MaxHp stats.Hp)
+        Attack = effective Attack stats.Attack
+        Defense = effective Defense stats.Defense
+        Speed = effective Speed stats.Speed
+        CritChance = effective CritChance stats.CritChance |> max 0 |> min 100
+        EvadeChance = effective EvadeChance stats.EvadeChance |> max 0 |> min 100 }
+
+/// The stats a combatant fights with: its base stats as modified by its equipment.
+///
+/// Corresponds to Rust: `Combatant::effective_stats()`
+let effectiveStats (combatant: Combatant) : Stats =
+    combatant.Inventory.Equipped
+    |> Map.toList
+    |> List.collect (fun (_, item) ->
+        match item.Kind with
+        | Equipment (_, modifiers) -> modifiers
+        | Consumable _ -> [])
+    |> applyModifiers combatant.Character.Stats
+
+/// Weight of loot entries of a rarity that do not set their own.
+///
+/// Corresponds to Rust: `Rarity::weight()`
+let rarityWeight (rarity: Rarity) : uint32 =
+    match rarity with
+    | Common -> 100u
+    | Uncommon -> 40u
+    | Rare -> 15u
+    | Epic -> 5u
+    | Legendary -> 1u
+
+/// A loot entry's chance relative to the table's other weighted entries.
+///
+/// Corresponds to Rust: `LootEntry::weight()`
+let entryWeight (entry: LootEntry) : uint32 =
+    entry.Weight |> Option.defaultWith (fun () -> rarityWeight entry.Rarity)
 
 /// AI decision-making function for monster combat behavior.
 ///
@@ -108,14 +299,14 @@ let computeAttack (attacker: Character) (defender: Character) (ability: Ability)
 /// Type Mapping (F# → Rust):
 /// - F# `chooseMonsterAction` → Rust `choose_monster_action`
 /// - F# `Character` → Rust `Character`
-/// - F# `Ability list` → Rust `Vec<Ability>`
+/// - F# `AbilitySlot list` → Rust `&[AbilitySlot]`
 ///
 /// Corresponds to Rust:
 /// ```rust
 /// pub fn choose_monster_action(
 ///     monster: &Character,
 ///     player: &Character,
-///     available_abilities: &[AbilityWithMeta],
+///     available_abilities: &[AbilitySlot],
 /// ) -> Ability
 /// ```
 ///
@@ -123,16 +314,16 @@ let computeAttack (attacker: Character) (defender: Character) (ability: Ability)
 /// ```fsharp
 /// let monster = { Name = "Goblin"; Hp = 5; Stats = { Hp = 20; Attack = 6; Defense = 1 } }
 /// let player = { Name = "Hero"; Hp = 25; Stats = { Hp = 30; Attack = 10; Defense = 2 } }
-/// let abilities = [basicAttackWithMeta; powerfulAttackWithMeta; healAbilityWithMeta]
+/// let abilities = [basicAttackSlot; powerfulAttackSlot; healSlot]
 /// let chosen = chooseMonsterAction monster player abilities
 /// // Since monster HP is 5/20 = 25% (< 30%), AI will choose heal if available
 /// ```
-let chooseMonsterAction (monster: Character) (_player: Character) (availableAbilities: AbilityWithMeta list) : Ability =
+let chooseMonsterAction (monster: Character) (_player: Character) (availableAbilities: AbilitySlot list) : Ability =
     // Calculate monster's HP percentage
     let hpPercent = float monster.Hp / float monster.Stats.Hp
 
     // Filter abilities that are not on cooldown
-    let usableAbilities = availableAbilities |> List.filter (fun a -> a.CurrentCooldown = 0)
+    let usableAbilities = availableAbilities |> List.filter (fun a -> isReady a.Cooldown)
 
     // Defensive strategy: HP < 30%, try to heal
     if hpPercent < 0.3 then
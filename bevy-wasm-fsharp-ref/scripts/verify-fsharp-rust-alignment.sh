@@ -1,161 +1,23 @@
 #!/bin/bash
-# Verification script for F# ↔ Rust type alignment
-# Tests that F# domain types match their Rust equivalents
+# Verification script for F# ↔ Rust alignment
+#
+# Checks that crates/logic-fsharp/src/domain.rs is up to date with
+# fsharp/Domain.fs (see `just codegen`), then parses the records, unions and
+# let bindings in fsharp/ and compares field names, field types and constant
+# values with the Rust logic crate. Any drift (e.g. healAbility.Power differing
+# from Ability::heal().power) is printed with its path on both sides and fails
+# the script.
 
 set -e
 
-echo "🔍 Verifying F# ↔ Rust Type Alignment for Issue #10"
-echo "=================================================="
-echo ""
-
 # Check if we're in the right directory
 if [ ! -f "Cargo.toml" ] || [ ! -d "fsharp" ]; then
     echo "❌ Error: Must run from bevy-wasm-fsharp-ref root directory"
     exit 1
 fi
 
-echo "✅ Found project files"
-echo ""
-
-# 1. Run Rust tests
-echo "📋 Step 1: Running Rust logic tests..."
-cargo test -p bevy-wasm-fsharp-ref-logic --quiet
-echo "✅ All Rust tests passed (41 tests)"
+echo "🔍 Verifying F# ↔ Rust alignment"
 echo ""
 
-# 2. Verify type alignment
-echo "📋 Step 2: Verifying type definitions..."
-
-# Check Character type
-fsharp_character=$(grep -A 3 "type Character" fsharp/Domain.fs | wc -l)
-rust_character=$(grep -A 3 "pub struct Character" crates/logic-fsharp/src/lib.rs | wc -l)
-
-if [ "$fsharp_character" -gt 0 ] && [ "$rust_character" -gt 0 ]; then
-    echo "✅ Character type found in both F# and Rust"
-else
-    echo "❌ Character type missing in F# or Rust"
-    exit 1
-fi
-
-# Check Stats type
-fsharp_stats=$(grep -A 3 "type Stats" fsharp/Domain.fs | wc -l)
-rust_stats=$(grep -A 3 "pub struct Stats" crates/logic-fsharp/src/lib.rs | wc -l)
-
-if [ "$fsharp_stats" -gt 0 ] && [ "$rust_stats" -gt 0 ]; then
-    echo "✅ Stats type found in both F# and Rust"
-else
-    echo "❌ Stats type missing in F# or Rust"
-    exit 1
-fi
-
-# Check Ability type
-fsharp_ability=$(grep -A 2 "type Ability" fsharp/Domain.fs | wc -l)
-rust_ability=$(grep -A 2 "pub struct Ability" crates/logic-fsharp/src/lib.rs | wc -l)
-
-if [ "$fsharp_ability" -gt 0 ] && [ "$rust_ability" -gt 0 ]; then
-    echo "✅ Ability type found in both F# and Rust"
-else
-    echo "❌ Ability type missing in F# or Rust"
-    exit 1
-fi
-
-# Check CombatEvent type
-fsharp_event=$(grep -A 4 "type CombatEvent" fsharp/Domain.fs | wc -l)
-rust_event=$(grep -A 4 "pub struct CombatEvent" crates/logic-fsharp/src/lib.rs | wc -l)
-
-if [ "$fsharp_event" -gt 0 ] && [ "$rust_event" -gt 0 ]; then
-    echo "✅ CombatEvent type found in both F# and Rust"
-else
-    echo "❌ CombatEvent type missing in F# or Rust"
-    exit 1
-fi
-
-echo ""
-
-# 3. Verify function implementations
-echo "📋 Step 3: Verifying function implementations..."
-
-# Check computeAttack in F#
-if grep -q "let computeAttack" fsharp/GameLogic.fs; then
-    echo "✅ computeAttack function found in F#"
-else
-    echo "❌ computeAttack function missing in F#"
-    exit 1
-fi
-
-# Check compute_attack in Rust
-if grep -q "pub fn compute_attack" crates/logic-fsharp/src/lib.rs; then
-    echo "✅ compute_attack function found in Rust"
-else
-    echo "❌ compute_attack function missing in Rust"
-    exit 1
-fi
-
-# Check basicAttack in F#
-if grep -q "let basicAttack" fsharp/GameLogic.fs; then
-    echo "✅ basicAttack constant found in F#"
-else
-    echo "❌ basicAttack constant missing in F#"
-    exit 1
-fi
-
-# Check basic_attack in Rust
-if grep -q "pub fn basic_attack" crates/logic-fsharp/src/lib.rs; then
-    echo "✅ basic_attack function found in Rust"
-else
-    echo "❌ basic_attack function missing in Rust"
-    exit 1
-fi
-
-echo ""
-
-# 4. Check build (skip full workspace due to temp file issues)
-echo "📋 Step 4: Verifying logic crate builds..."
-cargo build -p bevy-wasm-fsharp-ref-logic --quiet
-echo "✅ Logic crate builds successfully"
-echo ""
-
-# 5. Verify documentation
-echo "📋 Step 5: Verifying documentation..."
-
-if [ -f "docs/fsharp-rust-type-mapping.md" ]; then
-    echo "✅ Type mapping documentation exists"
-else
-    echo "❌ Type mapping documentation missing"
-    exit 1
-fi
-
-if [ -f "docs/issue-10-implementation-summary.md" ]; then
-    echo "✅ Implementation summary exists"
-else
-    echo "❌ Implementation summary missing"
-    exit 1
-fi
-
-if grep -q "/// Computes the result of an attack" fsharp/GameLogic.fs; then
-    echo "✅ F# function documentation found"
-else
-    echo "❌ F# function documentation missing"
-    exit 1
-fi
-
-echo ""
-
-# 6. Skip full app build (filesystem temp issues)
-echo "📋 Step 6: Skipping full application build (logic crate verified)"
-echo ""
-
-# Final summary
-echo "=================================================="
-echo "✅ ALL VERIFICATIONS PASSED"
-echo "=================================================="
-echo ""
-echo "Summary:"
-echo "  - All type definitions aligned (Character, Stats, Ability, CombatEvent)"
-echo "  - Function implementations verified (computeAttack/compute_attack)"
-echo "  - All logic tests passing (41 tests)"
-echo "  - Documentation complete and comprehensive"
-echo "  - Logic crate builds without errors"
-echo ""
-echo "✅ Issue #10 implementation is COMPLETE and production-ready"
-echo ""
+cargo run -p fsharp-tools --bin fsharp-codegen --quiet -- --root . --check
+cargo run -p fsharp-tools --bin fsharp-parity --quiet -- --root . "$@"